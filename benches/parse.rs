@@ -0,0 +1,142 @@
+//! 構文解析の速さを測るベンチマークです。
+//!
+//! 確保を数えるアロケーターを使うため、テストとは別のターゲットにしている。
+//! JavaScript のファイルを置いたディレクトリを指定して実行する。
+//!
+//! ```sh
+//! DWJI_BENCH_CORPUS=path/to/corpus cargo bench --bench parse
+//! ```
+//!
+//! ファイルごとの解析時間と、構文木を作るために確保したメモリの回数と量を表示する。
+//! スクリプトとして読めないファイルはモジュールとして読み直し、どちらも失敗したら数える。
+//!
+//! 未対応: 構文木のアリーナ確保と、リポジトリに含めるコーパスはまだ実装していない。
+//! どちらも依頼者の合意を得て範囲を決めるまで未解決として扱う。
+//! コーパスは第三者のソースコードのライセンスの確認が必要になる。
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fs,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use dwjiscript::ast::{
+    reader_struct::SourceCodeReader,
+    script::{parse_module, parse_script},
+};
+
+/// 確保の回数と量を数えるアロケーター
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(new_size, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// ディレクトリの下の `.js` `.mjs` `.cjs` ファイルを集める。
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(path) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else if path
+            .extension()
+            .is_some_and(|extension| matches!(extension.to_str(), Some("js" | "mjs" | "cjs")))
+        {
+            files.push(path);
+        }
+    }
+}
+
+#[derive(Default)]
+struct Measurement {
+    files: usize,
+    bytes: usize,
+    failures: usize,
+    parse_time: Duration,
+    drop_time: Duration,
+    allocations: usize,
+    allocated_bytes: usize,
+}
+
+fn main() {
+    let Ok(corpus) = std::env::var("DWJI_BENCH_CORPUS") else {
+        eprintln!("set DWJI_BENCH_CORPUS to a directory of JavaScript files");
+        return;
+    };
+
+    let mut files = Vec::new();
+    collect_files(Path::new(&corpus), &mut files);
+    files.sort();
+
+    let mut measurement = Measurement::default();
+    for path in files {
+        let Ok(source) = fs::read_to_string(&path) else {
+            continue;
+        };
+
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+        let allocated_bytes = ALLOCATED_BYTES.load(Ordering::Relaxed);
+        let start = Instant::now();
+
+        let mut output = parse_script(SourceCodeReader::new(&source));
+        if !output.errors.is_empty() {
+            output = parse_module(SourceCodeReader::new(&source));
+        }
+
+        measurement.parse_time += start.elapsed();
+        measurement.allocations += ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+        measurement.allocated_bytes += ALLOCATED_BYTES.load(Ordering::Relaxed) - allocated_bytes;
+        measurement.files += 1;
+        measurement.bytes += source.len();
+        if !output.errors.is_empty() {
+            measurement.failures += 1;
+        }
+
+        let start = Instant::now();
+        drop(output);
+        measurement.drop_time += start.elapsed();
+    }
+
+    let megabytes = measurement.bytes as f64 / 1_000_000.0;
+    eprintln!(
+        "{} files, {:.2} MB, {} failed",
+        measurement.files, megabytes, measurement.failures
+    );
+    eprintln!(
+        "parse: {:.2?} ({:.2} MB/s), drop: {:.2?}",
+        measurement.parse_time,
+        megabytes / measurement.parse_time.as_secs_f64(),
+        measurement.drop_time
+    );
+    eprintln!(
+        "allocations: {} ({:.1} per KB), {:.2} MB allocated",
+        measurement.allocations,
+        measurement.allocations as f64 / (measurement.bytes as f64 / 1000.0),
+        measurement.allocated_bytes as f64 / 1_000_000.0
+    );
+}
@@ -1,3 +1,16 @@
+pub mod cfg;
+pub mod codegen;
+pub mod cst;
+pub mod diff;
+pub mod early_errors;
+pub mod error;
+pub mod estree;
+pub mod line_index;
+pub mod parser;
+pub mod quote;
 pub mod reader_struct;
+pub mod rewrite;
+pub mod scope;
 pub mod script;
 pub mod structs;
+pub mod visit;
@@ -0,0 +1,526 @@
+//! 関数ごとの制御フローグラフを作るモジュールです。
+//!
+//! 基本ブロックは実行される順にノード (文と式) を持つ。文はその実行が始まる位置に置き、
+//! 式は短絡評価などで分岐する場合だけ、部分式を評価し終えた位置に置く。
+//!
+//! - `if` と条件式、`&&` `||` は真と偽の辺で分岐する (`!` は真と偽を入れ替える)。
+//! - `??` とオプショナルチェーンは null か undefined であるかで分岐する。
+//! - switch 文のケースから次のケースへの落ち込みは [`EdgeKind::Fallthrough`] の辺になる。
+//! - `finally` は、そこを通って抜ける `return` `break` `continue` `throw` ごとに複製する。
+//! - try ブロックの中の基本ブロックからは、catch (または finally) へ例外の辺を引く。
+//!
+//! 入口から辿れない基本ブロックは到達できないコードを表す。
+
+mod builder;
+
+use std::fmt::Write;
+
+use self::builder::Builder;
+use super::{
+    structs::{
+        class::StaticBlock,
+        expression::Expression,
+        function::{ArrowFunctionExpression, Function},
+        span::{Span, Spanned},
+        statement::{Statement, SwitchCase},
+        JsAst,
+    },
+    visit::{Visit, VisitWith},
+};
+
+/// 基本ブロックの番号 ([`ControlFlowGraph::blocks`] の添字)
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub struct BlockId(pub usize);
+
+/// 基本ブロックで実行されるもの
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Node<'ast> {
+    Statement(&'ast Statement),
+    Expression(&'ast Expression),
+
+    /// switch 文のケースの本体の始まり
+    SwitchCase(&'ast SwitchCase),
+}
+
+impl Spanned for Node<'_> {
+    fn span(&self) -> Span {
+        match self {
+            Node::Statement(statement) => statement.span(),
+            Node::Expression(expression) => expression.span(),
+            Node::SwitchCase(case) => case.span,
+        }
+    }
+}
+
+/// 途中で分岐も合流もしない、連続して実行されるノード
+#[derive(Debug, Clone, Default)]
+pub struct BasicBlock<'ast> {
+    pub nodes: Vec<Node<'ast>>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum EdgeKind {
+    /// 無条件の移動
+    Normal,
+
+    /// 直前の式が真
+    True,
+
+    /// 直前の式が偽
+    False,
+
+    /// 直前の式が null か undefined
+    Nullish,
+
+    /// 直前の式が null でも undefined でもない
+    NotNullish,
+
+    /// switch 文のケースの終わりから次のケースへ
+    Fallthrough,
+
+    /// 例外による catch、finally、関数の外への移動
+    Exception,
+}
+
+impl EdgeKind {
+    fn name(&self) -> &'static str {
+        match self {
+            EdgeKind::Normal => "",
+            EdgeKind::True => "true",
+            EdgeKind::False => "false",
+            EdgeKind::Nullish => "nullish",
+            EdgeKind::NotNullish => "not nullish",
+            EdgeKind::Fallthrough => "fallthrough",
+            EdgeKind::Exception => "exception",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Edge {
+    pub from: BlockId,
+    pub to: BlockId,
+    pub kind: EdgeKind,
+}
+
+/// 関数 (またはスクリプト、static ブロック) の制御フローグラフ
+#[derive(Debug, Clone)]
+pub struct ControlFlowGraph<'ast> {
+    /// 関数の範囲
+    pub span: Span,
+    pub blocks: Vec<BasicBlock<'ast>>,
+    pub edges: Vec<Edge>,
+    pub entry: BlockId,
+
+    /// `return` と本体の終わりから移る出口 (ノードを持たない)
+    pub exit: BlockId,
+}
+
+impl<'ast> ControlFlowGraph<'ast> {
+    pub fn block(&self, id: BlockId) -> &BasicBlock<'ast> {
+        &self.blocks[id.0]
+    }
+
+    pub fn successors(&self, id: BlockId) -> impl Iterator<Item = &Edge> + '_ {
+        self.edges.iter().filter(move |edge| edge.from == id)
+    }
+
+    pub fn predecessors(&self, id: BlockId) -> impl Iterator<Item = &Edge> + '_ {
+        self.edges.iter().filter(move |edge| edge.to == id)
+    }
+
+    /// 入口から辿れる基本ブロックを、番号を添字として返す。
+    pub fn reachable(&self) -> Vec<bool> {
+        let mut reachable = vec![false; self.blocks.len()];
+        let mut stack = vec![self.entry];
+        while let Some(id) = stack.pop() {
+            if std::mem::replace(&mut reachable[id.0], true) {
+                continue;
+            }
+            stack.extend(self.successors(id).map(|edge| edge.to));
+        }
+        reachable
+    }
+
+    /// ノードを持つ基本ブロックを全て返す (`finally` の文は複数のブロックにある)。
+    pub fn blocks_of(&self, span: Span) -> Vec<BlockId> {
+        (0..self.blocks.len())
+            .map(BlockId)
+            .filter(|&id| self.block(id).nodes.iter().any(|node| node.span() == span))
+            .collect()
+    }
+
+    /// Graphviz の DOT 形式にする。到達できない基本ブロックは破線で描く。
+    pub fn to_dot(&self, source: &str) -> String {
+        let reachable = self.reachable();
+        let mut output = String::from("digraph cfg {\n  node [shape=box, fontname=monospace];\n");
+        for (index, block) in self.blocks.iter().enumerate() {
+            let id = BlockId(index);
+            let mut label = match id {
+                id if id == self.entry => "entry".to_string(),
+                id if id == self.exit => "exit".to_string(),
+                _ => format!("B{index}"),
+            };
+            label += "\\l";
+            for node in &block.nodes {
+                label += &escape(&snippet(node.span().source_text(source)));
+                label += "\\l";
+            }
+            write!(output, "  b{index} [label=\"{label}\"").unwrap();
+            if !reachable[index] {
+                output += ", style=dashed";
+            }
+            output += "];\n";
+        }
+        for edge in &self.edges {
+            write!(output, "  b{} -> b{}", edge.from.0, edge.to.0).unwrap();
+            match edge.kind {
+                EdgeKind::Normal => {}
+                EdgeKind::Exception => output += " [label=\"exception\", style=dashed]",
+                kind => write!(output, " [label=\"{}\"]", kind.name()).unwrap(),
+            }
+            output += ";\n";
+        }
+        output += "}\n";
+        output
+    }
+}
+
+/// 表示する長さ (文字数)
+const SNIPPET_LENGTH: usize = 30;
+
+/// ソースコードの最初の行 (長い場合は省略する)
+fn snippet(text: &str) -> String {
+    let line = text.lines().next().unwrap_or_default().trim();
+    match line.char_indices().nth(SNIPPET_LENGTH) {
+        Some((index, _)) => format!("{}...", &line[..index]),
+        None => line.to_string(),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// 関数の制御フローグラフを作る。
+pub fn function_cfg(function: &Function) -> ControlFlowGraph<'_> {
+    let mut builder = Builder::new();
+    builder.statements(&function.body.body);
+    builder.finish(function.span)
+}
+
+/// アロー関数の制御フローグラフを作る。
+pub fn arrow_function_cfg(function: &ArrowFunctionExpression) -> ControlFlowGraph<'_> {
+    let mut builder = Builder::new();
+    builder.arrow_function_body(&function.body);
+    builder.finish(function.span)
+}
+
+/// static ブロックの制御フローグラフを作る。
+pub fn static_block_cfg(block: &StaticBlock) -> ControlFlowGraph<'_> {
+    let mut builder = Builder::new();
+    builder.statements(&block.body);
+    builder.finish(block.span)
+}
+
+/// スクリプトとモジュールのトップレベルの制御フローグラフを作る。
+pub fn program_cfg(ast: &JsAst) -> ControlFlowGraph<'_> {
+    let mut builder = Builder::new();
+    builder.module_items(&ast.body);
+    builder.finish(ast.span)
+}
+
+/// トップレベルと、全ての関数と static ブロックの制御フローグラフを前順に作る。
+pub fn control_flow_graphs(ast: &JsAst) -> Vec<ControlFlowGraph<'_>> {
+    let mut collector = Collector {
+        graphs: vec![program_cfg(ast)],
+    };
+    ast.visit_children_with(&mut collector);
+    collector.graphs
+}
+
+struct Collector<'ast> {
+    graphs: Vec<ControlFlowGraph<'ast>>,
+}
+
+impl<'ast> Visit<'ast> for Collector<'ast> {
+    fn visit_function(&mut self, node: &'ast Function) {
+        self.graphs.push(function_cfg(node));
+        node.visit_children_with(self);
+    }
+
+    fn visit_arrow_function_expression(&mut self, node: &'ast ArrowFunctionExpression) {
+        self.graphs.push(arrow_function_cfg(node));
+        node.visit_children_with(self);
+    }
+
+    fn visit_static_block(&mut self, node: &'ast StaticBlock) {
+        self.graphs.push(static_block_cfg(node));
+        node.visit_children_with(self);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{control_flow_graphs, program_cfg, BlockId, ControlFlowGraph, EdgeKind, Node};
+    use crate::ast::{
+        reader_struct::SourceCodeReader,
+        script::parse_script,
+        structs::{
+            span::{Span, Spanned},
+            JsAst,
+        },
+    };
+
+    fn parse(source: &str) -> JsAst {
+        parse_script(SourceCodeReader::new(source))
+            .into_result()
+            .unwrap_or_else(|error| panic!("{source}: {error:?}"))
+    }
+
+    /// 到達できないブロックにある文 (finally の複製のどれかに到達できれば除く)
+    fn unreachable<'a>(cfg: &ControlFlowGraph, source: &'a str) -> Vec<&'a str> {
+        let reachable = cfg.reachable();
+        let mut statements: Vec<&str> = Vec::new();
+        for (index, block) in cfg.blocks.iter().enumerate() {
+            for node in &block.nodes {
+                let Node::Statement(statement) = node else {
+                    continue;
+                };
+                let live = cfg
+                    .blocks_of(statement.span())
+                    .iter()
+                    .any(|id| reachable[id.0]);
+                let text = statement.span().source_text(source);
+                if !reachable[index] && !live && !statements.contains(&text) {
+                    statements.push(text);
+                }
+            }
+        }
+        statements
+    }
+
+    fn unreachable_in_program(source: &str) -> Vec<&str> {
+        let ast = parse(source);
+        let cfg = program_cfg(&ast);
+        unreachable(&cfg, source)
+    }
+
+    /// 関数の中で到達できない文
+    fn unreachable_in_function(source: &str) -> Vec<&str> {
+        let ast = parse(source);
+        let graphs = control_flow_graphs(&ast);
+        unreachable(&graphs[1], source)
+    }
+
+    fn edge_kinds(cfg: &ControlFlowGraph) -> Vec<EdgeKind> {
+        cfg.edges
+            .iter()
+            .map(|edge| edge.kind)
+            .filter(|kind| *kind != EdgeKind::Normal)
+            .collect()
+    }
+
+    #[test]
+    fn jumps() {
+        assert_eq!(
+            unreachable_in_function("function f() { a(); return; b(); c(); }"),
+            ["b();", "c();"]
+        );
+        assert_eq!(
+            unreachable_in_function("function f() { if (x) { return 1; } else { throw e; } g(); }"),
+            ["g();"]
+        );
+        assert_eq!(
+            unreachable_in_function("function f() { if (x) { return 1; } g(); }"),
+            Vec::<&str>::new()
+        );
+        assert_eq!(
+            unreachable_in_program("while (a) { break; b(); }\nc();"),
+            ["b();"]
+        );
+    }
+
+    #[test]
+    fn loops() {
+        // 条件のない for と while (true) は break でしか抜けない
+        assert_eq!(unreachable_in_program("for (;;) {}\na();"), ["a();"]);
+        assert_eq!(unreachable_in_program("while (true) {}\na();"), ["a();"]);
+        assert_eq!(
+            unreachable_in_program("while (true) { if (x) break; }\na();"),
+            Vec::<&str>::new()
+        );
+        assert_eq!(
+            unreachable_in_program("do { continue; a(); } while (x);\nb();"),
+            ["a();"]
+        );
+        assert_eq!(
+            unreachable_in_program("for (const x of xs) { continue; a(); }\nb();"),
+            ["a();"]
+        );
+    }
+
+    #[test]
+    fn labels() {
+        let source = "outer: while (a) {\n  while (b) { continue outer; x(); }\n  y();\n}";
+        assert_eq!(unreachable_in_program(source), ["x();"]);
+
+        let source = "outer: for (;;) { for (;;) { break outer; } y(); }\nz();";
+        assert_eq!(unreachable_in_program(source), ["y();"]);
+
+        let source = "block: { if (a) break block; b(); }\nc();";
+        assert_eq!(unreachable_in_program(source), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn switch_fallthrough() {
+        let source = "switch (x) { case 1: a(); case 2: b(); break; default: c(); }";
+        let ast = parse(source);
+        let cfg = program_cfg(&ast);
+        assert_eq!(
+            edge_kinds(&cfg),
+            [
+                EdgeKind::True,
+                EdgeKind::False,
+                EdgeKind::True,
+                EdgeKind::False,
+                EdgeKind::Fallthrough,
+                EdgeKind::Fallthrough,
+            ]
+        );
+        assert_eq!(unreachable(&cfg, source), Vec::<&str>::new());
+
+        let source = "switch (x) { case 1: return; a(); case 2: b(); }";
+        let ast = parse(&format!("function f() {{ {source} }}"));
+        let graphs = control_flow_graphs(&ast);
+        let fallthroughs = graphs[1]
+            .edges
+            .iter()
+            .filter(|edge| edge.kind == EdgeKind::Fallthrough);
+        let reachable = graphs[1].reachable();
+        assert!(fallthroughs.into_iter().all(|edge| !reachable[edge.from.0]));
+    }
+
+    #[test]
+    fn finally_interrupts_jumps() {
+        let source = "function f() { try { return a(); } finally { cleanup(); } after(); }";
+        assert_eq!(unreachable_in_function(source), ["after();"]);
+
+        let ast = parse(source);
+        let cfg = &control_flow_graphs(&ast)[1];
+        let cleanup = source.find("cleanup();").unwrap();
+        let blocks: Vec<_> =
+            cfg.blocks
+                .iter()
+                .enumerate()
+                .filter(|(_, block)| {
+                    block.nodes.iter().any(|node| {
+                        matches!(node, Node::Statement(_)) && node.span().start == cleanup
+                    })
+                })
+                .map(|(index, _)| index)
+                .collect();
+        // return の経路、例外の経路、通常の経路
+        assert_eq!(blocks.len(), 3);
+        let reachable = cfg.reachable();
+        let return_path = blocks[0];
+        assert!(reachable[return_path]);
+        assert!(cfg
+            .successors(BlockId(return_path))
+            .any(|edge| edge.to == cfg.exit));
+
+        // finally の return が try の return を上書きする
+        let source = "function f() { try { return 1; } finally { return 2; } }";
+        assert_eq!(unreachable_in_function(source), Vec::<&str>::new());
+        let source = "function f() { while (a) { try { break; } finally { continue; } } done(); }";
+        assert_eq!(unreachable_in_function(source), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn exceptions() {
+        let source = "try { a(); } catch (e) { b(); }\nc();";
+        let ast = parse(source);
+        let cfg = program_cfg(&ast);
+        assert!(edge_kinds(&cfg).contains(&EdgeKind::Exception));
+        assert_eq!(unreachable(&cfg, source), Vec::<&str>::new());
+
+        assert_eq!(
+            unreachable_in_program("try { throw e; a(); } catch (e) { b(); }\nc();"),
+            ["a();"]
+        );
+        assert_eq!(
+            unreachable_in_program("try { throw e; } finally { f(); }\nc();"),
+            ["c();"]
+        );
+    }
+
+    #[test]
+    fn short_circuits() {
+        let ast = parse("a && b();");
+        assert_eq!(
+            edge_kinds(&program_cfg(&ast)),
+            [EdgeKind::True, EdgeKind::False]
+        );
+
+        let ast = parse("x = a ?? b;");
+        assert_eq!(
+            edge_kinds(&program_cfg(&ast)),
+            [EdgeKind::Nullish, EdgeKind::NotNullish]
+        );
+
+        let ast = parse("a?.b.c(d?.());");
+        assert_eq!(
+            edge_kinds(&program_cfg(&ast)),
+            [
+                EdgeKind::Nullish,
+                EdgeKind::NotNullish,
+                EdgeKind::Nullish,
+                EdgeKind::NotNullish,
+            ]
+        );
+
+        // 条件の && は偽の辺で直接 else へ移る
+        let source = "if (a && !b) x(); else y();";
+        let ast = parse(source);
+        let cfg = program_cfg(&ast);
+        let else_block = cfg.blocks_of(span_of(source, "y();"))[0];
+        let into_else: Vec<EdgeKind> = cfg.predecessors(else_block).map(|edge| edge.kind).collect();
+        assert_eq!(into_else, [EdgeKind::False, EdgeKind::True]);
+    }
+
+    fn span_of(source: &str, text: &str) -> Span {
+        let start = source.find(text).unwrap();
+        Span::new(start, start + text.len())
+    }
+
+    #[test]
+    fn graphs_per_function() {
+        let ast = parse("function f() { return () => 1; }\nclass A { static { g(); } m() {} }");
+        let graphs = control_flow_graphs(&ast);
+        assert_eq!(graphs.len(), 5);
+    }
+
+    #[test]
+    fn dot() {
+        let source = "if (a) { b(\"x\"); }";
+        let ast = parse(source);
+        assert_eq!(
+            program_cfg(&ast).to_dot(source),
+            [
+                "digraph cfg {",
+                "  node [shape=box, fontname=monospace];",
+                "  b0 [label=\"entry\\lif (a) { b(\\\"x\\\"); }\\la\\l\"];",
+                "  b1 [label=\"exit\\l\"];",
+                "  b2 [label=\"B2\\l{ b(\\\"x\\\"); }\\lb(\\\"x\\\");\\lb(\\\"x\\\")\\l\"];",
+                "  b3 [label=\"B3\\l\"];",
+                "  b0 -> b2 [label=\"true\"];",
+                "  b0 -> b3 [label=\"false\"];",
+                "  b2 -> b3;",
+                "  b3 -> b1;",
+                "}",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -0,0 +1,719 @@
+//! 構文木から制御フローグラフを組み立てるモジュールです。
+
+use super::{BasicBlock, BlockId, ControlFlowGraph, Edge, EdgeKind, Node};
+use crate::ast::{
+    structs::{
+        class::Class,
+        expression::{AssignmentOperator, Expression, LogicalOperator, UnaryOperator},
+        function::{ArrowFunctionBody, ArrowFunctionExpression, Function},
+        literal::{Identifier, LiteralValue},
+        module::{Declaration, ExportDefaultKind, ModuleDeclaration, ModuleItem},
+        pattern::Pattern,
+        span::Span,
+        statement::{
+            BlockStatement, ForInOfLeft, ForInit, Statement, SwitchStatement, TryStatement,
+            VariableDeclaration,
+        },
+    },
+    visit::{Visit, VisitWith},
+};
+
+/// `break` `continue` `return` `throw` の移動先を決める文脈
+enum Context<'ast> {
+    /// ループ、switch 文、ラベル付きの文
+    Jump {
+        labels: Vec<&'ast str>,
+        break_target: BlockId,
+
+        /// ループの場合の `continue` の移動先
+        continue_target: Option<BlockId>,
+
+        /// ラベルのない `break` を受けるか (ループと switch 文)
+        unlabeled: bool,
+    },
+
+    /// try 文の try ブロックと catch 節
+    Try {
+        catch: Option<BlockId>,
+        finalizer: Option<&'ast BlockStatement>,
+
+        /// catch 節の中であるか
+        in_catch: bool,
+
+        /// 例外が起きうる基本ブロック
+        blocks: Vec<BlockId>,
+    },
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Jump<'ast> {
+    Break(Option<&'ast str>),
+    Continue(Option<&'ast str>),
+    Return,
+    Throw,
+}
+
+pub(super) struct Builder<'ast> {
+    blocks: Vec<BasicBlock<'ast>>,
+    edges: Vec<Edge>,
+    current: BlockId,
+    exit: BlockId,
+    contexts: Vec<Context<'ast>>,
+}
+
+impl<'ast> Builder<'ast> {
+    pub(super) fn new() -> Builder<'ast> {
+        Builder {
+            blocks: vec![BasicBlock::default(), BasicBlock::default()],
+            edges: Vec::new(),
+            current: BlockId(0),
+            exit: BlockId(1),
+            contexts: Vec::new(),
+        }
+    }
+
+    pub(super) fn finish(mut self, span: Span) -> ControlFlowGraph<'ast> {
+        self.goto(self.exit);
+        ControlFlowGraph {
+            span,
+            blocks: self.blocks,
+            edges: self.edges,
+            entry: BlockId(0),
+            exit: self.exit,
+        }
+    }
+
+    /// 基本ブロックを作る。try ブロックの中であれば、例外が起きうるブロックとして記録する。
+    fn new_block(&mut self) -> BlockId {
+        let id = BlockId(self.blocks.len());
+        self.blocks.push(BasicBlock::default());
+        let innermost_try = self
+            .contexts
+            .iter_mut()
+            .rev()
+            .find_map(|context| match context {
+                Context::Try { blocks, .. } => Some(blocks),
+                Context::Jump { .. } => None,
+            });
+        if let Some(blocks) = innermost_try {
+            blocks.push(id);
+        }
+        id
+    }
+
+    fn edge(&mut self, from: BlockId, to: BlockId, kind: EdgeKind) {
+        self.edges.push(Edge { from, to, kind });
+    }
+
+    /// 現在のブロックから無条件に移る。
+    fn goto(&mut self, target: BlockId) {
+        self.edge(self.current, target, EdgeKind::Normal);
+    }
+
+    /// 現在のブロックから分岐する。
+    fn branch(
+        &mut self,
+        (kind, target): (EdgeKind, BlockId),
+        (other_kind, other): (EdgeKind, BlockId),
+    ) {
+        self.edge(self.current, target, kind);
+        self.edge(self.current, other, other_kind);
+    }
+
+    fn push(&mut self, node: Node<'ast>) {
+        self.blocks[self.current.0].nodes.push(node);
+    }
+
+    pub(super) fn statements(&mut self, statements: &'ast [Statement]) {
+        for statement in statements {
+            self.statement(statement, Vec::new());
+        }
+    }
+
+    pub(super) fn arrow_function_body(&mut self, body: &'ast ArrowFunctionBody) {
+        match body {
+            ArrowFunctionBody::Expression(expression) => self.expression(expression),
+            ArrowFunctionBody::FunctionBody(body) => self.statements(&body.body),
+        }
+    }
+
+    pub(super) fn module_items(&mut self, items: &'ast [ModuleItem]) {
+        for item in items {
+            match item {
+                ModuleItem::Statement(statement) => self.statement(statement, Vec::new()),
+                ModuleItem::ModuleDeclaration(ModuleDeclaration::ExportNamed(export)) => {
+                    if let Some(Declaration::Variable(declaration)) = &export.declaration {
+                        self.variable_declaration(declaration);
+                    }
+                }
+                ModuleItem::ModuleDeclaration(ModuleDeclaration::ExportDefault(export)) => {
+                    if let ExportDefaultKind::Expression(expression) = &export.declaration {
+                        self.expression(expression);
+                    }
+                }
+                ModuleItem::ModuleDeclaration(_) => {}
+            }
+        }
+    }
+
+    /// 文を加える。`labels` は文に付いたラベル。
+    fn statement(&mut self, statement: &'ast Statement, labels: Vec<&'ast str>) {
+        self.push(Node::Statement(statement));
+        match statement {
+            Statement::Expression(statement) => self.expression(&statement.expression),
+            Statement::Block(block) => self.statements(&block.body),
+            Statement::VariableDeclaration(declaration) => self.variable_declaration(declaration),
+            Statement::With(with) => {
+                self.expression(&with.object);
+                self.statement(&with.body, Vec::new());
+            }
+            Statement::Return(statement) => {
+                if let Some(argument) = &statement.argument {
+                    self.expression(argument);
+                }
+                self.jump(Jump::Return);
+            }
+            Statement::Throw(statement) => {
+                self.expression(&statement.argument);
+                self.jump(Jump::Throw);
+            }
+            Statement::Break(statement) => {
+                self.jump(Jump::Break(label_name(&statement.label)));
+            }
+            Statement::Continue(statement) => {
+                self.jump(Jump::Continue(label_name(&statement.label)));
+            }
+            Statement::Labeled(statement) => {
+                let mut labels = labels;
+                labels.push(&statement.label.name);
+                match &statement.body {
+                    body @ (Statement::Labeled(_)
+                    | Statement::While(_)
+                    | Statement::DoWhile(_)
+                    | Statement::For(_)
+                    | Statement::ForIn(_)
+                    | Statement::ForOf(_)
+                    | Statement::Switch(_)) => self.statement(body, labels),
+                    body => {
+                        let exit = self.new_block();
+                        self.contexts.push(Context::Jump {
+                            labels,
+                            break_target: exit,
+                            continue_target: None,
+                            unlabeled: false,
+                        });
+                        self.statement(body, Vec::new());
+                        self.contexts.pop();
+                        self.goto(exit);
+                        self.current = exit;
+                    }
+                }
+            }
+            Statement::If(statement) => {
+                let consequent = self.new_block();
+                let join = self.new_block();
+                let alternate = match statement.alternate {
+                    Some(_) => self.new_block(),
+                    None => join,
+                };
+                self.condition(&statement.test, consequent, alternate);
+                self.current = consequent;
+                self.statement(&statement.consequent, Vec::new());
+                self.goto(join);
+                if let Some(statement) = &statement.alternate {
+                    self.current = alternate;
+                    self.statement(statement, Vec::new());
+                    self.goto(join);
+                }
+                self.current = join;
+            }
+            Statement::While(statement) => {
+                let test = self.new_block();
+                let body = self.new_block();
+                let exit = self.new_block();
+                self.goto(test);
+                self.current = test;
+                self.condition(&statement.test, body, exit);
+                self.current = body;
+                self.loop_body(&statement.body, labels, exit, test);
+                self.goto(test);
+                self.current = exit;
+            }
+            Statement::DoWhile(statement) => {
+                let body = self.new_block();
+                let test = self.new_block();
+                let exit = self.new_block();
+                self.goto(body);
+                self.current = body;
+                self.loop_body(&statement.body, labels, exit, test);
+                self.goto(test);
+                self.current = test;
+                self.condition(&statement.test, body, exit);
+                self.current = exit;
+            }
+            Statement::For(statement) => {
+                match &statement.init {
+                    Some(ForInit::VariableDeclaration(declaration)) => {
+                        self.variable_declaration(declaration)
+                    }
+                    Some(ForInit::Expression(expression)) => self.expression(expression),
+                    None => {}
+                }
+                let test = self.new_block();
+                let body = self.new_block();
+                let exit = self.new_block();
+                let update = statement.update.as_ref().map(|_| self.new_block());
+                self.goto(test);
+                self.current = test;
+                match &statement.test {
+                    Some(expression) => self.condition(expression, body, exit),
+                    None => self.goto(body),
+                }
+                self.current = body;
+                let continue_target = update.unwrap_or(test);
+                self.loop_body(&statement.body, labels, exit, continue_target);
+                self.goto(continue_target);
+                if let (Some(block), Some(expression)) = (update, &statement.update) {
+                    self.current = block;
+                    self.expression(expression);
+                    self.goto(test);
+                }
+                self.current = exit;
+            }
+            Statement::ForIn(statement) => {
+                self.expression(&statement.right);
+                self.for_in_of(&statement.left, &statement.body, labels);
+            }
+            Statement::ForOf(statement) => {
+                self.expression(&statement.right);
+                self.for_in_of(&statement.left, &statement.body, labels);
+            }
+            Statement::Switch(statement) => self.switch(statement, labels),
+            Statement::Try(statement) => self.try_statement(statement),
+            Statement::Empty(_)
+            | Statement::Debugger(_)
+            | Statement::FunctionDeclaration(_)
+            | Statement::ClassDeclaration(_)
+            | Statement::Error(_) => {}
+        }
+    }
+
+    fn variable_declaration(&mut self, declaration: &'ast VariableDeclaration) {
+        for declarator in &declaration.declarations {
+            if let Some(init) = &declarator.init {
+                self.expression(init);
+            }
+        }
+    }
+
+    /// ループの本体を加える。
+    fn loop_body(
+        &mut self,
+        body: &'ast Statement,
+        labels: Vec<&'ast str>,
+        break_target: BlockId,
+        continue_target: BlockId,
+    ) {
+        self.contexts.push(Context::Jump {
+            labels,
+            break_target,
+            continue_target: Some(continue_target),
+            unlabeled: true,
+        });
+        self.statement(body, Vec::new());
+        self.contexts.pop();
+    }
+
+    fn for_in_of(
+        &mut self,
+        left: &'ast ForInOfLeft,
+        body: &'ast Statement,
+        labels: Vec<&'ast str>,
+    ) {
+        // 次の値があれば本体へ、なければ抜ける
+        let head = self.new_block();
+        let body_block = self.new_block();
+        let exit = self.new_block();
+        self.goto(head);
+        self.current = head;
+        self.branch((EdgeKind::Normal, body_block), (EdgeKind::Normal, exit));
+        self.current = body_block;
+        if let ForInOfLeft::Pattern(pattern) = left {
+            self.pattern(pattern);
+        }
+        self.loop_body(body, labels, exit, head);
+        self.goto(head);
+        self.current = exit;
+    }
+
+    fn switch(&mut self, statement: &'ast SwitchStatement, labels: Vec<&'ast str>) {
+        self.expression(&statement.discriminant);
+        let exit = self.new_block();
+        let bodies: Vec<BlockId> = statement.cases.iter().map(|_| self.new_block()).collect();
+
+        // ケースの式を順に比べ、どれにも一致しなければ default (なければ switch 文の後) へ
+        for (case, &body) in statement.cases.iter().zip(&bodies) {
+            if let Some(test) = &case.test {
+                self.expression(test);
+                let next = self.new_block();
+                self.branch((EdgeKind::True, body), (EdgeKind::False, next));
+                self.current = next;
+            }
+        }
+        let default = statement.cases.iter().position(|case| case.test.is_none());
+        self.goto(default.map_or(exit, |index| bodies[index]));
+
+        self.contexts.push(Context::Jump {
+            labels,
+            break_target: exit,
+            continue_target: None,
+            unlabeled: true,
+        });
+        for (index, case) in statement.cases.iter().enumerate() {
+            self.current = bodies[index];
+            self.push(Node::SwitchCase(case));
+            self.statements(&case.consequent);
+            match bodies.get(index + 1) {
+                Some(&next) => self.edge(self.current, next, EdgeKind::Fallthrough),
+                None => self.goto(exit),
+            }
+        }
+        self.contexts.pop();
+        self.current = exit;
+    }
+
+    fn try_statement(&mut self, statement: &'ast TryStatement) {
+        let catch = statement.handler.as_ref().map(|_| self.new_block());
+        self.contexts.push(Context::Try {
+            catch,
+            finalizer: statement.finalizer.as_ref(),
+            in_catch: false,
+            blocks: Vec::new(),
+        });
+        let entry = self.new_block();
+        self.goto(entry);
+        self.current = entry;
+        self.statements(&statement.block.body);
+        let mut ends = vec![self.current];
+        let mut throwing = self.take_try_blocks();
+
+        if let (Some(handler), Some(catch)) = (&statement.handler, catch) {
+            for block in throwing {
+                self.edge(block, catch, EdgeKind::Exception);
+            }
+            if let Some(Context::Try { in_catch, .. }) = self.contexts.last_mut() {
+                *in_catch = true;
+            }
+            self.current = catch;
+            if let Some(param) = &handler.param {
+                self.pattern(param);
+            }
+            self.statements(&handler.body.body);
+            ends.push(self.current);
+            throwing = self.take_try_blocks();
+        }
+        self.contexts.pop();
+
+        let join = self.new_block();
+        for end in ends {
+            self.edge(end, join, EdgeKind::Normal);
+        }
+        match &statement.finalizer {
+            Some(finalizer) => {
+                // 例外の後の finally は、実行してから例外を外へ投げ直す
+                if !throwing.is_empty() {
+                    let block = self.new_block();
+                    for from in throwing {
+                        self.edge(from, block, EdgeKind::Exception);
+                    }
+                    self.current = block;
+                    self.statements(&finalizer.body);
+                    self.jump(Jump::Throw);
+                }
+                self.current = join;
+                self.statements(&finalizer.body);
+            }
+            None => {
+                // catch 節の中の例外は外側の try 文が受ける
+                if let Some(Context::Try { blocks, .. }) = self
+                    .contexts
+                    .iter_mut()
+                    .rev()
+                    .find(|context| matches!(context, Context::Try { .. }))
+                {
+                    blocks.extend(throwing);
+                }
+                self.current = join;
+            }
+        }
+    }
+
+    /// 最も内側の try 文が記録した、例外が起きうるブロックを取り出す。
+    fn take_try_blocks(&mut self) -> Vec<BlockId> {
+        match self.contexts.last_mut() {
+            Some(Context::Try { blocks, .. }) => std::mem::take(blocks),
+            _ => Vec::new(),
+        }
+    }
+
+    /// 文脈を内側から辿って移動先を探し、途中の finally を実行してから移る。
+    fn jump(&mut self, jump: Jump<'ast>) {
+        let mut index = self.contexts.len();
+        while index > 0 {
+            index -= 1;
+            let target = match &self.contexts[index] {
+                Context::Jump {
+                    labels,
+                    break_target,
+                    continue_target,
+                    unlabeled,
+                } => match jump {
+                    Jump::Break(None) if *unlabeled => Some((*break_target, EdgeKind::Normal)),
+                    Jump::Break(Some(label)) if labels.contains(&label) => {
+                        Some((*break_target, EdgeKind::Normal))
+                    }
+                    Jump::Continue(None) => {
+                        continue_target.map(|target| (target, EdgeKind::Normal))
+                    }
+                    Jump::Continue(Some(label)) if labels.contains(&label) => {
+                        continue_target.map(|target| (target, EdgeKind::Normal))
+                    }
+                    _ => None,
+                },
+                Context::Try {
+                    catch: Some(catch),
+                    in_catch: false,
+                    ..
+                } if matches!(jump, Jump::Throw) => Some((*catch, EdgeKind::Exception)),
+                Context::Try {
+                    finalizer: Some(finalizer),
+                    ..
+                } => {
+                    let finalizer = *finalizer;
+                    self.finally_copy(index, finalizer);
+                    None
+                }
+                Context::Try { .. } => None,
+            };
+            if let Some((target, kind)) = target {
+                self.edge(self.current, target, kind);
+                self.current = self.new_block();
+                return;
+            }
+        }
+
+        match jump {
+            Jump::Return => self.goto(self.exit),
+            Jump::Throw => self.edge(self.current, self.exit, EdgeKind::Exception),
+            // 移動先のない break と continue は構文エラー
+            Jump::Break(_) | Jump::Continue(_) => {}
+        }
+        self.current = self.new_block();
+    }
+
+    /// 抜ける途中で実行する finally を、try 文より外側の文脈で複製する。
+    fn finally_copy(&mut self, index: usize, finalizer: &'ast BlockStatement) {
+        let inner = self.contexts.split_off(index);
+        self.statements(&finalizer.body);
+        self.contexts.extend(inner);
+    }
+
+    /// 条件を評価し、真なら `on_true` へ、偽なら `on_false` へ移る。
+    fn condition(&mut self, test: &'ast Expression, on_true: BlockId, on_false: BlockId) {
+        match test {
+            Expression::Logical(logical) if logical.operator == LogicalOperator::And => {
+                let right = self.new_block();
+                self.condition(&logical.left, right, on_false);
+                self.current = right;
+                self.condition(&logical.right, on_true, on_false);
+            }
+            Expression::Logical(logical) if logical.operator == LogicalOperator::Or => {
+                let right = self.new_block();
+                self.condition(&logical.left, on_true, right);
+                self.current = right;
+                self.condition(&logical.right, on_true, on_false);
+            }
+            Expression::Unary(unary) if unary.operator == UnaryOperator::LogicalNot => {
+                self.condition(&unary.argument, on_false, on_true);
+            }
+            // 定数の条件は片方にしか進まない
+            Expression::Literal(literal) if matches!(literal.value, LiteralValue::Boolean(_)) => {
+                self.push(Node::Expression(test));
+                let target = match literal.value {
+                    LiteralValue::Boolean(true) => (EdgeKind::True, on_true),
+                    _ => (EdgeKind::False, on_false),
+                };
+                self.edge(self.current, target.1, target.0);
+            }
+            _ => {
+                self.expression(test);
+                self.branch((EdgeKind::True, on_true), (EdgeKind::False, on_false));
+            }
+        }
+    }
+
+    /// 式を評価する。分岐を含む式は部分式に分けて評価する。
+    fn expression(&mut self, expression: &'ast Expression) {
+        if !has_branch(expression) {
+            self.push(Node::Expression(expression));
+            return;
+        }
+
+        match expression {
+            Expression::Logical(logical) => {
+                self.expression(&logical.left);
+                let right = self.new_block();
+                let join = self.new_block();
+                self.short_circuit(logical.operator, right, join);
+                self.current = right;
+                self.expression(&logical.right);
+                self.goto(join);
+                self.current = join;
+            }
+            Expression::Conditional(conditional) => {
+                let consequent = self.new_block();
+                let alternate = self.new_block();
+                let join = self.new_block();
+                self.condition(&conditional.test, consequent, alternate);
+                self.current = consequent;
+                self.expression(&conditional.consequent);
+                self.goto(join);
+                self.current = alternate;
+                self.expression(&conditional.alternate);
+                self.goto(join);
+                self.current = join;
+            }
+            Expression::Assignment(assignment)
+                if logical_operator(assignment.operator).is_some() =>
+            {
+                self.pattern(&assignment.left);
+                let right = self.new_block();
+                let join = self.new_block();
+                if let Some(operator) = logical_operator(assignment.operator) {
+                    self.short_circuit(operator, right, join);
+                }
+                self.current = right;
+                self.expression(&assignment.right);
+                self.goto(join);
+                self.current = join;
+            }
+            Expression::Chain(chain) => {
+                let end = self.new_block();
+                self.chain_element(&chain.expression, end);
+                self.goto(end);
+                self.current = end;
+            }
+            _ => expression.visit_children_with(&mut Children(self)),
+        }
+        self.push(Node::Expression(expression));
+    }
+
+    /// 左辺を評価した後で、右辺へ進むか評価を終えるかを分ける。
+    fn short_circuit(&mut self, operator: LogicalOperator, right: BlockId, join: BlockId) {
+        match operator {
+            LogicalOperator::And => self.branch((EdgeKind::True, right), (EdgeKind::False, join)),
+            LogicalOperator::Or => self.branch((EdgeKind::False, right), (EdgeKind::True, join)),
+            LogicalOperator::NullishCoalesce => {
+                self.branch((EdgeKind::Nullish, right), (EdgeKind::NotNullish, join))
+            }
+        }
+    }
+
+    /// オプショナルチェーンの要素を評価する。`?.` の左が null か undefined なら `end` へ移る。
+    fn chain_element(&mut self, expression: &'ast Expression, end: BlockId) {
+        match expression {
+            Expression::Member(member) => {
+                self.chain_element(&member.object, end);
+                if member.optional {
+                    self.optional(end);
+                }
+                member.property.visit_with(&mut Children(self));
+            }
+            Expression::Call(call) => {
+                self.chain_element(&call.callee, end);
+                if call.optional {
+                    self.optional(end);
+                }
+                call.arguments.visit_with(&mut Children(self));
+            }
+            _ => {
+                self.expression(expression);
+                return;
+            }
+        }
+        self.push(Node::Expression(expression));
+    }
+
+    fn optional(&mut self, end: BlockId) {
+        let next = self.new_block();
+        self.branch((EdgeKind::Nullish, end), (EdgeKind::NotNullish, next));
+        self.current = next;
+    }
+
+    /// 代入先のパターンの中の式 (メンバー参照のオブジェクトや既定値) を評価する。
+    fn pattern(&mut self, pattern: &'ast Pattern) {
+        pattern.visit_children_with(&mut Children(self));
+    }
+}
+
+fn label_name(label: &Option<Identifier>) -> Option<&str> {
+    label.as_ref().map(|label| label.name.as_str())
+}
+
+fn logical_operator(operator: AssignmentOperator) -> Option<LogicalOperator> {
+    match operator {
+        AssignmentOperator::LogicalAndAssign => Some(LogicalOperator::And),
+        AssignmentOperator::LogicalOrAssign => Some(LogicalOperator::Or),
+        AssignmentOperator::NullishCoalesceAssign => Some(LogicalOperator::NullishCoalesce),
+        _ => None,
+    }
+}
+
+/// 式が (内側の関数を除いて) 短絡評価や条件式、オプショナルチェーンを含むかを返す。
+fn has_branch(expression: &Expression) -> bool {
+    let mut finder = BranchFinder { found: false };
+    finder.visit_expression(expression);
+    finder.found
+}
+
+struct BranchFinder {
+    found: bool,
+}
+
+impl<'ast> Visit<'ast> for BranchFinder {
+    fn visit_expression(&mut self, node: &'ast Expression) {
+        match node {
+            Expression::Logical(_) | Expression::Conditional(_) | Expression::Chain(_) => {
+                self.found = true
+            }
+            Expression::Assignment(assignment)
+                if logical_operator(assignment.operator).is_some() =>
+            {
+                self.found = true
+            }
+            _ if !self.found => node.visit_children_with(self),
+            _ => {}
+        }
+    }
+
+    fn visit_function(&mut self, _: &'ast Function) {}
+
+    fn visit_arrow_function_expression(&mut self, _: &'ast ArrowFunctionExpression) {}
+
+    fn visit_class(&mut self, _: &'ast Class) {}
+}
+
+/// 直下の式を評価の順に [`Builder::expression`] へ渡す。内側の関数とクラスは評価しない。
+struct Children<'a, 'ast>(&'a mut Builder<'ast>);
+
+impl<'ast> Visit<'ast> for Children<'_, 'ast> {
+    fn visit_expression(&mut self, node: &'ast Expression) {
+        self.0.expression(node);
+    }
+
+    fn visit_function(&mut self, _: &'ast Function) {}
+
+    fn visit_arrow_function_expression(&mut self, _: &'ast ArrowFunctionExpression) {}
+
+    fn visit_class(&mut self, _: &'ast Class) {}
+}
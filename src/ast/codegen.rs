@@ -0,0 +1,1503 @@
+//! 構文木を JavaScript のソースコードに戻すモジュールです。
+//!
+//! 括弧は構文木の形を保つために必要な場所にだけ入れる。
+//! 出力を解析し直すと、位置を除いて元と同じ構文木になる。
+//!
+//! 構文エラーのノードは `/* error */` と、文なら空文、式なら `void 0` にする。
+
+#[cfg(test)]
+mod random;
+
+use super::structs::{
+    class::{Class, ClassElement, MethodKind},
+    expression::{
+        BinaryOperator, Expression, ExpressionOrSpread, LogicalOperator, MemberProperty,
+        ObjectProperty, Property, PropertyKey, PropertyKind, UnaryOperator,
+    },
+    function::{ArrowFunctionBody, ArrowFunctionExpression, Function, FunctionBody},
+    literal::{Identifier, Literal, LiteralValue},
+    module::{
+        Declaration, ExportDefaultKind, ImportAttribute, ImportDeclarationSpecifier,
+        ModuleDeclaration, ModuleExportName, ModuleItem,
+    },
+    pattern::{ObjectPatternProperty, Pattern},
+    statement::{ForInOfLeft, ForInit, Statement, VariableDeclaration},
+    template::TemplateLiteral,
+    JsAst,
+};
+use crate::{
+    json::format_number, to_source_string::ToSourceString, token::tokenize::is_identifier_part,
+};
+
+/// 字下げの幅
+const INDENT: &str = "  ";
+
+/// 式の優先順位 (小さいほど弱く結び付く)
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+enum Precedence {
+    /// a, b
+    Sequence,
+
+    /// a = b, yield a, () => a
+    Assignment,
+
+    /// a ? b : c
+    Conditional,
+
+    /// a ?? b
+    NullishCoalesce,
+
+    /// a || b
+    LogicalOr,
+
+    /// a && b
+    LogicalAnd,
+    BitOr,
+    BitXor,
+    BitAnd,
+    Equality,
+    Relational,
+    Shift,
+    Additive,
+    Multiplicative,
+
+    /// a ** b
+    Exponentiation,
+
+    /// -a, await a
+    Unary,
+
+    /// a++, ++a
+    Update,
+
+    /// new a
+    New,
+
+    /// a.b, a(), new a()
+    LeftHandSide,
+
+    /// a, 1, (a)
+    Primary,
+}
+
+impl Precedence {
+    fn binary(operator: BinaryOperator) -> Precedence {
+        use BinaryOperator::*;
+
+        match operator {
+            Equal | NotEqual | StrictEqual | StrictNotEqual => Precedence::Equality,
+            LessThan | LessThanEqual | GreaterThan | GreaterThanEqual | In | Instanceof => {
+                Precedence::Relational
+            }
+            LeftShift | RightShift | UnsignedRightShift => Precedence::Shift,
+            Plus | Minus => Precedence::Additive,
+            Multiply | Divide | Remainder => Precedence::Multiplicative,
+            Exponentiation => Precedence::Exponentiation,
+            BitOr => Precedence::BitOr,
+            BitXor => Precedence::BitXor,
+            BitAnd => Precedence::BitAnd,
+        }
+    }
+
+    fn logical(operator: LogicalOperator) -> Precedence {
+        match operator {
+            LogicalOperator::Or => Precedence::LogicalOr,
+            LogicalOperator::And => Precedence::LogicalAnd,
+            LogicalOperator::NullishCoalesce => Precedence::NullishCoalesce,
+        }
+    }
+
+    fn of(expression: &Expression) -> Precedence {
+        match expression {
+            Expression::Sequence(_) => Precedence::Sequence,
+            Expression::Assignment(_) | Expression::Yield(_) | Expression::ArrowFunction(_) => {
+                Precedence::Assignment
+            }
+            Expression::Conditional(_) => Precedence::Conditional,
+            Expression::Logical(logical) => Precedence::logical(logical.operator),
+            Expression::Binary(binary) => Precedence::binary(binary.operator),
+            Expression::Unary(_) | Expression::Await(_) | Expression::Error(_) => Precedence::Unary,
+            Expression::Update(_) => Precedence::Update,
+            Expression::Member(_)
+            | Expression::Call(_)
+            | Expression::New(_)
+            | Expression::Chain(_)
+            | Expression::TaggedTemplate(_)
+            | Expression::Import(_) => Precedence::LeftHandSide,
+            _ => Precedence::Primary,
+        }
+    }
+}
+
+/// 式文や `export default` の先頭に置けない書き出し
+const AMBIGUOUS_STATEMENT_STARTS: &[&str] = &["{", "function", "class", "let[", "async function"];
+
+/// ソースコードを組み立てる。
+#[derive(Default)]
+struct Printer {
+    output: String,
+    indent: usize,
+
+    /// `for (a; ; )` の初期化式の中で、`in` 演算子を括弧で囲む
+    no_in: bool,
+}
+
+impl Printer {
+    /// 前の文字とつながって別のトークンにならないように空白を挟んで書く。
+    fn write(&mut self, text: &str) {
+        if let (Some(last), Some(first)) = (self.output.chars().last(), text.chars().next()) {
+            let is_word = |char: char| is_identifier_part(char) || char == '\\';
+            if (is_word(last) && is_word(first))
+                || (last == '+' && first == '+')
+                || (last == '-' && first == '-')
+            {
+                self.output.push(' ');
+            }
+        }
+        self.output.push_str(text);
+    }
+
+    fn new_line(&mut self) {
+        self.output.push('\n');
+        for _ in 0..self.indent {
+            self.output.push_str(INDENT);
+        }
+    }
+
+    /// `start` から書いた部分を括弧で囲む。
+    fn wrap_from(&mut self, start: usize) {
+        self.output.insert(start, '(');
+        self.output.push(')');
+    }
+
+    /// `start` から書いた部分が、文の始まりとして別の意味になる書き出しかを返す。
+    fn starts_ambiguously(&self, start: usize, starts: &[&str]) -> bool {
+        let text = &self.output[start..];
+        starts.iter().any(|prefix| {
+            text.strip_prefix(prefix).is_some_and(|rest| {
+                let is_word = prefix.chars().last().is_some_and(is_identifier_part);
+                !is_word || !rest.chars().next().is_some_and(is_identifier_part)
+            })
+        })
+    }
+
+    /// `in` 演算子を囲まずに書ける文脈で書く。
+    fn allowing_in(&mut self, print: impl FnOnce(&mut Self)) {
+        let no_in = std::mem::replace(&mut self.no_in, false);
+        print(self);
+        self.no_in = no_in;
+    }
+
+    fn comma_separated<T>(&mut self, items: &[T], mut print: impl FnMut(&mut Self, &T)) {
+        for (index, item) in items.iter().enumerate() {
+            if index > 0 {
+                self.write(", ");
+            }
+            print(self, item);
+        }
+    }
+
+    fn program(&mut self, ast: &JsAst) {
+        for (index, item) in ast.body.iter().enumerate() {
+            if index > 0 {
+                self.new_line();
+            }
+            match item {
+                ModuleItem::Statement(statement) => self.statement(statement),
+                ModuleItem::ModuleDeclaration(declaration) => self.module_declaration(declaration),
+            }
+        }
+    }
+
+    /// 中括弧で囲んだ文のリストを書く。
+    fn block(&mut self, statements: &[Statement]) {
+        self.write("{");
+        if statements.is_empty() {
+            self.write("}");
+            return;
+        }
+
+        self.indent += 1;
+        self.allowing_in(|this| {
+            for statement in statements {
+                this.new_line();
+                this.statement(statement);
+            }
+        });
+        self.indent -= 1;
+        self.new_line();
+        self.write("}");
+    }
+
+    /// `if` や `while` の本体を書く。
+    fn body(&mut self, body: &Statement) {
+        self.write(" ");
+        self.statement(body);
+    }
+
+    fn statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Expression(statement) => {
+                let start = self.output.len();
+                self.expression(&statement.expression, Precedence::Sequence);
+
+                // ディレクティブでない文字列の式文は括弧で囲み、ディレクティブと区別する
+                let is_string = matches!(
+                    &statement.expression,
+                    Expression::Literal(Literal {
+                        value: LiteralValue::String(_),
+                        ..
+                    })
+                );
+                if (is_string && statement.directive.is_none())
+                    || self.starts_ambiguously(start, AMBIGUOUS_STATEMENT_STARTS)
+                {
+                    self.wrap_from(start);
+                }
+                self.write(";");
+            }
+            Statement::Block(block) => self.block(&block.body),
+            Statement::Empty(_) => self.write(";"),
+            Statement::Debugger(_) => self.write("debugger;"),
+            Statement::With(statement) => {
+                self.write("with (");
+                self.expression(&statement.object, Precedence::Sequence);
+                self.write(")");
+                self.body(&statement.body);
+            }
+            Statement::Return(statement) => {
+                self.write("return");
+                if let Some(argument) = &statement.argument {
+                    self.write(" ");
+                    self.expression(argument, Precedence::Sequence);
+                }
+                self.write(";");
+            }
+            Statement::Labeled(statement) => {
+                self.identifier(&statement.label);
+                self.write(":");
+                self.body(&statement.body);
+            }
+            Statement::Break(statement) => {
+                self.write("break");
+                if let Some(label) = &statement.label {
+                    self.write(" ");
+                    self.identifier(label);
+                }
+                self.write(";");
+            }
+            Statement::Continue(statement) => {
+                self.write("continue");
+                if let Some(label) = &statement.label {
+                    self.write(" ");
+                    self.identifier(label);
+                }
+                self.write(";");
+            }
+            Statement::If(statement) => {
+                self.write("if (");
+                self.expression(&statement.test, Precedence::Sequence);
+                self.write(")");
+
+                match &statement.alternate {
+                    Some(alternate) => {
+                        // else が内側の if に結び付かないように、ブロックで囲む
+                        if ends_with_dangling_if(&statement.consequent) {
+                            self.write(" ");
+                            self.block(std::slice::from_ref(&statement.consequent));
+                        } else {
+                            self.body(&statement.consequent);
+                        }
+                        self.write(" else");
+                        self.body(alternate);
+                    }
+                    None => self.body(&statement.consequent),
+                }
+            }
+            Statement::Switch(statement) => {
+                self.write("switch (");
+                self.expression(&statement.discriminant, Precedence::Sequence);
+                self.write(") {");
+                self.indent += 1;
+                for case in &statement.cases {
+                    self.new_line();
+                    match &case.test {
+                        Some(test) => {
+                            self.write("case ");
+                            self.expression(test, Precedence::Sequence);
+                            self.write(":");
+                        }
+                        None => self.write("default:"),
+                    }
+                    self.indent += 1;
+                    for statement in &case.consequent {
+                        self.new_line();
+                        self.statement(statement);
+                    }
+                    self.indent -= 1;
+                }
+                self.indent -= 1;
+                self.new_line();
+                self.write("}");
+            }
+            Statement::Throw(statement) => {
+                self.write("throw ");
+                self.expression(&statement.argument, Precedence::Sequence);
+                self.write(";");
+            }
+            Statement::Try(statement) => {
+                self.write("try ");
+                self.block(&statement.block.body);
+                if let Some(handler) = &statement.handler {
+                    self.write(" catch ");
+                    if let Some(param) = &handler.param {
+                        self.write("(");
+                        self.pattern(param);
+                        self.write(") ");
+                    }
+                    self.block(&handler.body.body);
+                }
+                if let Some(finalizer) = &statement.finalizer {
+                    self.write(" finally ");
+                    self.block(&finalizer.body);
+                }
+            }
+            Statement::While(statement) => {
+                self.write("while (");
+                self.expression(&statement.test, Precedence::Sequence);
+                self.write(")");
+                self.body(&statement.body);
+            }
+            Statement::DoWhile(statement) => {
+                self.write("do");
+                self.body(&statement.body);
+                self.write(" while (");
+                self.expression(&statement.test, Precedence::Sequence);
+                self.write(");");
+            }
+            Statement::For(statement) => {
+                self.write("for (");
+                if let Some(init) = &statement.init {
+                    let no_in = std::mem::replace(&mut self.no_in, true);
+                    match init {
+                        ForInit::VariableDeclaration(declaration) => {
+                            self.variable_declaration(declaration)
+                        }
+                        ForInit::Expression(expression) => {
+                            let start = self.output.len();
+                            self.expression(expression, Precedence::Sequence);
+                            if self.starts_ambiguously(start, &["let"]) {
+                                self.wrap_from(start);
+                            }
+                        }
+                    }
+                    self.no_in = no_in;
+                }
+                self.write(";");
+                if let Some(test) = &statement.test {
+                    self.write(" ");
+                    self.expression(test, Precedence::Sequence);
+                }
+                self.write(";");
+                if let Some(update) = &statement.update {
+                    self.write(" ");
+                    self.expression(update, Precedence::Sequence);
+                }
+                self.write(")");
+                self.body(&statement.body);
+            }
+            Statement::ForIn(statement) => {
+                self.write("for (");
+                self.for_in_of_left(&statement.left, false);
+                self.write(" in ");
+                self.expression(&statement.right, Precedence::Sequence);
+                self.write(")");
+                self.body(&statement.body);
+            }
+            Statement::ForOf(statement) => {
+                self.write(if statement.is_await {
+                    "for await ("
+                } else {
+                    "for ("
+                });
+                self.for_in_of_left(&statement.left, true);
+                self.write(" of ");
+                self.expression(&statement.right, Precedence::Assignment);
+                self.write(")");
+                self.body(&statement.body);
+            }
+            Statement::FunctionDeclaration(function) => self.function(function),
+            Statement::VariableDeclaration(declaration) => {
+                self.variable_declaration(declaration);
+                self.write(";");
+            }
+            Statement::ClassDeclaration(class) => self.class(class),
+            Statement::Error(_) => self.write("/* error */;"),
+        }
+    }
+
+    fn for_in_of_left(&mut self, left: &ForInOfLeft, is_of: bool) {
+        let no_in = std::mem::replace(&mut self.no_in, true);
+        match left {
+            ForInOfLeft::VariableDeclaration(declaration) => self.variable_declaration(declaration),
+            ForInOfLeft::Pattern(pattern) => {
+                let start = self.output.len();
+                self.pattern(pattern);
+
+                // `for (async of a)` と `for (let ...` は別の構文になる
+                let is_async = is_of
+                    && matches!(pattern, Pattern::Identifier(identifier) if identifier.name == "async");
+                if is_async || self.starts_ambiguously(start, &["let"]) {
+                    self.wrap_from(start);
+                }
+            }
+        }
+        self.no_in = no_in;
+    }
+
+    fn variable_declaration(&mut self, declaration: &VariableDeclaration) {
+        self.write(declaration.kind.as_str());
+        self.write(" ");
+        self.comma_separated(&declaration.declarations, |this, declarator| {
+            this.pattern(&declarator.id);
+            if let Some(init) = &declarator.init {
+                this.write(" = ");
+                this.expression(init, Precedence::Assignment);
+            }
+        });
+    }
+
+    fn function(&mut self, function: &Function) {
+        if function.is_async {
+            self.write("async ");
+        }
+        self.write("function");
+        if function.generator {
+            self.write("*");
+        }
+        self.write(" ");
+        if let Some(id) = &function.id {
+            self.identifier(id);
+        }
+        self.function_rest(function);
+    }
+
+    /// 引数と本体を書く。
+    fn function_rest(&mut self, function: &Function) {
+        self.parameters(&function.params);
+        self.write(" ");
+        self.function_body(&function.body);
+    }
+
+    fn parameters(&mut self, params: &[Pattern]) {
+        self.write("(");
+        self.allowing_in(|this| this.comma_separated(params, Self::pattern));
+        self.write(")");
+    }
+
+    fn function_body(&mut self, body: &FunctionBody) {
+        self.block(&body.body);
+    }
+
+    fn arrow_function(&mut self, arrow: &ArrowFunctionExpression) {
+        if arrow.is_async {
+            self.write("async ");
+        }
+        self.parameters(&arrow.params);
+        self.write(" => ");
+
+        match &arrow.body {
+            ArrowFunctionBody::Expression(expression) => {
+                let start = self.output.len();
+                self.expression(expression, Precedence::Assignment);
+                if self.starts_ambiguously(start, &["{"]) {
+                    self.wrap_from(start);
+                }
+            }
+            ArrowFunctionBody::FunctionBody(body) => self.function_body(body),
+        }
+    }
+
+    fn class(&mut self, class: &Class) {
+        self.write("class");
+        if let Some(id) = &class.id {
+            self.write(" ");
+            self.identifier(id);
+        }
+        if let Some(super_class) = &class.super_class {
+            self.write(" extends ");
+            self.expression(super_class, Precedence::LeftHandSide);
+        }
+        self.write(" {");
+        if class.body.body.is_empty() {
+            self.write("}");
+            return;
+        }
+
+        self.indent += 1;
+        self.allowing_in(|this| {
+            for element in &class.body.body {
+                this.new_line();
+                this.class_element(element);
+            }
+        });
+        self.indent -= 1;
+        self.new_line();
+        self.write("}");
+    }
+
+    fn class_element(&mut self, element: &ClassElement) {
+        match element {
+            ClassElement::Method(method) => {
+                if method.is_static {
+                    self.write("static ");
+                }
+                match method.kind {
+                    MethodKind::Get => self.write("get "),
+                    MethodKind::Set => self.write("set "),
+                    MethodKind::Constructor | MethodKind::Method => {}
+                }
+                self.method(&method.key, &method.value);
+            }
+            ClassElement::Property(property) => {
+                if property.is_static {
+                    self.write("static ");
+                }
+                self.property_key(&property.key);
+                if let Some(value) = &property.value {
+                    self.write(" = ");
+                    self.expression(value, Precedence::Assignment);
+                }
+                self.write(";");
+            }
+            ClassElement::StaticBlock(block) => {
+                self.write("static ");
+                self.block(&block.body);
+            }
+        }
+    }
+
+    /// `async *key(params) {}` を書く。
+    fn method(&mut self, key: &PropertyKey, function: &Function) {
+        if function.is_async {
+            self.write("async ");
+        }
+        if function.generator {
+            self.write("*");
+        }
+        self.property_key(key);
+        self.function_rest(function);
+    }
+
+    fn property_key(&mut self, key: &PropertyKey) {
+        match key {
+            PropertyKey::Identifier(identifier) => self.identifier(identifier),
+            PropertyKey::Literal(literal) => self.literal(literal),
+            PropertyKey::PrivateIdentifier(identifier) => {
+                self.write("#");
+                self.write(&identifier.name);
+            }
+            PropertyKey::Computed(expression) => {
+                self.write("[");
+                self.allowing_in(|this| this.expression(expression, Precedence::Assignment));
+                self.write("]");
+            }
+        }
+    }
+
+    fn property(&mut self, property: &Property) {
+        match property.kind {
+            PropertyKind::Get => {
+                self.write("get ");
+                self.object_method(property);
+            }
+            PropertyKind::Set => {
+                self.write("set ");
+                self.object_method(property);
+            }
+            PropertyKind::Init if property.method => self.object_method(property),
+            PropertyKind::Init => {
+                if property.shorthand && is_same_identifier(&property.key, &property.value) {
+                    self.expression(&property.value, Precedence::Assignment);
+                    return;
+                }
+                self.property_key(&property.key);
+                self.write(": ");
+                self.expression(&property.value, Precedence::Assignment);
+            }
+        }
+    }
+
+    fn object_method(&mut self, property: &Property) {
+        match &property.value {
+            Expression::Function(function) => self.method(&property.key, function),
+            value => {
+                // 関数でない値はメソッドとして書けないため、通常のプロパティにする
+                self.property_key(&property.key);
+                self.write(": ");
+                self.expression(value, Precedence::Assignment);
+            }
+        }
+    }
+
+    fn pattern(&mut self, pattern: &Pattern) {
+        match pattern {
+            Pattern::Identifier(identifier) => self.identifier(identifier),
+            Pattern::Object(object) => {
+                self.write("{");
+                if !object.properties.is_empty() {
+                    self.write(" ");
+                    self.allowing_in(|this| {
+                        this.comma_separated(&object.properties, |this, property| match property {
+                            ObjectPatternProperty::Property(property) => {
+                                let is_shorthand = property.shorthand
+                                    && match &property.value {
+                                        Pattern::Assignment(assignment) => {
+                                            is_same_binding(&property.key, &assignment.left)
+                                        }
+                                        value => is_same_binding(&property.key, value),
+                                    };
+                                if !is_shorthand {
+                                    this.property_key(&property.key);
+                                    this.write(": ");
+                                }
+                                this.pattern(&property.value);
+                            }
+                            ObjectPatternProperty::Rest(rest) => {
+                                this.write("...");
+                                this.pattern(&rest.argument);
+                            }
+                        });
+                    });
+                    self.write(" ");
+                }
+                self.write("}");
+            }
+            Pattern::Array(array) => {
+                self.write("[");
+                self.allowing_in(|this| {
+                    this.elements(&array.elements, Self::pattern);
+                });
+                self.write("]");
+            }
+            Pattern::Rest(rest) => {
+                self.write("...");
+                self.pattern(&rest.argument);
+            }
+            Pattern::Assignment(assignment) => {
+                self.pattern(&assignment.left);
+                self.write(" = ");
+                self.expression(&assignment.right, Precedence::Assignment);
+            }
+            Pattern::Expression(expression) => {
+                self.expression(expression, Precedence::LeftHandSide)
+            }
+        }
+    }
+
+    /// 配列の要素を書く。最後の要素が省略されている場合は末尾にカンマを足す。
+    fn elements<T>(&mut self, elements: &[Option<T>], mut print: impl FnMut(&mut Self, &T)) {
+        self.comma_separated(elements, |this, element| {
+            if let Some(element) = element {
+                print(this, element);
+            }
+        });
+        if matches!(elements.last(), Some(None)) {
+            self.write(",");
+        }
+    }
+
+    fn identifier(&mut self, identifier: &Identifier) {
+        self.write(&identifier.name);
+    }
+
+    fn literal(&mut self, literal: &Literal) {
+        if !literal.raw.is_empty() {
+            self.write(&literal.raw);
+            return;
+        }
+
+        // 変換で作ったノードは表記を持たないため、値から書く
+        match &literal.value {
+            LiteralValue::Null => self.write("null"),
+            LiteralValue::Boolean(value) => self.write(if *value { "true" } else { "false" }),
+            LiteralValue::Number(value) if value.is_nan() => self.write("NaN"),
+            LiteralValue::Number(value) if value.is_infinite() => self.write("Infinity"),
+            LiteralValue::Number(value) => self.write(&format_number(*value)),
+            LiteralValue::String(value) => self.write(&quote_string(value)),
+            LiteralValue::BigInt(value) => self.write(&format!("{value}n")),
+            LiteralValue::RegExp(regexp) => {
+                self.write(&format!("/{}/{}", regexp.pattern, regexp.flags))
+            }
+        }
+    }
+
+    fn template_literal(&mut self, template: &TemplateLiteral) {
+        self.write("`");
+        for (index, quasi) in template.quasis.iter().enumerate() {
+            self.output.push_str(&quasi.raw);
+            if let Some(expression) = template.expressions.get(index) {
+                self.output.push_str("${");
+                self.allowing_in(|this| this.expression(expression, Precedence::Sequence));
+                self.output.push('}');
+            }
+        }
+        self.output.push('`');
+    }
+
+    fn arguments(&mut self, arguments: &[ExpressionOrSpread]) {
+        self.write("(");
+        self.allowing_in(|this| this.comma_separated(arguments, Self::expression_or_spread));
+        self.write(")");
+    }
+
+    fn expression_or_spread(&mut self, expression: &ExpressionOrSpread) {
+        match expression {
+            ExpressionOrSpread::Expression(expression) => {
+                self.expression(expression, Precedence::Assignment)
+            }
+            ExpressionOrSpread::Spread(spread) => {
+                self.write("...");
+                self.expression(&spread.argument, Precedence::Assignment);
+            }
+        }
+    }
+
+    /// 式を書く。式の優先順位が `precedence` より低い場合は括弧で囲む。
+    fn expression(&mut self, expression: &Expression, precedence: Precedence) {
+        let is_in = matches!(
+            expression,
+            Expression::Binary(binary) if binary.operator == BinaryOperator::In
+        );
+
+        if Precedence::of(expression) < precedence || (is_in && self.no_in) {
+            self.write("(");
+            self.allowing_in(|this| this.expression_without_parentheses(expression));
+            self.write(")");
+        } else {
+            self.expression_without_parentheses(expression);
+        }
+    }
+
+    fn expression_without_parentheses(&mut self, expression: &Expression) {
+        match expression {
+            Expression::Identifier(identifier) => self.identifier(identifier),
+            Expression::Literal(literal) => self.literal(literal),
+            Expression::This(_) => self.write("this"),
+            Expression::Super(_) => self.write("super"),
+            Expression::Array(array) => {
+                self.write("[");
+                self.allowing_in(|this| this.elements(&array.elements, Self::expression_or_spread));
+                self.write("]");
+            }
+            Expression::Object(object) => {
+                self.write("{");
+                if !object.properties.is_empty() {
+                    self.write(" ");
+                    self.allowing_in(|this| {
+                        this.comma_separated(&object.properties, |this, property| match property {
+                            ObjectProperty::Property(property) => this.property(property),
+                            ObjectProperty::Spread(spread) => {
+                                this.write("...");
+                                this.expression(&spread.argument, Precedence::Assignment);
+                            }
+                        });
+                    });
+                    self.write(" ");
+                }
+                self.write("}");
+            }
+            Expression::Function(function) => self.function(function),
+            Expression::ArrowFunction(arrow) => self.arrow_function(arrow),
+            Expression::Class(class) => self.class(class),
+            Expression::TemplateLiteral(template) => self.template_literal(template),
+            Expression::TaggedTemplate(tagged) => {
+                self.callee(&tagged.tag);
+                self.template_literal(&tagged.quasi);
+            }
+            Expression::Member(member) => {
+                let is_integer = matches!(
+                    &member.object,
+                    Expression::Literal(literal @ Literal { value: LiteralValue::Number(_), .. })
+                        if literal.raw.chars().all(|char| char.is_ascii_digit())
+                );
+                if is_integer {
+                    // `1.a` は小数点として読まれる
+                    self.write("(");
+                    self.expression(&member.object, Precedence::Primary);
+                    self.write(")");
+                } else {
+                    self.callee(&member.object);
+                }
+
+                match &member.property {
+                    MemberProperty::Identifier(identifier) => {
+                        self.write(if member.optional { "?." } else { "." });
+                        self.identifier(identifier);
+                    }
+                    MemberProperty::PrivateIdentifier(identifier) => {
+                        self.write(if member.optional { "?.#" } else { ".#" });
+                        self.write(&identifier.name);
+                    }
+                    MemberProperty::Computed(property) => {
+                        self.write(if member.optional { "?.[" } else { "[" });
+                        self.allowing_in(|this| this.expression(property, Precedence::Sequence));
+                        self.write("]");
+                    }
+                }
+            }
+            Expression::Call(call) => {
+                self.callee(&call.callee);
+                if call.optional {
+                    self.write("?.");
+                }
+                self.arguments(&call.arguments);
+            }
+            Expression::New(new) => {
+                self.write("new ");
+                if new_callee_needs_parentheses(&new.callee) {
+                    self.write("(");
+                    self.allowing_in(|this| this.expression(&new.callee, Precedence::Sequence));
+                    self.write(")");
+                } else {
+                    self.expression(&new.callee, Precedence::LeftHandSide);
+                }
+                self.arguments(&new.arguments);
+            }
+            Expression::Chain(chain) => self.expression_without_parentheses(&chain.expression),
+            Expression::Unary(unary) => {
+                self.write(&unary.operator.to_source_string());
+                if matches!(
+                    unary.operator,
+                    UnaryOperator::Typeof | UnaryOperator::Void | UnaryOperator::Delete
+                ) {
+                    self.write(" ");
+                }
+                self.expression(&unary.argument, Precedence::Unary);
+            }
+            Expression::Update(update) => {
+                if update.prefix {
+                    self.write(&update.operator.to_source_string());
+                    self.expression(&update.argument, Precedence::Unary);
+                } else {
+                    self.expression(&update.argument, Precedence::LeftHandSide);
+                    self.write(&update.operator.to_source_string());
+                }
+            }
+            Expression::Binary(binary) => {
+                let precedence = Precedence::binary(binary.operator);
+
+                // ** は右結合で、左辺に単項演算子を置けない
+                let (left, right) = if binary.operator == BinaryOperator::Exponentiation {
+                    (Precedence::Update, precedence)
+                } else {
+                    (precedence, next(precedence))
+                };
+
+                if let Expression::PrivateIdentifier(identifier) = &binary.left {
+                    self.write("#");
+                    self.write(&identifier.name);
+                } else {
+                    self.expression(&binary.left, left);
+                }
+                self.write(" ");
+                self.write(&binary.operator.to_source_string());
+                self.write(" ");
+                self.expression(&binary.right, right);
+            }
+            Expression::Logical(logical) => {
+                let precedence = Precedence::logical(logical.operator);
+
+                // ?? は || と && を括弧なしで混ぜることができない
+                let (left, right) = if logical.operator == LogicalOperator::NullishCoalesce {
+                    let left = match &logical.left {
+                        Expression::Logical(left)
+                            if left.operator == LogicalOperator::NullishCoalesce =>
+                        {
+                            precedence
+                        }
+                        _ => Precedence::BitOr,
+                    };
+                    (left, Precedence::BitOr)
+                } else {
+                    (precedence, next(precedence))
+                };
+
+                self.expression(&logical.left, left);
+                self.write(" ");
+                self.write(&logical.operator.to_source_string());
+                self.write(" ");
+                self.expression(&logical.right, right);
+            }
+            Expression::Conditional(conditional) => {
+                self.expression(&conditional.test, Precedence::NullishCoalesce);
+                self.write(" ? ");
+                self.allowing_in(|this| {
+                    this.expression(&conditional.consequent, Precedence::Assignment)
+                });
+                self.write(" : ");
+                self.expression(&conditional.alternate, Precedence::Assignment);
+            }
+            Expression::Assignment(assignment) => {
+                self.pattern(&assignment.left);
+                self.write(" ");
+                self.write(&assignment.operator.to_source_string());
+                self.write(" ");
+                self.expression(&assignment.right, Precedence::Assignment);
+            }
+            Expression::Sequence(sequence) => {
+                self.comma_separated(&sequence.expressions, |this, expression| {
+                    this.expression(expression, Precedence::Assignment)
+                });
+            }
+            Expression::Yield(yield_expression) => {
+                self.write("yield");
+                if yield_expression.delegate {
+                    self.write("*");
+                }
+                if let Some(argument) = &yield_expression.argument {
+                    self.write(" ");
+                    self.expression(argument, Precedence::Assignment);
+                }
+            }
+            Expression::Await(await_expression) => {
+                self.write("await ");
+                self.expression(&await_expression.argument, Precedence::Unary);
+            }
+            Expression::MetaProperty(meta) => {
+                self.identifier(&meta.meta);
+                self.write(".");
+                self.identifier(&meta.property);
+            }
+            Expression::Import(import) => {
+                self.write("import(");
+                self.allowing_in(|this| {
+                    this.expression(&import.source, Precedence::Assignment);
+                    if let Some(options) = &import.options {
+                        this.write(", ");
+                        this.expression(options, Precedence::Assignment);
+                    }
+                });
+                self.write(")");
+            }
+            Expression::PrivateIdentifier(identifier) => {
+                self.write("#");
+                self.write(&identifier.name);
+            }
+            Expression::Error(_) => self.write("/* error */ void 0"),
+        }
+    }
+
+    /// 呼び出しとメンバー参照の対象を書く。
+    ///
+    /// `(a?.b).c` のように、オプショナルチェーンの外側に続ける場合は括弧で囲む。
+    fn callee(&mut self, callee: &Expression) {
+        if matches!(callee, Expression::Chain(_)) {
+            self.write("(");
+            self.allowing_in(|this| this.expression_without_parentheses(callee));
+            self.write(")");
+        } else {
+            self.expression(callee, Precedence::LeftHandSide);
+        }
+    }
+
+    fn module_declaration(&mut self, declaration: &ModuleDeclaration) {
+        match declaration {
+            ModuleDeclaration::Import(import) => {
+                self.write("import ");
+                let mut named = Vec::new();
+                let mut parts = Vec::new();
+                for specifier in &import.specifiers {
+                    match specifier {
+                        ImportDeclarationSpecifier::Default(specifier) => {
+                            parts.push(specifier.local.name.clone())
+                        }
+                        ImportDeclarationSpecifier::Namespace(specifier) => {
+                            parts.push(format!("* as {}", specifier.local.name))
+                        }
+                        ImportDeclarationSpecifier::Named(specifier) => named.push(specifier),
+                    }
+                }
+                if !named.is_empty() {
+                    let named = named
+                        .iter()
+                        .map(|specifier| {
+                            let imported = module_export_name(&specifier.imported);
+                            if imported == specifier.local.name {
+                                imported
+                            } else {
+                                format!("{imported} as {}", specifier.local.name)
+                            }
+                        })
+                        .collect::<Vec<_>>();
+                    parts.push(format!("{{ {} }}", named.join(", ")));
+                }
+                if !parts.is_empty() {
+                    self.write(&parts.join(", "));
+                    self.write(" from ");
+                }
+                self.literal(&import.source);
+                self.import_attributes(&import.attributes);
+                self.write(";");
+            }
+            ModuleDeclaration::ExportNamed(export) => {
+                self.write("export ");
+                if let Some(declaration) = &export.declaration {
+                    match declaration {
+                        Declaration::Function(function) => self.function(function),
+                        Declaration::Variable(declaration) => {
+                            self.variable_declaration(declaration);
+                            self.write(";");
+                        }
+                        Declaration::Class(class) => self.class(class),
+                    }
+                    return;
+                }
+
+                let specifiers = export
+                    .specifiers
+                    .iter()
+                    .map(|specifier| {
+                        let local = module_export_name(&specifier.local);
+                        let exported = module_export_name(&specifier.exported);
+                        if local == exported {
+                            local
+                        } else {
+                            format!("{local} as {exported}")
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                if specifiers.is_empty() {
+                    self.write("{}");
+                } else {
+                    self.write(&format!("{{ {} }}", specifiers.join(", ")));
+                }
+                if let Some(source) = &export.source {
+                    self.write(" from ");
+                    self.literal(source);
+                    self.import_attributes(&export.attributes);
+                }
+                self.write(";");
+            }
+            ModuleDeclaration::ExportDefault(export) => {
+                self.write("export default ");
+                match &export.declaration {
+                    ExportDefaultKind::Function(function) => self.function(function),
+                    ExportDefaultKind::Class(class) => self.class(class),
+                    ExportDefaultKind::Expression(expression) => {
+                        let start = self.output.len();
+                        self.expression(expression, Precedence::Assignment);
+                        if self.starts_ambiguously(start, AMBIGUOUS_STATEMENT_STARTS) {
+                            self.wrap_from(start);
+                        }
+                        self.write(";");
+                    }
+                }
+            }
+            ModuleDeclaration::ExportAll(export) => {
+                self.write("export *");
+                if let Some(exported) = &export.exported {
+                    self.write(" as ");
+                    self.write(&module_export_name(exported));
+                }
+                self.write(" from ");
+                self.literal(&export.source);
+                self.import_attributes(&export.attributes);
+                self.write(";");
+            }
+        }
+    }
+
+    fn import_attributes(&mut self, attributes: &[ImportAttribute]) {
+        if attributes.is_empty() {
+            return;
+        }
+        self.write(" with { ");
+        self.comma_separated(attributes, |this, attribute| {
+            this.property_key(&attribute.key);
+            this.write(": ");
+            this.literal(&attribute.value);
+        });
+        self.write(" }");
+    }
+}
+
+/// 1つ強く結び付く優先順位を返す。
+fn next(precedence: Precedence) -> Precedence {
+    use Precedence::*;
+
+    match precedence {
+        Sequence => Assignment,
+        Assignment => Conditional,
+        Conditional => NullishCoalesce,
+        NullishCoalesce => LogicalOr,
+        LogicalOr => LogicalAnd,
+        LogicalAnd => BitOr,
+        BitOr => BitXor,
+        BitXor => BitAnd,
+        BitAnd => Equality,
+        Equality => Relational,
+        Relational => Shift,
+        Shift => Additive,
+        Additive => Multiplicative,
+        Multiplicative => Exponentiation,
+        Exponentiation => Unary,
+        Unary => Update,
+        Update => New,
+        New => LeftHandSide,
+        LeftHandSide | Primary => Primary,
+    }
+}
+
+/// 文の最後が else のない if で終わるかを返す。
+fn ends_with_dangling_if(statement: &Statement) -> bool {
+    match statement {
+        Statement::If(statement) => match &statement.alternate {
+            Some(alternate) => ends_with_dangling_if(alternate),
+            None => true,
+        },
+        Statement::With(statement) => ends_with_dangling_if(&statement.body),
+        Statement::Labeled(statement) => ends_with_dangling_if(&statement.body),
+        Statement::While(statement) => ends_with_dangling_if(&statement.body),
+        Statement::For(statement) => ends_with_dangling_if(&statement.body),
+        Statement::ForIn(statement) => ends_with_dangling_if(&statement.body),
+        Statement::ForOf(statement) => ends_with_dangling_if(&statement.body),
+        _ => false,
+    }
+}
+
+/// `new` の対象が呼び出しやオプショナルチェーンを含み、括弧が必要かを返す。
+///
+/// `new (a())()` の括弧を外すと `new a()` の呼び出しになる。
+fn new_callee_needs_parentheses(callee: &Expression) -> bool {
+    match callee {
+        Expression::Call(_) | Expression::Chain(_) | Expression::Import(_) => true,
+        Expression::Member(member) => new_callee_needs_parentheses(&member.object),
+        Expression::TaggedTemplate(tagged) => new_callee_needs_parentheses(&tagged.tag),
+        _ => false,
+    }
+}
+
+/// 省略記法で書けるプロパティかを返す。
+fn is_same_identifier(key: &PropertyKey, value: &Expression) -> bool {
+    matches!(
+        (key, value),
+        (PropertyKey::Identifier(key), Expression::Identifier(value)) if key.name == value.name
+    )
+}
+
+fn is_same_binding(key: &PropertyKey, value: &Pattern) -> bool {
+    matches!(
+        (key, value),
+        (PropertyKey::Identifier(key), Pattern::Identifier(value)) if key.name == value.name
+    )
+}
+
+fn module_export_name(name: &ModuleExportName) -> String {
+    match name {
+        ModuleExportName::Identifier(identifier) => identifier.name.clone(),
+        ModuleExportName::Literal(literal) if !literal.raw.is_empty() => literal.raw.clone(),
+        ModuleExportName::Literal(literal) => quote_string(literal.as_str().unwrap_or_default()),
+    }
+}
+
+/// 文字列を二重引用符の文字列リテラルにする。
+fn quote_string(value: &str) -> String {
+    let mut output = String::from("\"");
+    for char in value.chars() {
+        match char {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\u{2028}' => output.push_str("\\u2028"),
+            '\u{2029}' => output.push_str("\\u2029"),
+            '\0'..='\u{1f}' => output.push_str(&format!("\\x{:02x}", char as u32)),
+            _ => output.push(char),
+        }
+    }
+    output.push('"');
+    output
+}
+
+impl ToSourceString for JsAst {
+    fn to_source_string(&self) -> String {
+        let mut printer = Printer::default();
+        printer.program(self);
+        printer.output
+    }
+}
+
+impl ToSourceString for Statement {
+    fn to_source_string(&self) -> String {
+        let mut printer = Printer::default();
+        printer.statement(self);
+        printer.output
+    }
+}
+
+impl ToSourceString for Expression {
+    fn to_source_string(&self) -> String {
+        let mut printer = Printer::default();
+        printer.expression(self, Precedence::Sequence);
+        printer.output
+    }
+}
+
+impl ToSourceString for Pattern {
+    fn to_source_string(&self) -> String {
+        let mut printer = Printer::default();
+        printer.pattern(self);
+        printer.output
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::random::RandomExpression;
+    use crate::{
+        ast::{
+            estree::to_estree,
+            reader_struct::SourceCodeReader,
+            script::{parse_module, parse_script},
+            structs::{
+                module::ModuleItem,
+                span::Span,
+                statement::{ExpressionStatement, Statement},
+                JsAst, SourceType,
+            },
+        },
+        json::JsonValue,
+        to_source_string::ToSourceString,
+    };
+
+    fn parse(source: &str, source_type: SourceType) -> JsAst {
+        let output = match source_type {
+            SourceType::Script => parse_script(SourceCodeReader::new(source)),
+            SourceType::Module => parse_module(SourceCodeReader::new(source)),
+        };
+        output
+            .into_result()
+            .unwrap_or_else(|error| panic!("{source}: {error:?}"))
+    }
+
+    /// 位置を除いた ESTree の JSON
+    fn without_positions(value: JsonValue) -> JsonValue {
+        match value {
+            JsonValue::Array(values) => {
+                JsonValue::Array(values.into_iter().map(without_positions).collect())
+            }
+            JsonValue::Object(entries) => JsonValue::Object(
+                entries
+                    .into_iter()
+                    .filter(|(key, _)| !matches!(key.as_str(), "start" | "end" | "loc" | "range"))
+                    .map(|(key, value)| (key, without_positions(value)))
+                    .collect(),
+            ),
+            value => value,
+        }
+    }
+
+    /// ソースコードに戻して解析し直すと、同じ構文木になることを確かめる。
+    fn assert_round_trip(ast: &JsAst, source: &str) -> String {
+        let printed = ast.to_source_string();
+        let reparsed = parse(&printed, ast.source_type);
+        assert_eq!(
+            without_positions(to_estree(&reparsed, &printed)).to_pretty_string(),
+            without_positions(to_estree(ast, source)).to_pretty_string(),
+            "{source}\n=> {printed}"
+        );
+        printed
+    }
+
+    fn print(source: &str) -> String {
+        assert_round_trip(&parse(source, SourceType::Script), source)
+    }
+
+    #[test]
+    fn parentheses() {
+        assert_eq!(print("(a + b) * c; a + b * c;"), "(a + b) * c;\na + b * c;");
+        assert_eq!(
+            print("a - (b - c); (a - b) - c;"),
+            "a - (b - c);\na - b - c;"
+        );
+        assert_eq!(
+            print("(a ** b) ** c; a ** (b ** c);"),
+            "(a ** b) ** c;\na ** b ** c;"
+        );
+        assert_eq!(
+            print("(-a) ** b; (a ?? b) || c;"),
+            "(-a) ** b;\n(a ?? b) || c;"
+        );
+        assert_eq!(print("a ?? (b ?? c); (a, b);"), "a ?? (b ?? c);\na, b;");
+        assert_eq!(
+            print("(a ? b : c) ? d : (e, f);"),
+            "(a ? b : c) ? d : (e, f);"
+        );
+        assert_eq!(print("(a = b).c; f((a, b));"), "(a = b).c;\nf((a, b));");
+        assert_eq!(
+            print("(() => a)(); (a?.b).c; (a?.b)();"),
+            "(() => a)();\n(a?.b).c;\n(a?.b)();"
+        );
+        assert_eq!(
+            print("new (a())(); new (a.b().c); new a;"),
+            "new (a())();\nnew (a.b().c)();\nnew a();"
+        );
+        assert_eq!(
+            print("(1).a; 1.5.a; (typeof a).b;"),
+            "(1).a;\n1.5.a;\n(typeof a).b;"
+        );
+    }
+
+    #[test]
+    fn adjacent_operators() {
+        assert_eq!(
+            print("a + +b; a - -b; a + -b;"),
+            "a + +b;\na - -b;\na + -b;"
+        );
+        assert_eq!(
+            print("+ +a; - --a; + ++a; !-a;"),
+            "+ +a;\n- --a;\n+ ++a;\n!-a;"
+        );
+        assert_eq!(
+            print("typeof typeof a; void (a, b);"),
+            "typeof typeof a;\nvoid (a, b);"
+        );
+    }
+
+    #[test]
+    fn statement_start() {
+        assert_eq!(print("({ a } = b);"), "({ a } = b);");
+        assert_eq!(print("({}).a; ({}) + 1;"), "({}.a);\n({} + 1);");
+        assert_eq!(print("(function () {})();"), "(function () {}());");
+        assert_eq!(
+            print("(class {}).a; (async function () {});"),
+            "(class {}.a);\n(async function () {});"
+        );
+        assert_eq!(
+            print("(let[0]) = 1; let.a = 1;"),
+            "(let[0] = 1);\nlet.a = 1;"
+        );
+        assert_eq!(print("'a'; ('b'); 'c';"), "'a';\n('b');\n('c');");
+        assert_eq!(
+            print("a => ({}); a => ({}).b;"),
+            "(a) => ({});\n(a) => ({}.b);"
+        );
+        assert_eq!(
+            print("functionA; classA; letA[0];"),
+            "functionA;\nclassA;\nletA[0];"
+        );
+    }
+
+    #[test]
+    fn for_heads() {
+        assert_eq!(
+            print("for (var a = (b in c); ;) ;"),
+            "for (var a = (b in c);;) ;"
+        );
+        assert_eq!(
+            print("for (a = (b in c) ? d : e; ;) ;"),
+            "for (a = (b in c) ? d : e;;) ;"
+        );
+        assert_eq!(print("for (f(a in b); ;) ;"), "for (f(a in b);;) ;");
+        assert_eq!(
+            print("for (a = () => { b in c; }; ;) ;"),
+            "for (a = () => {\n  b in c;\n};;) ;"
+        );
+        assert_eq!(
+            print("for ((let) in a) ; for ((let).a of b) ;"),
+            "for ((let) in a) ;\nfor ((let.a) of b) ;"
+        );
+        assert_eq!(print("for ((async) of a) ;"), "for ((async) of a) ;");
+        assert_eq!(print("for (a of (b, c)) ;"), "for (a of (b, c)) ;");
+    }
+
+    #[test]
+    fn statements() {
+        assert_eq!(
+            print("if (a) { if (b) c; } else d;"),
+            "if (a) {\n  if (b) c;\n} else d;"
+        );
+        assert_eq!(
+            print("if (a) b; else if (c) d; else e;"),
+            "if (a) b; else if (c) d; else e;"
+        );
+        assert_eq!(
+            print("switch (a) { case 1: b; default: }"),
+            "switch (a) {\n  case 1:\n    b;\n  default:\n}"
+        );
+        assert_eq!(print("[a, , b, ,];"), "[a, , b, ,];");
+        assert_eq!(
+            print("x = { a, b: b, c: d, ['e']: f, g() {}, get h() {} };"),
+            "x = { a, b: b, c: d, ['e']: f, g() {}, get h() {} };"
+        );
+    }
+
+    #[test]
+    fn corpus() {
+        let sources = [
+            "a: for (let i = 0, j; i < 10; i++) { if (i) continue a; else break; }",
+            "for (const [a, { b = 1, ...c }] of d) ; for (e.f in g) ; for (;;) {}",
+            "do a(); while (b) while (c) with (d) e;",
+            "switch (a) { case 1: b; default: c; } try {} catch { } finally {}",
+            "try { throw a } catch ({ b }) { debugger }",
+            "function* f(a, [b] = c, ...d) { yield* e; yield; return } function g(h) { 'use strict'; }",
+            "async function f() { await a; for await (b of c) ; } (async () => d); async (a) => await a;",
+            "function f() { new.target; return function () { 'use strict' } }",
+            "class A extends B { #a = 1; static b; static { this.#a } constructor() { super() } get c() {} set [d](e) {} static async *f() {} 'g'() {} }",
+            "x = { a, b: 1, 'c': 2, 3: d, [e]: f, g() {}, get h() {}, set h(i) {}, ...j, async *k() {} };",
+            "[a, , ...b] = [c, , ...d]; ({ a, b: { c } = {}, d = 1 } = e);",
+            "a?.b.c?.(d)?.[e]; new (f())(); new g; h`i${j}\\u{k}`; `l${m}n`; (a?.b)`c`;",
+            "a = b ? c : d, e += f ** -g, h ||= !i && j, k ?? l, typeof l, void m, delete n.o, ++p, q--;",
+            "class C { #c; m() { a in b, #c in d, e instanceof f; } } g, g >>> h, i !== j, k / l % m << n | o & p ^ q;",
+            "import('a'); import('b', { with: {} }); x = /a[/]b/g; y = 0x10n;",
+            "x = function g() { 'a'; 'use strict'; 10 }; y = () => { 'use strict' }",
+            "a = b = c; a = (b, c); (a, b) ? c : d; a ? b : c ? d : e; (a ? b : c) ? d : e;",
+            "x = a => b => c; x = (a => b)(c); x = (a, b) => ({ a, b }); x = async () => {};",
+            "if (a) for (;;) if (b) c; else d; else e;",
+            "x = new (a.b.c); x = new (a().b); x = new a.b.c(); x = (new a)(); x = new new a()();",
+            "x = -(-a); x = +(+a); x = - (--a); x = (a--) - b; x = a-- - --b;",
+            "x = 'a\\'b'; x = \"c\"; x = 1e3; x = .5; x = 0b11; x = 1_000;",
+            "label: { break label; } ; ; var a = 1, b;",
+        ];
+
+        for source in sources {
+            print(source);
+        }
+
+        let modules = [
+            "import a, { b as c, 'd' as e } from 'f' with { type: 'json' }; import * as g from 'h'; import 'i'; import {} from 'j';",
+            "let a, c; export { a as b, c as 'd' }; export * from 'e'; export * as f from 'g'; export { h } from 'i'; export {};",
+            "export default function () {} export const a = 1; export class B {} export function c() {}",
+            "export default class {}",
+            "export default (function () {});",
+            "export default (class {}).a;",
+            "export default a = 1; import.meta.url; await b;",
+        ];
+
+        for source in modules {
+            assert_round_trip(&parse(source, SourceType::Module), source);
+        }
+    }
+
+    #[test]
+    fn random_expressions() {
+        let mut random = RandomExpression::new(0x5eed);
+
+        for _ in 0..2000 {
+            let expression = random.expression(4);
+            let ast = JsAst {
+                span: Span::default(),
+                source_type: SourceType::Script,
+                body: vec![ModuleItem::Statement(Statement::Expression(
+                    ExpressionStatement {
+                        span: Span::default(),
+                        expression,
+                        directive: None,
+                    },
+                ))],
+            };
+            assert_round_trip(&ast, "");
+        }
+    }
+}
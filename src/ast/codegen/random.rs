@@ -0,0 +1,314 @@
+//! テスト用に、でたらめな式の構文木を作るモジュールです。
+//!
+//! 作る式はどれも構文として正しく、ソースコードに戻して解析し直せる。
+
+use crate::ast::structs::{
+    expression::{
+        ArrayExpression, AssignmentExpression, AssignmentOperator, BinaryExpression,
+        BinaryOperator, CallExpression, ChainExpression, ConditionalExpression, Expression,
+        ExpressionOrSpread, LogicalExpression, LogicalOperator, MemberExpression, MemberProperty,
+        NewExpression, ObjectExpression, ObjectProperty, Property, PropertyKey, PropertyKind,
+        SequenceExpression, SpreadElement, UnaryExpression, UnaryOperator, UpdateExpression,
+        UpdateOperator,
+    },
+    function::{ArrowFunctionBody, ArrowFunctionExpression},
+    literal::{Identifier, Literal, LiteralValue},
+    pattern::Pattern,
+    span::Span,
+    template::{TemplateElement, TemplateLiteral},
+};
+
+const NAMES: &[&str] = &["a", "b", "c"];
+
+const BINARY_OPERATORS: &[BinaryOperator] = &[
+    BinaryOperator::Equal,
+    BinaryOperator::StrictNotEqual,
+    BinaryOperator::LessThan,
+    BinaryOperator::In,
+    BinaryOperator::Instanceof,
+    BinaryOperator::LeftShift,
+    BinaryOperator::Plus,
+    BinaryOperator::Minus,
+    BinaryOperator::Multiply,
+    BinaryOperator::Divide,
+    BinaryOperator::Exponentiation,
+    BinaryOperator::BitOr,
+    BinaryOperator::BitXor,
+    BinaryOperator::BitAnd,
+];
+
+const LOGICAL_OPERATORS: &[LogicalOperator] = &[
+    LogicalOperator::Or,
+    LogicalOperator::And,
+    LogicalOperator::NullishCoalesce,
+];
+
+const UNARY_OPERATORS: &[UnaryOperator] = &[
+    UnaryOperator::Minus,
+    UnaryOperator::Plus,
+    UnaryOperator::LogicalNot,
+    UnaryOperator::BitNot,
+    UnaryOperator::Typeof,
+    UnaryOperator::Void,
+];
+
+const ASSIGNMENT_OPERATORS: &[AssignmentOperator] = &[
+    AssignmentOperator::Assign,
+    AssignmentOperator::PlusAssign,
+    AssignmentOperator::ExponentiationAssign,
+    AssignmentOperator::NullishCoalesceAssign,
+];
+
+/// xorshift による疑似乱数で式を作る。
+pub struct RandomExpression {
+    state: u64,
+}
+
+impl RandomExpression {
+    pub fn new(seed: u64) -> RandomExpression {
+        RandomExpression { state: seed.max(1) }
+    }
+
+    fn next(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    /// `0..n` の値を返す。
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn choose<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.below(items.len())]
+    }
+
+    fn identifier(&mut self) -> Identifier {
+        let name = self.choose(NAMES);
+        Identifier::new(Span::default(), name)
+    }
+
+    fn literal(&mut self) -> Literal {
+        let (value, raw) = match self.below(4) {
+            0 => (LiteralValue::Number(1.0), "1"),
+            1 => (LiteralValue::Number(2.5), "2.5"),
+            2 => (LiteralValue::String("s".to_string()), "'s'"),
+            _ => (LiteralValue::Null, "null"),
+        };
+        Literal {
+            span: Span::default(),
+            value,
+            raw: raw.to_string(),
+        }
+    }
+
+    /// 代入と更新の対象 (識別子か、オプショナルでないメンバー参照)
+    fn target(&mut self, depth: usize) -> Expression {
+        if depth == 0 || self.below(2) == 0 {
+            return Expression::Identifier(self.identifier());
+        }
+        Expression::Member(Box::new(MemberExpression {
+            span: Span::default(),
+            object: self.expression(depth - 1),
+            property: MemberProperty::Identifier(self.identifier()),
+            optional: false,
+        }))
+    }
+
+    fn arguments(&mut self, depth: usize) -> Vec<ExpressionOrSpread> {
+        (0..self.below(3))
+            .map(|_| {
+                let expression = self.expression(depth);
+                if self.below(4) == 0 {
+                    ExpressionOrSpread::Spread(SpreadElement {
+                        span: Span::default(),
+                        argument: expression,
+                    })
+                } else {
+                    ExpressionOrSpread::Expression(expression)
+                }
+            })
+            .collect()
+    }
+
+    /// 深さ `depth` までの式を作る。
+    pub fn expression(&mut self, depth: usize) -> Expression {
+        let span = Span::default();
+        if depth == 0 {
+            return match self.below(2) {
+                0 => Expression::Identifier(self.identifier()),
+                _ => Expression::Literal(self.literal()),
+            };
+        }
+
+        let depth = depth - 1;
+        match self.below(17) {
+            0 => Expression::Binary(Box::new(BinaryExpression {
+                span,
+                operator: self.choose(BINARY_OPERATORS),
+                left: self.expression(depth),
+                right: self.expression(depth),
+            })),
+            1 => Expression::Logical(Box::new(LogicalExpression {
+                span,
+                operator: self.choose(LOGICAL_OPERATORS),
+                left: self.expression(depth),
+                right: self.expression(depth),
+            })),
+            2 => Expression::Unary(Box::new(UnaryExpression {
+                span,
+                operator: self.choose(UNARY_OPERATORS),
+                argument: self.expression(depth),
+            })),
+            3 => Expression::Update(Box::new(UpdateExpression {
+                span,
+                operator: if self.below(2) == 0 {
+                    UpdateOperator::Increment
+                } else {
+                    UpdateOperator::Decrement
+                },
+                prefix: self.below(2) == 0,
+                argument: self.target(depth),
+            })),
+            4 => Expression::Conditional(Box::new(ConditionalExpression {
+                span,
+                test: self.expression(depth),
+                consequent: self.expression(depth),
+                alternate: self.expression(depth),
+            })),
+            5 => {
+                let left = match self.target(depth) {
+                    Expression::Identifier(identifier) => Pattern::Identifier(identifier),
+                    target => Pattern::Expression(Box::new(target)),
+                };
+                Expression::Assignment(Box::new(AssignmentExpression {
+                    span,
+                    operator: self.choose(ASSIGNMENT_OPERATORS),
+                    left,
+                    right: self.expression(depth),
+                }))
+            }
+            6 => Expression::Sequence(SequenceExpression {
+                span,
+                expressions: vec![self.expression(depth), self.expression(depth)],
+            }),
+            7 => Expression::Call(Box::new(CallExpression {
+                span,
+                callee: self.expression(depth),
+                arguments: self.arguments(depth),
+                optional: false,
+            })),
+            8 => {
+                let property = if self.below(2) == 0 {
+                    MemberProperty::Identifier(self.identifier())
+                } else {
+                    MemberProperty::Computed(self.expression(depth))
+                };
+                Expression::Member(Box::new(MemberExpression {
+                    span,
+                    object: self.expression(depth),
+                    property,
+                    optional: false,
+                }))
+            }
+            9 => Expression::New(Box::new(NewExpression {
+                span,
+                callee: self.expression(depth),
+                arguments: self.arguments(depth),
+            })),
+            10 => {
+                let params = (0..self.below(3))
+                    .map(|index| Pattern::Identifier(Identifier::new(span, NAMES[index])))
+                    .collect();
+                Expression::ArrowFunction(Box::new(ArrowFunctionExpression {
+                    span,
+                    params,
+                    body: ArrowFunctionBody::Expression(self.expression(depth)),
+                    is_async: false,
+                    strict: false,
+                }))
+            }
+            11 => {
+                let properties = (0..self.below(3))
+                    .map(|_| {
+                        let key = self.identifier();
+                        let shorthand = self.below(2) == 0;
+                        let value = if shorthand {
+                            Expression::Identifier(key.clone())
+                        } else {
+                            self.expression(depth)
+                        };
+                        ObjectProperty::Property(Property {
+                            span,
+                            key: PropertyKey::Identifier(key),
+                            value,
+                            kind: PropertyKind::Init,
+                            method: false,
+                            shorthand,
+                        })
+                    })
+                    .collect();
+                Expression::Object(ObjectExpression { span, properties })
+            }
+            12 => {
+                let elements = (0..self.below(4))
+                    .map(|_| {
+                        (self.below(4) != 0)
+                            .then(|| ExpressionOrSpread::Expression(self.expression(depth)))
+                    })
+                    .collect();
+                Expression::Array(ArrayExpression { span, elements })
+            }
+            13 => {
+                let expressions: Vec<_> =
+                    (0..self.below(3)).map(|_| self.expression(depth)).collect();
+                let quasis = (0..=expressions.len())
+                    .map(|index| TemplateElement {
+                        span,
+                        raw: "t".to_string(),
+                        cooked: Some("t".to_string()),
+                        tail: index == expressions.len(),
+                    })
+                    .collect();
+                Expression::TemplateLiteral(TemplateLiteral {
+                    span,
+                    quasis,
+                    expressions,
+                })
+            }
+            14 => {
+                // a?.b.c や a?.()
+                let head = if self.below(2) == 0 {
+                    Expression::Member(Box::new(MemberExpression {
+                        span,
+                        object: self.expression(depth),
+                        property: MemberProperty::Identifier(self.identifier()),
+                        optional: true,
+                    }))
+                } else {
+                    Expression::Call(Box::new(CallExpression {
+                        span,
+                        callee: self.expression(depth),
+                        arguments: self.arguments(depth),
+                        optional: true,
+                    }))
+                };
+                let expression = if self.below(2) == 0 {
+                    Expression::Member(Box::new(MemberExpression {
+                        span,
+                        object: head,
+                        property: MemberProperty::Identifier(self.identifier()),
+                        optional: false,
+                    }))
+                } else {
+                    head
+                };
+                Expression::Chain(Box::new(ChainExpression { span, expression }))
+            }
+            15 => Expression::Identifier(self.identifier()),
+            _ => Expression::Literal(self.literal()),
+        }
+    }
+}
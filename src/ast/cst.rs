@@ -0,0 +1,424 @@
+//! ソースコードの全ての文字を保つ具象構文木 (CST) を作るモジュールです。
+//!
+//! 抽象構文木は括弧やカンマ、コメントを持たないが、整形やリファクタリングには必要になる。
+//! 具象構文木は次の3層からなる。
+//!
+//! - [`green`] は種類と子だけを持つ変更しないノード。部分木を共有できる。
+//! - [`red`] は親と位置を付けたカーソル。たどる時に作る。
+//! - [`typed`] はノードの種類ごとの型と、子を取り出すメソッド。
+//!
+//! ソースコードの全てのバイトは、いずれかのトークン (空白やコメントを含む) に属する。
+//! 具象構文木は構文解析と同じ解析から作るため、抽象構文木と同時に得られる。
+
+mod builder;
+pub mod green;
+pub mod red;
+pub mod typed;
+
+use std::rc::Rc;
+
+use self::{
+    builder::{build, with_trivia, NodeCollector},
+    green::GreenNode,
+    red::SyntaxNode,
+};
+use super::{
+    error::ParseError,
+    reader_struct::SourceCodeReader,
+    script::{parse_with_tokens, ParseOptions},
+    structs::{span::Span, JsAst, SourceType},
+    visit::VisitWith,
+};
+use crate::token::structs::{
+    comment::Comment,
+    token::{Token, TokenType},
+};
+
+/// トークンの種類
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TokenKind {
+    Token(TokenType),
+
+    /// 字句解析できなかった文字
+    Unknown,
+}
+
+impl TokenKind {
+    /// 空白、改行、コメントであるかを返す。
+    pub fn is_trivia(&self) -> bool {
+        matches!(
+            self,
+            TokenKind::Token(TokenType::WhiteSpace | TokenType::LineTerminator)
+                | TokenKind::Token(TokenType::Comment(_))
+        )
+    }
+
+    pub fn is_comment(&self) -> bool {
+        matches!(self, TokenKind::Token(TokenType::Comment(_)))
+    }
+}
+
+/// ノードの種類の一覧を `$callback!` に渡す。
+///
+/// 種類は抽象構文木の構造体と1対1に対応する。`TemplateElement` はトークンの一部しか
+/// 範囲に持たないため、ノードにしない。
+macro_rules! for_each_node_kind {
+    ($callback:ident) => {
+        $callback! {
+            Program [visit_js_ast: JsAst],
+            ErrorNode [visit_error_node: ErrorNode],
+
+            ExpressionStatement [visit_expression_statement: ExpressionStatement],
+            BlockStatement [visit_block_statement: BlockStatement],
+            EmptyStatement [visit_empty_statement: EmptyStatement],
+            DebuggerStatement [visit_debugger_statement: DebuggerStatement],
+            WithStatement [visit_with_statement: WithStatement],
+            ReturnStatement [visit_return_statement: ReturnStatement],
+            LabeledStatement [visit_labeled_statement: LabeledStatement],
+            BreakStatement [visit_break_statement: BreakStatement],
+            ContinueStatement [visit_continue_statement: ContinueStatement],
+            IfStatement [visit_if_statement: IfStatement],
+            SwitchStatement [visit_switch_statement: SwitchStatement],
+            SwitchCase [visit_switch_case: SwitchCase],
+            ThrowStatement [visit_throw_statement: ThrowStatement],
+            TryStatement [visit_try_statement: TryStatement],
+            CatchClause [visit_catch_clause: CatchClause],
+            WhileStatement [visit_while_statement: WhileStatement],
+            DoWhileStatement [visit_do_while_statement: DoWhileStatement],
+            ForStatement [visit_for_statement: ForStatement],
+            ForInStatement [visit_for_in_statement: ForInStatement],
+            ForOfStatement [visit_for_of_statement: ForOfStatement],
+            VariableDeclaration [visit_variable_declaration: VariableDeclaration],
+            VariableDeclarator [visit_variable_declarator: VariableDeclarator],
+
+            ThisExpression [visit_this_expression: ThisExpression],
+            Super [visit_super: Super],
+            ArrayExpression [visit_array_expression: ArrayExpression],
+            SpreadElement [visit_spread_element: SpreadElement],
+            ObjectExpression [visit_object_expression: ObjectExpression],
+            Property [visit_property: Property],
+            MemberExpression [visit_member_expression: MemberExpression],
+            CallExpression [visit_call_expression: CallExpression],
+            NewExpression [visit_new_expression: NewExpression],
+            ChainExpression [visit_chain_expression: ChainExpression],
+            UnaryExpression [visit_unary_expression: UnaryExpression],
+            UpdateExpression [visit_update_expression: UpdateExpression],
+            BinaryExpression [visit_binary_expression: BinaryExpression],
+            LogicalExpression [visit_logical_expression: LogicalExpression],
+            ConditionalExpression [visit_conditional_expression: ConditionalExpression],
+            AssignmentExpression [visit_assignment_expression: AssignmentExpression],
+            SequenceExpression [visit_sequence_expression: SequenceExpression],
+            YieldExpression [visit_yield_expression: YieldExpression],
+            AwaitExpression [visit_await_expression: AwaitExpression],
+            MetaProperty [visit_meta_property: MetaProperty],
+            ImportExpression [visit_import_expression: ImportExpression],
+
+            Function [visit_function: Function],
+            FunctionBody [visit_function_body: FunctionBody],
+            ArrowFunctionExpression [visit_arrow_function_expression: ArrowFunctionExpression],
+            Class [visit_class: Class],
+            ClassBody [visit_class_body: ClassBody],
+            MethodDefinition [visit_method_definition: MethodDefinition],
+            PropertyDefinition [visit_property_definition: PropertyDefinition],
+            StaticBlock [visit_static_block: StaticBlock],
+
+            ObjectPattern [visit_object_pattern: ObjectPattern],
+            AssignmentProperty [visit_assignment_property: AssignmentProperty],
+            ArrayPattern [visit_array_pattern: ArrayPattern],
+            RestElement [visit_rest_element: RestElement],
+            AssignmentPattern [visit_assignment_pattern: AssignmentPattern],
+
+            TemplateLiteral [visit_template_literal: TemplateLiteral],
+            TaggedTemplateExpression [visit_tagged_template_expression: TaggedTemplateExpression],
+            Identifier [visit_identifier: Identifier],
+            PrivateIdentifier [visit_private_identifier: PrivateIdentifier],
+            Literal [visit_literal: Literal],
+
+            ImportDeclaration [visit_import_declaration: ImportDeclaration],
+            ImportSpecifier [visit_import_specifier: ImportSpecifier],
+            ImportDefaultSpecifier [visit_import_default_specifier: ImportDefaultSpecifier],
+            ImportNamespaceSpecifier [visit_import_namespace_specifier: ImportNamespaceSpecifier],
+            ImportAttribute [visit_import_attribute: ImportAttribute],
+            ExportNamedDeclaration [visit_export_named_declaration: ExportNamedDeclaration],
+            ExportSpecifier [visit_export_specifier: ExportSpecifier],
+            ExportDefaultDeclaration [visit_export_default_declaration: ExportDefaultDeclaration],
+            ExportAllDeclaration [visit_export_all_declaration: ExportAllDeclaration],
+        }
+    };
+}
+
+pub(crate) use for_each_node_kind;
+
+macro_rules! define_node_kinds {
+    ($($kind:ident [$visit:ident: $struct:ident]),* $(,)?) => {
+        /// ノードの種類
+        #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+        pub enum NodeKind {
+            $($kind,)*
+        }
+
+        mod collect {
+            use super::{builder::NodeCollector, NodeKind};
+            use crate::ast::{structs::*, visit::{Visit, VisitWith}};
+            use crate::ast::structs::{
+                class::*, expression::*, function::*, literal::*, module::*, pattern::*,
+                statement::*, template::*,
+            };
+
+            impl<'ast> Visit<'ast> for NodeCollector {
+                $(
+                    fn $visit(&mut self, node: &'ast $struct) {
+                        self.enter(NodeKind::$kind, node.span);
+                        node.visit_children_with(self);
+                        self.exit();
+                    }
+                )*
+            }
+        }
+    };
+}
+
+for_each_node_kind!(define_node_kinds);
+
+/// 具象構文木を作った結果
+#[derive(Debug, Clone)]
+pub struct CstOutput {
+    /// 同じ解析から作った抽象構文木
+    pub ast: JsAst,
+    pub syntax: SyntaxNode,
+
+    /// 構文エラーと早期エラー (位置の順)
+    pub errors: Vec<ParseError>,
+}
+
+/// ソースコードを解析し、抽象構文木と具象構文木を作る。
+///
+/// 構文エラーがあっても、全ての文字を含む具象構文木を返す。
+pub fn parse_cst(source: &str, source_type: SourceType) -> CstOutput {
+    let (output, tokens) = parse_with_tokens(
+        SourceCodeReader::new(source),
+        source_type,
+        ParseOptions::default(),
+    );
+    let green = build_green(source, &output.ast, &tokens);
+
+    CstOutput {
+        ast: output.ast,
+        syntax: SyntaxNode::new_root(Rc::new(green)),
+        errors: output.errors,
+    }
+}
+
+/// ソースコードのコメントの種類と範囲を位置の順に返す。
+///
+/// 具象構文木と同じく、構文解析で読んだトークン `tokens` ([`parse_with_tokens`] の結果) の
+/// 間を字句解析して求める。ソースコードを解析し直さない。
+pub fn comments(source: &str, tokens: &[Token]) -> Vec<(Comment, Span)> {
+    with_trivia(source, tokens)
+        .into_iter()
+        .filter_map(|(kind, span)| match kind {
+            TokenKind::Token(TokenType::Comment(comment)) => Some((comment, span)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn build_green(source: &str, ast: &JsAst, tokens: &[Token]) -> GreenNode {
+    let mut collector = NodeCollector::new();
+    ast.visit_with(&mut collector);
+    build(source, collector.finish(), with_trivia(source, tokens))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        comments, parse_cst,
+        typed::{
+            AstNode, BinaryExpression, ExpressionStatement, Function, Identifier, Program,
+            VariableDeclaration,
+        },
+        NodeKind, ParseOptions, SourceCodeReader, TokenKind,
+    };
+    use crate::{
+        ast::{
+            script::parse_with_tokens,
+            structs::{span::Span, SourceType},
+        },
+        token::structs::{comment::Comment, punctuator::Punctuator, token::TokenType},
+    };
+
+    /// 具象構文木の表記がソースコードと一致することを確かめる。
+    fn assert_lossless(source: &str, source_type: SourceType) {
+        let output = parse_cst(source, source_type);
+        assert_eq!(output.syntax.text(), source);
+        assert_eq!(output.syntax.span(), Span::new(0, source.len()));
+
+        // トークンは隙間なく並ぶ
+        let mut position = 0;
+        for token in output.syntax.descendant_tokens() {
+            assert_eq!(token.span().start, position, "{source}");
+            position = token.span().end;
+        }
+        assert_eq!(position, source.len());
+    }
+
+    #[test]
+    fn tree() {
+        let output = parse_cst("a + (b) ; // c\n", SourceType::Script);
+        assert_eq!(
+            output.syntax.debug_tree(),
+            r#"Program@0..15
+  ExpressionStatement@0..9
+    BinaryExpression@0..7
+      Identifier@0..1
+        Token(Identifier)@0..1 "a"
+      Token(WhiteSpace)@1..2 " "
+      Token(Punctuator(Plus))@2..3 "+"
+      Token(WhiteSpace)@3..4 " "
+      Token(Punctuator(LeftParen))@4..5 "("
+      Identifier@5..6
+        Token(Identifier)@5..6 "b"
+      Token(Punctuator(RightParen))@6..7 ")"
+    Token(WhiteSpace)@7..8 " "
+    Token(Punctuator(SemiColon))@8..9 ";"
+  Token(WhiteSpace)@9..10 " "
+  Token(Comment(SingleLineComment))@10..14 "// c"
+  Token(LineTerminator)@14..15 "\n"
+"#
+        );
+    }
+
+    #[test]
+    fn lossless() {
+        let scripts = [
+            "",
+            "   \n",
+            "#!/usr/bin/env node\n/* a */ a /* b */ ; // c",
+            "function f(a, { b = 1 }, ...c) { return `x${a}y${b}z` }",
+            "x = { a, b: 1, [c]: d, get e() {}, ...f }; ({ a = 1 } = g);",
+            "class A extends B { #a; static { this.#a } m() { super.m() } }",
+            "for (let i = 0; i < 1; i++) label: if (a) b; else { c\r\n}",
+            "a\n++b\n/re/g.test(c) / d",
+            "'あ😀' + `\\u{1F600}${'い'}`",
+            "if (a) { b( } c;",
+            "let x = @;",
+            "'unterminated\nb;",
+            include_str!("estree/testdata/declarations.js"),
+            include_str!("estree/testdata/expressions.js"),
+        ];
+        for source in scripts {
+            assert_lossless(source, SourceType::Script);
+        }
+
+        let modules = [
+            "import a, { b as c } from 'd'; export { c as default };",
+            "export default class {} export * as e from 'f' with { type: 'json' };",
+            include_str!("estree/testdata/module.js"),
+        ];
+        for source in modules {
+            assert_lossless(source, SourceType::Module);
+        }
+    }
+
+    #[test]
+    fn errors_and_ast() {
+        let output = parse_cst("a = 1; b(;", SourceType::Script);
+        assert_eq!(output.ast.body.len(), 2);
+        assert!(!output.errors.is_empty());
+        assert!(output
+            .syntax
+            .descendants()
+            .any(|node| node.kind() == NodeKind::ErrorNode));
+    }
+
+    #[test]
+    fn navigation() {
+        let source = "var x = 1; f(x, y);";
+        let syntax = parse_cst(source, SourceType::Script).syntax;
+
+        let token = syntax.token_at_offset(13).unwrap();
+        assert_eq!(token.text(), "x");
+        assert_eq!(token.parent().kind(), NodeKind::Identifier);
+        assert_eq!(
+            token
+                .parent()
+                .ancestors()
+                .map(|node| node.kind())
+                .collect::<Vec<_>>(),
+            [
+                NodeKind::Identifier,
+                NodeKind::CallExpression,
+                NodeKind::ExpressionStatement,
+                NodeKind::Program
+            ]
+        );
+        assert_eq!(token.next_token().unwrap().text(), ",");
+        assert_eq!(token.prev_token().unwrap().text(), "(");
+
+        let statement = syntax.first_child().unwrap();
+        assert_eq!(statement.kind(), NodeKind::VariableDeclaration);
+        assert_eq!(
+            statement.next_sibling().unwrap().kind(),
+            NodeKind::ExpressionStatement
+        );
+        assert_eq!(
+            syntax.covering_node(Span::new(13, 17)).kind(),
+            NodeKind::CallExpression
+        );
+    }
+
+    #[test]
+    fn typed_view() {
+        let source = "let a = 1, b;\n(x) * /* y */ y;\nfunction f(g) {}";
+        let syntax = parse_cst(source, SourceType::Script).syntax;
+        let program = Program::cast(syntax).unwrap();
+        let items: Vec<_> = program.items().collect();
+
+        let declaration = VariableDeclaration::cast(items[0].clone()).unwrap();
+        assert_eq!(declaration.kind_token().unwrap().text(), "let");
+        let names: Vec<_> = declaration
+            .declarators()
+            .map(|declarator| declarator.id().unwrap().text())
+            .collect();
+        assert_eq!(names, ["a", "b"]);
+        assert!(declaration.declarators().nth(1).unwrap().init().is_none());
+
+        let statement = ExpressionStatement::cast(items[1].clone()).unwrap();
+        assert_eq!(statement.semicolon_token().unwrap().text(), ";");
+        let binary = BinaryExpression::cast(statement.expression().unwrap()).unwrap();
+        let operator = binary.operator_token().unwrap();
+        assert_eq!(
+            operator.kind(),
+            TokenKind::Token(TokenType::Punctuator(Punctuator::Multiply))
+        );
+        assert_eq!(binary.right().unwrap().text(), "y");
+        assert!(Identifier::cast(binary.left().unwrap()).is_some());
+
+        let function = Function::cast(items[2].clone()).unwrap();
+        assert_eq!(function.name().unwrap().text(), "f");
+        assert!(function.body().is_some());
+        assert!(Function::cast(items[0].clone()).is_none());
+    }
+
+    #[test]
+    fn comment_tokens() {
+        let source = "#!/usr/bin/env node\n/* a */ x = '// b' / 2; // c\nr = /\\/* d/;";
+        let (_, tokens) = parse_with_tokens(
+            SourceCodeReader::new(source),
+            SourceType::Script,
+            ParseOptions::default(),
+        );
+        let found: Vec<(Comment, &str)> = comments(source, &tokens)
+            .into_iter()
+            .map(|(comment, span)| (comment, span.source_text(source)))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (Comment::HashBangComment, "#!/usr/bin/env node"),
+                (Comment::MultiLineComment, "/* a */"),
+                (Comment::SingleLineComment, "// c"),
+            ]
+        );
+    }
+}
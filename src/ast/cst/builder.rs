@@ -0,0 +1,171 @@
+//! 構文木とトークン列から具象構文木を組み立てるモジュールです。
+//!
+//! 1. 構文木をたどり、ノードの種類と範囲の入れ子を集める。
+//! 2. 構文解析で読んだトークンの間を読み直し、空白、改行、コメントを補う。
+//! 3. トークンを範囲の入れ子に振り分ける。ノードの間にあるトークン (括弧やカンマ、
+//!    空白など) は、それを含む最も内側のノードの子になる。
+
+use std::{iter::Peekable, rc::Rc};
+
+use super::{
+    green::{GreenElement, GreenNode, GreenToken},
+    NodeKind, TokenKind,
+};
+use crate::{
+    ast::{reader_struct::SourceCodeReader, structs::span::Span},
+    token::structs::{
+        context::{BraceContext, DivContext},
+        token::Token,
+    },
+};
+
+/// 組み立て中のノード
+#[derive(Debug)]
+pub(super) struct PendingNode {
+    kind: NodeKind,
+    span: Span,
+    children: Vec<PendingNode>,
+}
+
+/// 構文木をたどってノードの入れ子を集める。
+///
+/// `Visit` の実装は [`NodeKind`] の一覧から生成する。
+pub(super) struct NodeCollector {
+    stack: Vec<PendingNode>,
+}
+
+impl NodeCollector {
+    pub(super) fn new() -> NodeCollector {
+        NodeCollector { stack: Vec::new() }
+    }
+
+    pub(super) fn enter(&mut self, kind: NodeKind, span: Span) {
+        self.stack.push(PendingNode {
+            kind,
+            span,
+            children: Vec::new(),
+        });
+    }
+
+    pub(super) fn exit(&mut self) {
+        let node = self.stack.pop().expect("exit without enter");
+        match self.stack.last_mut() {
+            Some(parent) => parent.children.push(node),
+            None => self.stack.push(node),
+        }
+    }
+
+    /// 集め終えた根のノードを返す。
+    pub(super) fn finish(mut self) -> PendingNode {
+        debug_assert_eq!(self.stack.len(), 1);
+        self.stack.pop().expect("no root node")
+    }
+}
+
+/// 構文解析で読んだトークンに、間の空白、改行、コメントを補う。
+///
+/// 字句解析できなかった文字は1文字ずつ [`TokenKind::Unknown`] にする。
+pub(super) fn with_trivia(source: &str, tokens: &[Token]) -> Vec<(TokenKind, Span)> {
+    let mut result = Vec::with_capacity(tokens.len() * 2);
+    let mut position = 0;
+
+    for token in tokens {
+        push_trivia(source, position, token.start_index(), &mut result);
+        result.push((
+            TokenKind::Token(token.token_type()),
+            Span::new(token.start_index(), token.end_index()),
+        ));
+        position = token.end_index();
+    }
+    push_trivia(source, position, source.len(), &mut result);
+
+    result
+}
+
+fn push_trivia(source: &str, start: usize, end: usize, result: &mut Vec<(TokenKind, Span)>) {
+    let mut reader = SourceCodeReader::new(source);
+    let mut position = start;
+
+    while position < end {
+        reader.reset(position);
+
+        // 間には空白とコメントしかないため、`/` はコメントの始まりとして読まれる
+        let next = match reader.read_next_token(DivContext::RegExp, BraceContext::Block) {
+            Ok(token) if token.end_index() <= end && token.end_index() > position => {
+                (TokenKind::Token(token.token_type()), token.end_index())
+            }
+            _ => {
+                let char = source[position..].chars().next().unwrap_or_default();
+                (TokenKind::Unknown, position + char.len_utf8())
+            }
+        };
+        result.push((next.0, Span::new(position, next.1)));
+        position = next.1;
+    }
+}
+
+/// ノードの入れ子にトークンを振り分けて green tree を作る。
+pub(super) fn build(source: &str, root: PendingNode, tokens: Vec<(TokenKind, Span)>) -> GreenNode {
+    let mut builder = Builder {
+        source,
+        tokens: tokens.into_iter().peekable(),
+    };
+    let mut children = builder.children(&root);
+
+    // 根は残りのトークンを全て持つ
+    while let Some(token) = builder.tokens.next() {
+        children.push(builder.token(token));
+    }
+    GreenNode::new(root.kind, children)
+}
+
+struct Builder<'a, I: Iterator<Item = (TokenKind, Span)>> {
+    source: &'a str,
+    tokens: Peekable<I>,
+}
+
+impl<I: Iterator<Item = (TokenKind, Span)>> Builder<'_, I> {
+    fn token(&self, (kind, span): (TokenKind, Span)) -> GreenElement {
+        GreenElement::Token(Rc::new(GreenToken::new(
+            kind,
+            span.source_text(self.source),
+        )))
+    }
+
+    /// `end` までに終わるトークンを子にする。
+    fn tokens_until(&mut self, end: usize, children: &mut Vec<GreenElement>) {
+        while let Some(token) = self.tokens.next_if(|(_, span)| span.end <= end) {
+            children.push(self.token(token));
+        }
+    }
+
+    fn node(&mut self, node: &PendingNode) -> GreenNode {
+        let mut children = self.children(node);
+        self.tokens_until(node.span.end, &mut children);
+        GreenNode::new(node.kind, children)
+    }
+
+    fn children(&mut self, node: &PendingNode) -> Vec<GreenElement> {
+        let mut pending: Vec<&PendingNode> = node.children.iter().collect();
+        pending.sort_by_key(|child| child.span.start);
+
+        let mut children = Vec::new();
+        let mut end = 0;
+        for child in pending {
+            // 省略記法の `{ a }` のキーと値のように、同じ範囲を重ねて持つ子は1つにする
+            if child.span.start < end {
+                continue;
+            }
+            end = child.span.end;
+
+            while let Some(token) = self
+                .tokens
+                .next_if(|(_, span)| span.start < child.span.start)
+            {
+                children.push(self.token(token));
+            }
+            children.push(GreenElement::Node(Rc::new(self.node(child))));
+        }
+        children
+    }
+}
@@ -0,0 +1,112 @@
+//! 具象構文木の実体 (green tree) です。
+//!
+//! ノードは種類と子の列だけを持ち、位置や親を持たない。作った後は変更しないため、
+//! 同じ部分木を [`Rc`] で共有できる。
+
+use std::rc::Rc;
+
+use super::{NodeKind, TokenKind};
+
+/// 子を持つノード
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GreenNode {
+    kind: NodeKind,
+
+    /// ノードの表記のバイト数
+    len: usize,
+    children: Vec<GreenElement>,
+}
+
+/// 表記を持つトークン (空白やコメントも含む)
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GreenToken {
+    kind: TokenKind,
+    text: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum GreenElement {
+    Node(Rc<GreenNode>),
+    Token(Rc<GreenToken>),
+}
+
+impl GreenNode {
+    pub fn new(kind: NodeKind, children: Vec<GreenElement>) -> GreenNode {
+        let len = children.iter().map(GreenElement::len).sum();
+        GreenNode {
+            kind,
+            len,
+            children,
+        }
+    }
+
+    pub fn kind(&self) -> NodeKind {
+        self.kind
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn children(&self) -> &[GreenElement] {
+        &self.children
+    }
+
+    /// 子のトークンの表記をつなげる。
+    pub fn text(&self) -> String {
+        let mut text = String::with_capacity(self.len);
+        self.write_text(&mut text);
+        text
+    }
+
+    fn write_text(&self, text: &mut String) {
+        for child in &self.children {
+            match child {
+                GreenElement::Node(node) => node.write_text(text),
+                GreenElement::Token(token) => text.push_str(&token.text),
+            }
+        }
+    }
+}
+
+impl GreenToken {
+    pub fn new(kind: TokenKind, text: impl Into<String>) -> GreenToken {
+        GreenToken {
+            kind,
+            text: text.into(),
+        }
+    }
+
+    pub fn kind(&self) -> TokenKind {
+        self.kind
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn len(&self) -> usize {
+        self.text.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+}
+
+impl GreenElement {
+    pub fn len(&self) -> usize {
+        match self {
+            GreenElement::Node(node) => node.len(),
+            GreenElement::Token(token) => token.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
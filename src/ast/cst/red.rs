@@ -0,0 +1,341 @@
+//! 具象構文木をたどるカーソル (red tree) です。
+//!
+//! green tree のノードに、親と位置を付けたものである。必要になった時に作り、
+//! 複製は [`Rc`] の参照カウントを増やすだけで済む。
+
+use std::{fmt, rc::Rc};
+
+use super::{
+    green::{GreenElement, GreenNode, GreenToken},
+    NodeKind, TokenKind,
+};
+use crate::ast::structs::span::Span;
+
+/// 親と位置を持つノード
+#[derive(Clone)]
+pub struct SyntaxNode(Rc<NodeData>);
+
+struct NodeData {
+    green: Rc<GreenNode>,
+    parent: Option<SyntaxNode>,
+
+    /// 親の子の列での位置
+    index: usize,
+
+    /// ソースコード上の開始位置 (バイト)
+    offset: usize,
+}
+
+/// 親と位置を持つトークン
+#[derive(Clone)]
+pub struct SyntaxToken {
+    green: Rc<GreenToken>,
+    parent: SyntaxNode,
+    index: usize,
+    offset: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyntaxElement {
+    Node(SyntaxNode),
+    Token(SyntaxToken),
+}
+
+impl SyntaxNode {
+    /// 木の根のカーソルを作る。
+    pub fn new_root(green: Rc<GreenNode>) -> SyntaxNode {
+        SyntaxNode(Rc::new(NodeData {
+            green,
+            parent: None,
+            index: 0,
+            offset: 0,
+        }))
+    }
+
+    fn new_child(green: Rc<GreenNode>, parent: SyntaxNode, index: usize, offset: usize) -> Self {
+        SyntaxNode(Rc::new(NodeData {
+            green,
+            parent: Some(parent),
+            index,
+            offset,
+        }))
+    }
+
+    pub fn kind(&self) -> NodeKind {
+        self.0.green.kind()
+    }
+
+    pub fn green(&self) -> &Rc<GreenNode> {
+        &self.0.green
+    }
+
+    /// ノードの範囲 (前後の空白やコメントは親のノードに属するため含まない)
+    pub fn span(&self) -> Span {
+        Span::new(self.0.offset, self.0.offset + self.0.green.len())
+    }
+
+    /// ノードの表記 (ソースコードの一部と同じ)
+    pub fn text(&self) -> String {
+        self.0.green.text()
+    }
+
+    pub fn parent(&self) -> Option<SyntaxNode> {
+        self.0.parent.clone()
+    }
+
+    /// 自身から根までのノード
+    pub fn ancestors(&self) -> impl Iterator<Item = SyntaxNode> {
+        std::iter::successors(Some(self.clone()), SyntaxNode::parent)
+    }
+
+    /// 子のノードとトークン
+    pub fn children_with_tokens(&self) -> impl Iterator<Item = SyntaxElement> + '_ {
+        let mut offset = self.0.offset;
+        self.0
+            .green
+            .children()
+            .iter()
+            .enumerate()
+            .map(move |(index, child)| {
+                let start = offset;
+                offset += child.len();
+                self.child_element(child, index, start)
+            })
+    }
+
+    fn child_element(&self, child: &GreenElement, index: usize, offset: usize) -> SyntaxElement {
+        match child {
+            GreenElement::Node(green) => SyntaxElement::Node(SyntaxNode::new_child(
+                green.clone(),
+                self.clone(),
+                index,
+                offset,
+            )),
+            GreenElement::Token(green) => SyntaxElement::Token(SyntaxToken {
+                green: green.clone(),
+                parent: self.clone(),
+                index,
+                offset,
+            }),
+        }
+    }
+
+    /// 子のノード
+    pub fn children(&self) -> impl Iterator<Item = SyntaxNode> + '_ {
+        self.children_with_tokens()
+            .filter_map(SyntaxElement::into_node)
+    }
+
+    pub fn first_child(&self) -> Option<SyntaxNode> {
+        self.children().next()
+    }
+
+    pub fn last_child(&self) -> Option<SyntaxNode> {
+        self.children().last()
+    }
+
+    /// 子のトークンのうち、空白やコメントでないもの
+    pub fn child_tokens(&self) -> impl Iterator<Item = SyntaxToken> + '_ {
+        self.children_with_tokens()
+            .filter_map(SyntaxElement::into_token)
+            .filter(|token| !token.is_trivia())
+    }
+
+    /// 自身と子孫のノード (前順)
+    pub fn descendants(&self) -> impl Iterator<Item = SyntaxNode> {
+        let mut stack = vec![self.clone()];
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            let children: Vec<_> = node.children().collect();
+            stack.extend(children.into_iter().rev());
+            Some(node)
+        })
+    }
+
+    /// 子孫のトークン (位置の順)
+    pub fn descendant_tokens(&self) -> impl Iterator<Item = SyntaxToken> {
+        let mut stack = vec![SyntaxElement::Node(self.clone())];
+        std::iter::from_fn(move || loop {
+            match stack.pop()? {
+                SyntaxElement::Token(token) => return Some(token),
+                SyntaxElement::Node(node) => {
+                    let children: Vec<_> = node.children_with_tokens().collect();
+                    stack.extend(children.into_iter().rev());
+                }
+            }
+        })
+    }
+
+    pub fn first_token(&self) -> Option<SyntaxToken> {
+        self.descendant_tokens().next()
+    }
+
+    pub fn last_token(&self) -> Option<SyntaxToken> {
+        self.descendant_tokens().last()
+    }
+
+    pub fn next_sibling(&self) -> Option<SyntaxNode> {
+        let parent = self.parent()?;
+        let sibling = parent.children().find(|node| node.0.index > self.0.index);
+        sibling
+    }
+
+    pub fn prev_sibling(&self) -> Option<SyntaxNode> {
+        let parent = self.parent()?;
+        let sibling = parent
+            .children()
+            .take_while(|node| node.0.index < self.0.index)
+            .last();
+        sibling
+    }
+
+    /// 位置を含むトークンを返す。トークンの境目では後ろのトークンを返す。
+    pub fn token_at_offset(&self, offset: usize) -> Option<SyntaxToken> {
+        let mut node = self.clone();
+        'outer: loop {
+            let children: Vec<_> = node.children_with_tokens().collect();
+            for child in children {
+                if !child.span().contains(offset) {
+                    continue;
+                }
+                match child {
+                    SyntaxElement::Token(token) => return Some(token),
+                    SyntaxElement::Node(child) => {
+                        node = child;
+                        continue 'outer;
+                    }
+                }
+            }
+            return None;
+        }
+    }
+
+    /// 範囲を全て含む最も内側のノードを返す。
+    pub fn covering_node(&self, span: Span) -> SyntaxNode {
+        let mut node = self.clone();
+        loop {
+            let child = node.children().find(|child| {
+                let child_span = child.span();
+                child_span.start <= span.start && span.end <= child_span.end
+            });
+            match child {
+                Some(child) => node = child,
+                None => return node,
+            }
+        }
+    }
+
+    /// 木を字下げした文字列にする (テストと調査用)。
+    pub fn debug_tree(&self) -> String {
+        let mut output = String::new();
+        self.write_debug_tree(&mut output, 0);
+        output
+    }
+
+    fn write_debug_tree(&self, output: &mut String, depth: usize) {
+        output.push_str(&format!("{}{:?}\n", "  ".repeat(depth), self));
+        for child in self.children_with_tokens() {
+            match child {
+                SyntaxElement::Node(node) => node.write_debug_tree(output, depth + 1),
+                SyntaxElement::Token(token) => {
+                    output.push_str(&format!("{}{:?}\n", "  ".repeat(depth + 1), token))
+                }
+            }
+        }
+    }
+}
+
+impl PartialEq for SyntaxNode {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0.green, &other.0.green) && self.0.offset == other.0.offset
+    }
+}
+
+impl Eq for SyntaxNode {}
+
+impl fmt::Debug for SyntaxNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let span = self.span();
+        write!(f, "{:?}@{}..{}", self.kind(), span.start, span.end)
+    }
+}
+
+impl SyntaxToken {
+    pub fn kind(&self) -> TokenKind {
+        self.green.kind()
+    }
+
+    pub fn text(&self) -> &str {
+        self.green.text()
+    }
+
+    pub fn span(&self) -> Span {
+        Span::new(self.offset, self.offset + self.green.len())
+    }
+
+    pub fn parent(&self) -> SyntaxNode {
+        self.parent.clone()
+    }
+
+    /// 空白、改行、コメントであるかを返す。
+    pub fn is_trivia(&self) -> bool {
+        self.kind().is_trivia()
+    }
+
+    /// 次のトークン (空白やコメントも含む)
+    pub fn next_token(&self) -> Option<SyntaxToken> {
+        let root = self.parent.ancestors().last()?;
+        root.token_at_offset(self.span().end)
+    }
+
+    /// 前のトークン (空白やコメントも含む)
+    pub fn prev_token(&self) -> Option<SyntaxToken> {
+        let root = self.parent.ancestors().last()?;
+        root.token_at_offset(self.offset.checked_sub(1)?)
+    }
+}
+
+impl PartialEq for SyntaxToken {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.green, &other.green) && self.offset == other.offset
+    }
+}
+
+impl Eq for SyntaxToken {}
+
+impl fmt::Debug for SyntaxToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let span = self.span();
+        write!(
+            f,
+            "{:?}@{}..{} {:?}",
+            self.kind(),
+            span.start,
+            span.end,
+            self.text()
+        )
+    }
+}
+
+impl SyntaxElement {
+    pub fn span(&self) -> Span {
+        match self {
+            SyntaxElement::Node(node) => node.span(),
+            SyntaxElement::Token(token) => token.span(),
+        }
+    }
+
+    pub fn into_node(self) -> Option<SyntaxNode> {
+        match self {
+            SyntaxElement::Node(node) => Some(node),
+            SyntaxElement::Token(_) => None,
+        }
+    }
+
+    pub fn into_token(self) -> Option<SyntaxToken> {
+        match self {
+            SyntaxElement::Node(_) => None,
+            SyntaxElement::Token(token) => Some(token),
+        }
+    }
+}
@@ -0,0 +1,201 @@
+//! 具象構文木のノードを種類ごとの型で扱うモジュールです。
+//!
+//! 型は [`SyntaxNode`] を包むだけで、子はたどる時に取り出す。
+//! 子を取り出すメソッドは、よく使うノードにだけ用意している。
+
+use super::{
+    for_each_node_kind,
+    red::{SyntaxNode, SyntaxToken},
+    NodeKind, TokenKind,
+};
+use crate::token::structs::{punctuator::Punctuator, token::TokenType};
+
+/// 種類ごとの型で包んだノード
+pub trait AstNode: Sized {
+    /// 種類が一致する場合に包む。
+    fn cast(node: SyntaxNode) -> Option<Self>;
+
+    fn syntax(&self) -> &SyntaxNode;
+
+    /// 最初に見つかる `N` の子
+    fn child<N: AstNode>(&self) -> Option<N> {
+        self.syntax().children().find_map(N::cast)
+    }
+
+    /// `N` の子の全て
+    fn children<N: AstNode>(&self) -> std::vec::IntoIter<N> {
+        let children: Vec<_> = self.syntax().children().filter_map(N::cast).collect();
+        children.into_iter()
+    }
+
+    /// 空白やコメントでない最初の子のトークン
+    fn first_child_token(&self) -> Option<SyntaxToken> {
+        self.syntax().child_tokens().next()
+    }
+}
+
+macro_rules! define_typed_nodes {
+    ($($kind:ident [$visit:ident: $struct:ident]),* $(,)?) => {
+        $(
+            #[derive(Debug, Clone, PartialEq, Eq)]
+            pub struct $kind(SyntaxNode);
+
+            impl AstNode for $kind {
+                fn cast(node: SyntaxNode) -> Option<Self> {
+                    (node.kind() == NodeKind::$kind).then_some($kind(node))
+                }
+
+                fn syntax(&self) -> &SyntaxNode {
+                    &self.0
+                }
+            }
+        )*
+    };
+}
+
+for_each_node_kind!(define_typed_nodes);
+
+/// 子のトークンのうち、`node` の後ろにある最初の記号を返す。
+///
+/// 子を囲む括弧は飛ばす。
+fn token_after(parent: &SyntaxNode, node: &SyntaxNode) -> Option<SyntaxToken> {
+    let end = node.span().end;
+    parent.child_tokens().find(|token| {
+        token.span().start >= end
+            && token.kind() != TokenKind::Token(TokenType::Punctuator(Punctuator::RightParen))
+    })
+}
+
+fn is_punctuator(token: &SyntaxToken, punctuator: Punctuator) -> bool {
+    token.kind() == TokenKind::Token(TokenType::Punctuator(punctuator))
+}
+
+impl Program {
+    /// 文とモジュールの宣言
+    pub fn items(&self) -> impl Iterator<Item = SyntaxNode> + '_ {
+        self.0.children()
+    }
+}
+
+impl ExpressionStatement {
+    pub fn expression(&self) -> Option<SyntaxNode> {
+        self.0.first_child()
+    }
+
+    /// 文の終わりの `;` (自動セミコロン挿入の場合はない)
+    pub fn semicolon_token(&self) -> Option<SyntaxToken> {
+        self.0
+            .child_tokens()
+            .filter(|token| is_punctuator(token, Punctuator::SemiColon))
+            .last()
+    }
+}
+
+impl BinaryExpression {
+    pub fn left(&self) -> Option<SyntaxNode> {
+        self.0.first_child()
+    }
+
+    pub fn right(&self) -> Option<SyntaxNode> {
+        self.0.children().nth(1)
+    }
+
+    pub fn operator_token(&self) -> Option<SyntaxToken> {
+        token_after(&self.0, &self.left()?)
+    }
+}
+
+impl LogicalExpression {
+    pub fn left(&self) -> Option<SyntaxNode> {
+        self.0.first_child()
+    }
+
+    pub fn right(&self) -> Option<SyntaxNode> {
+        self.0.children().nth(1)
+    }
+
+    pub fn operator_token(&self) -> Option<SyntaxToken> {
+        token_after(&self.0, &self.left()?)
+    }
+}
+
+impl AssignmentExpression {
+    pub fn left(&self) -> Option<SyntaxNode> {
+        self.0.first_child()
+    }
+
+    pub fn right(&self) -> Option<SyntaxNode> {
+        self.0.children().nth(1)
+    }
+
+    pub fn operator_token(&self) -> Option<SyntaxToken> {
+        token_after(&self.0, &self.left()?)
+    }
+}
+
+impl CallExpression {
+    pub fn callee(&self) -> Option<SyntaxNode> {
+        self.0.first_child()
+    }
+
+    /// 引数 (スプレッドは [`SpreadElement`] のノード)
+    pub fn arguments(&self) -> impl Iterator<Item = SyntaxNode> + '_ {
+        self.0.children().skip(1)
+    }
+}
+
+impl VariableDeclaration {
+    /// `var` `let` `const` のトークン
+    pub fn kind_token(&self) -> Option<SyntaxToken> {
+        self.first_child_token()
+    }
+
+    pub fn declarators(&self) -> std::vec::IntoIter<VariableDeclarator> {
+        self.children()
+    }
+}
+
+impl VariableDeclarator {
+    pub fn id(&self) -> Option<SyntaxNode> {
+        self.0.first_child()
+    }
+
+    pub fn init(&self) -> Option<SyntaxNode> {
+        self.0.children().nth(1)
+    }
+}
+
+impl Function {
+    /// 関数名 (引数の `(` より前にある識別子)
+    pub fn name(&self) -> Option<Identifier> {
+        let paren = self
+            .0
+            .child_tokens()
+            .find(|token| is_punctuator(token, Punctuator::LeftParen))?;
+        self.children::<Identifier>()
+            .find(|identifier| identifier.syntax().span().end <= paren.span().start)
+    }
+
+    pub fn body(&self) -> Option<FunctionBody> {
+        self.child()
+    }
+}
+
+impl Identifier {
+    pub fn token(&self) -> Option<SyntaxToken> {
+        self.first_child_token()
+    }
+
+    /// ソースコード上の表記 (エスケープを含む)
+    pub fn text(&self) -> String {
+        self.token()
+            .map(|token| token.text().to_string())
+            .unwrap_or_default()
+    }
+}
+
+impl Literal {
+    pub fn token(&self) -> Option<SyntaxToken> {
+        self.first_child_token()
+    }
+}
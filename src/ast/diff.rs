@@ -0,0 +1,412 @@
+//! 2つの構文木の構造の差分を求めるモジュールです。
+//!
+//! 構文木を ESTree の形にして比べるため、空白やコメント、括弧、引用符の違いは差分にならない。
+//! ノードを対応させ ([`matching`])、対応から次の変更を求める。
+//!
+//! - 挿入: 新しい木にだけあるノード (挿入された部分木の根だけを報告する)
+//! - 削除: 古い木にだけあるノード (削除された部分木の根だけを報告する)
+//! - 更新: 対応するノードのラベル (名前、値、演算子など) が変わったもの
+//! - 移動: 対応するノードの親が変わったもの、または兄弟の間で順序が変わったもの
+
+mod matching;
+mod tree;
+
+use std::{collections::HashMap, fmt::Write};
+
+use self::{
+    matching::{match_trees, Mappings},
+    tree::Tree,
+};
+use super::{
+    estree::to_estree,
+    line_index::LineIndex,
+    structs::{span::Span, JsAst},
+};
+
+/// 変更の種類
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum EditKind {
+    Insert,
+    Delete,
+    Update,
+    Move,
+}
+
+impl EditKind {
+    fn name(&self) -> &'static str {
+        match self {
+            EditKind::Insert => "insert",
+            EditKind::Delete => "delete",
+            EditKind::Update => "update",
+            EditKind::Move => "move",
+        }
+    }
+}
+
+/// ノードの変更
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Edit {
+    pub kind: EditKind,
+
+    /// ノードの種類 (ESTree の `type`)
+    pub node: String,
+
+    /// 古いソースコードでの範囲 (挿入にはない)
+    pub old: Option<Span>,
+
+    /// 新しいソースコードでの範囲 (削除にはない)
+    pub new: Option<Span>,
+}
+
+/// 2つの構文木の差分を求める。
+///
+/// 変更は、古い木の前順 (削除、更新、移動) の後に新しい木の前順 (挿入) で並べる。
+pub fn diff(old: &JsAst, old_source: &str, new: &JsAst, new_source: &str) -> Vec<Edit> {
+    let src = Tree::from_estree(&to_estree(old, old_source), old_source);
+    let dst = Tree::from_estree(&to_estree(new, new_source), new_source);
+    let mappings = match_trees(&src, &dst);
+
+    let mut edits = Vec::new();
+    let moved = moved_nodes(&src, &dst, &mappings);
+    for (id, node) in src.nodes.iter().enumerate() {
+        let edit = |kind, new: Option<usize>| Edit {
+            kind,
+            node: node.kind.clone(),
+            old: Some(node.span),
+            new: new.map(|new| dst.nodes[new].span),
+        };
+        match mappings.dst(id) {
+            None => {
+                if node
+                    .parent
+                    .is_none_or(|parent| mappings.dst(parent).is_some())
+                {
+                    edits.push(edit(EditKind::Delete, None));
+                }
+            }
+            Some(new) => {
+                if node.label != dst.nodes[new].label {
+                    edits.push(edit(EditKind::Update, Some(new)));
+                }
+                if moved[id] {
+                    edits.push(edit(EditKind::Move, Some(new)));
+                }
+            }
+        }
+    }
+
+    for (id, node) in dst.nodes.iter().enumerate() {
+        let parent_matched = node
+            .parent
+            .is_none_or(|parent| mappings.src(parent).is_some());
+        if mappings.src(id).is_none() && parent_matched {
+            edits.push(Edit {
+                kind: EditKind::Insert,
+                node: node.kind.clone(),
+                old: None,
+                new: Some(node.span),
+            });
+        }
+    }
+    edits
+}
+
+/// 移動したノードを求める。
+///
+/// 親が対応しないノードと、対応する親の子の中で最長共通部分列から外れたノードが移動している。
+fn moved_nodes(src: &Tree, dst: &Tree, mappings: &Mappings) -> Vec<bool> {
+    let mut moved = vec![false; src.len()];
+    for (id, node) in src.nodes.iter().enumerate() {
+        let Some(new) = mappings.dst(id) else {
+            continue;
+        };
+        if let (Some(parent), Some(new_parent)) = (node.parent, dst.nodes[new].parent) {
+            moved[id] |= mappings.dst(parent) != Some(new_parent);
+        }
+
+        let src_children: Vec<usize> = node
+            .children
+            .iter()
+            .copied()
+            .filter(|&child| {
+                mappings
+                    .dst(child)
+                    .is_some_and(|c| dst.nodes[c].parent == Some(new))
+            })
+            .collect();
+        let dst_children: Vec<usize> = dst.nodes[new]
+            .children
+            .iter()
+            .copied()
+            .filter(|&child| {
+                mappings
+                    .src(child)
+                    .is_some_and(|c| src.nodes[c].parent == Some(id))
+            })
+            .collect();
+
+        // 子は1対1に対応するため、最長共通部分列は対応先の位置の最長増加部分列になる
+        let position: HashMap<usize, usize> = dst_children
+            .iter()
+            .enumerate()
+            .map(|(index, &child)| (child, index))
+            .collect();
+        let positions: Vec<usize> = src_children
+            .iter()
+            .filter_map(|&child| position.get(&mappings.dst(child)?).copied())
+            .collect();
+        let in_order = longest_increasing(&positions);
+        for (child, in_order) in src_children.into_iter().zip(in_order) {
+            moved[child] = !in_order;
+        }
+    }
+    moved
+}
+
+/// 最長増加部分列に含まれる要素を返す。
+fn longest_increasing(values: &[usize]) -> Vec<bool> {
+    // tails[k] は長さ k + 1 の増加部分列の最後の要素のうち、値が最小のものの添字
+    let mut tails: Vec<usize> = Vec::new();
+    let mut previous = vec![None; values.len()];
+    for (index, &value) in values.iter().enumerate() {
+        let length = tails.partition_point(|&tail| values[tail] < value);
+        previous[index] = length.checked_sub(1).map(|length| tails[length]);
+        match tails.get_mut(length) {
+            Some(tail) => *tail = index,
+            None => tails.push(index),
+        }
+    }
+
+    let mut included = vec![false; values.len()];
+    let mut current = tails.last().copied();
+    while let Some(index) = current {
+        included[index] = true;
+        current = previous[index];
+    }
+    included
+}
+
+/// 差分を読みやすい文字列にする。
+///
+/// ```text
+/// --- a.js
+/// +++ b.js
+/// update Identifier 1:5-1:8 -> 1:5-1:8: `foo` -> `bar`
+/// insert ExpressionStatement 3:1-3:5: `g();`
+/// ```
+///
+/// 行と列は1から数える。
+pub fn format_edits(
+    edits: &[Edit],
+    (old_name, old_source): (&str, &str),
+    (new_name, new_source): (&str, &str),
+) -> String {
+    let old_index = LineIndex::new(old_source);
+    let new_index = LineIndex::new(new_source);
+    let position = |index: &LineIndex, span: Span| {
+        let start = index.line_column(span.start);
+        let end = index.line_column(span.end);
+        format!(
+            "{}:{}-{}:{}",
+            start.line,
+            start.column + 1,
+            end.line,
+            end.column + 1
+        )
+    };
+
+    let mut output = format!("--- {old_name}\n+++ {new_name}\n");
+    for edit in edits {
+        write!(output, "{} {}", edit.kind.name(), edit.node).unwrap();
+        match (edit.old, edit.new) {
+            (Some(old), Some(new)) => write!(
+                output,
+                " {} -> {}",
+                position(&old_index, old),
+                position(&new_index, new)
+            ),
+            (Some(old), None) => write!(output, " {}", position(&old_index, old)),
+            (None, Some(new)) => write!(output, " {}", position(&new_index, new)),
+            (None, None) => Ok(()),
+        }
+        .unwrap();
+
+        let old_text = edit.old.map(|span| snippet(span.source_text(old_source)));
+        let new_text = edit.new.map(|span| snippet(span.source_text(new_source)));
+        match (edit.kind, old_text, new_text) {
+            (EditKind::Update, Some(old), Some(new)) => {
+                writeln!(output, ": `{old}` -> `{new}`").unwrap()
+            }
+            (_, Some(text), _) | (_, None, Some(text)) => writeln!(output, ": `{text}`").unwrap(),
+            _ => output.push('\n'),
+        }
+    }
+    output
+}
+
+/// 表示する長さ (文字数)
+const SNIPPET_LENGTH: usize = 40;
+
+/// 空白をまとめ、長い場合は省略したソースコードの一部
+fn snippet(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    match text.char_indices().nth(SNIPPET_LENGTH) {
+        Some((index, _)) => format!("{}...", &text[..index]),
+        None => text,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{diff, format_edits, Edit, EditKind};
+    use crate::ast::{
+        reader_struct::SourceCodeReader,
+        script::{parse_module, parse_script},
+        structs::span::Span,
+    };
+
+    fn edits(old: &str, new: &str) -> Vec<Edit> {
+        let parse = |source| {
+            parse_script(SourceCodeReader::new(source))
+                .into_result()
+                .unwrap_or_else(|error| panic!("{source}: {error:?}"))
+        };
+        diff(&parse(old), old, &parse(new), new)
+    }
+
+    fn formatted(old: &str, new: &str) -> String {
+        format_edits(&edits(old, new), ("a.js", old), ("b.js", new))
+            .lines()
+            .skip(2)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn formatting_is_ignored() {
+        assert_eq!(edits("a+b;", "a + b ;"), vec![]);
+        assert_eq!(
+            edits("f(1, 'x')", "// comment\nf(\n  1,\n  \"x\",\n);"),
+            vec![]
+        );
+        assert_eq!(edits("(a) * (b + c);", "a * (b + c)"), vec![]);
+        assert_eq!(edits("x = 1.0;", "x = 1;"), vec![]);
+    }
+
+    #[test]
+    fn update() {
+        assert_eq!(
+            edits("let foo = 1;", "let bar = 1;"),
+            vec![Edit {
+                kind: EditKind::Update,
+                node: "Identifier".to_string(),
+                old: Some(Span::new(4, 7)),
+                new: Some(Span::new(4, 7)),
+            }]
+        );
+        assert_eq!(
+            formatted("a + b;", "a - b;"),
+            "update BinaryExpression 1:1-1:6 -> 1:1-1:6: `a + b` -> `a - b`"
+        );
+        assert_eq!(
+            formatted("const x = 1;", "let x = 1;"),
+            "update VariableDeclaration 1:1-1:13 -> 1:1-1:11: `const x = 1;` -> `let x = 1;`"
+        );
+    }
+
+    #[test]
+    fn insert_and_delete() {
+        assert_eq!(
+            formatted("f();\ng();", "f();\nh(1);\ng();"),
+            "insert ExpressionStatement 2:1-2:6: `h(1);`"
+        );
+        assert_eq!(
+            formatted("f();\nh(1);\ng();", "f();\ng();"),
+            "delete ExpressionStatement 2:1-2:6: `h(1);`"
+        );
+        assert_eq!(
+            formatted("f(a);", "f(a, [b, c]);"),
+            "insert ArrayExpression 1:6-1:12: `[b, c]`"
+        );
+    }
+
+    #[test]
+    fn moves() {
+        let old = "function f() { return 1; }\nfunction g() { return 2; }\n";
+        let new = "function g() { return 2; }\nfunction f() { return 1; }\n";
+        assert_eq!(
+            formatted(old, new),
+            "move FunctionDeclaration 1:1-1:27 -> 2:1-2:27: `function f() { return 1; }`"
+        );
+
+        // 別の親に移った部分木
+        let old = "if (x) { a(1); }\nb(2);";
+        let new = "if (x) { a(1); b(2); }";
+        assert_eq!(
+            formatted(old, new),
+            "move ExpressionStatement 2:1-2:6 -> 1:16-1:21: `b(2);`"
+        );
+    }
+
+    #[test]
+    fn nested_changes() {
+        let old = "function sum(list) {\n  let total = 0;\n  for (const x of list) total += x;\n  return total;\n}\n";
+        let new = "function sum(items) {\n  let total = 0;\n  for (const x of items) {\n    total += x;\n  }\n  return total;\n}\n";
+        assert_eq!(
+            formatted(old, new),
+            [
+                "update Identifier 1:14-1:18 -> 1:14-1:19: `list` -> `items`",
+                "update Identifier 3:19-3:23 -> 3:19-3:24: `list` -> `items`",
+                "move ExpressionStatement 3:25-3:36 -> 4:5-4:16: `total += x;`",
+                "insert BlockStatement 3:26-5:4: `{ total += x; }`",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn module() {
+        let old = "import { a } from 'a';\nexport const b = a;";
+        let new = "import { a, c } from \"a\";\nexport const b = a;";
+        let parse = |source| {
+            parse_module(SourceCodeReader::new(source))
+                .into_result()
+                .unwrap()
+        };
+        let edits = diff(&parse(old), old, &parse(new), new);
+        assert_eq!(
+            format_edits(&edits, ("a.mjs", old), ("b.mjs", new)),
+            "--- a.mjs\n+++ b.mjs\ninsert ImportSpecifier 1:13-1:14: `c`\n"
+        );
+    }
+
+    #[test]
+    fn long_snippet() {
+        let new = "f(\n  aaaaaaaaaa + bbbbbbbbbb + cccccccccc + dddddddddd,\n);";
+        assert_eq!(
+            formatted("", new),
+            "insert ExpressionStatement 1:1-3:3: `f( aaaaaaaaaa + bbbbbbbbbb + cccccccccc ...`"
+        );
+    }
+
+    #[test]
+    fn repeated_subtrees_scale() {
+        // 同じ形の文が多いファイルでも、時間が行数にほぼ比例すること
+        let time = |count: usize| {
+            let old: String = (0..count)
+                .map(|index| format!("x = f(1);\ny{}();\n", index % 3))
+                .collect();
+            let new = format!("z();\n{old}");
+            let start = std::time::Instant::now();
+            assert_eq!(edits(&old, &new).len(), 1);
+            start.elapsed()
+        };
+
+        let small = (0..3).map(|_| time(250)).min().unwrap();
+        let large = time(2000);
+        assert!(
+            large < small * 24,
+            "8x more lines took {large:?} against {small:?}"
+        );
+    }
+}
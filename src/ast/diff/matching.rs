@@ -0,0 +1,295 @@
+//! 2つの木のノードを対応させるモジュールです。
+//!
+//! GumTree (Falleri ほか, 2014) と同じ3段階で対応を決める。
+//!
+//! 1. 上から: 高い部分木から順に、同形の部分木を対応させる。
+//! 2. 下から: 子孫の多くが対応しているノードを対応させる。
+//! 3. 回復: 対応させたノードの子のうち、残ったものを順序を保って対応させる。
+
+use std::collections::HashMap;
+
+use super::tree::Tree;
+
+/// これより低い部分木は上からの対応に使わない (`a` のような葉はどこにでも現れるため)
+const MIN_HEIGHT: usize = 2;
+
+/// 同じ形の部分木が複数ある場合に、順番の前後で比べる数
+const AMBIGUOUS_WINDOW: usize = 16;
+
+/// 下からの対応に必要な、子孫の対応の割合 (Dice 係数)
+const MIN_DICE: f64 = 0.5;
+
+/// ノードの対応
+#[derive(Debug, Clone)]
+pub(super) struct Mappings {
+    src_to_dst: Vec<Option<usize>>,
+    dst_to_src: Vec<Option<usize>>,
+}
+
+impl Mappings {
+    fn new(src: &Tree, dst: &Tree) -> Mappings {
+        Mappings {
+            src_to_dst: vec![None; src.len()],
+            dst_to_src: vec![None; dst.len()],
+        }
+    }
+
+    fn link(&mut self, src: usize, dst: usize) {
+        self.src_to_dst[src] = Some(dst);
+        self.dst_to_src[dst] = Some(src);
+    }
+
+    pub(super) fn dst(&self, src: usize) -> Option<usize> {
+        self.src_to_dst[src]
+    }
+
+    pub(super) fn src(&self, dst: usize) -> Option<usize> {
+        self.dst_to_src[dst]
+    }
+}
+
+/// 2つの木のノードを対応させる。
+pub(super) fn match_trees(src: &Tree, dst: &Tree) -> Mappings {
+    let mut matcher = Matcher {
+        src,
+        dst,
+        mappings: Mappings::new(src, dst),
+    };
+    matcher.top_down();
+    matcher.bottom_up();
+    matcher.mappings
+}
+
+struct Matcher<'a> {
+    src: &'a Tree,
+    dst: &'a Tree,
+    mappings: Mappings,
+}
+
+impl Matcher<'_> {
+    fn top_down(&mut self) {
+        let max_height = self.src.nodes[0].height.max(self.dst.nodes[0].height);
+
+        for height in (MIN_HEIGHT..=max_height).rev() {
+            // 同じハッシュの部分木を、見つけた順に集める
+            let mut groups: Vec<(Vec<usize>, Vec<usize>)> = Vec::new();
+            let mut group_of: HashMap<u64, usize> = HashMap::new();
+            for (tree, is_src) in [(self.src, true), (self.dst, false)] {
+                for (id, node) in tree.nodes.iter().enumerate() {
+                    let matched = match is_src {
+                        true => self.mappings.dst(id).is_some(),
+                        false => self.mappings.src(id).is_some(),
+                    };
+                    if node.height != height || matched {
+                        continue;
+                    }
+                    let group = *group_of.entry(node.hash).or_insert_with(|| {
+                        groups.push((Vec::new(), Vec::new()));
+                        groups.len() - 1
+                    });
+                    match is_src {
+                        true => groups[group].0.push(id),
+                        false => groups[group].1.push(id),
+                    }
+                }
+            }
+
+            for (srcs, dsts) in groups {
+                match (srcs.as_slice(), dsts.as_slice()) {
+                    ([], _) | (_, []) => {}
+                    ([src], [dst]) => self.link_subtrees(*src, *dst),
+                    _ => self.link_ambiguous(&srcs, &dsts),
+                }
+            }
+        }
+    }
+
+    /// 同じ形の部分木が複数ある場合は、親がよく対応しているものから組にする。
+    ///
+    /// 全ての組を比べると同じ形の部分木が多い場合に遅くなるため、
+    /// 順番が近いもの ([`AMBIGUOUS_WINDOW`] 個以内) だけを候補にする。
+    /// 親の Dice 係数は、同じ親の組について一度だけ求める。
+    fn link_ambiguous(&mut self, srcs: &[usize], dsts: &[usize]) {
+        let mut parent_dice: HashMap<(usize, usize), f64> = HashMap::new();
+        let mut candidates = Vec::new();
+        for (src_index, &src) in srcs.iter().enumerate() {
+            // 挿入や削除で順番がずれても近くを探せるように、順番の割合で中心を決める
+            let center = src_index * dsts.len() / srcs.len();
+            let first = center.saturating_sub(AMBIGUOUS_WINDOW);
+            let last = (center + AMBIGUOUS_WINDOW + 1).min(dsts.len());
+
+            for (dst_index, &dst) in dsts.iter().enumerate().take(last).skip(first) {
+                let dice = match (self.src.nodes[src].parent, self.dst.nodes[dst].parent) {
+                    (Some(src_parent), Some(dst_parent)) => *parent_dice
+                        .entry((src_parent, dst_parent))
+                        .or_insert_with(|| self.dice(src_parent, dst_parent)),
+                    _ => 0.0,
+                };
+                candidates.push((dice, src_index.abs_diff(dst_index), src, dst));
+            }
+        }
+        candidates.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)));
+
+        for (_, _, src, dst) in candidates {
+            if self.mappings.dst(src).is_none() && self.mappings.src(dst).is_none() {
+                self.link_subtrees(src, dst);
+            }
+        }
+    }
+
+    /// 同形の部分木のノードを全て対応させる。
+    fn link_subtrees(&mut self, src: usize, dst: usize) {
+        if !self.isomorphic(src, dst) {
+            return;
+        }
+        for offset in 0..self.src.nodes[src].size {
+            self.mappings.link(src + offset, dst + offset);
+        }
+    }
+
+    /// ハッシュの衝突に備えて、部分木の形を比べる。
+    fn isomorphic(&self, src: usize, dst: usize) -> bool {
+        let size = self.src.nodes[src].size;
+        size == self.dst.nodes[dst].size
+            && (0..size).all(|offset| {
+                let (src, dst) = (&self.src.nodes[src + offset], &self.dst.nodes[dst + offset]);
+                src.kind == dst.kind && src.label == dst.label && src.size == dst.size
+            })
+    }
+
+    fn bottom_up(&mut self) {
+        for src in self.src.postorder() {
+            let node = &self.src.nodes[src];
+            if node.parent.is_none() {
+                if self.mappings.src(0).is_none() && self.dst.nodes[0].kind == node.kind {
+                    self.mappings.link(src, 0);
+                }
+                if self.mappings.dst(src) == Some(0) {
+                    self.recover(src, 0);
+                }
+                continue;
+            }
+            if self.mappings.dst(src).is_some() || node.children.is_empty() {
+                continue;
+            }
+
+            let best = self
+                .candidates(src)
+                .into_iter()
+                .map(|dst| (self.dice(src, dst), dst))
+                .max_by(|a, b| a.0.total_cmp(&b.0));
+            if let Some((dice, dst)) = best {
+                if dice >= MIN_DICE {
+                    self.mappings.link(src, dst);
+                    self.recover(src, dst);
+                }
+            }
+        }
+    }
+
+    /// 子孫の対応先の祖先のうち、種類が同じで対応していないノード
+    fn candidates(&self, src: usize) -> Vec<usize> {
+        let kind = &self.src.nodes[src].kind;
+        let mut candidates = Vec::new();
+        for descendant in self.src.descendants(src) {
+            let Some(dst) = self.mappings.dst(descendant) else {
+                continue;
+            };
+            for ancestor in self.dst.ancestors(dst).skip(1) {
+                if self.dst.nodes[ancestor].kind == *kind
+                    && self.mappings.src(ancestor).is_none()
+                    && !candidates.contains(&ancestor)
+                {
+                    candidates.push(ancestor);
+                }
+            }
+        }
+        candidates
+    }
+
+    /// 子孫のうち互いに対応しているものの割合
+    fn dice(&self, src: usize, dst: usize) -> f64 {
+        let dst_descendants = self.dst.descendants(dst);
+        let common = self
+            .src
+            .descendants(src)
+            .filter(|&descendant| {
+                self.mappings
+                    .dst(descendant)
+                    .is_some_and(|mapped| dst_descendants.contains(&mapped))
+            })
+            .count();
+        let total = self.src.nodes[src].size + self.dst.nodes[dst].size - 2;
+        match total {
+            0 => 0.0,
+            total => 2.0 * common as f64 / total as f64,
+        }
+    }
+
+    /// 対応させたノードの子のうち、対応していないものを順序を保って対応させる。
+    ///
+    /// 同形、種類とラベルが同じ、種類が同じの順に試す。
+    fn recover(&mut self, src: usize, dst: usize) {
+        for pass in 0..3 {
+            let srcs: Vec<usize> = self.src.nodes[src]
+                .children
+                .iter()
+                .copied()
+                .filter(|&child| self.mappings.dst(child).is_none())
+                .collect();
+            let dsts: Vec<usize> = self.dst.nodes[dst]
+                .children
+                .iter()
+                .copied()
+                .filter(|&child| self.mappings.src(child).is_none())
+                .collect();
+
+            let pairs = lcs(&srcs, &dsts, |src, dst| {
+                let (src_node, dst_node) = (&self.src.nodes[src], &self.dst.nodes[dst]);
+                match pass {
+                    0 => src_node.hash == dst_node.hash && self.isomorphic(src, dst),
+                    1 => src_node.kind == dst_node.kind && src_node.label == dst_node.label,
+                    _ => src_node.kind == dst_node.kind,
+                }
+            });
+            for (src, dst) in pairs {
+                match pass {
+                    0 => self.link_subtrees(src, dst),
+                    _ => {
+                        self.mappings.link(src, dst);
+                        self.recover(src, dst);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// 最長共通部分列の組を返す。
+fn lcs(src: &[usize], dst: &[usize], equal: impl Fn(usize, usize) -> bool) -> Vec<(usize, usize)> {
+    // lengths[i][j] は src[i..] と dst[j..] の最長共通部分列の長さ
+    let mut lengths = vec![vec![0; dst.len() + 1]; src.len() + 1];
+    for i in (0..src.len()).rev() {
+        for j in (0..dst.len()).rev() {
+            lengths[i][j] = match equal(src[i], dst[j]) {
+                true => lengths[i + 1][j + 1] + 1,
+                false => lengths[i + 1][j].max(lengths[i][j + 1]),
+            };
+        }
+    }
+
+    let mut pairs = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < src.len() && j < dst.len() {
+        if equal(src[i], dst[j]) && lengths[i][j] == lengths[i + 1][j + 1] + 1 {
+            pairs.push((src[i], dst[j]));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs
+}
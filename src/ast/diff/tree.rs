@@ -0,0 +1,158 @@
+//! 差分を取るための木を ESTree の JSON から作るモジュールです。
+//!
+//! ESTree のノード (`type` を持つオブジェクト) を木のノードにし、位置と `raw` 以外の
+//! スカラーの値 (名前、値、演算子など) をラベルにする。ノードの値を持つプロパティは子になる。
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    ops::Range,
+};
+
+use crate::{
+    ast::{line_index::LineIndex, structs::span::Span},
+    json::JsonValue,
+};
+
+/// ラベルに含めないプロパティ (位置と表記)
+const IGNORED_KEYS: [&str; 6] = ["type", "start", "end", "loc", "range", "raw"];
+
+/// 木のノード
+#[derive(Debug, Clone)]
+pub(super) struct Node {
+    /// ESTree の `type`
+    pub(super) kind: String,
+    pub(super) label: String,
+
+    /// ソースコード上の範囲 (バイト位置)
+    pub(super) span: Span,
+    pub(super) parent: Option<usize>,
+    pub(super) children: Vec<usize>,
+
+    /// 葉を1とする高さ
+    pub(super) height: usize,
+
+    /// 自身を含む部分木のノードの数
+    pub(super) size: usize,
+
+    /// 種類とラベルと子から求めたハッシュ (同形の部分木は同じ値になる)
+    pub(super) hash: u64,
+}
+
+/// ノードを前順に並べた木
+///
+/// 部分木のノードは連続するため、`id` の部分木は `id..id + size` になる。
+#[derive(Debug, Clone)]
+pub(super) struct Tree {
+    pub(super) nodes: Vec<Node>,
+}
+
+impl Tree {
+    pub(super) fn from_estree(program: &JsonValue, source: &str) -> Tree {
+        let mut tree = Tree { nodes: Vec::new() };
+        tree.add(program, None, &LineIndex::new(source));
+        tree
+    }
+
+    fn add(&mut self, value: &JsonValue, parent: Option<usize>, index: &LineIndex) -> usize {
+        let JsonValue::Object(entries) = value else {
+            unreachable!("not an ESTree node");
+        };
+
+        let id = self.nodes.len();
+        let position = |key| {
+            let offset = value.get(key).and_then(JsonValue::as_f64).unwrap_or(0.0);
+            index.byte_offset(offset as usize)
+        };
+        self.nodes.push(Node {
+            kind: value
+                .get("type")
+                .and_then(JsonValue::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            label: String::new(),
+            span: Span::new(position("start"), position("end")),
+            parent,
+            children: Vec::new(),
+            height: 1,
+            size: 1,
+            hash: 0,
+        });
+
+        let mut labels = Vec::new();
+        let mut children = Vec::new();
+        for (key, value) in entries {
+            if IGNORED_KEYS.contains(&key.as_str()) {
+                continue;
+            }
+            match value {
+                JsonValue::Null => {}
+                JsonValue::Array(values) => {
+                    for value in values.iter().filter(|value| is_node(value)) {
+                        children.push(self.add(value, Some(id), index));
+                    }
+                }
+                value if is_node(value) => children.push(self.add(value, Some(id), index)),
+                JsonValue::String(string) => labels.push(format!("{key}={string}")),
+                value => labels.push(format!("{key}={}", value.to_json_string())),
+            }
+        }
+
+        let mut hasher = DefaultHasher::new();
+        self.nodes[id].kind.hash(&mut hasher);
+        labels.hash(&mut hasher);
+        let mut height = 0;
+        for &child in &children {
+            self.nodes[child].hash.hash(&mut hasher);
+            height = height.max(self.nodes[child].height);
+        }
+
+        let size = self.nodes.len() - id;
+        let node = &mut self.nodes[id];
+        node.label = labels.join(" ");
+        node.hash = hasher.finish();
+        node.children = children;
+        node.size = size;
+        node.height = height + 1;
+        id
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// 子孫のノード (自身を含まない)
+    pub(super) fn descendants(&self, id: usize) -> Range<usize> {
+        id + 1..id + self.nodes[id].size
+    }
+
+    /// 後順に並べたノード
+    pub(super) fn postorder(&self) -> Vec<usize> {
+        let mut order = Vec::with_capacity(self.len());
+        let mut stack = vec![(0, false)];
+        while let Some((id, visited)) = stack.pop() {
+            if visited {
+                order.push(id);
+                continue;
+            }
+            stack.push((id, true));
+            stack.extend(
+                self.nodes[id]
+                    .children
+                    .iter()
+                    .rev()
+                    .map(|&child| (child, false)),
+            );
+        }
+        order
+    }
+
+    /// 自身から根までのノード
+    pub(super) fn ancestors(&self, id: usize) -> impl Iterator<Item = usize> + '_ {
+        std::iter::successors(Some(id), |&id| self.nodes[id].parent)
+    }
+}
+
+fn is_node(value: &JsonValue) -> bool {
+    matches!(value, JsonValue::Object(_)) && value.get("type").is_some()
+}
@@ -0,0 +1,1248 @@
+//! 構文木に対して早期エラー (ECMA-262 の Static Semantics: Early Errors) を検査するモジュールです。
+//!
+//! 文法としては正しくても、宣言の重複や未定義のラベルへの `break` のように
+//! 実行前に報告しなければいけない誤りを、構文解析の後に構文木を辿って検出する。
+
+mod declarations;
+
+use std::collections::HashSet;
+
+use super::{
+    error::ParseError,
+    structs::{
+        class::{Class, ClassElement, MethodKind},
+        expression::{
+            Expression, ExpressionOrSpread, MemberExpression, MemberProperty, ObjectExpression,
+            ObjectProperty, PropertyKey, PropertyKind, UnaryOperator,
+        },
+        function::{ArrowFunctionBody, ArrowFunctionExpression, Function},
+        literal::{Identifier, PrivateIdentifier},
+        module::{Declaration, ExportDefaultKind, ModuleDeclaration, ModuleItem},
+        pattern::{ObjectPatternProperty, Pattern},
+        span::{Span, Spanned},
+        statement::{ForInOfLeft, ForInit, Statement, VariableDeclaration},
+        JsAst, SourceType,
+    },
+};
+
+use self::declarations::ScopeKind;
+
+/// 構文木の早期エラーを全て集め、位置の順に並べて返す。
+pub fn check_early_errors(ast: &JsAst) -> Vec<ParseError> {
+    let mut checker = Checker::default();
+    checker.check_program(ast);
+
+    let mut errors = checker.errors;
+    errors.sort_by_key(|error| error.span);
+    errors
+}
+
+/// 検査している位置で使えるもの
+#[derive(Debug, Clone, Copy, Default)]
+struct Context {
+    strict: bool,
+
+    /// new.target
+    new_target: bool,
+
+    /// super.a
+    super_property: bool,
+
+    /// super()
+    super_call: bool,
+
+    /// クラスのフィールドの初期化子と static ブロックの中 (`arguments` を使えない)
+    class_initializer: bool,
+
+    /// ラベルのない break
+    breakable: bool,
+
+    /// ラベルのない continue
+    iteration: bool,
+}
+
+/// 囲んでいるラベル
+#[derive(Debug)]
+struct Label<'a> {
+    name: &'a str,
+
+    /// 繰り返し文のラベルであるか (continue の対象にできる)
+    iteration: bool,
+}
+
+/// 関数の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FunctionKind {
+    /// 関数宣言と関数式
+    Normal,
+
+    /// オブジェクトリテラルとクラスのメソッド
+    Method,
+
+    /// クラスのコンストラクタ
+    Constructor { derived: bool },
+}
+
+#[derive(Debug, Default)]
+struct Checker<'a> {
+    errors: Vec<ParseError>,
+    context: Context,
+    labels: Vec<Label<'a>>,
+
+    /// 囲んでいるクラスで宣言されたプライベート名
+    private_names: Vec<HashSet<&'a str>>,
+}
+
+impl<'a> Checker<'a> {
+    fn error(&mut self, span: Span, message: impl Into<String>) {
+        self.errors.push(ParseError::new(span, message));
+    }
+
+    /// 文脈を変えて検査する。関数の境界ではラベルも引き継がない。
+    fn with_context(
+        &mut self,
+        context: Context,
+        is_function_boundary: bool,
+        f: impl FnOnce(&mut Self),
+    ) {
+        let outer_context = std::mem::replace(&mut self.context, context);
+        let outer_labels = if is_function_boundary {
+            std::mem::take(&mut self.labels)
+        } else {
+            Vec::new()
+        };
+
+        f(self);
+
+        self.context = outer_context;
+        if is_function_boundary {
+            self.labels = outer_labels;
+        }
+    }
+
+    fn check_program(&mut self, ast: &'a JsAst) {
+        let is_module = ast.source_type == SourceType::Module;
+        let statements = ast.body.iter().filter_map(|item| match item {
+            ModuleItem::Statement(statement) => Some(statement),
+            ModuleItem::ModuleDeclaration(_) => None,
+        });
+        self.context.strict = is_module || has_use_strict_directive(statements);
+
+        if is_module {
+            self.check_module_declarations(&ast.body);
+        } else {
+            let statements = ast
+                .body
+                .iter()
+                .filter_map(|item| match item {
+                    ModuleItem::Statement(statement) => Some(statement),
+                    ModuleItem::ModuleDeclaration(_) => None,
+                })
+                .collect::<Vec<_>>();
+            self.check_declarations(&statements, ScopeKind::TopLevel);
+        }
+
+        for item in &ast.body {
+            match item {
+                ModuleItem::Statement(statement) => self.check_statement(statement),
+                ModuleItem::ModuleDeclaration(declaration) => {
+                    self.check_module_declaration(declaration)
+                }
+            }
+        }
+    }
+
+    fn check_module_declaration(&mut self, declaration: &'a ModuleDeclaration) {
+        match declaration {
+            ModuleDeclaration::Import(_) | ModuleDeclaration::ExportAll(_) => {}
+            ModuleDeclaration::ExportNamed(export) => match &export.declaration {
+                Some(Declaration::Function(function)) => {
+                    self.check_function(function, FunctionKind::Normal)
+                }
+                Some(Declaration::Variable(variable)) => self.check_variable_declaration(variable),
+                Some(Declaration::Class(class)) => self.check_class(class),
+                None => {}
+            },
+            ModuleDeclaration::ExportDefault(export) => match &export.declaration {
+                ExportDefaultKind::Function(function) => {
+                    self.check_function(function, FunctionKind::Normal)
+                }
+                ExportDefaultKind::Class(class) => self.check_class(class),
+                ExportDefaultKind::Expression(expression) => self.check_expression(expression),
+            },
+        }
+    }
+
+    // 文
+
+    fn check_statements(&mut self, statements: &'a [Statement]) {
+        for statement in statements {
+            self.check_statement(statement);
+        }
+    }
+
+    fn check_block(&mut self, statements: &'a [Statement]) {
+        let list = statements.iter().collect::<Vec<_>>();
+        self.check_declarations(&list, ScopeKind::Block);
+        self.check_statements(statements);
+    }
+
+    fn check_statement(&mut self, statement: &'a Statement) {
+        match statement {
+            Statement::Expression(statement) => self.check_expression(&statement.expression),
+            Statement::Block(block) => self.check_block(&block.body),
+            Statement::Empty(_) | Statement::Debugger(_) | Statement::Error(_) => {}
+            Statement::With(with) => {
+                self.check_expression(&with.object);
+                self.check_statement(&with.body);
+            }
+            Statement::Return(statement) => {
+                if let Some(argument) = &statement.argument {
+                    self.check_expression(argument);
+                }
+            }
+            Statement::Labeled(labeled) => {
+                let name = labeled.label.name.as_str();
+                if self.labels.iter().any(|label| label.name == name) {
+                    self.error(
+                        labeled.label.span,
+                        format!("label '{name}' has already been declared"),
+                    );
+                }
+
+                self.labels.push(Label {
+                    name,
+                    iteration: is_iteration_statement(&labeled.body),
+                });
+                self.check_statement(&labeled.body);
+                self.labels.pop();
+            }
+            Statement::Break(statement) => match &statement.label {
+                Some(label) if !self.labels.iter().any(|l| l.name == label.name) => {
+                    self.error(label.span, format!("undefined label '{}'", label.name));
+                }
+                Some(_) => {}
+                None if !self.context.breakable => {
+                    self.error(statement.span, "illegal break statement");
+                }
+                None => {}
+            },
+            Statement::Continue(statement) => match &statement.label {
+                Some(label) => match self.labels.iter().rev().find(|l| l.name == label.name) {
+                    Some(Label {
+                        iteration: true, ..
+                    }) => {}
+                    Some(_) => self.error(
+                        label.span,
+                        format!("label '{}' is not an iteration statement", label.name),
+                    ),
+                    None => self.error(label.span, format!("undefined label '{}'", label.name)),
+                },
+                None if !self.context.iteration => {
+                    self.error(statement.span, "illegal continue statement");
+                }
+                None => {}
+            },
+            Statement::If(statement) => {
+                self.check_expression(&statement.test);
+                self.check_labelled_function(&statement.consequent);
+                self.check_statement(&statement.consequent);
+                if let Some(alternate) = &statement.alternate {
+                    self.check_labelled_function(alternate);
+                    self.check_statement(alternate);
+                }
+            }
+            Statement::Switch(switch) => {
+                self.check_expression(&switch.discriminant);
+
+                let statements = switch
+                    .cases
+                    .iter()
+                    .flat_map(|case| &case.consequent)
+                    .collect::<Vec<_>>();
+                self.check_declarations(&statements, ScopeKind::Block);
+
+                let context = Context {
+                    breakable: true,
+                    ..self.context
+                };
+                self.with_context(context, false, |checker| {
+                    for case in &switch.cases {
+                        if let Some(test) = &case.test {
+                            checker.check_expression(test);
+                        }
+                        checker.check_statements(&case.consequent);
+                    }
+                });
+            }
+            Statement::Throw(statement) => self.check_expression(&statement.argument),
+            Statement::Try(statement) => {
+                self.check_block(&statement.block.body);
+
+                if let Some(handler) = &statement.handler {
+                    if let Some(param) = &handler.param {
+                        self.check_catch_parameter(param, &handler.body.body);
+                        self.check_binding_pattern(param);
+                    }
+                    self.check_block(&handler.body.body);
+                }
+
+                if let Some(finalizer) = &statement.finalizer {
+                    self.check_block(&finalizer.body);
+                }
+            }
+            Statement::While(statement) => {
+                self.check_expression(&statement.test);
+                self.check_loop_body(&statement.body);
+            }
+            Statement::DoWhile(statement) => {
+                self.check_loop_body(&statement.body);
+                self.check_expression(&statement.test);
+            }
+            Statement::For(statement) => {
+                match &statement.init {
+                    Some(ForInit::VariableDeclaration(declaration)) => {
+                        self.check_for_declaration(declaration, &statement.body);
+                        self.check_variable_declaration(declaration);
+                    }
+                    Some(ForInit::Expression(expression)) => self.check_expression(expression),
+                    None => {}
+                }
+                if let Some(test) = &statement.test {
+                    self.check_expression(test);
+                }
+                if let Some(update) = &statement.update {
+                    self.check_expression(update);
+                }
+                self.check_loop_body(&statement.body);
+            }
+            Statement::ForIn(statement) => {
+                self.check_for_in_of_left(&statement.left, &statement.body);
+                self.check_expression(&statement.right);
+                self.check_loop_body(&statement.body);
+            }
+            Statement::ForOf(statement) => {
+                self.check_for_in_of_left(&statement.left, &statement.body);
+                self.check_expression(&statement.right);
+                self.check_loop_body(&statement.body);
+            }
+            Statement::FunctionDeclaration(function) => {
+                self.check_function(function, FunctionKind::Normal)
+            }
+            Statement::VariableDeclaration(declaration) => {
+                self.check_variable_declaration(declaration)
+            }
+            Statement::ClassDeclaration(class) => self.check_class(class),
+        }
+    }
+
+    fn check_loop_body(&mut self, body: &'a Statement) {
+        self.check_labelled_function(body);
+        let context = Context {
+            breakable: true,
+            iteration: true,
+            ..self.context
+        };
+        self.with_context(context, false, |checker| checker.check_statement(body));
+    }
+
+    /// 繰り返し文と if 文の本体は、ラベルを付けた関数宣言にできない。
+    fn check_labelled_function(&mut self, body: &Statement) {
+        if is_labelled_function(body) {
+            self.error(
+                body.span(),
+                "labelled function declaration is not allowed as the body of a statement",
+            );
+        }
+    }
+
+    fn check_for_in_of_left(&mut self, left: &'a ForInOfLeft, body: &'a Statement) {
+        match left {
+            ForInOfLeft::VariableDeclaration(declaration) => {
+                self.check_for_declaration(declaration, body);
+                self.check_variable_declaration(declaration);
+            }
+            ForInOfLeft::Pattern(pattern) => self.check_assignment_pattern(pattern),
+        }
+    }
+
+    fn check_variable_declaration(&mut self, declaration: &'a VariableDeclaration) {
+        for declarator in &declaration.declarations {
+            self.check_binding_pattern(&declarator.id);
+            if let Some(init) = &declarator.init {
+                self.check_expression(init);
+            }
+        }
+    }
+
+    // パターン
+
+    /// 束縛のパターン
+    fn check_binding_pattern(&mut self, pattern: &'a Pattern) {
+        self.check_pattern(pattern, true);
+    }
+
+    /// 代入の対象のパターン
+    fn check_assignment_pattern(&mut self, pattern: &'a Pattern) {
+        self.check_pattern(pattern, false);
+    }
+
+    fn check_pattern(&mut self, pattern: &'a Pattern, is_binding: bool) {
+        match pattern {
+            Pattern::Identifier(identifier) => {
+                if self.context.strict && is_eval_or_arguments(identifier) {
+                    let verb = if is_binding { "bind" } else { "assign to" };
+                    self.error(
+                        identifier.span,
+                        format!("cannot {verb} '{}' in strict mode", identifier.name),
+                    );
+                }
+                if !is_binding {
+                    self.check_identifier_reference(identifier);
+                }
+            }
+            Pattern::Object(object) => {
+                for property in &object.properties {
+                    match property {
+                        ObjectPatternProperty::Property(property) => {
+                            self.check_property_key(&property.key);
+                            self.check_pattern(&property.value, is_binding);
+                        }
+                        ObjectPatternProperty::Rest(rest) => {
+                            self.check_pattern(&rest.argument, is_binding)
+                        }
+                    }
+                }
+            }
+            Pattern::Array(array) => {
+                for element in array.elements.iter().flatten() {
+                    self.check_pattern(element, is_binding);
+                }
+            }
+            Pattern::Rest(rest) => self.check_pattern(&rest.argument, is_binding),
+            Pattern::Assignment(assignment) => {
+                self.check_pattern(&assignment.left, is_binding);
+                self.check_expression(&assignment.right);
+            }
+            Pattern::Expression(expression) => match expression.as_ref() {
+                Expression::Member(member) if !is_binding => self.check_member(member),
+                expression => self.error(expression.span(), "invalid assignment target"),
+            },
+        }
+    }
+
+    // 式
+
+    fn check_expressions(&mut self, expressions: &'a [ExpressionOrSpread]) {
+        for expression in expressions {
+            match expression {
+                ExpressionOrSpread::Expression(expression) => self.check_expression(expression),
+                ExpressionOrSpread::Spread(spread) => self.check_expression(&spread.argument),
+            }
+        }
+    }
+
+    fn check_expression(&mut self, expression: &'a Expression) {
+        match expression {
+            Expression::Identifier(identifier) => self.check_identifier_reference(identifier),
+            Expression::Literal(_) | Expression::This(_) | Expression::Error(_) => {}
+            Expression::Super(span) => self.error(span.span, "'super' keyword unexpected here"),
+            Expression::Array(array) => {
+                for element in array.elements.iter().flatten() {
+                    match element {
+                        ExpressionOrSpread::Expression(expression) => {
+                            self.check_expression(expression)
+                        }
+                        ExpressionOrSpread::Spread(spread) => {
+                            self.check_expression(&spread.argument)
+                        }
+                    }
+                }
+            }
+            Expression::Object(object) => self.check_object(object),
+            Expression::Function(function) => self.check_function(function, FunctionKind::Normal),
+            Expression::ArrowFunction(arrow) => self.check_arrow_function(arrow),
+            Expression::Class(class) => self.check_class(class),
+            Expression::TemplateLiteral(template) => {
+                for expression in &template.expressions {
+                    self.check_expression(expression);
+                }
+            }
+            Expression::TaggedTemplate(tagged) => {
+                self.check_expression(&tagged.tag);
+                for expression in &tagged.quasi.expressions {
+                    self.check_expression(expression);
+                }
+            }
+            Expression::Member(member) => self.check_member(member),
+            Expression::Call(call) => {
+                if let Expression::Super(callee) = &call.callee {
+                    if !self.context.super_call {
+                        self.error(
+                            callee.span,
+                            "'super' call is only allowed in derived class constructors",
+                        );
+                    }
+                } else {
+                    self.check_expression(&call.callee);
+                }
+                self.check_expressions(&call.arguments);
+            }
+            Expression::New(new) => {
+                self.check_expression(&new.callee);
+                self.check_expressions(&new.arguments);
+            }
+            Expression::Chain(chain) => self.check_expression(&chain.expression),
+            Expression::Unary(unary) => {
+                if unary.operator == UnaryOperator::Delete {
+                    self.check_delete_target(&unary.argument);
+                }
+                self.check_expression(&unary.argument);
+            }
+            Expression::Update(update) => {
+                if let Expression::Identifier(identifier) = &update.argument {
+                    if self.context.strict && is_eval_or_arguments(identifier) {
+                        self.error(
+                            identifier.span,
+                            format!("cannot assign to '{}' in strict mode", identifier.name),
+                        );
+                    }
+                }
+                self.check_expression(&update.argument);
+            }
+            Expression::Binary(binary) => {
+                self.check_expression(&binary.left);
+                self.check_expression(&binary.right);
+            }
+            Expression::Logical(logical) => {
+                self.check_expression(&logical.left);
+                self.check_expression(&logical.right);
+            }
+            Expression::Conditional(conditional) => {
+                self.check_expression(&conditional.test);
+                self.check_expression(&conditional.consequent);
+                self.check_expression(&conditional.alternate);
+            }
+            Expression::Assignment(assignment) => {
+                self.check_assignment_pattern(&assignment.left);
+                self.check_expression(&assignment.right);
+            }
+            Expression::Sequence(sequence) => {
+                for expression in &sequence.expressions {
+                    self.check_expression(expression);
+                }
+            }
+            Expression::Yield(expression) => {
+                if let Some(argument) = &expression.argument {
+                    self.check_expression(argument);
+                }
+            }
+            Expression::Await(expression) => self.check_expression(&expression.argument),
+            Expression::MetaProperty(meta) => {
+                if meta.meta.name == "new" && !self.context.new_target {
+                    self.error(meta.span, "'new.target' is only allowed in functions");
+                }
+            }
+            Expression::Import(import) => {
+                self.check_expression(&import.source);
+                if let Some(options) = &import.options {
+                    self.check_expression(options);
+                }
+            }
+            Expression::PrivateIdentifier(identifier) => self.check_private_name(identifier),
+        }
+    }
+
+    fn check_identifier_reference(&mut self, identifier: &Identifier) {
+        if self.context.class_initializer && identifier.name == "arguments" {
+            self.error(
+                identifier.span,
+                "'arguments' is not allowed in class field initializer or static initialization block",
+            );
+        }
+    }
+
+    fn check_member(&mut self, member: &'a MemberExpression) {
+        if let Expression::Super(object) = &member.object {
+            if !self.context.super_property {
+                self.error(object.span, "'super' keyword unexpected here");
+            }
+        } else {
+            self.check_expression(&member.object);
+        }
+
+        match &member.property {
+            MemberProperty::Identifier(_) => {}
+            MemberProperty::PrivateIdentifier(identifier) => self.check_private_name(identifier),
+            MemberProperty::Computed(expression) => self.check_expression(expression),
+        }
+    }
+
+    /// strict モードでは識別子を、どのモードでもプライベート名を delete できない。
+    fn check_delete_target(&mut self, argument: &Expression) {
+        let member = match argument {
+            Expression::Identifier(identifier) if self.context.strict => {
+                self.error(
+                    identifier.span,
+                    "delete of an unqualified identifier in strict mode",
+                );
+                return;
+            }
+            Expression::Member(member) => member,
+            Expression::Chain(chain) => match &chain.expression {
+                Expression::Member(member) => member,
+                _ => return,
+            },
+            _ => return,
+        };
+
+        if let MemberProperty::PrivateIdentifier(identifier) = &member.property {
+            self.error(identifier.span, "private fields cannot be deleted");
+        }
+    }
+
+    fn check_property_key(&mut self, key: &'a PropertyKey) {
+        if let PropertyKey::Computed(expression) = key {
+            self.check_expression(expression);
+        }
+    }
+
+    /// `__proto__: a` は1つのオブジェクトリテラルに1つしか書けない (Annex B)。
+    fn check_object(&mut self, object: &'a ObjectExpression) {
+        let mut has_proto = false;
+
+        for property in &object.properties {
+            let property = match property {
+                ObjectProperty::Property(property) => property,
+                ObjectProperty::Spread(spread) => {
+                    self.check_expression(&spread.argument);
+                    continue;
+                }
+            };
+
+            let is_proto = !property.shorthand
+                && !property.method
+                && property.key.static_name() == Some("__proto__")
+                && !property.key.is_computed();
+            if is_proto {
+                if has_proto {
+                    self.error(property.key.span(), "duplicate '__proto__' property");
+                }
+                has_proto = true;
+            }
+
+            self.check_property_key(&property.key);
+            match &property.value {
+                Expression::Function(function)
+                    if property.method || property.kind != PropertyKind::Init =>
+                {
+                    self.check_function(function, FunctionKind::Method)
+                }
+                value => self.check_expression(value),
+            }
+        }
+    }
+
+    // 関数とクラス
+
+    fn check_function(&mut self, function: &'a Function, kind: FunctionKind) {
+        let context = Context {
+            strict: function.strict,
+            new_target: true,
+            super_property: kind != FunctionKind::Normal,
+            super_call: kind == FunctionKind::Constructor { derived: true },
+            class_initializer: false,
+            breakable: false,
+            iteration: false,
+        };
+
+        self.with_context(context, true, |checker| {
+            if let Some(id) = &function.id {
+                checker.check_binding_pattern_name(id);
+            }
+
+            let allow_duplicates = !checker.context.strict
+                && kind == FunctionKind::Normal
+                && function.has_simple_parameters();
+            checker.check_parameters(&function.params, &function.body.body, allow_duplicates);
+
+            let statements = function.body.body.iter().collect::<Vec<_>>();
+            checker.check_declarations(&statements, ScopeKind::TopLevel);
+
+            for param in &function.params {
+                checker.check_binding_pattern(param);
+            }
+            checker.check_statements(&function.body.body);
+        });
+    }
+
+    fn check_binding_pattern_name(&mut self, identifier: &Identifier) {
+        if self.context.strict && is_eval_or_arguments(identifier) {
+            self.error(
+                identifier.span,
+                format!("cannot bind '{}' in strict mode", identifier.name),
+            );
+        }
+    }
+
+    /// アロー関数は new.target や super を外側から引き継ぐ。
+    fn check_arrow_function(&mut self, arrow: &'a ArrowFunctionExpression) {
+        let body = match &arrow.body {
+            ArrowFunctionBody::FunctionBody(body) => body.body.as_slice(),
+            ArrowFunctionBody::Expression(_) => &[],
+        };
+        let context = Context {
+            strict: arrow.strict,
+            breakable: false,
+            iteration: false,
+            ..self.context
+        };
+
+        self.with_context(context, true, |checker| {
+            checker.check_parameters(&arrow.params, body, false);
+
+            let statements = body.iter().collect::<Vec<_>>();
+            checker.check_declarations(&statements, ScopeKind::TopLevel);
+
+            for param in &arrow.params {
+                checker.check_binding_pattern(param);
+            }
+            match &arrow.body {
+                ArrowFunctionBody::FunctionBody(body) => checker.check_statements(&body.body),
+                ArrowFunctionBody::Expression(expression) => checker.check_expression(expression),
+            }
+        });
+    }
+
+    fn check_class(&mut self, class: &'a Class) {
+        let context = Context {
+            strict: true,
+            ..self.context
+        };
+
+        self.with_context(context, false, |checker| {
+            if let Some(id) = &class.id {
+                checker.check_binding_pattern_name(id);
+            }
+            if let Some(super_class) = &class.super_class {
+                checker.check_expression(super_class);
+            }
+
+            let private_names = class
+                .body
+                .body
+                .iter()
+                .filter_map(|element| match element {
+                    ClassElement::Method(method) => Some(&method.key),
+                    ClassElement::Property(property) => Some(&property.key),
+                    ClassElement::StaticBlock(_) => None,
+                })
+                .filter_map(|key| match key {
+                    PropertyKey::PrivateIdentifier(identifier) => Some(identifier.name.as_str()),
+                    _ => None,
+                })
+                .collect();
+            checker.private_names.push(private_names);
+
+            for element in &class.body.body {
+                checker.check_class_element(element, class.super_class.is_some());
+            }
+
+            checker.private_names.pop();
+        });
+    }
+
+    fn check_class_element(&mut self, element: &'a ClassElement, derived: bool) {
+        // フィールドの初期化子と static ブロックは引数のないメソッドのように扱う
+        let initializer_context = Context {
+            new_target: true,
+            super_property: true,
+            super_call: false,
+            class_initializer: true,
+            breakable: false,
+            iteration: false,
+            ..self.context
+        };
+
+        match element {
+            ClassElement::Method(method) => {
+                self.check_property_key(&method.key);
+
+                let kind = match method.kind {
+                    MethodKind::Constructor => FunctionKind::Constructor { derived },
+                    _ => FunctionKind::Method,
+                };
+                self.check_function(&method.value, kind);
+            }
+            ClassElement::Property(property) => {
+                self.check_property_key(&property.key);
+
+                if let Some(value) = &property.value {
+                    self.with_context(initializer_context, true, |checker| {
+                        checker.check_expression(value)
+                    });
+                }
+            }
+            ClassElement::StaticBlock(block) => {
+                self.with_context(initializer_context, true, |checker| {
+                    let statements = block.body.iter().collect::<Vec<_>>();
+                    checker.check_declarations(&statements, ScopeKind::TopLevel);
+                    checker.check_statements(&block.body);
+                });
+            }
+        }
+    }
+
+    /// プライベート名は囲んでいるクラスで宣言されていなければいけない。
+    fn check_private_name(&mut self, identifier: &PrivateIdentifier) {
+        let is_declared = self
+            .private_names
+            .iter()
+            .any(|names| names.contains(identifier.name.as_str()));
+
+        if !is_declared {
+            self.error(
+                identifier.span,
+                format!("private name '#{}' is not defined", identifier.name),
+            );
+        }
+    }
+}
+
+/// ディレクティブに `"use strict"` があるかを返す。
+///
+/// エスケープや行継続を含む表記は `"use strict"` とはみなさない。
+fn has_use_strict_directive<'a>(statements: impl IntoIterator<Item = &'a Statement>) -> bool {
+    statements
+        .into_iter()
+        .map_while(|statement| match statement {
+            Statement::Expression(statement) => statement.directive.as_deref(),
+            _ => None,
+        })
+        .any(|directive| directive == "use strict")
+}
+
+/// ラベルを除いた文が繰り返し文であるかを返す。
+fn is_iteration_statement(statement: &Statement) -> bool {
+    match statement {
+        Statement::Labeled(labeled) => is_iteration_statement(&labeled.body),
+        Statement::While(_)
+        | Statement::DoWhile(_)
+        | Statement::For(_)
+        | Statement::ForIn(_)
+        | Statement::ForOf(_) => true,
+        _ => false,
+    }
+}
+
+/// ラベルを除いた文が関数宣言であるかを返す。
+fn is_labelled_function(statement: &Statement) -> bool {
+    match statement {
+        Statement::Labeled(labeled) => match &labeled.body {
+            Statement::FunctionDeclaration(_) => true,
+            body => is_labelled_function(body),
+        },
+        _ => false,
+    }
+}
+
+fn is_eval_or_arguments(identifier: &Identifier) -> bool {
+    matches!(identifier.name.as_str(), "eval" | "arguments")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ast::parser::test_helper::{
+        parse_module_error, parse_module_sexp, parse_script_error, parse_script_sexp,
+    };
+
+    macro_rules! test_case {
+        ($test_name:ident, $source:expr) => {
+            #[test]
+            fn $test_name() {
+                assert!(
+                    parse_script_sexp($source).is_ok(),
+                    "{:?}",
+                    parse_script_sexp($source)
+                );
+            }
+        };
+    }
+
+    macro_rules! test_case_fail {
+        ($test_name:ident, $source:expr, $message:expr) => {
+            #[test]
+            fn $test_name() {
+                assert_eq!(
+                    parse_script_error($source).map(|error| error.message),
+                    Some($message.to_string())
+                );
+            }
+        };
+    }
+
+    macro_rules! test_case_module {
+        ($test_name:ident, $source:expr) => {
+            #[test]
+            fn $test_name() {
+                assert!(
+                    parse_module_sexp($source).is_ok(),
+                    "{:?}",
+                    parse_module_sexp($source)
+                );
+            }
+        };
+    }
+
+    macro_rules! test_case_module_fail {
+        ($test_name:ident, $source:expr, $message:expr) => {
+            #[test]
+            fn $test_name() {
+                assert_eq!(
+                    parse_module_error($source).map(|error| error.message),
+                    Some($message.to_string())
+                );
+            }
+        };
+    }
+
+    // 宣言の重複
+    test_case!(var_redeclaration, "var a; var a; function a() {}");
+    test_case!(shadowing_in_block, "let a; { let a; }");
+    test_case!(var_after_inner_let, "{ let a; } var a;");
+    test_case!(
+        sloppy_block_functions,
+        "{ function a() {} function a() {} }"
+    );
+    test_case!(catch_var, "try {} catch (e) { var e; }");
+    test_case!(for_let_shadowed_in_body, "for (let a;;) { let a; }");
+    test_case!(parameter_var, "function f(a) { var a; }");
+    test_case!(sloppy_duplicate_parameters, "function f(a, a) {}");
+    test_case!(
+        separate_switch_cases,
+        "switch (a) { case 1: { let b; } case 2: { let b; } }"
+    );
+    test_case_fail!(
+        let_let,
+        "let a; let a;",
+        "identifier 'a' has already been declared"
+    );
+    test_case_fail!(
+        let_var,
+        "let a; var a;",
+        "identifier 'a' has already been declared"
+    );
+    test_case_fail!(
+        var_in_block_let,
+        "let a; { var a; }",
+        "identifier 'a' has already been declared"
+    );
+    test_case_fail!(
+        let_function,
+        "let a; function a() {}",
+        "identifier 'a' has already been declared"
+    );
+    test_case_fail!(
+        const_class,
+        "const A = 1; class A {}",
+        "identifier 'A' has already been declared"
+    );
+    test_case_module_fail!(
+        strict_block_functions,
+        "{ function a() {} function a() {} }",
+        "identifier 'a' has already been declared"
+    );
+    test_case_fail!(
+        block_generator_functions,
+        "{ function a() {} function* a() {} }",
+        "identifier 'a' has already been declared"
+    );
+    test_case_fail!(
+        switch_cases,
+        "switch (a) { case 1: let b; case 2: let b; }",
+        "identifier 'b' has already been declared"
+    );
+    test_case_fail!(
+        destructuring,
+        "let [a, {b: a}] = c;",
+        "identifier 'a' has already been declared"
+    );
+    test_case_fail!(
+        parameter_let,
+        "function f(a) { let a; }",
+        "identifier 'a' has already been declared"
+    );
+    test_case_fail!(
+        arrow_duplicate_parameters,
+        "(a, a) => 1",
+        "duplicate parameter name 'a'"
+    );
+    test_case_module_fail!(
+        strict_duplicate_parameters,
+        "function f(a, a) {}",
+        "duplicate parameter name 'a'"
+    );
+    test_case_fail!(
+        non_simple_duplicate_parameters,
+        "function f(a, a = 1) {}",
+        "duplicate parameter name 'a'"
+    );
+    test_case_fail!(
+        method_duplicate_parameters,
+        "({ m(a, a) {} })",
+        "duplicate parameter name 'a'"
+    );
+    test_case_fail!(
+        catch_let,
+        "try {} catch (e) { let e; }",
+        "identifier 'e' has already been declared"
+    );
+    test_case_fail!(
+        catch_pattern_var,
+        "try {} catch ([e]) { var e; }",
+        "identifier 'e' has already been declared"
+    );
+    test_case_fail!(
+        catch_for_of_var,
+        "try {} catch (e) { for (var e of a); }",
+        "identifier 'e' has already been declared"
+    );
+    test_case_fail!(
+        catch_duplicate_pattern,
+        "try {} catch ([e, e]) {}",
+        "identifier 'e' has already been declared"
+    );
+    test_case_fail!(
+        for_let_duplicate,
+        "for (let a, a;;);",
+        "identifier 'a' has already been declared"
+    );
+    test_case_fail!(
+        for_let_body_var,
+        "for (let a of b) { var a; }",
+        "identifier 'a' has already been declared"
+    );
+    test_case_fail!(
+        static_block_let_var,
+        "class A { static { let a; var a; } }",
+        "identifier 'a' has already been declared"
+    );
+
+    // ラベル
+    test_case!(break_label, "a: { break a; }");
+    test_case!(continue_label, "a: b: while (c) { continue a; }");
+    test_case!(sibling_labels, "a: ; a: ;");
+    test_case!(break_in_switch, "switch (a) { case 1: break; }");
+    test_case!(labelled_function, "l: function f() {}");
+    test_case!(
+        labelled_function_in_block,
+        "while (x) { l: function f() {} }"
+    );
+    test_case_fail!(
+        labelled_function_loop_body,
+        "while (x) l: function f() {}",
+        "labelled function declaration is not allowed as the body of a statement"
+    );
+    test_case_fail!(
+        labelled_function_if_body,
+        "if (x) ; else a: b: function f() {}",
+        "labelled function declaration is not allowed as the body of a statement"
+    );
+    test_case_fail!(
+        break_undefined_label,
+        "while (a) break b;",
+        "undefined label 'b'"
+    );
+    test_case_fail!(
+        duplicate_label,
+        "a: a: ;",
+        "label 'a' has already been declared"
+    );
+    test_case_fail!(
+        continue_non_iteration,
+        "a: { while (b) continue a; }",
+        "label 'a' is not an iteration statement"
+    );
+    test_case_fail!(break_outside_loop, "break;", "illegal break statement");
+    test_case_fail!(
+        continue_in_switch,
+        "switch (a) { case 1: continue; }",
+        "illegal continue statement"
+    );
+    test_case_fail!(
+        label_across_function,
+        "a: while (b) { function f() { break a; } }",
+        "undefined label 'a'"
+    );
+    test_case_fail!(
+        break_across_arrow,
+        "while (a) { () => { break; } }",
+        "illegal break statement"
+    );
+
+    // new.target と super
+    test_case!(new_target_in_function, "function f() { return new.target }");
+    test_case!(
+        new_target_in_arrow,
+        "function f() { return () => new.target }"
+    );
+    test_case!(new_target_in_field, "class A { a = new.target }");
+    test_case!(
+        super_call_in_derived_constructor,
+        "class A extends B { constructor() { (() => super())() } }"
+    );
+    test_case!(super_property_in_method, "({ m() { return super.a } })");
+    test_case!(
+        super_property_in_field,
+        "class A { a = super.b; static { super.c } }"
+    );
+    test_case_fail!(
+        new_target_outside_function,
+        "new.target",
+        "'new.target' is only allowed in functions"
+    );
+    test_case_fail!(
+        new_target_in_top_level_arrow,
+        "() => new.target",
+        "'new.target' is only allowed in functions"
+    );
+    test_case_fail!(
+        super_call_in_base_constructor,
+        "class A { constructor() { super() } }",
+        "'super' call is only allowed in derived class constructors"
+    );
+    test_case_fail!(
+        super_call_in_method,
+        "class A extends B { m() { super() } }",
+        "'super' call is only allowed in derived class constructors"
+    );
+    test_case_fail!(
+        super_property_in_function,
+        "function f() { super.a }",
+        "'super' keyword unexpected here"
+    );
+
+    // strict モード
+    test_case!(sloppy_delete_identifier, "delete a");
+    test_case_module!(delete_member, "delete a.b");
+    test_case_module_fail!(
+        strict_delete_identifier,
+        "delete a",
+        "delete of an unqualified identifier in strict mode"
+    );
+    test_case_fail!(
+        strict_function_delete_identifier,
+        "class A { m() { delete (a) } }",
+        "delete of an unqualified identifier in strict mode"
+    );
+    test_case_fail!(
+        delete_private,
+        "class A { #a; m() { delete this.#a } }",
+        "private fields cannot be deleted"
+    );
+    test_case_module_fail!(
+        strict_assign_eval,
+        "eval = 1",
+        "cannot assign to 'eval' in strict mode"
+    );
+    test_case_fail!(
+        strict_bind_arguments,
+        "class A { m() { var arguments; } }",
+        "cannot bind 'arguments' in strict mode"
+    );
+    test_case_module_fail!(
+        strict_function_name,
+        "function eval() {}",
+        "cannot bind 'eval' in strict mode"
+    );
+
+    // プライベート名とクラス
+    test_case!(
+        private_in_nested_class,
+        "class A { #a; m() { class B { n(o) { return o.#a } } } }"
+    );
+    test_case!(
+        private_used_before_declaration,
+        "class A { m() { this.#a } #a }"
+    );
+    test_case_fail!(
+        undefined_private_name,
+        "class A { m() { this.#b } }",
+        "private name '#b' is not defined"
+    );
+    test_case_fail!(
+        private_name_outside_class,
+        "this.#a",
+        "private name '#a' is not defined"
+    );
+    test_case_fail!(
+        arguments_in_field,
+        "class A { a = arguments }",
+        "'arguments' is not allowed in class field initializer or static initialization block"
+    );
+    test_case_fail!(
+        arguments_in_field_arrow,
+        "class A { a = () => arguments }",
+        "'arguments' is not allowed in class field initializer or static initialization block"
+    );
+    test_case!(
+        arguments_in_field_function,
+        "class A { a = function () { arguments } }"
+    );
+
+    // 代入の対象とオブジェクトリテラル
+    test_case!(proto_shorthand, "({ __proto__: a, __proto__ })");
+    test_case!(proto_in_pattern, "({ __proto__: a, __proto__: b } = c)");
+    test_case_fail!(
+        duplicate_proto,
+        "({ __proto__: a, '__proto__': b })",
+        "duplicate '__proto__' property"
+    );
+    test_case_fail!(
+        invalid_assignment_target,
+        "a() = 1",
+        "invalid assignment target"
+    );
+
+    // モジュール
+    test_case_module!(
+        export_declared,
+        "var a; let b; function c() {} export { a, b, c as d };"
+    );
+    test_case_module!(export_import, "import a from 'b'; export { a };");
+    test_case_module_fail!(
+        duplicate_export,
+        "let a; export { a, a };",
+        "duplicate export 'a'"
+    );
+    test_case_module_fail!(
+        duplicate_default_export,
+        "export default 1; export default 2;",
+        "duplicate export 'default'"
+    );
+    test_case_module_fail!(
+        duplicate_export_declaration,
+        "export let a; let b; export { b as a };",
+        "duplicate export 'a'"
+    );
+    test_case_module_fail!(
+        undefined_export,
+        "export { a };",
+        "export 'a' is not defined"
+    );
+    test_case_module_fail!(
+        module_functions,
+        "function a() {} function a() {}",
+        "identifier 'a' has already been declared"
+    );
+    test_case_module_fail!(
+        import_let,
+        "import a from 'b'; let a;",
+        "identifier 'a' has already been declared"
+    );
+    test_case_module_fail!(
+        duplicate_import,
+        "import { a } from 'b'; import { c as a } from 'd';",
+        "identifier 'a' has already been declared"
+    );
+}
@@ -0,0 +1,449 @@
+//! 宣言の重複を検査するモジュールです。
+//!
+//! 同じスコープの let, const, class と、スコープ内の var や引数が同じ名前を束縛するとエラーになる。
+
+use std::collections::{HashMap, HashSet};
+
+use super::Checker;
+use crate::ast::structs::{
+    function::Function,
+    literal::Identifier,
+    module::{
+        Declaration, ExportDefaultKind, ImportDeclarationSpecifier, ModuleDeclaration, ModuleItem,
+    },
+    pattern::Pattern,
+    span::{Span, Spanned},
+    statement::{ForInOfLeft, ForInit, Statement, VariableDeclaration},
+};
+
+/// 文のリストの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ScopeKind {
+    /// スクリプト、関数本体、static ブロック (関数宣言は var と同じ扱いになる)
+    TopLevel,
+
+    /// ブロックと switch 文 (関数宣言は let と同じ扱いになる)
+    Block,
+
+    /// モジュール (関数宣言は let と同じ扱いになる)
+    Module,
+}
+
+/// ブロックスコープの宣言
+#[derive(Debug)]
+struct LexicalName<'a> {
+    identifier: &'a Identifier,
+
+    /// async でもジェネレーターでもない関数宣言であるか
+    is_plain_function: bool,
+}
+
+/// var で宣言された名前
+#[derive(Debug)]
+struct VarName<'a> {
+    identifier: &'a Identifier,
+
+    /// for-of 文の左辺であるか
+    is_for_of: bool,
+}
+
+impl<'a> Checker<'a> {
+    /// 文のリストのブロックスコープの宣言が重複していないかを検査する。
+    pub(super) fn check_declarations(&mut self, statements: &[&'a Statement], scope: ScopeKind) {
+        let lexical_names = lexically_declared_names(statements, scope);
+        let var_names = var_declared_names(statements, scope);
+
+        let declared = self.check_lexical_names(&lexical_names, scope);
+        for name in var_names {
+            if declared.contains(name.identifier.name.as_str()) {
+                self.redeclaration_error(name.identifier);
+            }
+        }
+    }
+
+    /// ブロックスコープの宣言の重複を検査し、宣言された名前を返す。
+    ///
+    /// strict モードでないブロックでは、関数宣言同士の重複を許す (Annex B)。
+    fn check_lexical_names(
+        &mut self,
+        names: &[LexicalName<'a>],
+        scope: ScopeKind,
+    ) -> HashSet<&'a str> {
+        let mut declared: HashMap<&str, &LexicalName> = HashMap::new();
+
+        for name in names {
+            match declared.get(name.identifier.name.as_str()) {
+                Some(previous)
+                    if scope == ScopeKind::Block
+                        && !self.context.strict
+                        && previous.is_plain_function
+                        && name.is_plain_function => {}
+                Some(_) => self.redeclaration_error(name.identifier),
+                None => {
+                    declared.insert(&name.identifier.name, name);
+                }
+            }
+        }
+
+        declared.into_keys().collect()
+    }
+
+    fn redeclaration_error(&mut self, identifier: &Identifier) {
+        self.error(
+            identifier.span,
+            format!("identifier '{}' has already been declared", identifier.name),
+        );
+    }
+
+    /// 引数の重複と、関数本体の宣言との重複を検査する。
+    pub(super) fn check_parameters(
+        &mut self,
+        params: &'a [Pattern],
+        body: &'a [Statement],
+        allow_duplicates: bool,
+    ) {
+        let mut declared = HashSet::new();
+
+        for identifier in params.iter().flat_map(Pattern::bound_names) {
+            if !declared.insert(identifier.name.as_str()) && !allow_duplicates {
+                self.error(
+                    identifier.span,
+                    format!("duplicate parameter name '{}'", identifier.name),
+                );
+            }
+        }
+
+        let statements = body.iter().collect::<Vec<_>>();
+        for name in lexically_declared_names(&statements, ScopeKind::TopLevel) {
+            if declared.contains(name.identifier.name.as_str()) {
+                self.redeclaration_error(name.identifier);
+            }
+        }
+    }
+
+    /// catch 節の引数と、catch 節のブロックの宣言との重複を検査する。
+    ///
+    /// 引数が識別子の場合は、for-of 文以外の var で同じ名前を宣言できる (Annex B)。
+    pub(super) fn check_catch_parameter(&mut self, param: &'a Pattern, body: &'a [Statement]) {
+        let mut declared = HashSet::new();
+        for identifier in param.bound_names() {
+            if !declared.insert(identifier.name.as_str()) {
+                self.redeclaration_error(identifier);
+            }
+        }
+
+        let statements = body.iter().collect::<Vec<_>>();
+        for name in lexically_declared_names(&statements, ScopeKind::Block) {
+            if declared.contains(name.identifier.name.as_str()) {
+                self.redeclaration_error(name.identifier);
+            }
+        }
+
+        let is_simple = param.is_simple();
+        for name in var_declared_names(&statements, ScopeKind::Block) {
+            if declared.contains(name.identifier.name.as_str()) && (!is_simple || name.is_for_of) {
+                self.redeclaration_error(name.identifier);
+            }
+        }
+    }
+
+    /// for 文の let と const の重複と、本体の var との重複を検査する。
+    pub(super) fn check_for_declaration(
+        &mut self,
+        declaration: &'a VariableDeclaration,
+        body: &'a Statement,
+    ) {
+        if !declaration.kind.is_lexical() {
+            return;
+        }
+
+        let mut declared = HashSet::new();
+        for identifier in declaration
+            .declarations
+            .iter()
+            .flat_map(|declarator| declarator.id.bound_names())
+        {
+            if !declared.insert(identifier.name.as_str()) {
+                self.redeclaration_error(identifier);
+            }
+        }
+
+        let mut var_names = Vec::new();
+        collect_var_names(body, &mut var_names);
+        for name in var_names {
+            if declared.contains(name.identifier.name.as_str()) {
+                self.redeclaration_error(name.identifier);
+            }
+        }
+    }
+
+    /// モジュールの最上位の宣言と export を検査する。
+    pub(super) fn check_module_declarations(&mut self, items: &'a [ModuleItem]) {
+        let mut statements = Vec::new();
+        let mut lexical_names = Vec::new();
+        let mut var_names = Vec::new();
+
+        for item in items {
+            match item {
+                ModuleItem::Statement(statement) => statements.push(statement),
+                ModuleItem::ModuleDeclaration(ModuleDeclaration::Import(import)) => {
+                    lexical_names.extend(import.specifiers.iter().map(|specifier| {
+                        let identifier = match specifier {
+                            ImportDeclarationSpecifier::Named(specifier) => &specifier.local,
+                            ImportDeclarationSpecifier::Default(specifier) => &specifier.local,
+                            ImportDeclarationSpecifier::Namespace(specifier) => &specifier.local,
+                        };
+                        LexicalName {
+                            identifier,
+                            is_plain_function: false,
+                        }
+                    }));
+                }
+                ModuleItem::ModuleDeclaration(ModuleDeclaration::ExportNamed(export)) => {
+                    match &export.declaration {
+                        Some(Declaration::Variable(variable)) if !variable.kind.is_lexical() => {
+                            var_names.extend(variable_var_names(variable, false))
+                        }
+                        Some(declaration) => {
+                            lexical_names.extend(declaration_names(declaration).into_iter().map(
+                                |identifier| LexicalName {
+                                    identifier,
+                                    is_plain_function: false,
+                                },
+                            ))
+                        }
+                        None => {}
+                    }
+                }
+                ModuleItem::ModuleDeclaration(ModuleDeclaration::ExportDefault(export)) => {
+                    let id = match &export.declaration {
+                        ExportDefaultKind::Function(function) => function.id.as_ref(),
+                        ExportDefaultKind::Class(class) => class.id.as_ref(),
+                        ExportDefaultKind::Expression(_) => None,
+                    };
+                    lexical_names.extend(id.map(|identifier| LexicalName {
+                        identifier,
+                        is_plain_function: false,
+                    }));
+                }
+                ModuleItem::ModuleDeclaration(ModuleDeclaration::ExportAll(_)) => {}
+            }
+        }
+
+        lexical_names.extend(lexically_declared_names(&statements, ScopeKind::Module));
+        var_names.extend(var_declared_names(&statements, ScopeKind::Module));
+        lexical_names.sort_by_key(|name| name.identifier.span);
+
+        let mut declared = self.check_lexical_names(&lexical_names, ScopeKind::Module);
+        for name in &var_names {
+            if declared.contains(name.identifier.name.as_str()) {
+                self.redeclaration_error(name.identifier);
+            }
+        }
+        declared.extend(var_names.iter().map(|name| name.identifier.name.as_str()));
+
+        self.check_exports(items, &declared);
+    }
+
+    /// 公開名の重複と、公開するローカルの名前が宣言されているかを検査する。
+    fn check_exports(&mut self, items: &'a [ModuleItem], declared: &HashSet<&'a str>) {
+        let mut exported: HashSet<&str> = HashSet::new();
+        let mut export_name = |checker: &mut Self, name: &'a str, span: Span| {
+            if !exported.insert(name) {
+                checker.error(span, format!("duplicate export '{name}'"));
+            }
+        };
+
+        for item in items {
+            let ModuleItem::ModuleDeclaration(declaration) = item else {
+                continue;
+            };
+
+            match declaration {
+                ModuleDeclaration::Import(_) => {}
+                ModuleDeclaration::ExportNamed(export) => {
+                    if let Some(declaration) = &export.declaration {
+                        for identifier in declaration_names(declaration) {
+                            export_name(self, &identifier.name, identifier.span);
+                        }
+                    }
+
+                    for specifier in &export.specifiers {
+                        export_name(self, specifier.exported.name(), specifier.exported.span());
+
+                        let local = specifier.local.name();
+                        if export.source.is_none() && !declared.contains(local) {
+                            self.error(
+                                specifier.local.span(),
+                                format!("export '{local}' is not defined"),
+                            );
+                        }
+                    }
+                }
+                ModuleDeclaration::ExportDefault(export) => {
+                    export_name(self, "default", export.span);
+                }
+                ModuleDeclaration::ExportAll(export) => {
+                    if let Some(exported) = &export.exported {
+                        export_name(self, exported.name(), exported.span());
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// 文のリストが直接宣言するブロックスコープの名前を集める。
+fn lexically_declared_names<'a>(
+    statements: &[&'a Statement],
+    scope: ScopeKind,
+) -> Vec<LexicalName<'a>> {
+    let mut names = Vec::new();
+
+    for statement in statements {
+        match statement {
+            Statement::VariableDeclaration(declaration) if declaration.kind.is_lexical() => {
+                names.extend(
+                    declaration
+                        .declarations
+                        .iter()
+                        .flat_map(|declarator| declarator.id.bound_names())
+                        .map(|identifier| LexicalName {
+                            identifier,
+                            is_plain_function: false,
+                        }),
+                );
+            }
+            Statement::ClassDeclaration(class) => {
+                names.extend(class.id.iter().map(|identifier| LexicalName {
+                    identifier,
+                    is_plain_function: false,
+                }));
+            }
+            _ if scope == ScopeKind::TopLevel => {}
+            statement => {
+                if let Some(function) = function_declaration(statement) {
+                    names.extend(function.id.iter().map(|identifier| LexicalName {
+                        identifier,
+                        is_plain_function: !function.is_async && !function.generator,
+                    }));
+                }
+            }
+        }
+    }
+
+    names
+}
+
+/// 文のリストの中で var で宣言された名前を集める。関数の中は含まない。
+fn var_declared_names<'a>(statements: &[&'a Statement], scope: ScopeKind) -> Vec<VarName<'a>> {
+    let mut names = Vec::new();
+
+    for statement in statements {
+        if scope == ScopeKind::TopLevel {
+            if let Some(function) = function_declaration(statement) {
+                names.extend(function.id.iter().map(|identifier| VarName {
+                    identifier,
+                    is_for_of: false,
+                }));
+                continue;
+            }
+        }
+        collect_var_names(statement, &mut names);
+    }
+
+    names
+}
+
+fn collect_var_names<'a>(statement: &'a Statement, names: &mut Vec<VarName<'a>>) {
+    match statement {
+        Statement::VariableDeclaration(declaration) => {
+            names.extend(variable_var_names(declaration, false))
+        }
+        Statement::Block(block) => {
+            for statement in &block.body {
+                collect_var_names(statement, names);
+            }
+        }
+        Statement::With(with) => collect_var_names(&with.body, names),
+        Statement::Labeled(labeled) => collect_var_names(&labeled.body, names),
+        Statement::If(statement) => {
+            collect_var_names(&statement.consequent, names);
+            if let Some(alternate) = &statement.alternate {
+                collect_var_names(alternate, names);
+            }
+        }
+        Statement::Switch(switch) => {
+            for statement in switch.cases.iter().flat_map(|case| &case.consequent) {
+                collect_var_names(statement, names);
+            }
+        }
+        Statement::Try(statement) => {
+            let blocks = std::iter::once(&statement.block)
+                .chain(statement.handler.iter().map(|handler| &handler.body))
+                .chain(statement.finalizer.iter());
+            for statement in blocks.flat_map(|block| &block.body) {
+                collect_var_names(statement, names);
+            }
+        }
+        Statement::While(statement) => collect_var_names(&statement.body, names),
+        Statement::DoWhile(statement) => collect_var_names(&statement.body, names),
+        Statement::For(statement) => {
+            if let Some(ForInit::VariableDeclaration(declaration)) = &statement.init {
+                names.extend(variable_var_names(declaration, false));
+            }
+            collect_var_names(&statement.body, names);
+        }
+        Statement::ForIn(statement) => {
+            if let ForInOfLeft::VariableDeclaration(declaration) = &statement.left {
+                names.extend(variable_var_names(declaration, false));
+            }
+            collect_var_names(&statement.body, names);
+        }
+        Statement::ForOf(statement) => {
+            if let ForInOfLeft::VariableDeclaration(declaration) = &statement.left {
+                names.extend(variable_var_names(declaration, true));
+            }
+            collect_var_names(&statement.body, names);
+        }
+        _ => {}
+    }
+}
+
+/// var 宣言が束縛する名前 (let と const の場合は空)
+fn variable_var_names(
+    declaration: &VariableDeclaration,
+    is_for_of: bool,
+) -> impl Iterator<Item = VarName<'_>> {
+    declaration
+        .declarations
+        .iter()
+        .filter(|_| !declaration.kind.is_lexical())
+        .flat_map(|declarator| declarator.id.bound_names())
+        .map(move |identifier| VarName {
+            identifier,
+            is_for_of,
+        })
+}
+
+/// export の対象の宣言が束縛する名前
+fn declaration_names(declaration: &Declaration) -> Vec<&Identifier> {
+    match declaration {
+        Declaration::Function(function) => function.id.iter().collect(),
+        Declaration::Class(class) => class.id.iter().collect(),
+        Declaration::Variable(variable) => variable
+            .declarations
+            .iter()
+            .flat_map(|declarator| declarator.id.bound_names())
+            .collect(),
+    }
+}
+
+/// ラベルを除いた文が関数宣言であれば返す。
+fn function_declaration(statement: &Statement) -> Option<&Function> {
+    match statement {
+        Statement::FunctionDeclaration(function) => Some(function),
+        Statement::Labeled(labeled) => function_declaration(&labeled.body),
+        _ => None,
+    }
+}
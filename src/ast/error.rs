@@ -0,0 +1,57 @@
+use std::fmt::{self, Display};
+
+use super::structs::span::Span;
+
+/// 構文解析のエラー
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseError {
+    pub span: Span,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(span: Span, message: impl Into<String>) -> ParseError {
+        ParseError {
+            span,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({}..{})",
+            self.message, self.span.start, self.span.end
+        )
+    }
+}
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+/// 構文は正しいが、意図と異なる解釈になりやすい箇所の警告
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseWarning {
+    pub span: Span,
+    pub message: String,
+}
+
+impl ParseWarning {
+    pub fn new(span: Span, message: impl Into<String>) -> ParseWarning {
+        ParseWarning {
+            span,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({}..{})",
+            self.message, self.span.start, self.span.end
+        )
+    }
+}
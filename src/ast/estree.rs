@@ -0,0 +1,229 @@
+//! 構文木と ESTree 形式の JSON を相互に変換するモジュールです。
+//!
+//! 出力は acorn に `locations: true, ranges: true` を指定した結果と同じ形にする。
+//! 位置 (`start` `end` `range` と `loc` の列) は UTF-16 のコード単位で数え、行は1から数える。
+//!
+//! acorn と異なる点:
+//! - 正規表現と BigInt のリテラルの `value` は JSON で表せないため `null` にする。
+//! - 構文エラーで読めなかった文と式は `ErrorNode` という独自のノードにする。
+
+mod deserialize;
+mod serialize;
+
+use std::fmt::{self, Display};
+
+use self::{deserialize::Deserializer, serialize::Serializer};
+use super::structs::{expression::Expression, statement::Statement, JsAst};
+use crate::json::{parse_json, JsonValue};
+
+/// ESTree から構文木を作れなかった理由
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct EstreeError {
+    pub message: String,
+}
+
+impl Display for EstreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// 構文木を ESTree の JSON の値にする。
+///
+/// `source` は構文木を解析したソースコードで、位置の変換に使う。
+pub fn to_estree(ast: &JsAst, source: &str) -> JsonValue {
+    Serializer::new(source).program(ast)
+}
+
+/// 式を ESTree の JSON の値にする。
+pub fn expression_to_estree(expression: &Expression, source: &str) -> JsonValue {
+    Serializer::new(source).expression(expression)
+}
+
+/// 文を ESTree の JSON の値にする。
+pub fn statement_to_estree(statement: &Statement, source: &str) -> JsonValue {
+    Serializer::new(source).statement(statement)
+}
+
+/// 構文木を ESTree の JSON にする (`JSON.stringify(ast, null, 2)` と同じ表記)。
+pub fn to_estree_json(ast: &JsAst, source: &str) -> String {
+    to_estree(ast, source).to_pretty_string()
+}
+
+/// ESTree の JSON の値から構文木を作る。
+///
+/// 位置をバイト位置に戻すため、JSON を作ったソースコードを渡す。
+pub fn from_estree(value: &JsonValue, source: &str) -> Result<JsAst, EstreeError> {
+    Deserializer::new(source).program(value)
+}
+
+/// ESTree の JSON から構文木を作る。
+pub fn from_estree_json(json: &str, source: &str) -> Result<JsAst, EstreeError> {
+    let value = parse_json(json).map_err(|error| EstreeError {
+        message: error.to_string(),
+    })?;
+    from_estree(&value, source)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{from_estree_json, to_estree, to_estree_json};
+    use crate::{
+        ast::{
+            reader_struct::SourceCodeReader,
+            script::{parse_module, parse_script},
+            structs::JsAst,
+        },
+        json::JsonValue,
+    };
+
+    fn parse(source: &str) -> JsAst {
+        parse_script(SourceCodeReader::new(source))
+            .into_result()
+            .expect("parse error")
+    }
+
+    fn parse_as_module(source: &str) -> JsAst {
+        parse_module(SourceCodeReader::new(source))
+            .into_result()
+            .expect("parse error")
+    }
+
+    /// JSON の値をキーの並びでたどる。
+    fn lookup<'a>(value: &'a JsonValue, path: &[&str]) -> &'a JsonValue {
+        path.iter()
+            .fold(value, |value, key| match key.parse::<usize>() {
+                Ok(index) => &value.as_array().expect("array")[index],
+                Err(_) => value.get(key).expect(key),
+            })
+    }
+
+    fn assert_round_trip(ast: JsAst, source: &str) {
+        let json = to_estree_json(&ast, source);
+        assert_eq!(from_estree_json(&json, source), Ok(ast), "{source}");
+    }
+
+    macro_rules! golden_test {
+        ($test_name:ident, $parse:ident, $name:literal) => {
+            #[test]
+            fn $test_name() {
+                let source = include_str!(concat!("estree/testdata/", $name, ".js"));
+                let expected = include_str!(concat!("estree/testdata/", $name, ".json"));
+                let ast = $parse(source);
+
+                assert_eq!(to_estree_json(&ast, source), expected.trim_end());
+                assert_round_trip(ast, source);
+            }
+        };
+    }
+
+    golden_test!(golden_declarations, parse, "declarations");
+    golden_test!(golden_expressions, parse, "expressions");
+    golden_test!(golden_module, parse_as_module, "module");
+
+    #[test]
+    fn utf16_positions() {
+        let source = "'😀';\r\nあ;";
+        let value = to_estree(&parse(source), source);
+
+        let identifier = lookup(&value, &["body", "1", "expression"]);
+        assert_eq!(identifier.get("start"), Some(&7.0.into()));
+        assert_eq!(
+            lookup(identifier, &["loc", "start", "line"]),
+            &JsonValue::from(2)
+        );
+        assert_eq!(
+            lookup(identifier, &["loc", "start", "column"]),
+            &JsonValue::from(0)
+        );
+        assert_eq!(
+            lookup(&value, &["body", "0", "expression", "end"]),
+            &JsonValue::from(4)
+        );
+        assert_round_trip(parse(source), source);
+    }
+
+    #[test]
+    fn literals() {
+        let source = "null; true; 1.5; 'a\\n'; /a+/gi; 0x1_0n; 1e400;";
+        let value = to_estree(&parse(source), source);
+        let literal = |index: usize| lookup(&value, &["body", &index.to_string(), "expression"]);
+
+        assert_eq!(literal(2).get("value"), Some(&1.5.into()));
+        assert_eq!(literal(3).get("value"), Some(&"a\n".into()));
+        assert_eq!(literal(4).get("value"), Some(&JsonValue::Null));
+        assert_eq!(lookup(literal(4), &["regex", "flags"]), &"gi".into());
+        assert_eq!(literal(5).get("bigint"), Some(&"0x10".into()));
+        assert_eq!(literal(6).get("value"), Some(&JsonValue::Null));
+        assert_round_trip(parse(source), source);
+    }
+
+    #[test]
+    fn round_trip() {
+        let sources = [
+            "a: for (let i = 0, j; i < 10; i++) { if (i) continue a; else break; }",
+            "for (const [a, { b = 1, ...c }] of d) ; for (e.f in g) ; for (;;) {}",
+            "do a(); while (b) while (c) with (d) e;",
+            "switch (a) { case 1: b; default: c; } try {} catch { } finally {}",
+            "try { throw a } catch ({ b }) { debugger }",
+            "function* f(a, [b] = c, ...d) { yield* e; return } function g(h) { 'use strict'; }",
+            "async function f() { await a; for await (b of c) ; } (async () => d);",
+            "function f() { new.target; return function () { 'use strict' } }",
+            "class A extends B { #a = 1; static b; static { this.#a } constructor() { super() } get c() {} set [d](e) {} static async *f() {} }",
+            "x = { a, b: 1, 'c': 2, 3: d, [e]: f, g() {}, get h() {}, set h(i) {}, ...j, async *k() {} };",
+            "[a, , ...b] = [c, , ...d]; ({ a, b: { c } = {} } = e);",
+            "a?.b.c?.(d)?.[e]; new (f())(); new g; h`i${j}\\u{k}`; `l${m}n`;",
+            "a = b ? c : d, e += f ** -g, h ||= !i && j, k ?? l, typeof l, void m, delete n.o, ++p, q--;",
+            "class C { #c; m() { a in b, #c in d, e instanceof f; } } g, g >>> h, i !== j, k / l % m << n | o & p ^ q;",
+            "import('a'); import('b', { with: {} });",
+            "x = function g() { 'a'; 'use strict'; 10 }; y = () => { 'use strict' }",
+        ];
+
+        for source in sources {
+            assert_round_trip(parse(source), source);
+        }
+
+        let modules = [
+            "import a, { b as c, 'd' as e } from 'f' with { type: 'json' }; import * as g from 'h'; import 'i';",
+            "let a, c; export { a as b, c as 'd' }; export * from 'e'; export * as f from 'g'; export { h } from 'i';",
+            "export default function () {} export const a = 1; export class B {} export function c() {}",
+            "export default class {}",
+            "export default (function () {});",
+            "export default a = 1; import.meta.url; await b;",
+        ];
+
+        for source in modules {
+            assert_round_trip(parse_as_module(source), source);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let message = |json: &str| from_estree_json(json, "").map_err(|error| error.message);
+
+        assert_eq!(
+            message("[]"),
+            Err("expected a node with a 'type'".to_string())
+        );
+        assert_eq!(
+            message(r#"{"type": "Identifier", "start": 0, "end": 0, "name": "a"}"#),
+            Err("unexpected Identifier where Program is expected".to_string())
+        );
+        assert_eq!(
+            message(r#"{"type": "Program", "start": 0, "end": 0, "body": []}"#),
+            Err("missing 'sourceType' in Program".to_string())
+        );
+        assert_eq!(
+            message(
+                r#"{"type": "Program", "start": 0, "end": 0, "sourceType": "script", "body": [
+                    {"type": "Identifier", "start": 0, "end": 0, "name": "a"}
+                ]}"#
+            ),
+            Err("unexpected Identifier where a statement is expected".to_string())
+        );
+        assert_eq!(
+            message("{"),
+            Err("unexpected end of input (at 1)".to_string())
+        );
+    }
+}
@@ -0,0 +1,960 @@
+//! ESTree の JSON から構文木を作るモジュールです。
+//!
+//! ESTree にない関数の strict モードは、ディレクティブと外側の関数から求め直す。
+
+use super::EstreeError;
+use crate::{
+    ast::{
+        line_index::LineIndex,
+        parser::literal::{bigint_value, numeric_value},
+        structs::{
+            class::{
+                Class, ClassBody, ClassElement, MethodDefinition, MethodKind, PropertyDefinition,
+                StaticBlock,
+            },
+            expression::{
+                ArrayExpression, AssignmentExpression, AssignmentOperator, AwaitExpression,
+                BinaryExpression, BinaryOperator, CallExpression, ChainExpression,
+                ConditionalExpression, Expression, ExpressionOrSpread, ImportExpression,
+                LogicalExpression, LogicalOperator, MemberExpression, MemberProperty, MetaProperty,
+                NewExpression, ObjectExpression, ObjectProperty, Property, PropertyKey,
+                PropertyKind, SequenceExpression, SpreadElement, Super, ThisExpression,
+                UnaryExpression, UnaryOperator, UpdateExpression, UpdateOperator, YieldExpression,
+            },
+            function::{ArrowFunctionBody, ArrowFunctionExpression, Function, FunctionBody},
+            literal::{Identifier, Literal, LiteralValue, PrivateIdentifier, RegExpLiteral},
+            module::{
+                Declaration, ExportAllDeclaration, ExportDefaultDeclaration, ExportDefaultKind,
+                ExportNamedDeclaration, ExportSpecifier, ImportAttribute, ImportDeclaration,
+                ImportDeclarationSpecifier, ImportDefaultSpecifier, ImportNamespaceSpecifier,
+                ImportSpecifier, ModuleDeclaration, ModuleExportName, ModuleItem,
+            },
+            pattern::{
+                ArrayPattern, AssignmentPattern, AssignmentProperty, ObjectPattern,
+                ObjectPatternProperty, Pattern, RestElement,
+            },
+            span::Span,
+            statement::{
+                BlockStatement, BreakStatement, CatchClause, ContinueStatement, DebuggerStatement,
+                DoWhileStatement, EmptyStatement, ExpressionStatement, ForInOfLeft, ForInStatement,
+                ForInit, ForOfStatement, ForStatement, IfStatement, LabeledStatement,
+                ReturnStatement, Statement, SwitchCase, SwitchStatement, ThrowStatement,
+                TryStatement, VariableDeclaration, VariableDeclarator, VariableKind,
+                WhileStatement, WithStatement,
+            },
+            template::{TaggedTemplateExpression, TemplateElement, TemplateLiteral},
+            ErrorNode, JsAst, SourceType,
+        },
+    },
+    json::JsonValue,
+    to_source_string::ToSourceString,
+    token::structs::literal::Literal as LiteralToken,
+};
+
+type Result<T> = std::result::Result<T, EstreeError>;
+
+/// 位置の変換表と、読んでいる位置が strict モードであるかを持つ。
+pub(super) struct Deserializer {
+    index: LineIndex,
+    strict: bool,
+}
+
+fn error<T>(message: impl Into<String>) -> Result<T> {
+    Err(EstreeError {
+        message: message.into(),
+    })
+}
+
+/// ノードの `type` を返す。
+fn kind(node: &JsonValue) -> Result<&str> {
+    match node.get("type").and_then(JsonValue::as_str) {
+        Some(kind) => Ok(kind),
+        None => error("expected a node with a 'type'"),
+    }
+}
+
+fn field<'v>(node: &'v JsonValue, key: &str) -> Result<&'v JsonValue> {
+    match node.get(key) {
+        Some(value) => Ok(value),
+        None => error(format!(
+            "missing '{key}' in {}",
+            kind(node).unwrap_or("node")
+        )),
+    }
+}
+
+fn string(node: &JsonValue, key: &str) -> Result<String> {
+    match field(node, key)?.as_str() {
+        Some(value) => Ok(value.to_string()),
+        None => error(format!("expected a string for '{key}'")),
+    }
+}
+
+fn boolean(node: &JsonValue, key: &str) -> Result<bool> {
+    match field(node, key)?.as_bool() {
+        Some(value) => Ok(value),
+        None => error(format!("expected a boolean for '{key}'")),
+    }
+}
+
+/// 存在しないか `null` の場合は `false` にする。
+fn optional_boolean(node: &JsonValue, key: &str) -> Result<bool> {
+    match node.get(key) {
+        None | Some(JsonValue::Null) => Ok(false),
+        Some(_) => boolean(node, key),
+    }
+}
+
+fn array<'v>(node: &'v JsonValue, key: &str) -> Result<&'v [JsonValue]> {
+    match field(node, key)?.as_array() {
+        Some(values) => Ok(values),
+        None => error(format!("expected an array for '{key}'")),
+    }
+}
+
+/// 演算子の文字列を列挙型に変換する。
+fn operator<T: ToSourceString + Copy>(node: &JsonValue, operators: &[T]) -> Result<T> {
+    let value = string(node, "operator")?;
+    match operators
+        .iter()
+        .find(|operator| operator.to_source_string() == value)
+    {
+        Some(operator) => Ok(*operator),
+        None => error(format!("unknown operator '{value}'")),
+    }
+}
+
+fn unexpected<T>(node: &JsonValue, expected: &str) -> Result<T> {
+    error(format!(
+        "unexpected {} where {expected} is expected",
+        kind(node)?
+    ))
+}
+
+/// 関数本体かプログラムの先頭に `"use strict"` があるかを返す。
+fn has_use_strict(body: &[JsonValue]) -> bool {
+    body.iter()
+        .map_while(|statement| statement.get("directive").and_then(JsonValue::as_str))
+        .any(|directive| directive == "use strict")
+}
+
+const UNARY_OPERATORS: &[UnaryOperator] = &[
+    UnaryOperator::Minus,
+    UnaryOperator::Plus,
+    UnaryOperator::LogicalNot,
+    UnaryOperator::BitNot,
+    UnaryOperator::Typeof,
+    UnaryOperator::Void,
+    UnaryOperator::Delete,
+];
+
+const UPDATE_OPERATORS: &[UpdateOperator] = &[UpdateOperator::Increment, UpdateOperator::Decrement];
+
+const BINARY_OPERATORS: &[BinaryOperator] = &[
+    BinaryOperator::Equal,
+    BinaryOperator::NotEqual,
+    BinaryOperator::StrictEqual,
+    BinaryOperator::StrictNotEqual,
+    BinaryOperator::LessThan,
+    BinaryOperator::LessThanEqual,
+    BinaryOperator::GreaterThan,
+    BinaryOperator::GreaterThanEqual,
+    BinaryOperator::LeftShift,
+    BinaryOperator::RightShift,
+    BinaryOperator::UnsignedRightShift,
+    BinaryOperator::Plus,
+    BinaryOperator::Minus,
+    BinaryOperator::Multiply,
+    BinaryOperator::Divide,
+    BinaryOperator::Remainder,
+    BinaryOperator::Exponentiation,
+    BinaryOperator::BitOr,
+    BinaryOperator::BitXor,
+    BinaryOperator::BitAnd,
+    BinaryOperator::In,
+    BinaryOperator::Instanceof,
+];
+
+const LOGICAL_OPERATORS: &[LogicalOperator] = &[
+    LogicalOperator::Or,
+    LogicalOperator::And,
+    LogicalOperator::NullishCoalesce,
+];
+
+const ASSIGNMENT_OPERATORS: &[AssignmentOperator] = &[
+    AssignmentOperator::Assign,
+    AssignmentOperator::PlusAssign,
+    AssignmentOperator::MinusAssign,
+    AssignmentOperator::MultiplyAssign,
+    AssignmentOperator::DivideAssign,
+    AssignmentOperator::RemainderAssign,
+    AssignmentOperator::ExponentiationAssign,
+    AssignmentOperator::LeftShiftAssign,
+    AssignmentOperator::RightShiftAssign,
+    AssignmentOperator::UnsignedRightShiftAssign,
+    AssignmentOperator::BitAndAssign,
+    AssignmentOperator::BitOrAssign,
+    AssignmentOperator::BitXorAssign,
+    AssignmentOperator::LogicalAndAssign,
+    AssignmentOperator::LogicalOrAssign,
+    AssignmentOperator::NullishCoalesceAssign,
+];
+
+impl Deserializer {
+    pub(super) fn new(source: &str) -> Deserializer {
+        Deserializer {
+            index: LineIndex::new(source),
+            strict: false,
+        }
+    }
+
+    pub(super) fn program(&mut self, node: &JsonValue) -> Result<JsAst> {
+        if kind(node)? != "Program" {
+            return unexpected(node, "Program");
+        }
+        let source_type = match string(node, "sourceType")?.as_str() {
+            "script" => SourceType::Script,
+            "module" => SourceType::Module,
+            other => return error(format!("unknown sourceType '{other}'")),
+        };
+
+        let body = array(node, "body")?;
+        self.strict = source_type == SourceType::Module || has_use_strict(body);
+
+        Ok(JsAst {
+            span: self.span(node)?,
+            source_type,
+            body: self.list(body, Self::module_item)?,
+        })
+    }
+
+    /// `start` と `end` (UTF-16 の位置) をバイト位置の範囲にする。
+    fn span(&self, node: &JsonValue) -> Result<Span> {
+        let offset = |key| match field(node, key)?.as_f64() {
+            Some(value) if value >= 0.0 && value.fract() == 0.0 => {
+                Ok(self.index.byte_offset(value as usize))
+            }
+            _ => error(format!("expected a position for '{key}'")),
+        };
+        Ok(Span::new(offset("start")?, offset("end")?))
+    }
+
+    fn list<T>(
+        &mut self,
+        values: &[JsonValue],
+        mut read: impl FnMut(&mut Self, &JsonValue) -> Result<T>,
+    ) -> Result<Vec<T>> {
+        values.iter().map(|value| read(self, value)).collect()
+    }
+
+    fn optional<T>(
+        &mut self,
+        value: &JsonValue,
+        read: impl FnOnce(&mut Self, &JsonValue) -> Result<T>,
+    ) -> Result<Option<T>> {
+        match value {
+            JsonValue::Null => Ok(None),
+            value => read(self, value).map(Some),
+        }
+    }
+
+    /// strict モードを切り替えて読む。
+    fn with_strict<T>(&mut self, strict: bool, read: impl FnOnce(&mut Self) -> T) -> T {
+        let outer = self.strict;
+        self.strict = strict;
+        let result = read(self);
+        self.strict = outer;
+        result
+    }
+
+    fn module_item(&mut self, node: &JsonValue) -> Result<ModuleItem> {
+        match kind(node)? {
+            "ImportDeclaration"
+            | "ExportNamedDeclaration"
+            | "ExportDefaultDeclaration"
+            | "ExportAllDeclaration" => Ok(ModuleItem::ModuleDeclaration(
+                self.module_declaration(node)?,
+            )),
+            _ => Ok(ModuleItem::Statement(self.statement(node)?)),
+        }
+    }
+
+    fn statement(&mut self, node: &JsonValue) -> Result<Statement> {
+        let span = self.span(node)?;
+        let statement = match kind(node)? {
+            "ExpressionStatement" => Statement::Expression(ExpressionStatement {
+                span,
+                expression: self.expression(field(node, "expression")?)?,
+                directive: match node.get("directive") {
+                    None | Some(JsonValue::Null) => None,
+                    Some(_) => Some(string(node, "directive")?),
+                },
+            }),
+            "BlockStatement" => Statement::Block(self.block(node)?),
+            "EmptyStatement" => Statement::Empty(EmptyStatement { span }),
+            "DebuggerStatement" => Statement::Debugger(DebuggerStatement { span }),
+            "WithStatement" => Statement::With(Box::new(WithStatement {
+                span,
+                object: self.expression(field(node, "object")?)?,
+                body: self.statement(field(node, "body")?)?,
+            })),
+            "ReturnStatement" => Statement::Return(ReturnStatement {
+                span,
+                argument: self.optional(field(node, "argument")?, Self::expression)?,
+            }),
+            "LabeledStatement" => Statement::Labeled(Box::new(LabeledStatement {
+                span,
+                label: self.identifier(field(node, "label")?)?,
+                body: self.statement(field(node, "body")?)?,
+            })),
+            "BreakStatement" => Statement::Break(BreakStatement {
+                span,
+                label: self.optional(field(node, "label")?, Self::identifier)?,
+            }),
+            "ContinueStatement" => Statement::Continue(ContinueStatement {
+                span,
+                label: self.optional(field(node, "label")?, Self::identifier)?,
+            }),
+            "IfStatement" => Statement::If(Box::new(IfStatement {
+                span,
+                test: self.expression(field(node, "test")?)?,
+                consequent: self.statement(field(node, "consequent")?)?,
+                alternate: self.optional(field(node, "alternate")?, Self::statement)?,
+            })),
+            "SwitchStatement" => Statement::Switch(SwitchStatement {
+                span,
+                discriminant: self.expression(field(node, "discriminant")?)?,
+                cases: self.list(array(node, "cases")?, Self::switch_case)?,
+            }),
+            "ThrowStatement" => Statement::Throw(ThrowStatement {
+                span,
+                argument: self.expression(field(node, "argument")?)?,
+            }),
+            "TryStatement" => Statement::Try(Box::new(TryStatement {
+                span,
+                block: self.block(field(node, "block")?)?,
+                handler: self.optional(field(node, "handler")?, Self::catch_clause)?,
+                finalizer: self.optional(field(node, "finalizer")?, Self::block)?,
+            })),
+            "WhileStatement" => Statement::While(Box::new(WhileStatement {
+                span,
+                test: self.expression(field(node, "test")?)?,
+                body: self.statement(field(node, "body")?)?,
+            })),
+            "DoWhileStatement" => Statement::DoWhile(Box::new(DoWhileStatement {
+                span,
+                body: self.statement(field(node, "body")?)?,
+                test: self.expression(field(node, "test")?)?,
+            })),
+            "ForStatement" => Statement::For(Box::new(ForStatement {
+                span,
+                init: self.optional(field(node, "init")?, Self::for_init)?,
+                test: self.optional(field(node, "test")?, Self::expression)?,
+                update: self.optional(field(node, "update")?, Self::expression)?,
+                body: self.statement(field(node, "body")?)?,
+            })),
+            "ForInStatement" => Statement::ForIn(Box::new(ForInStatement {
+                span,
+                left: self.for_in_of_left(field(node, "left")?)?,
+                right: self.expression(field(node, "right")?)?,
+                body: self.statement(field(node, "body")?)?,
+            })),
+            "ForOfStatement" => Statement::ForOf(Box::new(ForOfStatement {
+                span,
+                left: self.for_in_of_left(field(node, "left")?)?,
+                right: self.expression(field(node, "right")?)?,
+                body: self.statement(field(node, "body")?)?,
+                is_await: optional_boolean(node, "await")?,
+            })),
+            "FunctionDeclaration" => Statement::FunctionDeclaration(Box::new(self.function(node)?)),
+            "VariableDeclaration" => {
+                Statement::VariableDeclaration(self.variable_declaration(node)?)
+            }
+            "ClassDeclaration" => Statement::ClassDeclaration(Box::new(self.class(node)?)),
+            "ErrorNode" => Statement::Error(ErrorNode { span }),
+            _ => return unexpected(node, "a statement"),
+        };
+        Ok(statement)
+    }
+
+    fn block(&mut self, node: &JsonValue) -> Result<BlockStatement> {
+        if kind(node)? != "BlockStatement" {
+            return unexpected(node, "BlockStatement");
+        }
+        Ok(BlockStatement {
+            span: self.span(node)?,
+            body: self.list(array(node, "body")?, Self::statement)?,
+        })
+    }
+
+    fn switch_case(&mut self, node: &JsonValue) -> Result<SwitchCase> {
+        Ok(SwitchCase {
+            span: self.span(node)?,
+            test: self.optional(field(node, "test")?, Self::expression)?,
+            consequent: self.list(array(node, "consequent")?, Self::statement)?,
+        })
+    }
+
+    fn catch_clause(&mut self, node: &JsonValue) -> Result<CatchClause> {
+        Ok(CatchClause {
+            span: self.span(node)?,
+            param: self.optional(field(node, "param")?, Self::pattern)?,
+            body: self.block(field(node, "body")?)?,
+        })
+    }
+
+    fn for_init(&mut self, node: &JsonValue) -> Result<ForInit> {
+        match kind(node)? {
+            "VariableDeclaration" => Ok(ForInit::VariableDeclaration(
+                self.variable_declaration(node)?,
+            )),
+            _ => Ok(ForInit::Expression(self.expression(node)?)),
+        }
+    }
+
+    fn for_in_of_left(&mut self, node: &JsonValue) -> Result<ForInOfLeft> {
+        match kind(node)? {
+            "VariableDeclaration" => Ok(ForInOfLeft::VariableDeclaration(
+                self.variable_declaration(node)?,
+            )),
+            _ => Ok(ForInOfLeft::Pattern(self.pattern(node)?)),
+        }
+    }
+
+    fn variable_declaration(&mut self, node: &JsonValue) -> Result<VariableDeclaration> {
+        if kind(node)? != "VariableDeclaration" {
+            return unexpected(node, "VariableDeclaration");
+        }
+        let kind = match string(node, "kind")?.as_str() {
+            "var" => VariableKind::Var,
+            "let" => VariableKind::Let,
+            "const" => VariableKind::Const,
+            other => return error(format!("unknown variable kind '{other}'")),
+        };
+
+        Ok(VariableDeclaration {
+            span: self.span(node)?,
+            kind,
+            declarations: self.list(array(node, "declarations")?, Self::variable_declarator)?,
+        })
+    }
+
+    fn variable_declarator(&mut self, node: &JsonValue) -> Result<VariableDeclarator> {
+        Ok(VariableDeclarator {
+            span: self.span(node)?,
+            id: self.pattern(field(node, "id")?)?,
+            init: self.optional(field(node, "init")?, Self::expression)?,
+        })
+    }
+
+    /// 関数宣言と関数式を読む。
+    fn function(&mut self, node: &JsonValue) -> Result<Function> {
+        let body = field(node, "body")?;
+        let strict = self.strict || has_use_strict(array(body, "body")?);
+
+        self.with_strict(strict, |this| {
+            Ok(Function {
+                span: this.span(node)?,
+                id: this.optional(field(node, "id")?, Self::identifier)?,
+                params: this.list(array(node, "params")?, Self::pattern)?,
+                body: this.function_body(body)?,
+                is_async: optional_boolean(node, "async")?,
+                generator: optional_boolean(node, "generator")?,
+                strict,
+            })
+        })
+    }
+
+    fn function_body(&mut self, node: &JsonValue) -> Result<FunctionBody> {
+        let block = self.block(node)?;
+        Ok(FunctionBody {
+            span: block.span,
+            body: block.body,
+        })
+    }
+
+    fn arrow_function(&mut self, node: &JsonValue) -> Result<ArrowFunctionExpression> {
+        let body = field(node, "body")?;
+        let is_block = kind(body)? == "BlockStatement";
+        let strict = self.strict || (is_block && has_use_strict(array(body, "body")?));
+
+        self.with_strict(strict, |this| {
+            Ok(ArrowFunctionExpression {
+                span: this.span(node)?,
+                params: this.list(array(node, "params")?, Self::pattern)?,
+                body: if is_block {
+                    ArrowFunctionBody::FunctionBody(this.function_body(body)?)
+                } else {
+                    ArrowFunctionBody::Expression(this.expression(body)?)
+                },
+                is_async: optional_boolean(node, "async")?,
+                strict,
+            })
+        })
+    }
+
+    /// クラス宣言とクラス式を読む。クラスの中は全て strict モードになる。
+    fn class(&mut self, node: &JsonValue) -> Result<Class> {
+        self.with_strict(true, |this| {
+            Ok(Class {
+                span: this.span(node)?,
+                id: this.optional(field(node, "id")?, Self::identifier)?,
+                super_class: this.optional(field(node, "superClass")?, Self::expression)?,
+                body: this.class_body(field(node, "body")?)?,
+            })
+        })
+    }
+
+    fn class_body(&mut self, node: &JsonValue) -> Result<ClassBody> {
+        Ok(ClassBody {
+            span: self.span(node)?,
+            body: self.list(array(node, "body")?, Self::class_element)?,
+        })
+    }
+
+    fn class_element(&mut self, node: &JsonValue) -> Result<ClassElement> {
+        let span = self.span(node)?;
+        match kind(node)? {
+            "MethodDefinition" => {
+                let kind = match string(node, "kind")?.as_str() {
+                    "constructor" => MethodKind::Constructor,
+                    "method" => MethodKind::Method,
+                    "get" => MethodKind::Get,
+                    "set" => MethodKind::Set,
+                    other => return error(format!("unknown method kind '{other}'")),
+                };
+                Ok(ClassElement::Method(MethodDefinition {
+                    span,
+                    key: self.property_key(node)?,
+                    value: self.function(field(node, "value")?)?,
+                    kind,
+                    is_static: boolean(node, "static")?,
+                }))
+            }
+            "PropertyDefinition" => Ok(ClassElement::Property(PropertyDefinition {
+                span,
+                key: self.property_key(node)?,
+                value: self.optional(field(node, "value")?, Self::expression)?,
+                is_static: boolean(node, "static")?,
+            })),
+            "StaticBlock" => Ok(ClassElement::StaticBlock(StaticBlock {
+                span,
+                body: self.list(array(node, "body")?, Self::statement)?,
+            })),
+            _ => unexpected(node, "a class element"),
+        }
+    }
+
+    /// `computed` と `key` からプロパティ名を読む。
+    fn property_key(&mut self, node: &JsonValue) -> Result<PropertyKey> {
+        let key = field(node, "key")?;
+        if optional_boolean(node, "computed")? {
+            return Ok(PropertyKey::Computed(self.expression(key)?));
+        }
+
+        match kind(key)? {
+            "Identifier" => Ok(PropertyKey::Identifier(self.identifier(key)?)),
+            "Literal" => Ok(PropertyKey::Literal(self.literal(key)?)),
+            "PrivateIdentifier" => Ok(PropertyKey::PrivateIdentifier(
+                self.private_identifier(key)?,
+            )),
+            _ => unexpected(key, "a property name"),
+        }
+    }
+
+    fn expression(&mut self, node: &JsonValue) -> Result<Expression> {
+        let span = self.span(node)?;
+        let expression = match kind(node)? {
+            "Identifier" => Expression::Identifier(self.identifier(node)?),
+            "Literal" => Expression::Literal(self.literal(node)?),
+            "ThisExpression" => Expression::This(ThisExpression { span }),
+            "Super" => Expression::Super(Super { span }),
+            "ArrayExpression" => Expression::Array(ArrayExpression {
+                span,
+                elements: self.list(array(node, "elements")?, |this, element| {
+                    this.optional(element, Self::expression_or_spread)
+                })?,
+            }),
+            "ObjectExpression" => Expression::Object(ObjectExpression {
+                span,
+                properties: self.list(array(node, "properties")?, Self::object_property)?,
+            }),
+            "FunctionExpression" => Expression::Function(Box::new(self.function(node)?)),
+            "ArrowFunctionExpression" => {
+                Expression::ArrowFunction(Box::new(self.arrow_function(node)?))
+            }
+            "ClassExpression" => Expression::Class(Box::new(self.class(node)?)),
+            "TemplateLiteral" => Expression::TemplateLiteral(self.template_literal(node)?),
+            "TaggedTemplateExpression" => {
+                Expression::TaggedTemplate(Box::new(TaggedTemplateExpression {
+                    span,
+                    tag: self.expression(field(node, "tag")?)?,
+                    quasi: self.template_literal(field(node, "quasi")?)?,
+                }))
+            }
+            "MemberExpression" => {
+                let property = field(node, "property")?;
+                let property = if optional_boolean(node, "computed")? {
+                    MemberProperty::Computed(self.expression(property)?)
+                } else if kind(property)? == "PrivateIdentifier" {
+                    MemberProperty::PrivateIdentifier(self.private_identifier(property)?)
+                } else {
+                    MemberProperty::Identifier(self.identifier(property)?)
+                };
+                Expression::Member(Box::new(MemberExpression {
+                    span,
+                    object: self.expression(field(node, "object")?)?,
+                    property,
+                    optional: optional_boolean(node, "optional")?,
+                }))
+            }
+            "CallExpression" => Expression::Call(Box::new(CallExpression {
+                span,
+                callee: self.expression(field(node, "callee")?)?,
+                arguments: self.list(array(node, "arguments")?, Self::expression_or_spread)?,
+                optional: optional_boolean(node, "optional")?,
+            })),
+            "NewExpression" => Expression::New(Box::new(NewExpression {
+                span,
+                callee: self.expression(field(node, "callee")?)?,
+                arguments: self.list(array(node, "arguments")?, Self::expression_or_spread)?,
+            })),
+            "ChainExpression" => Expression::Chain(Box::new(ChainExpression {
+                span,
+                expression: self.expression(field(node, "expression")?)?,
+            })),
+            "UnaryExpression" => Expression::Unary(Box::new(UnaryExpression {
+                span,
+                operator: operator(node, UNARY_OPERATORS)?,
+                argument: self.expression(field(node, "argument")?)?,
+            })),
+            "UpdateExpression" => Expression::Update(Box::new(UpdateExpression {
+                span,
+                operator: operator(node, UPDATE_OPERATORS)?,
+                prefix: boolean(node, "prefix")?,
+                argument: self.expression(field(node, "argument")?)?,
+            })),
+            "BinaryExpression" => Expression::Binary(Box::new(BinaryExpression {
+                span,
+                operator: operator(node, BINARY_OPERATORS)?,
+                left: self.expression(field(node, "left")?)?,
+                right: self.expression(field(node, "right")?)?,
+            })),
+            "LogicalExpression" => Expression::Logical(Box::new(LogicalExpression {
+                span,
+                operator: operator(node, LOGICAL_OPERATORS)?,
+                left: self.expression(field(node, "left")?)?,
+                right: self.expression(field(node, "right")?)?,
+            })),
+            "ConditionalExpression" => Expression::Conditional(Box::new(ConditionalExpression {
+                span,
+                test: self.expression(field(node, "test")?)?,
+                consequent: self.expression(field(node, "consequent")?)?,
+                alternate: self.expression(field(node, "alternate")?)?,
+            })),
+            "AssignmentExpression" => Expression::Assignment(Box::new(AssignmentExpression {
+                span,
+                operator: operator(node, ASSIGNMENT_OPERATORS)?,
+                left: self.pattern(field(node, "left")?)?,
+                right: self.expression(field(node, "right")?)?,
+            })),
+            "SequenceExpression" => Expression::Sequence(SequenceExpression {
+                span,
+                expressions: self.list(array(node, "expressions")?, Self::expression)?,
+            }),
+            "YieldExpression" => Expression::Yield(Box::new(YieldExpression {
+                span,
+                argument: self.optional(field(node, "argument")?, Self::expression)?,
+                delegate: boolean(node, "delegate")?,
+            })),
+            "AwaitExpression" => Expression::Await(Box::new(AwaitExpression {
+                span,
+                argument: self.expression(field(node, "argument")?)?,
+            })),
+            "MetaProperty" => Expression::MetaProperty(MetaProperty {
+                span,
+                meta: self.identifier(field(node, "meta")?)?,
+                property: self.identifier(field(node, "property")?)?,
+            }),
+            "ImportExpression" => Expression::Import(Box::new(ImportExpression {
+                span,
+                source: self.expression(field(node, "source")?)?,
+                options: match node.get("options") {
+                    Some(options) => self.optional(options, Self::expression)?,
+                    None => None,
+                },
+            })),
+            "PrivateIdentifier" => Expression::PrivateIdentifier(self.private_identifier(node)?),
+            "ErrorNode" => Expression::Error(ErrorNode { span }),
+            _ => return unexpected(node, "an expression"),
+        };
+        Ok(expression)
+    }
+
+    fn expression_or_spread(&mut self, node: &JsonValue) -> Result<ExpressionOrSpread> {
+        match kind(node)? {
+            "SpreadElement" => Ok(ExpressionOrSpread::Spread(self.spread_element(node)?)),
+            _ => Ok(ExpressionOrSpread::Expression(self.expression(node)?)),
+        }
+    }
+
+    fn spread_element(&mut self, node: &JsonValue) -> Result<SpreadElement> {
+        Ok(SpreadElement {
+            span: self.span(node)?,
+            argument: self.expression(field(node, "argument")?)?,
+        })
+    }
+
+    fn object_property(&mut self, node: &JsonValue) -> Result<ObjectProperty> {
+        match kind(node)? {
+            "SpreadElement" => Ok(ObjectProperty::Spread(self.spread_element(node)?)),
+            "Property" => {
+                let kind = match string(node, "kind")?.as_str() {
+                    "init" => PropertyKind::Init,
+                    "get" => PropertyKind::Get,
+                    "set" => PropertyKind::Set,
+                    other => return error(format!("unknown property kind '{other}'")),
+                };
+                Ok(ObjectProperty::Property(Property {
+                    span: self.span(node)?,
+                    key: self.property_key(node)?,
+                    value: self.expression(field(node, "value")?)?,
+                    kind,
+                    method: optional_boolean(node, "method")?,
+                    shorthand: optional_boolean(node, "shorthand")?,
+                }))
+            }
+            _ => unexpected(node, "a property"),
+        }
+    }
+
+    fn pattern(&mut self, node: &JsonValue) -> Result<Pattern> {
+        let span = self.span(node)?;
+        let pattern = match kind(node)? {
+            "Identifier" => Pattern::Identifier(self.identifier(node)?),
+            "ObjectPattern" => Pattern::Object(ObjectPattern {
+                span,
+                properties: self.list(array(node, "properties")?, Self::object_pattern_property)?,
+            }),
+            "ArrayPattern" => Pattern::Array(ArrayPattern {
+                span,
+                elements: self.list(array(node, "elements")?, |this, element| {
+                    this.optional(element, Self::pattern)
+                })?,
+            }),
+            "RestElement" => Pattern::Rest(Box::new(self.rest_element(node)?)),
+            "AssignmentPattern" => Pattern::Assignment(Box::new(AssignmentPattern {
+                span,
+                left: self.pattern(field(node, "left")?)?,
+                right: self.expression(field(node, "right")?)?,
+            })),
+            _ => Pattern::Expression(Box::new(self.expression(node)?)),
+        };
+        Ok(pattern)
+    }
+
+    fn object_pattern_property(&mut self, node: &JsonValue) -> Result<ObjectPatternProperty> {
+        match kind(node)? {
+            "RestElement" => Ok(ObjectPatternProperty::Rest(self.rest_element(node)?)),
+            "Property" => Ok(ObjectPatternProperty::Property(AssignmentProperty {
+                span: self.span(node)?,
+                key: self.property_key(node)?,
+                value: self.pattern(field(node, "value")?)?,
+                shorthand: optional_boolean(node, "shorthand")?,
+            })),
+            _ => unexpected(node, "a property"),
+        }
+    }
+
+    fn rest_element(&mut self, node: &JsonValue) -> Result<RestElement> {
+        Ok(RestElement {
+            span: self.span(node)?,
+            argument: self.pattern(field(node, "argument")?)?,
+        })
+    }
+
+    fn template_literal(&mut self, node: &JsonValue) -> Result<TemplateLiteral> {
+        if kind(node)? != "TemplateLiteral" {
+            return unexpected(node, "TemplateLiteral");
+        }
+        Ok(TemplateLiteral {
+            span: self.span(node)?,
+            quasis: self.list(array(node, "quasis")?, Self::template_element)?,
+            expressions: self.list(array(node, "expressions")?, Self::expression)?,
+        })
+    }
+
+    fn template_element(&mut self, node: &JsonValue) -> Result<TemplateElement> {
+        let value = field(node, "value")?;
+        Ok(TemplateElement {
+            span: self.span(node)?,
+            raw: string(value, "raw")?,
+            cooked: match field(value, "cooked")? {
+                JsonValue::Null => None,
+                _ => Some(string(value, "cooked")?),
+            },
+            tail: boolean(node, "tail")?,
+        })
+    }
+
+    fn identifier(&mut self, node: &JsonValue) -> Result<Identifier> {
+        if kind(node)? != "Identifier" {
+            return unexpected(node, "Identifier");
+        }
+        Ok(Identifier {
+            span: self.span(node)?,
+            name: string(node, "name")?,
+        })
+    }
+
+    fn private_identifier(&mut self, node: &JsonValue) -> Result<PrivateIdentifier> {
+        if kind(node)? != "PrivateIdentifier" {
+            return unexpected(node, "PrivateIdentifier");
+        }
+        Ok(PrivateIdentifier {
+            span: self.span(node)?,
+            name: string(node, "name")?,
+        })
+    }
+
+    fn literal(&mut self, node: &JsonValue) -> Result<Literal> {
+        if kind(node)? != "Literal" {
+            return unexpected(node, "Literal");
+        }
+        let raw = string(node, "raw")?;
+
+        let value = if let Some(regex) = node.get("regex") {
+            LiteralValue::RegExp(RegExpLiteral {
+                pattern: string(regex, "pattern")?,
+                flags: string(regex, "flags")?,
+            })
+        } else if node.get("bigint").is_some() {
+            LiteralValue::BigInt(bigint_value(&raw, bigint_token(&raw)))
+        } else {
+            match field(node, "value")? {
+                // JSON で表せない大きな数は null になる
+                JsonValue::Null if raw != "null" => {
+                    LiteralValue::Number(numeric_value(&raw, LiteralToken::DecimalExponentNumber))
+                }
+                JsonValue::Null => LiteralValue::Null,
+                JsonValue::Bool(value) => LiteralValue::Boolean(*value),
+                JsonValue::Number(value) => LiteralValue::Number(*value),
+                JsonValue::String(value) => LiteralValue::String(value.clone()),
+                _ => return error("unexpected literal value"),
+            }
+        };
+
+        Ok(Literal {
+            span: self.span(node)?,
+            value,
+            raw,
+        })
+    }
+
+    fn module_declaration(&mut self, node: &JsonValue) -> Result<ModuleDeclaration> {
+        let span = self.span(node)?;
+        let attributes = |this: &mut Self| match node.get("attributes") {
+            Some(attributes) => this.list(
+                attributes.as_array().unwrap_or_default(),
+                Self::import_attribute,
+            ),
+            None => Ok(Vec::new()),
+        };
+
+        let declaration = match kind(node)? {
+            "ImportDeclaration" => ModuleDeclaration::Import(ImportDeclaration {
+                span,
+                specifiers: self.list(array(node, "specifiers")?, Self::import_specifier)?,
+                source: self.literal(field(node, "source")?)?,
+                attributes: attributes(self)?,
+            }),
+            "ExportNamedDeclaration" => ModuleDeclaration::ExportNamed(ExportNamedDeclaration {
+                span,
+                declaration: self.optional(field(node, "declaration")?, Self::declaration)?,
+                specifiers: self.list(array(node, "specifiers")?, Self::export_specifier)?,
+                source: self.optional(field(node, "source")?, Self::literal)?,
+                attributes: attributes(self)?,
+            }),
+            "ExportDefaultDeclaration" => {
+                let value = field(node, "declaration")?;
+                let declaration = match kind(value)? {
+                    "FunctionDeclaration" => {
+                        ExportDefaultKind::Function(Box::new(self.function(value)?))
+                    }
+                    "ClassDeclaration" => ExportDefaultKind::Class(Box::new(self.class(value)?)),
+                    _ => ExportDefaultKind::Expression(self.expression(value)?),
+                };
+                ModuleDeclaration::ExportDefault(ExportDefaultDeclaration { span, declaration })
+            }
+            "ExportAllDeclaration" => ModuleDeclaration::ExportAll(ExportAllDeclaration {
+                span,
+                exported: self.optional(field(node, "exported")?, Self::module_export_name)?,
+                source: self.literal(field(node, "source")?)?,
+                attributes: attributes(self)?,
+            }),
+            _ => return unexpected(node, "a module declaration"),
+        };
+        Ok(declaration)
+    }
+
+    fn import_specifier(&mut self, node: &JsonValue) -> Result<ImportDeclarationSpecifier> {
+        let span = self.span(node)?;
+        let local = self.identifier(field(node, "local")?)?;
+        match kind(node)? {
+            "ImportSpecifier" => Ok(ImportDeclarationSpecifier::Named(ImportSpecifier {
+                span,
+                imported: self.module_export_name(field(node, "imported")?)?,
+                local,
+            })),
+            "ImportDefaultSpecifier" => Ok(ImportDeclarationSpecifier::Default(
+                ImportDefaultSpecifier { span, local },
+            )),
+            "ImportNamespaceSpecifier" => Ok(ImportDeclarationSpecifier::Namespace(
+                ImportNamespaceSpecifier { span, local },
+            )),
+            _ => unexpected(node, "an import specifier"),
+        }
+    }
+
+    fn import_attribute(&mut self, node: &JsonValue) -> Result<ImportAttribute> {
+        Ok(ImportAttribute {
+            span: self.span(node)?,
+            key: self.property_key(node)?,
+            value: self.literal(field(node, "value")?)?,
+        })
+    }
+
+    fn export_specifier(&mut self, node: &JsonValue) -> Result<ExportSpecifier> {
+        Ok(ExportSpecifier {
+            span: self.span(node)?,
+            local: self.module_export_name(field(node, "local")?)?,
+            exported: self.module_export_name(field(node, "exported")?)?,
+        })
+    }
+
+    fn module_export_name(&mut self, node: &JsonValue) -> Result<ModuleExportName> {
+        match kind(node)? {
+            "Literal" => Ok(ModuleExportName::Literal(self.literal(node)?)),
+            _ => Ok(ModuleExportName::Identifier(self.identifier(node)?)),
+        }
+    }
+
+    fn declaration(&mut self, node: &JsonValue) -> Result<Declaration> {
+        match kind(node)? {
+            "FunctionDeclaration" => Ok(Declaration::Function(Box::new(self.function(node)?))),
+            "VariableDeclaration" => Ok(Declaration::Variable(self.variable_declaration(node)?)),
+            "ClassDeclaration" => Ok(Declaration::Class(Box::new(self.class(node)?))),
+            _ => unexpected(node, "a declaration"),
+        }
+    }
+}
+
+/// BigInt リテラルの表記から基数を判断する。
+fn bigint_token(raw: &str) -> LiteralToken {
+    match raw.get(..2) {
+        Some("0b" | "0B") => LiteralToken::BinaryBigInteger,
+        Some("0o" | "0O") => LiteralToken::OctalBigInteger,
+        Some("0x" | "0X") => LiteralToken::HexBigInteger,
+        _ => LiteralToken::DecimalBigInteger,
+    }
+}
@@ -0,0 +1,819 @@
+//! 構文木を ESTree の JSON に変換するモジュールです。
+//!
+//! キーの順序は acorn が作るノードと同じにする。
+
+use crate::{
+    ast::{
+        line_index::LineIndex,
+        structs::{
+            class::{Class, ClassBody, ClassElement, MethodKind},
+            expression::{
+                Expression, ExpressionOrSpread, MemberProperty, ObjectProperty, Property,
+                PropertyKey, PropertyKind, SpreadElement,
+            },
+            function::{ArrowFunctionBody, Function, FunctionBody},
+            literal::{Identifier, Literal, LiteralValue, PrivateIdentifier},
+            module::{
+                Declaration, ExportDefaultKind, ExportSpecifier, ImportAttribute,
+                ImportDeclarationSpecifier, ModuleDeclaration, ModuleExportName, ModuleItem,
+            },
+            pattern::{ObjectPatternProperty, Pattern, RestElement},
+            span::Span,
+            statement::{
+                BlockStatement, CatchClause, ForInOfLeft, ForInit, Statement, SwitchCase,
+                VariableDeclaration, VariableDeclarator,
+            },
+            template::{TemplateElement, TemplateLiteral},
+            JsAst, SourceType,
+        },
+    },
+    json::JsonValue,
+    to_source_string::ToSourceString,
+};
+
+/// 位置の変換表を持ち、ノードを JSON にする。
+pub(super) struct Serializer {
+    index: LineIndex,
+}
+
+/// ESTree のノードのキーを順に並べる。
+struct Node(Vec<(String, JsonValue)>);
+
+impl Node {
+    fn field(mut self, key: &str, value: impl Into<JsonValue>) -> Node {
+        self.0.push((key.to_string(), value.into()));
+        self
+    }
+
+    fn build(self) -> JsonValue {
+        JsonValue::Object(self.0)
+    }
+}
+
+/// ESTree の JSON に変換できるノード
+trait ToEstree {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue;
+}
+
+impl<T: ToEstree> ToEstree for Box<T> {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        self.as_ref().to_estree(serializer)
+    }
+}
+
+impl<T: ToEstree> ToEstree for Option<T> {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        match self {
+            Some(node) => node.to_estree(serializer),
+            None => JsonValue::Null,
+        }
+    }
+}
+
+impl<T: ToEstree> ToEstree for Vec<T> {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        JsonValue::Array(self.iter().map(|node| node.to_estree(serializer)).collect())
+    }
+}
+
+impl Serializer {
+    pub(super) fn new(source: &str) -> Serializer {
+        Serializer {
+            index: LineIndex::new(source),
+        }
+    }
+
+    pub(super) fn program(&self, ast: &JsAst) -> JsonValue {
+        let source_type = match ast.source_type {
+            SourceType::Script => "script",
+            SourceType::Module => "module",
+        };
+
+        self.node("Program", ast.span)
+            .field("body", ast.body.to_estree(self))
+            .field("sourceType", source_type)
+            .build()
+    }
+
+    pub(super) fn expression(&self, expression: &Expression) -> JsonValue {
+        expression.to_estree(self)
+    }
+
+    pub(super) fn statement(&self, statement: &Statement) -> JsonValue {
+        statement.to_estree(self)
+    }
+
+    /// `type` と位置 (`start` `end` `loc` `range`) を持つノードを始める。
+    fn node(&self, kind: &str, span: Span) -> Node {
+        let start = self.index.utf16_offset(span.start);
+        let end = self.index.utf16_offset(span.end);
+        let position = |offset| {
+            let line_column = self.index.line_column(offset);
+            Node(Vec::new())
+                .field("line", line_column.line)
+                .field("column", line_column.column)
+                .build()
+        };
+        let loc = Node(Vec::new())
+            .field("start", position(span.start))
+            .field("end", position(span.end))
+            .build();
+
+        Node(Vec::new())
+            .field("type", kind)
+            .field("start", start)
+            .field("end", end)
+            .field("loc", loc)
+            .field("range", JsonValue::Array(vec![start.into(), end.into()]))
+    }
+
+    fn function(&self, function: &Function, kind: &str) -> JsonValue {
+        self.node(kind, function.span)
+            .field("id", function.id.to_estree(self))
+            .field("expression", false)
+            .field("generator", function.generator)
+            .field("async", function.is_async)
+            .field("params", function.params.to_estree(self))
+            .field("body", function.body.to_estree(self))
+            .build()
+    }
+
+    fn class(&self, class: &Class, kind: &str) -> JsonValue {
+        self.node(kind, class.span)
+            .field("id", class.id.to_estree(self))
+            .field("superClass", class.super_class.to_estree(self))
+            .field("body", class.body.to_estree(self))
+            .build()
+    }
+
+    /// プロパティ名を `computed` と `key` にする。
+    fn property_key(&self, node: Node, key: &PropertyKey) -> Node {
+        node.field("computed", key.is_computed())
+            .field("key", key.to_estree(self))
+    }
+}
+
+impl ToEstree for ModuleItem {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        match self {
+            ModuleItem::Statement(statement) => statement.to_estree(serializer),
+            ModuleItem::ModuleDeclaration(declaration) => declaration.to_estree(serializer),
+        }
+    }
+}
+
+impl ToEstree for Statement {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        let s = serializer;
+        match self {
+            Statement::Expression(statement) => {
+                let node = s
+                    .node("ExpressionStatement", statement.span)
+                    .field("expression", statement.expression.to_estree(s));
+                match &statement.directive {
+                    Some(directive) => node.field("directive", directive.as_str()).build(),
+                    None => node.build(),
+                }
+            }
+            Statement::Block(block) => block.to_estree(s),
+            Statement::Empty(statement) => s.node("EmptyStatement", statement.span).build(),
+            Statement::Debugger(statement) => s.node("DebuggerStatement", statement.span).build(),
+            Statement::With(statement) => s
+                .node("WithStatement", statement.span)
+                .field("object", statement.object.to_estree(s))
+                .field("body", statement.body.to_estree(s))
+                .build(),
+            Statement::Return(statement) => s
+                .node("ReturnStatement", statement.span)
+                .field("argument", statement.argument.to_estree(s))
+                .build(),
+            Statement::Labeled(statement) => s
+                .node("LabeledStatement", statement.span)
+                .field("body", statement.body.to_estree(s))
+                .field("label", statement.label.to_estree(s))
+                .build(),
+            Statement::Break(statement) => s
+                .node("BreakStatement", statement.span)
+                .field("label", statement.label.to_estree(s))
+                .build(),
+            Statement::Continue(statement) => s
+                .node("ContinueStatement", statement.span)
+                .field("label", statement.label.to_estree(s))
+                .build(),
+            Statement::If(statement) => s
+                .node("IfStatement", statement.span)
+                .field("test", statement.test.to_estree(s))
+                .field("consequent", statement.consequent.to_estree(s))
+                .field("alternate", statement.alternate.to_estree(s))
+                .build(),
+            Statement::Switch(statement) => s
+                .node("SwitchStatement", statement.span)
+                .field("discriminant", statement.discriminant.to_estree(s))
+                .field("cases", statement.cases.to_estree(s))
+                .build(),
+            Statement::Throw(statement) => s
+                .node("ThrowStatement", statement.span)
+                .field("argument", statement.argument.to_estree(s))
+                .build(),
+            Statement::Try(statement) => s
+                .node("TryStatement", statement.span)
+                .field("block", statement.block.to_estree(s))
+                .field("handler", statement.handler.to_estree(s))
+                .field("finalizer", statement.finalizer.to_estree(s))
+                .build(),
+            Statement::While(statement) => s
+                .node("WhileStatement", statement.span)
+                .field("test", statement.test.to_estree(s))
+                .field("body", statement.body.to_estree(s))
+                .build(),
+            Statement::DoWhile(statement) => s
+                .node("DoWhileStatement", statement.span)
+                .field("body", statement.body.to_estree(s))
+                .field("test", statement.test.to_estree(s))
+                .build(),
+            Statement::For(statement) => s
+                .node("ForStatement", statement.span)
+                .field("init", statement.init.to_estree(s))
+                .field("test", statement.test.to_estree(s))
+                .field("update", statement.update.to_estree(s))
+                .field("body", statement.body.to_estree(s))
+                .build(),
+            Statement::ForIn(statement) => s
+                .node("ForInStatement", statement.span)
+                .field("left", statement.left.to_estree(s))
+                .field("right", statement.right.to_estree(s))
+                .field("body", statement.body.to_estree(s))
+                .build(),
+            Statement::ForOf(statement) => s
+                .node("ForOfStatement", statement.span)
+                .field("await", statement.is_await)
+                .field("left", statement.left.to_estree(s))
+                .field("right", statement.right.to_estree(s))
+                .field("body", statement.body.to_estree(s))
+                .build(),
+            Statement::FunctionDeclaration(function) => s.function(function, "FunctionDeclaration"),
+            Statement::VariableDeclaration(declaration) => declaration.to_estree(s),
+            Statement::ClassDeclaration(class) => s.class(class, "ClassDeclaration"),
+            Statement::Error(error) => s.node("ErrorNode", error.span).build(),
+        }
+    }
+}
+
+impl ToEstree for BlockStatement {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        serializer
+            .node("BlockStatement", self.span)
+            .field("body", self.body.to_estree(serializer))
+            .build()
+    }
+}
+
+impl ToEstree for SwitchCase {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        serializer
+            .node("SwitchCase", self.span)
+            .field("consequent", self.consequent.to_estree(serializer))
+            .field("test", self.test.to_estree(serializer))
+            .build()
+    }
+}
+
+impl ToEstree for CatchClause {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        serializer
+            .node("CatchClause", self.span)
+            .field("param", self.param.to_estree(serializer))
+            .field("body", self.body.to_estree(serializer))
+            .build()
+    }
+}
+
+impl ToEstree for ForInit {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        match self {
+            ForInit::VariableDeclaration(declaration) => declaration.to_estree(serializer),
+            ForInit::Expression(expression) => expression.to_estree(serializer),
+        }
+    }
+}
+
+impl ToEstree for ForInOfLeft {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        match self {
+            ForInOfLeft::VariableDeclaration(declaration) => declaration.to_estree(serializer),
+            ForInOfLeft::Pattern(pattern) => pattern.to_estree(serializer),
+        }
+    }
+}
+
+impl ToEstree for VariableDeclaration {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        serializer
+            .node("VariableDeclaration", self.span)
+            .field("declarations", self.declarations.to_estree(serializer))
+            .field("kind", self.kind.as_str())
+            .build()
+    }
+}
+
+impl ToEstree for VariableDeclarator {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        serializer
+            .node("VariableDeclarator", self.span)
+            .field("id", self.id.to_estree(serializer))
+            .field("init", self.init.to_estree(serializer))
+            .build()
+    }
+}
+
+impl ToEstree for FunctionBody {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        serializer
+            .node("BlockStatement", self.span)
+            .field("body", self.body.to_estree(serializer))
+            .build()
+    }
+}
+
+impl ToEstree for Expression {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        let s = serializer;
+        match self {
+            Expression::Identifier(identifier) => identifier.to_estree(s),
+            Expression::Literal(literal) => literal.to_estree(s),
+            Expression::This(expression) => s.node("ThisExpression", expression.span).build(),
+            Expression::Super(expression) => s.node("Super", expression.span).build(),
+            Expression::Array(expression) => s
+                .node("ArrayExpression", expression.span)
+                .field("elements", expression.elements.to_estree(s))
+                .build(),
+            Expression::Object(expression) => s
+                .node("ObjectExpression", expression.span)
+                .field("properties", expression.properties.to_estree(s))
+                .build(),
+            Expression::Function(function) => s.function(function, "FunctionExpression"),
+            Expression::ArrowFunction(arrow) => {
+                let body = match &arrow.body {
+                    ArrowFunctionBody::Expression(expression) => expression.to_estree(s),
+                    ArrowFunctionBody::FunctionBody(body) => body.to_estree(s),
+                };
+                s.node("ArrowFunctionExpression", arrow.span)
+                    .field("id", JsonValue::Null)
+                    .field("expression", arrow.is_expression())
+                    .field("generator", false)
+                    .field("async", arrow.is_async)
+                    .field("params", arrow.params.to_estree(s))
+                    .field("body", body)
+                    .build()
+            }
+            Expression::Class(class) => s.class(class, "ClassExpression"),
+            Expression::TemplateLiteral(template) => template.to_estree(s),
+            Expression::TaggedTemplate(expression) => s
+                .node("TaggedTemplateExpression", expression.span)
+                .field("tag", expression.tag.to_estree(s))
+                .field("quasi", expression.quasi.to_estree(s))
+                .build(),
+            Expression::Member(expression) => s
+                .node("MemberExpression", expression.span)
+                .field("object", expression.object.to_estree(s))
+                .field("property", expression.property.to_estree(s))
+                .field(
+                    "computed",
+                    matches!(expression.property, MemberProperty::Computed(_)),
+                )
+                .field("optional", expression.optional)
+                .build(),
+            Expression::Call(expression) => s
+                .node("CallExpression", expression.span)
+                .field("callee", expression.callee.to_estree(s))
+                .field("arguments", expression.arguments.to_estree(s))
+                .field("optional", expression.optional)
+                .build(),
+            Expression::New(expression) => s
+                .node("NewExpression", expression.span)
+                .field("callee", expression.callee.to_estree(s))
+                .field("arguments", expression.arguments.to_estree(s))
+                .build(),
+            Expression::Chain(expression) => s
+                .node("ChainExpression", expression.span)
+                .field("expression", expression.expression.to_estree(s))
+                .build(),
+            Expression::Unary(expression) => s
+                .node("UnaryExpression", expression.span)
+                .field("operator", expression.operator.to_source_string())
+                .field("prefix", true)
+                .field("argument", expression.argument.to_estree(s))
+                .build(),
+            Expression::Update(expression) => s
+                .node("UpdateExpression", expression.span)
+                .field("operator", expression.operator.to_source_string())
+                .field("prefix", expression.prefix)
+                .field("argument", expression.argument.to_estree(s))
+                .build(),
+            Expression::Binary(expression) => s
+                .node("BinaryExpression", expression.span)
+                .field("left", expression.left.to_estree(s))
+                .field("operator", expression.operator.to_source_string())
+                .field("right", expression.right.to_estree(s))
+                .build(),
+            Expression::Logical(expression) => s
+                .node("LogicalExpression", expression.span)
+                .field("left", expression.left.to_estree(s))
+                .field("operator", expression.operator.to_source_string())
+                .field("right", expression.right.to_estree(s))
+                .build(),
+            Expression::Conditional(expression) => s
+                .node("ConditionalExpression", expression.span)
+                .field("test", expression.test.to_estree(s))
+                .field("consequent", expression.consequent.to_estree(s))
+                .field("alternate", expression.alternate.to_estree(s))
+                .build(),
+            Expression::Assignment(expression) => s
+                .node("AssignmentExpression", expression.span)
+                .field("operator", expression.operator.to_source_string())
+                .field("left", expression.left.to_estree(s))
+                .field("right", expression.right.to_estree(s))
+                .build(),
+            Expression::Sequence(expression) => s
+                .node("SequenceExpression", expression.span)
+                .field("expressions", expression.expressions.to_estree(s))
+                .build(),
+            Expression::Yield(expression) => s
+                .node("YieldExpression", expression.span)
+                .field("delegate", expression.delegate)
+                .field("argument", expression.argument.to_estree(s))
+                .build(),
+            Expression::Await(expression) => s
+                .node("AwaitExpression", expression.span)
+                .field("argument", expression.argument.to_estree(s))
+                .build(),
+            Expression::MetaProperty(expression) => s
+                .node("MetaProperty", expression.span)
+                .field("meta", expression.meta.to_estree(s))
+                .field("property", expression.property.to_estree(s))
+                .build(),
+            Expression::Import(expression) => s
+                .node("ImportExpression", expression.span)
+                .field("source", expression.source.to_estree(s))
+                .field("options", expression.options.to_estree(s))
+                .build(),
+            Expression::PrivateIdentifier(identifier) => identifier.to_estree(s),
+            Expression::Error(error) => s.node("ErrorNode", error.span).build(),
+        }
+    }
+}
+
+impl ToEstree for ExpressionOrSpread {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        match self {
+            ExpressionOrSpread::Expression(expression) => expression.to_estree(serializer),
+            ExpressionOrSpread::Spread(spread) => spread.to_estree(serializer),
+        }
+    }
+}
+
+impl ToEstree for SpreadElement {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        serializer
+            .node("SpreadElement", self.span)
+            .field("argument", self.argument.to_estree(serializer))
+            .build()
+    }
+}
+
+impl ToEstree for ObjectProperty {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        match self {
+            ObjectProperty::Property(property) => property.to_estree(serializer),
+            ObjectProperty::Spread(spread) => spread.to_estree(serializer),
+        }
+    }
+}
+
+impl ToEstree for Property {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        let kind = match self.kind {
+            PropertyKind::Init => "init",
+            PropertyKind::Get => "get",
+            PropertyKind::Set => "set",
+        };
+        let node = serializer
+            .node("Property", self.span)
+            .field("method", self.method)
+            .field("shorthand", self.shorthand);
+        let node = serializer.property_key(node, &self.key);
+
+        // acorn は `a: b` の場合だけ値の後に kind を置く
+        let value = self.value.to_estree(serializer);
+        if self.kind == PropertyKind::Init && !self.method && !self.shorthand {
+            node.field("value", value).field("kind", kind).build()
+        } else {
+            node.field("kind", kind).field("value", value).build()
+        }
+    }
+}
+
+impl ToEstree for PropertyKey {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        match self {
+            PropertyKey::Identifier(identifier) => identifier.to_estree(serializer),
+            PropertyKey::Literal(literal) => literal.to_estree(serializer),
+            PropertyKey::PrivateIdentifier(identifier) => identifier.to_estree(serializer),
+            PropertyKey::Computed(expression) => expression.to_estree(serializer),
+        }
+    }
+}
+
+impl ToEstree for MemberProperty {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        match self {
+            MemberProperty::Identifier(identifier) => identifier.to_estree(serializer),
+            MemberProperty::PrivateIdentifier(identifier) => identifier.to_estree(serializer),
+            MemberProperty::Computed(expression) => expression.to_estree(serializer),
+        }
+    }
+}
+
+impl ToEstree for ClassBody {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        serializer
+            .node("ClassBody", self.span)
+            .field("body", self.body.to_estree(serializer))
+            .build()
+    }
+}
+
+impl ToEstree for ClassElement {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        let s = serializer;
+        match self {
+            ClassElement::Method(method) => {
+                let kind = match method.kind {
+                    MethodKind::Constructor => "constructor",
+                    MethodKind::Method => "method",
+                    MethodKind::Get => "get",
+                    MethodKind::Set => "set",
+                };
+                let node = s
+                    .node("MethodDefinition", method.span)
+                    .field("static", method.is_static);
+                s.property_key(node, &method.key)
+                    .field("kind", kind)
+                    .field("value", s.function(&method.value, "FunctionExpression"))
+                    .build()
+            }
+            ClassElement::Property(property) => {
+                let node = s
+                    .node("PropertyDefinition", property.span)
+                    .field("static", property.is_static);
+                s.property_key(node, &property.key)
+                    .field("value", property.value.to_estree(s))
+                    .build()
+            }
+            ClassElement::StaticBlock(block) => s
+                .node("StaticBlock", block.span)
+                .field("body", block.body.to_estree(s))
+                .build(),
+        }
+    }
+}
+
+impl ToEstree for Pattern {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        let s = serializer;
+        match self {
+            Pattern::Identifier(identifier) => identifier.to_estree(s),
+            Pattern::Object(pattern) => s
+                .node("ObjectPattern", pattern.span)
+                .field("properties", pattern.properties.to_estree(s))
+                .build(),
+            Pattern::Array(pattern) => s
+                .node("ArrayPattern", pattern.span)
+                .field("elements", pattern.elements.to_estree(s))
+                .build(),
+            Pattern::Rest(rest) => rest.to_estree(s),
+            Pattern::Assignment(pattern) => s
+                .node("AssignmentPattern", pattern.span)
+                .field("left", pattern.left.to_estree(s))
+                .field("right", pattern.right.to_estree(s))
+                .build(),
+            Pattern::Expression(expression) => expression.to_estree(s),
+        }
+    }
+}
+
+impl ToEstree for ObjectPatternProperty {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        match self {
+            ObjectPatternProperty::Property(property) => {
+                let node = serializer
+                    .node("Property", property.span)
+                    .field("method", false)
+                    .field("shorthand", property.shorthand);
+                let node = serializer.property_key(node, &property.key);
+                let value = property.value.to_estree(serializer);
+                if property.shorthand {
+                    node.field("kind", "init").field("value", value).build()
+                } else {
+                    node.field("value", value).field("kind", "init").build()
+                }
+            }
+            ObjectPatternProperty::Rest(rest) => rest.to_estree(serializer),
+        }
+    }
+}
+
+impl ToEstree for RestElement {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        serializer
+            .node("RestElement", self.span)
+            .field("argument", self.argument.to_estree(serializer))
+            .build()
+    }
+}
+
+impl ToEstree for TemplateLiteral {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        serializer
+            .node("TemplateLiteral", self.span)
+            .field("expressions", self.expressions.to_estree(serializer))
+            .field("quasis", self.quasis.to_estree(serializer))
+            .build()
+    }
+}
+
+impl ToEstree for TemplateElement {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        let cooked = match &self.cooked {
+            Some(cooked) => cooked.as_str().into(),
+            None => JsonValue::Null,
+        };
+        let value = Node(Vec::new())
+            .field("raw", self.raw.as_str())
+            .field("cooked", cooked)
+            .build();
+
+        serializer
+            .node("TemplateElement", self.span)
+            .field("value", value)
+            .field("tail", self.tail)
+            .build()
+    }
+}
+
+impl ToEstree for Identifier {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        serializer
+            .node("Identifier", self.span)
+            .field("name", self.name.as_str())
+            .build()
+    }
+}
+
+impl ToEstree for PrivateIdentifier {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        serializer
+            .node("PrivateIdentifier", self.span)
+            .field("name", self.name.as_str())
+            .build()
+    }
+}
+
+impl ToEstree for Literal {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        // 正規表現と BigInt と無限大の値は JSON で表せないため null にする
+        let value = match &self.value {
+            LiteralValue::Boolean(value) => (*value).into(),
+            LiteralValue::Number(value) if value.is_finite() => (*value).into(),
+            LiteralValue::String(value) => value.as_str().into(),
+            _ => JsonValue::Null,
+        };
+        let node = serializer
+            .node("Literal", self.span)
+            .field("value", value)
+            .field("raw", self.raw.as_str());
+
+        match &self.value {
+            LiteralValue::RegExp(regexp) => {
+                let regex = Node(Vec::new())
+                    .field("pattern", regexp.pattern.as_str())
+                    .field("flags", regexp.flags.as_str())
+                    .build();
+                node.field("regex", regex).build()
+            }
+            // acorn と同じく、ソースコード上の表記から `n` と `_` を除く
+            LiteralValue::BigInt(_) => {
+                let digits = self.raw.trim_end_matches('n').replace('_', "");
+                node.field("bigint", digits).build()
+            }
+            _ => node.build(),
+        }
+    }
+}
+
+impl ToEstree for ModuleDeclaration {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        let s = serializer;
+        match self {
+            ModuleDeclaration::Import(declaration) => s
+                .node("ImportDeclaration", declaration.span)
+                .field("specifiers", declaration.specifiers.to_estree(s))
+                .field("source", declaration.source.to_estree(s))
+                .field("attributes", declaration.attributes.to_estree(s))
+                .build(),
+            ModuleDeclaration::ExportNamed(declaration) => {
+                let node = s
+                    .node("ExportNamedDeclaration", declaration.span)
+                    .field("declaration", declaration.declaration.to_estree(s))
+                    .field("specifiers", declaration.specifiers.to_estree(s))
+                    .field("source", declaration.source.to_estree(s));
+
+                // acorn は `from` がある場合だけ attributes を持つ
+                if declaration.source.is_some() {
+                    node.field("attributes", declaration.attributes.to_estree(s))
+                        .build()
+                } else {
+                    node.build()
+                }
+            }
+            ModuleDeclaration::ExportDefault(declaration) => {
+                let value = match &declaration.declaration {
+                    ExportDefaultKind::Function(function) => {
+                        s.function(function, "FunctionDeclaration")
+                    }
+                    ExportDefaultKind::Class(class) => s.class(class, "ClassDeclaration"),
+                    ExportDefaultKind::Expression(expression) => expression.to_estree(s),
+                };
+                s.node("ExportDefaultDeclaration", declaration.span)
+                    .field("declaration", value)
+                    .build()
+            }
+            ModuleDeclaration::ExportAll(declaration) => s
+                .node("ExportAllDeclaration", declaration.span)
+                .field("exported", declaration.exported.to_estree(s))
+                .field("source", declaration.source.to_estree(s))
+                .field("attributes", declaration.attributes.to_estree(s))
+                .build(),
+        }
+    }
+}
+
+impl ToEstree for ImportDeclarationSpecifier {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        let s = serializer;
+        match self {
+            ImportDeclarationSpecifier::Named(specifier) => s
+                .node("ImportSpecifier", specifier.span)
+                .field("imported", specifier.imported.to_estree(s))
+                .field("local", specifier.local.to_estree(s))
+                .build(),
+            ImportDeclarationSpecifier::Default(specifier) => s
+                .node("ImportDefaultSpecifier", specifier.span)
+                .field("local", specifier.local.to_estree(s))
+                .build(),
+            ImportDeclarationSpecifier::Namespace(specifier) => s
+                .node("ImportNamespaceSpecifier", specifier.span)
+                .field("local", specifier.local.to_estree(s))
+                .build(),
+        }
+    }
+}
+
+impl ToEstree for ImportAttribute {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        serializer
+            .node("ImportAttribute", self.span)
+            .field("key", self.key.to_estree(serializer))
+            .field("value", self.value.to_estree(serializer))
+            .build()
+    }
+}
+
+impl ToEstree for ModuleExportName {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        match self {
+            ModuleExportName::Identifier(identifier) => identifier.to_estree(serializer),
+            ModuleExportName::Literal(literal) => literal.to_estree(serializer),
+        }
+    }
+}
+
+impl ToEstree for Declaration {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        match self {
+            Declaration::Function(function) => serializer.function(function, "FunctionDeclaration"),
+            Declaration::Variable(declaration) => declaration.to_estree(serializer),
+            Declaration::Class(class) => serializer.class(class, "ClassDeclaration"),
+        }
+    }
+}
+
+impl ToEstree for ExportSpecifier {
+    fn to_estree(&self, serializer: &Serializer) -> JsonValue {
+        serializer
+            .node("ExportSpecifier", self.span)
+            .field("local", self.local.to_estree(serializer))
+            .field("exported", self.exported.to_estree(serializer))
+            .build()
+    }
+}
@@ -0,0 +1,8 @@
+'use strict';
+var a = 1, b;
+function f(c, d = 2) {
+  return c;
+}
+class E extends F {
+  static g() {}
+}
@@ -0,0 +1,2 @@
+x = { y, z: [1, , ...w], get v() {} };
+a?.b(c) + -d * `e${f}`;
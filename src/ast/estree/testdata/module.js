@@ -0,0 +1,3 @@
+import a, { b as c } from "d";
+export default a;
+export * as e from "f";
@@ -0,0 +1,113 @@
+//! バイト位置と、行・列や UTF-16 の位置を変換するモジュールです。
+//!
+//! 構文木の [`Span`](super::structs::span::Span) はバイト位置で持つ。
+//! JavaScript のツールは位置を UTF-16 のコード単位で数えるため、出力する時に変換する。
+
+use crate::character_patterns;
+
+/// 行と列 (行は1から、列は UTF-16 のコード単位で0から数える)
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct LineColumn {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// ソースコードの行の始まりと、バイト位置ごとの UTF-16 の位置の表
+#[derive(Debug, Clone)]
+pub struct LineIndex {
+    /// 各行の始まりのバイト位置
+    line_starts: Vec<usize>,
+
+    /// バイト位置ごとの UTF-16 の位置 (文字の途中のバイトは文字の始まりと同じ値)
+    utf16: Vec<usize>,
+}
+
+impl LineIndex {
+    pub fn new(source: &str) -> LineIndex {
+        let mut line_starts = vec![0];
+        let mut utf16 = Vec::with_capacity(source.len() + 1);
+        let mut units = 0;
+        let mut chars = source.char_indices().peekable();
+
+        while let Some((index, char)) = chars.next() {
+            utf16.extend(std::iter::repeat_n(units, char.len_utf8()));
+            units += char.len_utf16();
+
+            if let character_patterns!(LineTerminator) = char {
+                // <CR><LF> は1つの改行
+                if char == '\r' && matches!(chars.peek(), Some((_, '\n'))) {
+                    continue;
+                }
+                line_starts.push(index + char.len_utf8());
+            }
+        }
+        utf16.push(units);
+
+        LineIndex { line_starts, utf16 }
+    }
+
+    /// バイト位置を UTF-16 の位置に変換する。
+    pub fn utf16_offset(&self, offset: usize) -> usize {
+        self.utf16[offset.min(self.utf16.len() - 1)]
+    }
+
+    /// UTF-16 の位置をバイト位置に変換する。
+    ///
+    /// サロゲートペアの途中を指す場合は次の文字の始まりを返す。
+    pub fn byte_offset(&self, utf16_offset: usize) -> usize {
+        self.utf16.partition_point(|&units| units < utf16_offset)
+    }
+
+    /// バイト位置の行と列を返す。
+    pub fn line_column(&self, offset: usize) -> LineColumn {
+        let line = self.line_starts.partition_point(|&start| start <= offset);
+        let line_start = self.line_starts[line - 1];
+
+        LineColumn {
+            line,
+            column: self.utf16_offset(offset) - self.utf16_offset(line_start),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{LineColumn, LineIndex};
+
+    fn line_column(line: usize, column: usize) -> LineColumn {
+        LineColumn { line, column }
+    }
+
+    #[test]
+    fn ascii() {
+        let index = LineIndex::new("ab\ncd");
+        assert_eq!(index.utf16_offset(4), 4);
+        assert_eq!(index.byte_offset(4), 4);
+        assert_eq!(index.line_column(0), line_column(1, 0));
+        assert_eq!(index.line_column(2), line_column(1, 2));
+        assert_eq!(index.line_column(3), line_column(2, 0));
+        assert_eq!(index.line_column(5), line_column(2, 2));
+    }
+
+    #[test]
+    fn line_terminators() {
+        let index = LineIndex::new("a\r\nb\rc\u{2028}d");
+        assert_eq!(index.line_column(3), line_column(2, 0));
+        assert_eq!(index.line_column(5), line_column(3, 0));
+        assert_eq!(index.line_column(9), line_column(4, 0));
+    }
+
+    #[test]
+    fn multibyte() {
+        // "あ" は3バイトで1単位、"😀" は4バイトで2単位
+        let source = "あ😀b";
+        let index = LineIndex::new(source);
+        assert_eq!(index.utf16_offset(3), 1);
+        assert_eq!(index.utf16_offset(7), 3);
+        assert_eq!(index.utf16_offset(source.len()), 4);
+        assert_eq!(index.byte_offset(1), 3);
+        assert_eq!(index.byte_offset(3), 7);
+        assert_eq!(index.byte_offset(4), 8);
+        assert_eq!(index.line_column(7), line_column(1, 3));
+    }
+}
@@ -0,0 +1,534 @@
+//! トークン列から構文木を作るモジュールです。
+
+use std::collections::HashSet;
+
+use crate::token::structs::{
+    comment::Comment,
+    context::{BraceContext, DivContext},
+    literal::Literal as LiteralToken,
+    punctuator::Punctuator,
+    token::{Token, TokenType},
+};
+
+use super::{
+    error::{ParseError, ParseResult, ParseWarning},
+    reader_struct::SourceCodeReader,
+    script::ParseOptions,
+    structs::{
+        literal::Identifier,
+        module::ModuleItem,
+        span::{Span, Spanned},
+        statement::Statement,
+        JsAst, SourceType,
+    },
+};
+use yield_await::YieldAwait;
+
+mod arrow;
+mod asi;
+mod class;
+mod cover;
+mod directive;
+mod expression;
+mod fragment;
+mod function;
+pub(crate) mod literal;
+mod module;
+mod pattern;
+mod recovery;
+mod statement;
+mod yield_await;
+
+#[cfg(test)]
+pub(crate) mod test_helper;
+
+/// 構文の文脈
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Context {
+    /// `in` 演算子を使えるか (for 文の初期化式では使えない)
+    pub allow_in: bool,
+
+    /// strict モードであるか
+    pub strict: bool,
+
+    /// `return` 文を使えるか (関数の中でのみ使える)
+    pub allow_return: bool,
+
+    /// `await` 式を使えるか (モジュールの最上位でも使える)
+    pub allow_await: bool,
+
+    /// `yield` 式を使えるか (ジェネレーターの中でのみ使える)
+    pub allow_yield: bool,
+
+    /// クラスの static ブロックの中であるか (`await` を識別子として使えない)
+    pub in_static_block: bool,
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Context {
+            allow_in: true,
+            strict: false,
+            allow_return: false,
+            allow_await: false,
+            allow_yield: false,
+            in_static_block: false,
+        }
+    }
+}
+
+/// 全ての文脈で予約されている語
+const RESERVED_WORDS: &[&str] = &[
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "import",
+    "in",
+    "instanceof",
+    "new",
+    "null",
+    "return",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+];
+
+/// strict モードで予約されている語
+const STRICT_RESERVED_WORDS: &[&str] = &[
+    "implements",
+    "interface",
+    "let",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "static",
+    "yield",
+];
+
+pub(crate) struct Parser<'a> {
+    source: &'a str,
+    reader: SourceCodeReader<'a>,
+
+    /// 現在のトークン
+    token: Token,
+
+    /// 現在のトークンの前に改行があるか
+    has_newline_before: bool,
+
+    /// 直前のトークンの終了位置
+    prev_token_end: usize,
+
+    /// スクリプトかモジュールか
+    source_type: SourceType,
+
+    context: Context,
+
+    /// 括弧で囲まれた式の範囲
+    parenthesized: HashSet<Span>,
+
+    /// アロー関数が始まることができる位置 (AssignmentExpression の始まり)
+    arrow_start: Option<usize>,
+
+    /// パターンに変換されるまで保留している `{a = 1}` の範囲
+    cover_initialized_names: Vec<Span>,
+
+    /// 後ろにカンマが続く配列の `...a` の範囲 (パターンに変換できない)
+    trailing_comma_spreads: HashSet<Span>,
+
+    /// 関数の終わりまで保留している `yield` と `await` (引数リストに置けない)
+    yield_await: Vec<YieldAwait>,
+
+    options: ParseOptions,
+
+    /// 解析中に見つけた警告
+    warnings: Vec<ParseWarning>,
+
+    /// 解析中に見つけ、回復したエラー
+    errors: Vec<ParseError>,
+
+    /// 読み進めたトークン (空白、改行、コメントを除く)
+    tokens: Vec<Token>,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(reader: SourceCodeReader<'a>) -> ParseResult<Parser<'a>> {
+        Parser::with_options(reader, ParseOptions::default())
+    }
+
+    pub fn with_options(
+        reader: SourceCodeReader<'a>,
+        options: ParseOptions,
+    ) -> ParseResult<Parser<'a>> {
+        let source = reader.source();
+        let mut parser = Parser {
+            source,
+            reader,
+            token: Token::new(TokenType::EndOfFile, 0, 0),
+            has_newline_before: false,
+            prev_token_end: 0,
+            source_type: SourceType::Script,
+            context: Context::default(),
+            parenthesized: HashSet::new(),
+            arrow_start: None,
+            cover_initialized_names: Vec::new(),
+            trailing_comma_spreads: HashSet::new(),
+            yield_await: Vec::new(),
+            options,
+            warnings: Vec::new(),
+            errors: Vec::new(),
+            tokens: Vec::new(),
+        };
+
+        let (token, has_newline_before) = parser.read_token(DivContext::RegExp)?;
+        parser.token = token;
+        parser.has_newline_before = has_newline_before;
+
+        Ok(parser)
+    }
+
+    /// プログラム全体を解析する。
+    ///
+    /// モジュールは strict モードで、最上位で `await` を使える。
+    /// 構文エラーは [`Parser::take_errors`] で取り出す。
+    pub fn parse_program(&mut self, source_type: SourceType) -> JsAst {
+        self.source_type = source_type;
+        let mut body = Vec::new();
+
+        if source_type == SourceType::Module {
+            self.context.strict = true;
+            self.context.allow_await = true;
+
+            while !self.is_end() {
+                body.push(self.parse_or_recover(Self::parse_module_item, |node| {
+                    ModuleItem::Statement(Statement::Error(node))
+                }));
+            }
+        } else {
+            // 文のエラーは記録して回復するため、ここではエラーにならない
+            let statements = self
+                .parse_directives_and_statements(&[], |_| false)
+                .unwrap_or_default();
+            body.extend(statements.into_iter().map(ModuleItem::Statement));
+        }
+
+        JsAst {
+            span: Span::new(0, self.source.len()),
+            source_type,
+            body,
+        }
+    }
+
+    /// 解析中に見つけた警告を取り出す。
+    pub fn take_warnings(&mut self) -> Vec<ParseWarning> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    /// 読み進めたトークンを位置の順に取り出す。
+    pub fn take_tokens(&mut self) -> Vec<Token> {
+        std::mem::take(&mut self.tokens)
+    }
+
+    // トークンの読み進め
+
+    /// 空白、改行、コメントを飛ばして次のトークンを読む。
+    ///
+    /// 字句解析のエラーは記録し、読めない文字を読み飛ばして続ける。
+    fn read_token(&mut self, div: DivContext) -> ParseResult<(Token, bool)> {
+        loop {
+            match read_significant_token(&mut self.reader, div, BraceContext::Block) {
+                Ok(result) => return Ok(result),
+                Err(error) => {
+                    let position = error.span.start;
+                    let Some(char) = self.source[position..].chars().next() else {
+                        return Err(error);
+                    };
+
+                    // 読み直しで同じ文字を再び読んだ場合は記録しない
+                    if self.errors.last() != Some(&error) {
+                        self.errors.push(error);
+                    }
+                    self.reader.reset(position + char.len_utf8());
+                }
+            }
+        }
+    }
+
+    /// 次のトークンに進む。
+    fn advance(&mut self) -> ParseResult<()> {
+        if !self.is_end() {
+            self.tokens.push(self.token.clone());
+        }
+        self.prev_token_end = self.token.end_index();
+        let (token, has_newline_before) = self.read_token(DivContext::Divide)?;
+        self.token = token;
+        self.has_newline_before = has_newline_before;
+        Ok(())
+    }
+
+    /// 現在のトークンを別の文脈で読み直す。
+    fn rescan(&mut self, div: DivContext, brace: BraceContext) -> ParseResult<()> {
+        self.reader.reset(self.token.start_index());
+        self.token = self
+            .reader
+            .read_next_token(div, brace)
+            .map_err(|message| ParseError::new(self.token_span(), message))?;
+        Ok(())
+    }
+
+    /// 次のトークンを先読みする。
+    ///
+    /// トークンと、その前に改行があるかを返す。
+    fn lookahead(&self) -> ParseResult<(Token, bool)> {
+        let mut reader = self.reader.clone();
+        read_significant_token(&mut reader, DivContext::Divide, BraceContext::Block)
+    }
+
+    // 現在のトークンの情報
+
+    fn token_type(&self) -> TokenType {
+        self.token.token_type()
+    }
+
+    /// 現在のトークンの表記
+    fn text(&self) -> &'a str {
+        self.token.source_text(self.source)
+    }
+
+    fn token_span(&self) -> Span {
+        Span::new(self.token.start_index(), self.token.end_index())
+    }
+
+    /// 現在のトークンの開始位置
+    fn start(&self) -> usize {
+        self.token.start_index()
+    }
+
+    /// 開始位置から直前のトークンまでの範囲
+    fn span_from(&self, start: usize) -> Span {
+        Span::new(start, self.prev_token_end.max(start))
+    }
+
+    fn is_end(&self) -> bool {
+        self.token_type() == TokenType::EndOfFile
+    }
+
+    fn is(&self, punctuator: Punctuator) -> bool {
+        self.token_type() == TokenType::Punctuator(punctuator)
+    }
+
+    fn is_string_literal(&self) -> bool {
+        matches!(
+            self.token_type(),
+            TokenType::Literal(LiteralToken::SingleString | LiteralToken::DoubleString)
+        )
+    }
+
+    /// 現在のトークンが指定した予約語や文脈依存の語であるかを返す。
+    ///
+    /// エスケープを含む識別子は一致しない。
+    fn is_keyword(&self, keyword: &str) -> bool {
+        self.token_type() == TokenType::Identifier && self.text() == keyword
+    }
+
+    fn eat(&mut self, punctuator: Punctuator) -> ParseResult<bool> {
+        if self.is(punctuator) {
+            self.advance()?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn expect(&mut self, punctuator: Punctuator) -> ParseResult<()> {
+        if self.eat(punctuator)? {
+            Ok(())
+        } else {
+            self.unexpected()
+        }
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> ParseResult<bool> {
+        if self.is_keyword(keyword) {
+            self.advance()?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> ParseResult<()> {
+        if self.eat_keyword(keyword)? {
+            Ok(())
+        } else {
+            self.unexpected()
+        }
+    }
+
+    // エラー
+
+    fn error<T>(&self, span: Span, message: impl Into<String>) -> ParseResult<T> {
+        Err(ParseError::new(span, message))
+    }
+
+    fn unexpected<T>(&self) -> ParseResult<T> {
+        if self.is_end() {
+            self.error(self.token_span(), "unexpected end of input")
+        } else {
+            self.error(
+                self.token_span(),
+                format!("unexpected token '{}'", self.text()),
+            )
+        }
+    }
+
+    // 文脈
+
+    /// 文脈を変更して解析する。
+    fn with_context<T>(
+        &mut self,
+        context: Context,
+        parse: impl FnOnce(&mut Self) -> ParseResult<T>,
+    ) -> ParseResult<T> {
+        let saved = self.context;
+        self.context = context;
+        let result = parse(self);
+        self.context = saved;
+        result
+    }
+
+    /// `in` 演算子を使えるかを変更して解析する。
+    fn with_allow_in<T>(
+        &mut self,
+        allow_in: bool,
+        parse: impl FnOnce(&mut Self) -> ParseResult<T>,
+    ) -> ParseResult<T> {
+        let context = Context {
+            allow_in,
+            ..self.context
+        };
+        self.with_context(context, parse)
+    }
+
+    fn is_module(&self) -> bool {
+        self.source_type == SourceType::Module
+    }
+
+    /// 式が括弧で囲まれていたかを返す。
+    fn is_parenthesized(&self, node: &impl Spanned) -> bool {
+        self.parenthesized.contains(&node.span())
+    }
+
+    // 識別子
+
+    /// 予約語を含む識別子名 (プロパティ名など)
+    fn parse_identifier_name(&mut self) -> ParseResult<Identifier> {
+        if self.token_type() != TokenType::Identifier {
+            return self.unexpected();
+        }
+
+        let span = self.token_span();
+        let name = literal::identifier_value(self.text())
+            .map_err(|message| ParseError::new(span, message))?;
+        self.advance()?;
+
+        Ok(Identifier { span, name })
+    }
+
+    /// 変数の参照
+    fn parse_identifier_reference(&mut self) -> ParseResult<Identifier> {
+        let has_escape = self.text().contains('\\');
+        let identifier = self.parse_identifier_name()?;
+        self.check_reserved_word(&identifier, has_escape)?;
+        Ok(identifier)
+    }
+
+    /// 予約語を識別子として使っていないかを検査する。
+    ///
+    /// 識別子としての `await` は async アロー関数の引数の検査のために記録する。
+    fn check_reserved_word(
+        &mut self,
+        identifier: &Identifier,
+        has_escape: bool,
+    ) -> ParseResult<()> {
+        let name = identifier.name.as_str();
+
+        let is_reserved = RESERVED_WORDS.contains(&name)
+            || (self.context.strict && STRICT_RESERVED_WORDS.contains(&name))
+            || (name == "await"
+                && (self.is_module() || self.context.allow_await || self.context.in_static_block))
+            || (name == "yield" && self.context.allow_yield);
+
+        if is_reserved {
+            let message = if has_escape {
+                format!("keyword '{name}' must not contain escaped characters")
+            } else {
+                format!("unexpected keyword '{name}'")
+            };
+            return self.error(identifier.span, message);
+        }
+
+        if name == "await" {
+            self.yield_await
+                .push(YieldAwait::AwaitIdentifier(identifier.span));
+        }
+        Ok(())
+    }
+}
+
+/// 空白、改行、コメントを飛ばしてトークンを読む。
+fn read_significant_token(
+    reader: &mut SourceCodeReader,
+    div: DivContext,
+    brace: BraceContext,
+) -> ParseResult<(Token, bool)> {
+    let mut has_newline_before = false;
+
+    loop {
+        let position = reader.position();
+        let token = reader
+            .read_next_token(div, brace)
+            .map_err(|message| ParseError::new(Span::new(position, position), message))?;
+
+        match token.token_type() {
+            TokenType::WhiteSpace | TokenType::Comment(Comment::SingleLineComment) => {}
+            TokenType::Comment(Comment::HashBangComment) => {}
+
+            TokenType::LineTerminator => has_newline_before = true,
+
+            // 改行を含む複数行コメントは改行とみなす
+            TokenType::Comment(Comment::MultiLineComment) => {
+                let text = token.source_text(reader.source());
+                if text.contains(['\u{000A}', '\u{000D}', '\u{2028}', '\u{2029}']) {
+                    has_newline_before = true;
+                }
+            }
+
+            _ => return Ok((token, has_newline_before)),
+        }
+    }
+}
@@ -0,0 +1,359 @@
+//! アロー関数と、その引数リストを兼ねる括弧式を読むモジュールです。
+
+use crate::token::structs::{punctuator::Punctuator, token::TokenType};
+
+use super::{cover::PatternKind, Context, Parser};
+use crate::ast::{
+    error::ParseResult,
+    structs::{
+        expression::{
+            AssignmentOperator, CallExpression, Expression, ExpressionOrSpread, SequenceExpression,
+        },
+        function::{ArrowFunctionBody, ArrowFunctionExpression},
+        pattern::{AssignmentPattern, Pattern, RestElement},
+        span::Spanned,
+    },
+};
+
+impl<'a> Parser<'a> {
+    /// 括弧で囲まれていないアロー関数であるかを返す。
+    ///
+    /// アロー関数は AssignmentExpression であるため、後ろに演算子や呼び出しを続けられない。
+    pub(crate) fn is_bare_arrow_function(&self, expression: &Expression) -> bool {
+        matches!(expression, Expression::ArrowFunction(_)) && !self.is_parenthesized(expression)
+    }
+
+    /// `a => b` と `async a => b` を読む。
+    ///
+    /// アロー関数でない場合は `None` を返す。
+    pub(crate) fn try_parse_identifier_arrow_function(
+        &mut self,
+    ) -> ParseResult<Option<Expression>> {
+        if self.token_type() != TokenType::Identifier {
+            return Ok(None);
+        }
+
+        let start = self.start();
+        let (next, has_newline) = self.lookahead()?;
+
+        // a => b
+        if next.token_type() == TokenType::Punctuator(Punctuator::Arrow) && !has_newline {
+            let param = Pattern::Identifier(self.parse_binding_identifier()?);
+            return Ok(Some(self.parse_arrow_function_rest(
+                start,
+                vec![param],
+                false,
+            )?));
+        }
+
+        // async a => b
+        let is_async_arrow = self.is_keyword("async")
+            && !has_newline
+            && next.token_type() == TokenType::Identifier
+            && next.source_text(self.source) != "function";
+
+        if is_async_arrow {
+            self.advance()?;
+            let param = Pattern::Identifier(self.parse_binding_identifier()?);
+            if !self.is(Punctuator::Arrow) {
+                return self.unexpected();
+            }
+            return Ok(Some(self.parse_arrow_function_rest(
+                start,
+                vec![param],
+                true,
+            )?));
+        }
+
+        Ok(None)
+    }
+
+    /// `(a, b)` を括弧式か、アロー関数の引数リストとして読む。
+    ///
+    /// CoverParenthesizedExpressionAndArrowParameterList
+    pub(crate) fn parse_parenthesized_or_arrow_function(&mut self) -> ParseResult<Expression> {
+        let start = self.start();
+        let is_arrow_position = self.arrow_start == Some(start);
+        self.expect(Punctuator::LeftParen)?;
+
+        let (items, rest, trailing_comma) = self.with_allow_in(true, |parser| {
+            let mut items = Vec::new();
+            let mut rest = None;
+            let mut trailing_comma = None;
+
+            while !parser.is(Punctuator::RightParen) {
+                // (a, ...b) => c
+                if parser.is(Punctuator::DotThree) {
+                    rest = Some(parser.parse_binding_rest_element()?);
+                    break;
+                }
+
+                items.push(parser.parse_assignment_expression_cover()?);
+
+                if !parser.is(Punctuator::RightParen) {
+                    let comma = parser.token_span();
+                    if !parser.eat(Punctuator::Comma)? {
+                        break;
+                    }
+                    if parser.is(Punctuator::RightParen) {
+                        trailing_comma = Some(comma);
+                    }
+                }
+            }
+
+            Ok((items, rest, trailing_comma))
+        })?;
+        self.expect_closing(Punctuator::RightParen)?;
+
+        if is_arrow_position && self.is(Punctuator::Arrow) {
+            let mut params = Vec::new();
+            for item in items {
+                params.push(self.expression_to_parameter(item)?);
+            }
+            if let Some(rest) = rest {
+                params.push(Pattern::Rest(Box::new(rest)));
+            }
+            return self.parse_arrow_function_rest(start, params, false);
+        }
+
+        // 括弧式では空の括弧、残余要素、末尾のカンマは使えない
+        if let Some(rest) = rest {
+            return self.error(
+                rest.span,
+                "rest element is only allowed in arrow parameters",
+            );
+        }
+        if let Some(comma) = trailing_comma {
+            return self.error(comma, "trailing comma is only allowed in arrow parameters");
+        }
+
+        let expression = match items.len() {
+            0 => return self.error(self.span_from(start), "empty parentheses"),
+            1 => items.into_iter().next().unwrap_or_else(|| unreachable!()),
+            _ => {
+                let span = items[0].span().merge(items[items.len() - 1].span());
+                Expression::Sequence(SequenceExpression {
+                    span,
+                    expressions: items,
+                })
+            }
+        };
+        self.check_cover_initialized_names(start)?;
+
+        self.parenthesized.insert(expression.span());
+        Ok(expression)
+    }
+
+    /// `async(a)` を呼び出しか、async アロー関数として読む。
+    pub(crate) fn parse_async_call_or_arrow_function(&mut self) -> ParseResult<Expression> {
+        let start = self.start();
+        let is_arrow_position = self.arrow_start == Some(start);
+        let callee = Expression::Identifier(self.parse_identifier_reference()?);
+        self.expect(Punctuator::LeftParen)?;
+
+        let (arguments, trailing_comma) = self.with_allow_in(true, |parser| {
+            let mut arguments = Vec::new();
+            let mut trailing_comma = None;
+
+            while !parser.is(Punctuator::RightParen) {
+                arguments.push(if parser.is(Punctuator::DotThree) {
+                    ExpressionOrSpread::Spread(parser.parse_spread_element_cover()?)
+                } else {
+                    ExpressionOrSpread::Expression(parser.parse_assignment_expression_cover()?)
+                });
+
+                if !parser.is(Punctuator::RightParen) {
+                    let comma = parser.token_span();
+                    if !parser.eat(Punctuator::Comma)? {
+                        break;
+                    }
+                    if parser.is(Punctuator::RightParen) {
+                        trailing_comma = Some(comma);
+                    }
+                }
+            }
+
+            Ok((arguments, trailing_comma))
+        })?;
+        self.expect_closing(Punctuator::RightParen)?;
+
+        if is_arrow_position && self.is(Punctuator::Arrow) && !self.has_line_terminator_here() {
+            let mut params = Vec::new();
+            let count = arguments.len();
+
+            for (index, argument) in arguments.into_iter().enumerate() {
+                match argument {
+                    ExpressionOrSpread::Expression(expression) => {
+                        params.push(self.expression_to_parameter(expression)?);
+                    }
+                    ExpressionOrSpread::Spread(spread) => {
+                        if index + 1 != count || trailing_comma.is_some() {
+                            return self.error(
+                                spread.span,
+                                "rest parameter must be last formal parameter",
+                            );
+                        }
+                        let argument =
+                            self.expression_to_pattern(spread.argument, PatternKind::Binding)?;
+                        params.push(Pattern::Rest(Box::new(RestElement {
+                            span: spread.span,
+                            argument,
+                        })));
+                    }
+                }
+            }
+
+            return self.parse_arrow_function_rest(start, params, true);
+        }
+
+        self.check_cover_initialized_names(start)?;
+
+        Ok(Expression::Call(Box::new(CallExpression {
+            span: self.span_from(start),
+            callee,
+            arguments,
+            optional: false,
+        })))
+    }
+
+    /// アロー関数の引数を式から変換する。
+    fn expression_to_parameter(&mut self, expression: Expression) -> ParseResult<Pattern> {
+        if self.is_parenthesized(&expression) {
+            return self.error(expression.span(), "invalid arrow function parameter");
+        }
+
+        match expression {
+            Expression::Assignment(assignment) => {
+                if assignment.operator != AssignmentOperator::Assign {
+                    return self.error(assignment.span, "invalid arrow function parameter");
+                }
+                let left = self.pattern_to_binding(assignment.left)?;
+                self.cover_initialized_names
+                    .retain(|name| !assignment.span.contains(name.start));
+
+                Ok(Pattern::Assignment(Box::new(AssignmentPattern {
+                    span: assignment.span,
+                    left,
+                    right: assignment.right,
+                })))
+            }
+            expression => self.expression_to_pattern(expression, PatternKind::Binding),
+        }
+    }
+
+    /// `=>` からアロー関数の終わりまでを読む。
+    pub(crate) fn parse_arrow_function_rest(
+        &mut self,
+        start: usize,
+        params: Vec<Pattern>,
+        is_async: bool,
+    ) -> ParseResult<Expression> {
+        // `=>` の前に改行を置くことはできない
+        if self.has_line_terminator_here() {
+            return self.error(self.token_span(), "line terminator before '=>'");
+        }
+        self.check_parameters_yield_await(start, is_async)?;
+        self.expect(Punctuator::Arrow)?;
+
+        let context = Context {
+            allow_await: is_async,
+            allow_yield: false,
+            ..self.context
+        };
+
+        let (body, strict) = self.with_context(context, |parser| {
+            if parser.is(Punctuator::LeftBrace) {
+                let (body, strict) = parser.parse_function_body(&params)?;
+                Ok((ArrowFunctionBody::FunctionBody(body), strict))
+            } else {
+                let body = parser.parse_assignment_expression()?;
+                Ok((ArrowFunctionBody::Expression(body), parser.context.strict))
+            }
+        })?;
+        self.discard_yield_await(start);
+
+        Ok(Expression::ArrowFunction(Box::new(
+            ArrowFunctionExpression {
+                span: self.span_from(start),
+                params,
+                body,
+                is_async,
+                strict,
+            },
+        )))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test_helper::{parse_expression_error, parse_expression_sexp};
+
+    macro_rules! test_case {
+        ($test_name:ident, $source:expr, $expected:expr) => {
+            #[test]
+            fn $test_name() {
+                assert_eq!(parse_expression_sexp($source), Ok($expected.to_string()));
+            }
+        };
+    }
+
+    macro_rules! test_case_fail {
+        ($test_name:ident, $source:expr) => {
+            #[test]
+            fn $test_name() {
+                assert!(parse_expression_error($source).is_some());
+            }
+        };
+    }
+
+    test_case!(identifier_param, "a => b", "(=> a b)");
+    test_case!(no_params, "() => {}", "(=> (block))");
+    test_case!(
+        block_body,
+        "(a) => { return a }",
+        "(=> a (block (return a)))"
+    );
+    test_case!(
+        cover_params,
+        "(a, {b = 1}, ...c) => x",
+        "(=> a (object-pattern (: b (= b 1))) (... c) x)"
+    );
+    test_case!(
+        default_param,
+        "(a = 1, [b]) => b",
+        "(=> (= a 1) (array-pattern b) b)"
+    );
+    test_case!(trailing_comma, "(a, b,) => a", "(=> a b a)");
+    test_case!(nested, "a => b => c", "(=> a (=> b c))");
+    test_case!(arrow_in_arguments, "f(a => b, c)", "(call f (=> a b) c)");
+    test_case!(
+        arrow_in_conditional,
+        "a ? b => c : d => e",
+        "(? a (=> b c) (=> d e))"
+    );
+    test_case!(arrow_assign, "f = () => 1", "(= f (=> 1))");
+    test_case!(parenthesized_arrow_call, "(() => 1)()", "(call (=> 1))");
+    test_case!(async_arrow, "async (x) => x", "(async=> x x)");
+    test_case!(async_identifier_arrow, "async x => x", "(async=> x x)");
+    test_case!(async_call, "async(x)", "(call async x)");
+    test_case!(async_call_spread, "async(...x)", "(call async (... x))");
+    test_case!(async_as_param, "async => async", "(=> async async)");
+    test_case!(sequence, "(a, b)", "(, a b)");
+
+    test_case_fail!(parenthesized_param, "((a)) => 1");
+    test_case_fail!(rest_trailing_comma, "(a, ...b,) => 1");
+    test_case_fail!(rest_not_last, "(...a, b) => 1");
+    test_case_fail!(rest_default, "(...a = 1) => 1");
+    test_case_fail!(newline_before_arrow, "(a)\n=> b");
+    test_case_fail!(newline_before_identifier_arrow, "a\n=> b");
+    test_case_fail!(arrow_after_operator, "x + (a) => b");
+    test_case_fail!(call_bare_arrow, "() => {}()");
+    test_case_fail!(operator_after_arrow, "() => {} + 1");
+    test_case_fail!(member_param, "(a.b) => 1");
+    test_case_fail!(literal_param, "(1) => 1");
+    test_case_fail!(empty_parentheses, "()");
+    test_case_fail!(rest_in_parentheses, "(...a)");
+    test_case_fail!(trailing_comma_in_parentheses, "(a,)");
+    test_case_fail!(async_rest_not_last, "async (...a, b) => 1");
+    test_case_fail!(async_newline_before_arrow, "async (a)\n=> b");
+}
@@ -0,0 +1,187 @@
+//! 自動セミコロン挿入 (ECMA-262 §12.10) を扱うモジュールです。
+//!
+//! 1. 文法上許されないトークンの前に改行がある場合、そのトークンが `}` の場合、
+//!    do-while 文の `)` の後の場合は `;` を補う。
+//! 2. ソースコードの終わりでは `;` を補う。
+//! 3. 「ここに改行を置かない」とされる位置に改行がある場合は、そこで文を終える。
+//!
+//! ただし、補った `;` が空文や for 文の括弧の中の `;` になる場合は補わない。
+
+use crate::token::structs::{punctuator::Punctuator, template::Template, token::TokenType};
+
+use super::Parser;
+use crate::ast::error::{ParseResult, ParseWarning};
+
+impl<'a> Parser<'a> {
+    /// 文の終わりの `;` を読む。
+    ///
+    /// 改行の後、`}` の前、ソースコードの終わりでは省略できる。
+    pub(crate) fn consume_semicolon(&mut self) -> ParseResult<()> {
+        if self.eat(Punctuator::SemiColon)? {
+            return Ok(());
+        }
+
+        if self.has_newline_before || self.is(Punctuator::RightBrace) || self.is_end() {
+            return Ok(());
+        }
+
+        self.unexpected()
+    }
+
+    /// 「ここに改行を置かない」位置で改行があるかを返す。
+    ///
+    /// 後置の `++` `--`、`return` `break` `continue` `yield` の後ろ、
+    /// `=>` の前、`async` の後ろで使う。
+    pub(crate) fn has_line_terminator_here(&self) -> bool {
+        self.has_newline_before
+    }
+
+    /// 文を終える位置であるか (restricted production の後ろで値を読むか) を返す。
+    pub(crate) fn is_statement_end(&self) -> bool {
+        self.is(Punctuator::SemiColon)
+            || self.is(Punctuator::RightBrace)
+            || self.is_end()
+            || self.has_line_terminator_here()
+    }
+
+    /// 行頭の `(` `[` テンプレートが前の行の式に続く場合に警告する。
+    ///
+    /// これらの前には `;` が補われないため、前の行の式の呼び出しやメンバー参照になる。
+    pub(crate) fn warn_continued_line(&mut self) {
+        if !self.options.asi_hazard_warnings || !self.has_newline_before {
+            return;
+        }
+
+        let token = match self.token_type() {
+            TokenType::Punctuator(Punctuator::LeftParen) => "(",
+            TokenType::Punctuator(Punctuator::LeftBracket) => "[",
+            TokenType::Template(Template::NoSubstitution | Template::Head) => "`",
+            _ => return,
+        };
+
+        let message = format!(
+            "line starting with '{token}' continues the previous expression; \
+             no semicolon is inserted"
+        );
+        self.warnings
+            .push(ParseWarning::new(self.token_span(), message));
+    }
+
+    /// `return` の後ろの改行で値が切り離される場合に警告する。
+    pub(crate) fn warn_return_newline(&mut self) {
+        if !self.options.asi_hazard_warnings || !self.has_newline_before {
+            return;
+        }
+        if self.is(Punctuator::RightBrace) || self.is(Punctuator::SemiColon) || self.is_end() {
+            return;
+        }
+
+        self.warnings.push(ParseWarning::new(
+            self.token_span(),
+            "a semicolon is inserted after 'return'; the next line is not returned",
+        ));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test_helper::{parse_script_error, parse_script_sexp, parse_script_warnings};
+
+    macro_rules! test_case {
+        ($test_name:ident, $source:expr, $expected:expr) => {
+            #[test]
+            fn $test_name() {
+                assert_eq!(parse_script_sexp($source), Ok($expected.to_string()));
+            }
+        };
+    }
+
+    macro_rules! test_case_fail {
+        ($test_name:ident, $source:expr) => {
+            #[test]
+            fn $test_name() {
+                assert!(parse_script_error($source).is_some());
+            }
+        };
+    }
+
+    macro_rules! test_case_warnings {
+        ($test_name:ident, $source:expr, $expected:expr) => {
+            #[test]
+            fn $test_name() {
+                assert_eq!(parse_script_warnings($source), $expected);
+            }
+        };
+    }
+
+    // 規則 1
+    test_case!(newline, "a\nb", "a b");
+    test_case!(before_right_brace, "{ a } b", "(block a) b");
+    test_case!(after_do_while, "do a; while (b) c", "(do a b) c");
+    test_case!(
+        declarations,
+        "var a = 1\nlet b = 2\nconst c = 3",
+        "(var (= a 1)) (let (= b 2)) (const (= c 3))"
+    );
+    test_case!(no_insertion_before_operator, "a\n+ b", "(+ a b)");
+    test_case!(no_insertion_before_paren, "a\n(b)", "(call a b)");
+    test_case!(no_insertion_before_bracket, "a\n[b]", "([] a b)");
+    test_case!(no_insertion_before_template, "a\n`b`", "(tag a (` \"b\"))");
+    test_case!(no_insertion_before_regexp, "a\n/b/g", "(/ (/ a b) g)");
+    test_case_fail!(same_line, "a b");
+    test_case_fail!(empty_statement, "if (a)\nelse b");
+    test_case_fail!(for_header, "for (a\nb) c");
+    test_case_fail!(for_header_second, "for (a; b\n) c");
+
+    // 規則 2
+    test_case!(end_of_input, "a = 1", "(= a 1)");
+
+    // 規則 3
+    test_case!(prefix_after_newline, "a\n++b", "a (++prefix b)");
+    test_case!(update_between_lines, "a\n++\nb", "a (++prefix b)");
+    test_case!(postfix_same_line, "a++\nb", "(++postfix a) b");
+    test_case!(
+        return_newline,
+        "function f() { return\na + b }",
+        "(function f (return) (+ a b))"
+    );
+    test_case!(
+        break_newline,
+        "a: while (b) { break\na }",
+        "(label a (while b (block (break) a)))"
+    );
+    test_case!(
+        continue_newline,
+        "a: while (b) { continue\na }",
+        "(label a (while b (block (continue) a)))"
+    );
+    test_case_fail!(throw_newline, "throw\na");
+
+    // 警告
+    test_case_warnings!(
+        warn_paren,
+        "a = b\n(c || d).e()",
+        vec!["line starting with '(' continues the previous expression; no semicolon is inserted (6..7)"]
+    );
+    test_case_warnings!(
+        warn_bracket,
+        "a = b\n[c, d].forEach(e)",
+        vec!["line starting with '[' continues the previous expression; no semicolon is inserted (6..7)"]
+    );
+    test_case_warnings!(
+        warn_template,
+        "a = b\n`c`",
+        vec!["line starting with '`' continues the previous expression; no semicolon is inserted (6..9)"]
+    );
+    test_case_warnings!(
+        warn_return,
+        "function f() { return\na }",
+        vec!["a semicolon is inserted after 'return'; the next line is not returned (22..23)"]
+    );
+    test_case_warnings!(
+        no_warning_with_semicolon,
+        "a = b;\n(c)",
+        Vec::<String>::new()
+    );
+    test_case_warnings!(no_warning_same_line, "a(b)[c]`d`", Vec::<String>::new());
+}
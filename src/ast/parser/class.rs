@@ -0,0 +1,470 @@
+use std::collections::HashMap;
+
+use crate::token::structs::{punctuator::Punctuator, token::TokenType};
+
+use super::{function::MethodModifier, Context, Parser};
+use crate::ast::{
+    error::{ParseError, ParseResult},
+    structs::{
+        class::{
+            Class, ClassBody, ClassElement, MethodDefinition, MethodKind, PropertyDefinition,
+            StaticBlock,
+        },
+        expression::{Expression, PropertyKey},
+        span::Spanned,
+        statement::Statement,
+    },
+};
+
+impl<'a> Parser<'a> {
+    /// `class A {}`
+    pub(crate) fn parse_class_declaration(&mut self) -> ParseResult<Statement> {
+        let class = self.parse_class(true)?;
+        Ok(Statement::ClassDeclaration(Box::new(class)))
+    }
+
+    /// `class {}`
+    pub(crate) fn parse_class_expression(&mut self) -> ParseResult<Expression> {
+        let class = self.parse_class(false)?;
+        Ok(Expression::Class(Box::new(class)))
+    }
+
+    /// `class` からクラスの終わりまでを読む。
+    ///
+    /// クラスの中は全て strict モードになる。
+    pub(crate) fn parse_class(&mut self, require_id: bool) -> ParseResult<Class> {
+        let start = self.start();
+        self.expect_keyword("class")?;
+
+        let context = Context {
+            strict: true,
+            ..self.context
+        };
+
+        self.with_context(context, |parser| {
+            let id =
+                if parser.token_type() == TokenType::Identifier && !parser.is_keyword("extends") {
+                    Some(parser.parse_binding_identifier()?)
+                } else if require_id {
+                    return parser.unexpected();
+                } else {
+                    None
+                };
+
+            let super_class = if parser.eat_keyword("extends")? {
+                Some(parser.parse_left_hand_side_expression()?)
+            } else {
+                None
+            };
+
+            let body = parser.parse_class_body()?;
+
+            Ok(Class {
+                span: parser.span_from(start),
+                id,
+                super_class,
+                body,
+            })
+        })
+    }
+
+    /// `{ a() {} static b() {} }`
+    fn parse_class_body(&mut self) -> ParseResult<ClassBody> {
+        let start = self.start();
+        self.expect(Punctuator::LeftBrace)?;
+
+        let mut body = Vec::new();
+        let mut has_constructor = false;
+        let mut private_names = HashMap::new();
+
+        while !self.eat(Punctuator::RightBrace)? {
+            if self.eat(Punctuator::SemiColon)? {
+                continue;
+            }
+
+            let element = self.parse_class_element()?;
+
+            if let ClassElement::Method(MethodDefinition {
+                kind: MethodKind::Constructor,
+                key,
+                ..
+            }) = &element
+            {
+                if has_constructor {
+                    self.report(ParseError::new(
+                        key.span(),
+                        "a class may only have one constructor",
+                    ));
+                }
+                has_constructor = true;
+            }
+            self.declare_private_name(&mut private_names, &element);
+
+            body.push(element);
+        }
+
+        Ok(ClassBody {
+            span: self.span_from(start),
+            body,
+        })
+    }
+
+    /// クラスの要素
+    fn parse_class_element(&mut self) -> ParseResult<ClassElement> {
+        let start = self.start();
+        let is_static = self.is_keyword("static") && {
+            let (next, _) = self.lookahead()?;
+            !matches!(
+                next.token_type(),
+                TokenType::Punctuator(
+                    Punctuator::LeftParen
+                        | Punctuator::Assign
+                        | Punctuator::SemiColon
+                        | Punctuator::RightBrace
+                )
+            )
+        };
+        if is_static {
+            self.advance()?;
+
+            // static { ... }
+            if self.is(Punctuator::LeftBrace) {
+                return self.parse_static_block(start);
+            }
+        }
+
+        let modifier = self.parse_method_modifier()?;
+        let key = self.parse_class_element_name()?;
+
+        if let PropertyKey::PrivateIdentifier(identifier) = &key {
+            if identifier.name == "constructor" {
+                self.report(ParseError::new(
+                    key.span(),
+                    "class members may not be named '#constructor'",
+                ));
+            }
+        }
+
+        // a = 1;
+        if modifier == MethodModifier::None && !self.is(Punctuator::LeftParen) {
+            return self.parse_field_definition(start, key, is_static);
+        }
+
+        let is_constructor = !is_static && key.static_name() == Some("constructor");
+        let kind = match modifier {
+            MethodModifier::Get => MethodKind::Get,
+            MethodModifier::Set => MethodKind::Set,
+            _ if is_constructor => MethodKind::Constructor,
+            _ => MethodKind::Method,
+        };
+
+        if is_constructor && modifier != MethodModifier::None {
+            self.report(ParseError::new(
+                key.span(),
+                "class constructor may not be an accessor, generator or async method",
+            ));
+        }
+        if is_static && key.static_name() == Some("prototype") {
+            self.report(ParseError::new(
+                key.span(),
+                "classes may not have a static property named 'prototype'",
+            ));
+        }
+
+        let value = self.parse_method_function(modifier)?;
+
+        Ok(ClassElement::Method(MethodDefinition {
+            span: self.span_from(start),
+            key,
+            value,
+            kind,
+            is_static,
+        }))
+    }
+
+    /// クラスの要素の名前
+    ///
+    /// クラスの中ではプライベート名 `#a` を使うことができる。
+    fn parse_class_element_name(&mut self) -> ParseResult<PropertyKey> {
+        if self.token_type() == TokenType::PrivateIdentifier {
+            Ok(PropertyKey::PrivateIdentifier(
+                self.parse_private_identifier()?,
+            ))
+        } else {
+            self.parse_property_key()
+        }
+    }
+
+    /// `a = 1;`
+    fn parse_field_definition(
+        &mut self,
+        start: usize,
+        key: PropertyKey,
+        is_static: bool,
+    ) -> ParseResult<ClassElement> {
+        match key.static_name() {
+            Some("constructor") => self.report(ParseError::new(
+                key.span(),
+                "classes may not have a field named 'constructor'",
+            )),
+            Some("prototype") if is_static => self.report(ParseError::new(
+                key.span(),
+                "classes may not have a static property named 'prototype'",
+            )),
+            _ => {}
+        }
+
+        // 初期化子は引数を持たないメソッドのように扱う
+        let context = Context {
+            allow_in: true,
+            allow_return: false,
+            allow_await: false,
+            allow_yield: false,
+            ..self.context
+        };
+
+        let value = if self.eat(Punctuator::Assign)? {
+            Some(self.with_context(context, Self::parse_assignment_expression)?)
+        } else {
+            None
+        };
+        self.consume_semicolon()?;
+
+        Ok(ClassElement::Property(PropertyDefinition {
+            span: self.span_from(start),
+            key,
+            value,
+            is_static,
+        }))
+    }
+
+    /// `static { ... }`
+    fn parse_static_block(&mut self, start: usize) -> ParseResult<ClassElement> {
+        let context = Context {
+            allow_in: true,
+            allow_return: false,
+            allow_await: false,
+            allow_yield: false,
+            in_static_block: true,
+            ..self.context
+        };
+
+        self.with_context(context, |parser| {
+            parser.expect(Punctuator::LeftBrace)?;
+            let body = parser.parse_statement_list(|parser| parser.is(Punctuator::RightBrace))?;
+            parser.expect_closing(Punctuator::RightBrace)?;
+
+            Ok(ClassElement::StaticBlock(StaticBlock {
+                span: parser.span_from(start),
+                body,
+            }))
+        })
+    }
+
+    /// プライベート名の重複を検査する。
+    ///
+    /// 同じ名前を使えるのは、static が一致するゲッターとセッターの組だけである。
+    fn declare_private_name(
+        &mut self,
+        private_names: &mut HashMap<String, PrivateName>,
+        element: &ClassElement,
+    ) {
+        let (key, name) = match element {
+            ClassElement::Method(MethodDefinition {
+                key,
+                kind: MethodKind::Get,
+                is_static,
+                ..
+            }) => (key, PrivateName::Getter(*is_static)),
+            ClassElement::Method(MethodDefinition {
+                key,
+                kind: MethodKind::Set,
+                is_static,
+                ..
+            }) => (key, PrivateName::Setter(*is_static)),
+            ClassElement::Method(MethodDefinition { key, .. })
+            | ClassElement::Property(PropertyDefinition { key, .. }) => (key, PrivateName::Other),
+            ClassElement::StaticBlock(_) => return,
+        };
+        let PropertyKey::PrivateIdentifier(identifier) = key else {
+            return;
+        };
+
+        let name = match (private_names.get(&identifier.name), name) {
+            (None, name) => name,
+            (Some(PrivateName::Getter(a)), PrivateName::Setter(b))
+            | (Some(PrivateName::Setter(a)), PrivateName::Getter(b))
+                if *a == b =>
+            {
+                PrivateName::Other
+            }
+            _ => {
+                self.report(ParseError::new(
+                    identifier.span,
+                    format!("duplicate private name '#{}'", identifier.name),
+                ));
+                return;
+            }
+        };
+
+        private_names.insert(identifier.name.clone(), name);
+    }
+}
+
+/// 宣言済みのプライベート名
+#[derive(Debug, Clone, Copy)]
+enum PrivateName {
+    /// ゲッターのみ (static であるか)
+    Getter(bool),
+
+    /// セッターのみ (static であるか)
+    Setter(bool),
+
+    /// それ以外 (再宣言できない)
+    Other,
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test_helper::{parse_script_error, parse_script_sexp};
+
+    macro_rules! test_case {
+        ($test_name:ident, $source:expr, $expected:expr) => {
+            #[test]
+            fn $test_name() {
+                assert_eq!(parse_script_sexp($source), Ok($expected.to_string()));
+            }
+        };
+    }
+
+    macro_rules! test_case_fail {
+        ($test_name:ident, $source:expr) => {
+            #[test]
+            fn $test_name() {
+                assert!(parse_script_error($source).is_some());
+            }
+        };
+    }
+
+    test_case!(empty, "class A {}", "(class A)");
+    test_case!(expression, "(class {})", "(class)");
+    test_case!(
+        fields,
+        "class A { a; b = 1; static c = 2; 'd' = 3; [e] = 4 }",
+        "(class A (field a) (field b 1) (static-field c 2) (field 'd' 3) (field [e] 4))"
+    );
+    test_case!(
+        fields_without_semicolon,
+        "class A { a = 1\nb\nc() {} }",
+        "(class A (field a 1) (field b) (method c))"
+    );
+    test_case!(
+        private_members,
+        "class A { #a = 1; #b() {} get #c() {} set #c(v) {} static #d; m() { return this.#a } }",
+        "(class A (field #a 1) (method #b) (get #c) (set #c) (static-field #d) (method m))"
+    );
+    test_case!(
+        private_in,
+        "class A { #a; static is(o) { return #a in o } }",
+        "(class A (field #a) (static-method is))"
+    );
+    test_case!(
+        static_block,
+        "class A { static { a = 1; } static {} }",
+        "(class A (static (= a 1)) (static))"
+    );
+    test_case!(
+        await_in_static_block_function,
+        "class A { static { function f() { await; } } }",
+        "(class A (static (function f await)))"
+    );
+    test_case!(
+        computed_keys,
+        "class A { [a]() {} get [b]() {} static [c] = 1 }",
+        "(class A (method [a]) (get [b]) (static-field [c] 1))"
+    );
+    test_case!(
+        async_and_generator_methods,
+        "class A { async a() { await b } *c() {} async *d() {} static async e() {} }",
+        "(class A (async-method a) (method* c) (async-method* d) (static-async-method e))"
+    );
+    test_case!(
+        modifier_names,
+        "class A { get; set = 1; static; async\na() {} static() {} get get() {} }",
+        "(class A (field get) (field set 1) (field static) (field async) (method a) \
+         (method static) (get get))"
+    );
+    test_case!(
+        static_modifier_names,
+        "class A { static static() {} static async() {} static get() {} static = 1 }",
+        "(class A (static-method static) (static-method async) (static-method get) (field static 1))"
+    );
+    test_case!(
+        computed_constructor,
+        "class A { ['constructor']() {} static constructor() {} }",
+        "(class A (method ['constructor']) (static-method constructor))"
+    );
+    test_case!(
+        extends_call,
+        "class A extends mixin(B, C) {}",
+        "(class A (extends (call mixin B C)))"
+    );
+    test_case!(
+        extends_member,
+        "class A extends b.c[d] {}",
+        "(class A (extends ([] (. b c) d)))"
+    );
+    test_case!(
+        static_accessor_pair,
+        "class A { static get #a() {} static set #a(v) {} }",
+        "(class A (static-get #a) (static-set #a))"
+    );
+
+    test_case_fail!(duplicate_private_field, "class A { #a; #a }");
+    test_case_fail!(duplicate_private_method, "class A { #a() {} #a }");
+    test_case_fail!(
+        duplicate_private_getter,
+        "class A { get #a() {} get #a() {} }"
+    );
+    test_case_fail!(
+        private_accessor_static_mismatch,
+        "class A { get #a() {} static set #a(v) {} }"
+    );
+    test_case_fail!(
+        private_accessor_after_pair,
+        "class A { get #a() {} set #a(v) {} get #a() {} }"
+    );
+    test_case_fail!(private_constructor, "class A { #constructor() {} }");
+    test_case_fail!(getter_constructor, "class A { get constructor() {} }");
+    test_case_fail!(generator_constructor, "class A { *constructor() {} }");
+    test_case_fail!(async_constructor, "class A { async constructor() {} }");
+    test_case_fail!(
+        duplicate_constructor,
+        "class A { constructor() {} constructor() {} }"
+    );
+    test_case_fail!(constructor_field, "class A { constructor = 1 }");
+    test_case_fail!(string_constructor_field, "class A { 'constructor' }");
+    test_case_fail!(static_prototype_method, "class A { static prototype() {} }");
+    test_case_fail!(static_prototype_field, "class A { static prototype = 1 }");
+    test_case_fail!(fields_same_line, "class A { a b }");
+    test_case_fail!(extends_assignment, "class A extends B = C {}");
+    test_case_fail!(private_in_object, "({ #a: 1 })");
+    test_case_fail!(await_outside_async_method, "class A { m() { await b } }");
+    test_case_fail!(
+        return_in_static_block,
+        "function f() { class A { static { return } } }"
+    );
+    test_case_fail!(await_in_static_block, "class A { static { await } }");
+    test_case_fail!(
+        await_binding_in_static_block,
+        "class A { static { var await; } }"
+    );
+    test_case_fail!(
+        return_in_field,
+        "function f() { class A { a = () => { return 1 }; b = return } }"
+    );
+    test_case_fail!(
+        private_without_in_in_class,
+        "class A { #a; m() { #a + 1 } }"
+    );
+}
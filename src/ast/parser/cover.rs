@@ -0,0 +1,368 @@
+//! 式として読んだ範囲をパターンとして読み直すモジュールです。
+//!
+//! `[a, b] = c` や `(a, {b = 1}) => c` は `=` や `=>` を読むまで
+//! 式かパターンかを決められないため、まず式として読んでから変換する。
+//!
+//! `{a = 1}` (CoverInitializedName) は式としては使えないため、
+//! パターンに変換されなかった場合はエラーにする。
+
+use super::Parser;
+use crate::ast::{
+    error::ParseResult,
+    structs::{
+        expression::{
+            AssignmentOperator, Expression, ExpressionOrSpread, ObjectProperty, PropertyKind,
+        },
+        pattern::{
+            ArrayPattern, AssignmentPattern, AssignmentProperty, ObjectPattern,
+            ObjectPatternProperty, Pattern, RestElement,
+        },
+        span::{Span, Spanned},
+    },
+};
+
+/// パターンの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PatternKind {
+    /// 分割代入の左辺 (メンバー参照を含むことができる)
+    Assignment,
+
+    /// 引数などの束縛 (識別子のみを含む)
+    Binding,
+}
+
+impl<'a> Parser<'a> {
+    /// `start` より後ろの `{a = 1}` がパターンに変換されずに残っていればエラーにする。
+    pub(crate) fn check_cover_initialized_names(&mut self, start: usize) -> ParseResult<()> {
+        match self
+            .cover_initialized_names
+            .iter()
+            .find(|span| span.start >= start)
+        {
+            Some(span) => self.error(*span, "invalid shorthand property initializer"),
+            None => Ok(()),
+        }
+    }
+
+    /// 式をパターンに変換する。
+    pub(crate) fn expression_to_pattern(
+        &mut self,
+        expression: Expression,
+        kind: PatternKind,
+    ) -> ParseResult<Pattern> {
+        let span = expression.span();
+        let pattern = self.convert_to_pattern(expression, kind)?;
+
+        self.cover_initialized_names
+            .retain(|name| !span.contains(name.start));
+        Ok(pattern)
+    }
+
+    fn convert_to_pattern(
+        &mut self,
+        expression: Expression,
+        kind: PatternKind,
+    ) -> ParseResult<Pattern> {
+        let is_parenthesized = self.is_parenthesized(&expression);
+
+        match expression {
+            Expression::Identifier(identifier) => {
+                if kind == PatternKind::Binding && is_parenthesized {
+                    return self.error(identifier.span, "invalid destructuring target");
+                }
+                self.check_binding_name(&identifier)?;
+                Ok(Pattern::Identifier(identifier))
+            }
+
+            expression @ Expression::Member(_) if kind == PatternKind::Assignment => {
+                Ok(Pattern::Expression(Box::new(expression)))
+            }
+
+            Expression::Array(array) if !is_parenthesized => {
+                let mut elements = Vec::new();
+                let count = array.elements.len();
+
+                for (index, element) in array.elements.into_iter().enumerate() {
+                    let element = match element {
+                        None => None,
+                        Some(ExpressionOrSpread::Expression(expression)) => {
+                            Some(self.convert_to_element(expression, kind)?)
+                        }
+                        Some(ExpressionOrSpread::Spread(spread)) => {
+                            if index + 1 != count
+                                || self.trailing_comma_spreads.contains(&spread.span)
+                            {
+                                return self
+                                    .error(spread.span, "rest element must be last element");
+                            }
+                            Some(Pattern::Rest(Box::new(self.convert_to_rest(
+                                spread.span,
+                                spread.argument,
+                                kind,
+                            )?)))
+                        }
+                    };
+                    elements.push(element);
+                }
+
+                Ok(Pattern::Array(ArrayPattern {
+                    span: array.span,
+                    elements,
+                }))
+            }
+
+            Expression::Object(object) if !is_parenthesized => {
+                let mut properties = Vec::new();
+                let count = object.properties.len();
+
+                for (index, property) in object.properties.into_iter().enumerate() {
+                    let property = match property {
+                        ObjectProperty::Property(property) => {
+                            if property.method || property.kind != PropertyKind::Init {
+                                return self.error(property.span, "invalid destructuring target");
+                            }
+
+                            ObjectPatternProperty::Property(AssignmentProperty {
+                                span: property.span,
+                                key: property.key,
+                                value: self.convert_to_element(property.value, kind)?,
+                                shorthand: property.shorthand,
+                            })
+                        }
+                        ObjectProperty::Spread(spread) => {
+                            if index + 1 != count {
+                                return self
+                                    .error(spread.span, "rest element must be last element");
+                            }
+
+                            // オブジェクトの残余要素は分割できない
+                            if matches!(
+                                spread.argument,
+                                Expression::Array(_) | Expression::Object(_)
+                            ) {
+                                return self.error(
+                                    spread.argument.span(),
+                                    "invalid rest element in object pattern",
+                                );
+                            }
+                            ObjectPatternProperty::Rest(self.convert_to_rest(
+                                spread.span,
+                                spread.argument,
+                                kind,
+                            )?)
+                        }
+                    };
+                    properties.push(property);
+                }
+
+                Ok(Pattern::Object(ObjectPattern {
+                    span: object.span,
+                    properties,
+                }))
+            }
+
+            expression => self.error(expression.span(), "invalid destructuring target"),
+        }
+    }
+
+    /// 既定値を持つことができる要素 `a = 1`
+    fn convert_to_element(
+        &mut self,
+        expression: Expression,
+        kind: PatternKind,
+    ) -> ParseResult<Pattern> {
+        match expression {
+            Expression::Assignment(assignment)
+                if assignment.operator == AssignmentOperator::Assign
+                    && !self.parenthesized.contains(&assignment.span) =>
+            {
+                let left = match kind {
+                    PatternKind::Assignment => assignment.left,
+                    PatternKind::Binding => self.pattern_to_binding(assignment.left)?,
+                };
+
+                Ok(Pattern::Assignment(Box::new(AssignmentPattern {
+                    span: assignment.span,
+                    left,
+                    right: assignment.right,
+                })))
+            }
+            expression => self.convert_to_pattern(expression, kind),
+        }
+    }
+
+    /// 残余要素 `...a`
+    fn convert_to_rest(
+        &mut self,
+        span: Span,
+        argument: Expression,
+        kind: PatternKind,
+    ) -> ParseResult<RestElement> {
+        if let Expression::Assignment(assignment) = &argument {
+            if !self.parenthesized.contains(&assignment.span) {
+                return self.error(assignment.span, "rest element cannot have a default value");
+            }
+        }
+
+        Ok(RestElement {
+            span,
+            argument: self.convert_to_pattern(argument, kind)?,
+        })
+    }
+
+    /// 分割代入のパターンを束縛のパターンとして検査する。
+    ///
+    /// `(a.b = 1) => c` のようなメンバー参照を含むパターンは束縛にできない。
+    pub(crate) fn pattern_to_binding(&self, pattern: Pattern) -> ParseResult<Pattern> {
+        self.check_binding_pattern(&pattern)?;
+        Ok(pattern)
+    }
+
+    fn check_binding_pattern(&self, pattern: &Pattern) -> ParseResult<()> {
+        match pattern {
+            Pattern::Identifier(identifier) => {
+                if self.is_parenthesized(identifier) {
+                    return self.error(identifier.span, "invalid destructuring target");
+                }
+                self.check_binding_name(identifier)
+            }
+            Pattern::Object(object) => {
+                for property in &object.properties {
+                    match property {
+                        ObjectPatternProperty::Property(property) => {
+                            self.check_binding_pattern(&property.value)?
+                        }
+                        ObjectPatternProperty::Rest(rest) => {
+                            self.check_binding_pattern(&rest.argument)?
+                        }
+                    }
+                }
+                Ok(())
+            }
+            Pattern::Array(array) => {
+                for element in array.elements.iter().flatten() {
+                    self.check_binding_pattern(element)?;
+                }
+                Ok(())
+            }
+            Pattern::Rest(rest) => self.check_binding_pattern(&rest.argument),
+            Pattern::Assignment(assignment) => self.check_binding_pattern(&assignment.left),
+            Pattern::Expression(expression) => {
+                self.error(expression.span(), "invalid destructuring target")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test_helper::{
+        parse_expression_error, parse_expression_sexp, parse_script_error, parse_script_sexp,
+    };
+
+    macro_rules! test_case {
+        ($test_name:ident, $source:expr, $expected:expr) => {
+            #[test]
+            fn $test_name() {
+                assert_eq!(parse_expression_sexp($source), Ok($expected.to_string()));
+            }
+        };
+    }
+
+    macro_rules! test_case_fail {
+        ($test_name:ident, $source:expr) => {
+            #[test]
+            fn $test_name() {
+                assert!(parse_expression_error($source).is_some());
+            }
+        };
+    }
+
+    test_case!(
+        array_assignment,
+        "[a, b] = arr",
+        "(= (array-pattern a b) arr)"
+    );
+    test_case!(
+        array_hole_rest,
+        "[, a, ...b] = c",
+        "(= (array-pattern _ a (... b)) c)"
+    );
+    test_case!(
+        array_default,
+        "[a = 1] = b",
+        "(= (array-pattern (= a 1)) b)"
+    );
+    test_case!(
+        array_member,
+        "[a.b, c[0]] = d",
+        "(= (array-pattern (. a b) ([] c 0)) d)"
+    );
+    test_case!(parenthesized_target, "[(a)] = b", "(= (array-pattern a) b)");
+    test_case!(
+        object_initializer,
+        "({a = 1} = b)",
+        "(= (object-pattern (: a (= a 1))) b)"
+    );
+    test_case!(
+        object_nested,
+        "({a: [b], c: {d}, ...e} = f)",
+        "(= (object-pattern (: a (array-pattern b)) (: c (object-pattern (: d d))) (... e)) f)"
+    );
+    test_case!(
+        initializer_in_nested_assignment,
+        "[{a = 1}] = [{b = 2} = c]",
+        "(= (array-pattern (object-pattern (: a (= a 1)))) (array (= (object-pattern (: b (= b 2))) c)))"
+    );
+    test_case!(
+        chained,
+        "[a] = [b] = c",
+        "(= (array-pattern a) (= (array-pattern b) c))"
+    );
+    test_case!(
+        spread_trailing_comma_expression,
+        "[...a,]",
+        "(array (... a))"
+    );
+
+    test_case_fail!(initializer_in_expression, "({a = 1})");
+    test_case_fail!(initializer_in_arguments, "f({a = 1})");
+    test_case_fail!(initializer_in_array, "[{a = 1}]");
+    test_case_fail!(parenthesized_object, "({a}) = 1");
+    test_case_fail!(parenthesized_array, "([a]) = 1");
+    test_case_fail!(literal_value, "({a: 1} = b)");
+    test_case_fail!(binary_target, "[a + b] = c");
+    test_case_fail!(call_target, "[f()] = c");
+    test_case_fail!(spread_trailing_comma, "[...a,] = b");
+    test_case_fail!(rest_not_last, "[...a, b] = c");
+    test_case_fail!(rest_default, "[...a = 1] = b");
+    test_case_fail!(object_rest_pattern, "({...{a}} = b)");
+    test_case_fail!(method_in_pattern, "({a() {}} = b)");
+    test_case_fail!(compound_pattern, "[a] += b");
+
+    #[test]
+    fn for_of_pattern() {
+        assert_eq!(
+            parse_script_sexp("for ([a, b] of c);"),
+            Ok("(for-of (array-pattern a b) c (empty))".to_string())
+        );
+    }
+
+    #[test]
+    fn for_in_initializer() {
+        assert_eq!(
+            parse_script_sexp("for ({a = 1} in b);"),
+            Ok("(for-in (object-pattern (: a (= a 1))) b (empty))".to_string())
+        );
+    }
+
+    #[test]
+    fn for_init_initializer() {
+        assert!(parse_script_error("for ({a = 1}; b;);").is_some());
+    }
+
+    #[test]
+    fn strict_eval_target() {
+        assert!(parse_script_error("class A { m() { [eval] = a } }").is_some());
+    }
+}
@@ -0,0 +1,285 @@
+//! ディレクティブ・プロローグを解析するモジュールです。
+//!
+//! スクリプトと関数本体の先頭に並ぶ文字列リテラルだけの式文をディレクティブとみなす。
+//! `"use strict"` があれば、それ以降を strict モードで解析する。
+
+use super::{literal::cook_string, Parser};
+use crate::ast::{
+    error::{ParseError, ParseResult},
+    structs::{
+        expression::Expression,
+        literal::{Literal, LiteralValue},
+        pattern::Pattern,
+        span::{Span, Spanned},
+        statement::Statement,
+    },
+};
+
+impl<'a> Parser<'a> {
+    /// ディレクティブ・プロローグと、それに続く文のリストを読む。
+    ///
+    /// `params` は関数の引数で、単純でない引数を持つ関数では `"use strict"` を使えない。
+    pub(crate) fn parse_directives_and_statements(
+        &mut self,
+        params: &[Pattern],
+        is_end: impl Fn(&Self) -> bool,
+    ) -> ParseResult<Vec<Statement>> {
+        let mut statements = Vec::new();
+
+        while self.is_string_literal() && !is_end(self) {
+            let mut statement = self.parse_statement_list_item_or_recover();
+
+            let Statement::Expression(expression_statement) = &mut statement else {
+                statements.push(statement);
+                break;
+            };
+            let Expression::Literal(Literal {
+                span,
+                value: LiteralValue::String(_),
+                raw,
+            }) = &expression_statement.expression
+            else {
+                statements.push(statement);
+                break;
+            };
+
+            // エスケープを含む表記は値が同じでも `"use strict"` とはみなさない
+            let directive = raw[1..raw.len() - 1].to_string();
+            if directive == "use strict" {
+                self.enter_strict_directive(*span, &statements, params);
+            }
+
+            expression_statement.directive = Some(directive);
+            statements.push(statement);
+        }
+
+        statements.extend(self.parse_statement_list(is_end)?);
+        Ok(statements)
+    }
+
+    /// `"use strict"` 以降を strict モードにする。
+    ///
+    /// 既に読んだディレクティブと引数は strict モードの規則で検査し直す。
+    fn enter_strict_directive(&mut self, span: Span, directives: &[Statement], params: &[Pattern]) {
+        if !params.iter().all(Pattern::is_simple) {
+            self.report(ParseError::new(
+                span,
+                "'use strict' is not allowed in a function with non-simple parameters",
+            ));
+        }
+
+        if self.context.strict {
+            return;
+        }
+        self.context.strict = true;
+
+        for statement in directives {
+            let Statement::Expression(statement) = statement else {
+                continue;
+            };
+            let has_legacy_escape = statement
+                .directive
+                .as_deref()
+                .and_then(|directive| cook_string(directive).ok())
+                .is_some_and(|cooked| cooked.has_legacy_escape);
+
+            if has_legacy_escape {
+                self.report(ParseError::new(
+                    statement.expression.span(),
+                    "octal escape sequence is not allowed in strict mode",
+                ));
+            }
+        }
+
+        for identifier in params.iter().flat_map(Pattern::bound_names) {
+            if let Err(error) = self.check_reserved_word(identifier, false) {
+                self.report(error);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test_helper::{parse_script_error, parse_script_sexp};
+    use crate::ast::{
+        reader_struct::SourceCodeReader,
+        script::parse_script,
+        structs::{module::ModuleItem, statement::Statement},
+    };
+
+    /// 関数宣言が strict モードであるかを、入れ子も含めて出現順に返す。
+    fn function_strictness(source: &str) -> Vec<bool> {
+        fn collect(statements: &[Statement], result: &mut Vec<bool>) {
+            for statement in statements {
+                if let Statement::FunctionDeclaration(function) = statement {
+                    result.push(function.strict);
+                    collect(&function.body.body, result);
+                }
+            }
+        }
+
+        let ast = parse_script(SourceCodeReader::new(source))
+            .into_result()
+            .expect("parse error");
+        let statements = ast
+            .body
+            .into_iter()
+            .filter_map(|item| match item {
+                ModuleItem::Statement(statement) => Some(statement),
+                ModuleItem::ModuleDeclaration(_) => None,
+            })
+            .collect::<Vec<_>>();
+
+        let mut result = Vec::new();
+        collect(&statements, &mut result);
+        result
+    }
+
+    macro_rules! test_case {
+        ($test_name:ident, $source:expr, $expected:expr) => {
+            #[test]
+            fn $test_name() {
+                assert_eq!(parse_script_sexp($source), Ok($expected.to_string()));
+            }
+        };
+    }
+
+    macro_rules! test_case_fail {
+        ($test_name:ident, $source:expr, $expected:expr) => {
+            #[test]
+            fn $test_name() {
+                assert_eq!(
+                    parse_script_error($source).map(|error| error.message),
+                    Some($expected.to_string())
+                );
+            }
+        };
+    }
+
+    test_case!(directive, "'use strict'; a;", "'use strict' a");
+    test_case!(
+        sloppy_with,
+        "'use\\x20strict'; with (a) b;",
+        "'use\\x20strict' (with a b)"
+    );
+    test_case!(
+        not_first,
+        "a; 'use strict'; with (a) b;",
+        "a 'use strict' (with a b)"
+    );
+    test_case!(
+        parenthesized,
+        "('use strict'); with (a) b;",
+        "'use strict' (with a b)"
+    );
+    test_case!(
+        not_only_literal,
+        "'use strict' + a; with (a) b;",
+        "(+ 'use strict' a) (with a b)"
+    );
+    test_case!(
+        function_does_not_leak,
+        "function f() { 'use strict' } with (a) b;",
+        "(function f 'use strict') (with a b)"
+    );
+    test_case!(
+        after_other_directive,
+        "function f() { 'a'; 'use strict'; } 010;",
+        "(function f 'a' 'use strict') 8"
+    );
+
+    test_case_fail!(
+        strict_with,
+        "'use strict'; with (a) b;",
+        "'with' is not allowed in strict mode"
+    );
+    test_case_fail!(
+        strict_octal,
+        "\"use strict\"; 010;",
+        "legacy octal literal is not allowed in strict mode"
+    );
+    test_case_fail!(
+        function_octal,
+        "function f() { 'use strict'; 010; }",
+        "legacy octal literal is not allowed in strict mode"
+    );
+    test_case_fail!(
+        second_directive,
+        "function f() { 'a'; 'use strict'; 010; }",
+        "legacy octal literal is not allowed in strict mode"
+    );
+    test_case_fail!(
+        nested_function,
+        "'use strict'; function f() { 010; }",
+        "legacy octal literal is not allowed in strict mode"
+    );
+    test_case_fail!(
+        octal_escape_before,
+        "function f() { '\\01'; 'use strict'; }",
+        "octal escape sequence is not allowed in strict mode"
+    );
+    test_case_fail!(
+        non_simple_parameters,
+        "function f(a = 1) { 'use strict'; }",
+        "'use strict' is not allowed in a function with non-simple parameters"
+    );
+    test_case_fail!(
+        non_simple_arrow_parameters,
+        "({ a }) => { 'use strict'; };",
+        "'use strict' is not allowed in a function with non-simple parameters"
+    );
+    test_case_fail!(
+        non_simple_in_strict,
+        "'use strict'; function f(...a) { 'use strict'; }",
+        "'use strict' is not allowed in a function with non-simple parameters"
+    );
+    test_case_fail!(
+        reserved_function_name,
+        "function static() { 'use strict'; }",
+        "unexpected keyword 'static'"
+    );
+    test_case_fail!(
+        reserved_parameter,
+        "function f(package) { 'use strict'; }",
+        "unexpected keyword 'package'"
+    );
+    test_case_fail!(
+        reserved_arrow_parameter,
+        "(interface) => { 'use strict'; };",
+        "unexpected keyword 'interface'"
+    );
+    test_case_fail!(
+        duplicate_parameters,
+        "function f(a, a) { 'use strict'; }",
+        "duplicate parameter name 'a'"
+    );
+    test_case_fail!(
+        eval_function_name,
+        "function eval() { 'use strict'; }",
+        "cannot bind 'eval' in strict mode"
+    );
+    test_case_fail!(
+        strict_delete,
+        "'use strict'; delete a;",
+        "delete of an unqualified identifier in strict mode"
+    );
+
+    #[test]
+    fn strictness() {
+        assert_eq!(
+            function_strictness(
+                "function f() { 'use strict'; function g() {} } function h() { function i() {} }"
+            ),
+            vec![true, true, false, false]
+        );
+        assert_eq!(
+            function_strictness("'use strict'; function f() {}"),
+            vec![true]
+        );
+        assert_eq!(
+            function_strictness("function f() { 'use\\x20strict'; }"),
+            vec![false]
+        );
+    }
+}
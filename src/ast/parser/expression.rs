@@ -0,0 +1,1336 @@
+use crate::token::structs::{
+    context::{BraceContext, DivContext},
+    literal::Literal as LiteralToken,
+    punctuator::Punctuator,
+    template::Template,
+    token::TokenType,
+};
+
+use super::{
+    cover::PatternKind,
+    function::MethodModifier,
+    literal::{bigint_value, cook_string, cook_template, normalize_template_raw, numeric_value},
+    yield_await::YieldAwait,
+    Parser,
+};
+use crate::ast::{
+    error::{ParseError, ParseResult},
+    structs::{
+        expression::{
+            ArrayExpression, AssignmentExpression, AssignmentOperator, AwaitExpression,
+            BinaryExpression, BinaryOperator, CallExpression, ChainExpression,
+            ConditionalExpression, Expression, ExpressionOrSpread, LogicalExpression,
+            LogicalOperator, MemberExpression, MemberProperty, MetaProperty, NewExpression,
+            ObjectExpression, ObjectProperty, Property, PropertyKey, PropertyKind,
+            SequenceExpression, SpreadElement, Super, ThisExpression, UnaryExpression,
+            UnaryOperator, UpdateExpression, UpdateOperator,
+        },
+        literal::{Identifier, Literal, LiteralValue, PrivateIdentifier, RegExpLiteral},
+        pattern::Pattern,
+        span::{Span, Spanned},
+        template::{TaggedTemplateExpression, TemplateElement, TemplateLiteral},
+    },
+};
+
+/// 二項演算子と論理演算子
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InfixOperator {
+    Binary(BinaryOperator),
+    Logical(LogicalOperator),
+}
+
+impl InfixOperator {
+    /// 優先順位 (大きいほど強く結合する)
+    fn precedence(&self) -> u8 {
+        match self {
+            InfixOperator::Logical(LogicalOperator::NullishCoalesce) => 1,
+            InfixOperator::Logical(LogicalOperator::Or) => 2,
+            InfixOperator::Logical(LogicalOperator::And) => 3,
+            InfixOperator::Binary(operator) => match operator {
+                BinaryOperator::BitOr => 4,
+                BinaryOperator::BitXor => 5,
+                BinaryOperator::BitAnd => 6,
+                BinaryOperator::Equal
+                | BinaryOperator::NotEqual
+                | BinaryOperator::StrictEqual
+                | BinaryOperator::StrictNotEqual => 7,
+                BinaryOperator::LessThan
+                | BinaryOperator::LessThanEqual
+                | BinaryOperator::GreaterThan
+                | BinaryOperator::GreaterThanEqual
+                | BinaryOperator::In
+                | BinaryOperator::Instanceof => 8,
+                BinaryOperator::LeftShift
+                | BinaryOperator::RightShift
+                | BinaryOperator::UnsignedRightShift => 9,
+                BinaryOperator::Plus | BinaryOperator::Minus => 10,
+                BinaryOperator::Multiply | BinaryOperator::Divide | BinaryOperator::Remainder => 11,
+                BinaryOperator::Exponentiation => 12,
+            },
+        }
+    }
+}
+
+impl<'a> Parser<'a> {
+    /// Expression (カンマ区切りの式)
+    pub(crate) fn parse_expression(&mut self) -> ParseResult<Expression> {
+        let start = self.start();
+        let expression = self.parse_expression_cover()?;
+        self.check_cover_initialized_names(start)?;
+        Ok(expression)
+    }
+
+    /// パターンに変換される可能性がある Expression
+    ///
+    /// `{a = 1}` の検査は呼び出し側で行う。
+    pub(crate) fn parse_expression_cover(&mut self) -> ParseResult<Expression> {
+        let start = self.start();
+        let first = self.parse_assignment_expression_cover()?;
+
+        if !self.is(Punctuator::Comma) {
+            return Ok(first);
+        }
+
+        let mut expressions = vec![first];
+        while self.eat(Punctuator::Comma)? {
+            expressions.push(self.parse_assignment_expression_cover()?);
+        }
+
+        Ok(Expression::Sequence(SequenceExpression {
+            span: self.span_from(start),
+            expressions,
+        }))
+    }
+
+    /// AssignmentExpression
+    pub(crate) fn parse_assignment_expression(&mut self) -> ParseResult<Expression> {
+        let start = self.start();
+        let expression = self.parse_assignment_expression_cover()?;
+        self.check_cover_initialized_names(start)?;
+        Ok(expression)
+    }
+
+    /// パターンに変換される可能性がある AssignmentExpression
+    ///
+    /// `{a = 1}` の検査は呼び出し側で行う。
+    pub(crate) fn parse_assignment_expression_cover(&mut self) -> ParseResult<Expression> {
+        let start = self.start();
+
+        if self.context.allow_yield && self.is_keyword("yield") {
+            return self.parse_yield_expression();
+        }
+
+        if let Some(arrow) = self.try_parse_identifier_arrow_function()? {
+            return Ok(arrow);
+        }
+
+        self.arrow_start = Some(start);
+        let left = self.parse_conditional_expression()?;
+        if self.is_bare_arrow_function(&left) {
+            return Ok(left);
+        }
+
+        let Some(operator) = self.assignment_operator() else {
+            return Ok(left);
+        };
+
+        let left = self.expression_to_assignment_target(left, operator)?;
+        self.advance()?;
+        let right = self.parse_assignment_expression()?;
+
+        Ok(Expression::Assignment(Box::new(AssignmentExpression {
+            span: self.span_from(start),
+            operator,
+            left,
+            right,
+        })))
+    }
+
+    fn assignment_operator(&self) -> Option<AssignmentOperator> {
+        let TokenType::Punctuator(punctuator) = self.token_type() else {
+            return None;
+        };
+
+        let operator = match punctuator {
+            Punctuator::Assign => AssignmentOperator::Assign,
+            Punctuator::PlusAssign => AssignmentOperator::PlusAssign,
+            Punctuator::MinusAssign => AssignmentOperator::MinusAssign,
+            Punctuator::MultiplyAssign => AssignmentOperator::MultiplyAssign,
+            Punctuator::DivideAssign => AssignmentOperator::DivideAssign,
+            Punctuator::RemainderAssign => AssignmentOperator::RemainderAssign,
+            Punctuator::ExponentiationAssign => AssignmentOperator::ExponentiationAssign,
+            Punctuator::LeftShiftAssign => AssignmentOperator::LeftShiftAssign,
+            Punctuator::RightShiftAssign => AssignmentOperator::RightShiftAssign,
+            Punctuator::UnsignedRightShiftAssign => AssignmentOperator::UnsignedRightShiftAssign,
+            Punctuator::BitAndAssign => AssignmentOperator::BitAndAssign,
+            Punctuator::BitOrAssign => AssignmentOperator::BitOrAssign,
+            Punctuator::BitXorAssign => AssignmentOperator::BitXorAssign,
+            Punctuator::LogicalAndAssign => AssignmentOperator::LogicalAndAssign,
+            Punctuator::LogicalOrAssign => AssignmentOperator::LogicalOrAssign,
+            Punctuator::NullishCoalesceAssign => AssignmentOperator::NullishCoalesceAssign,
+            _ => return None,
+        };
+
+        Some(operator)
+    }
+
+    /// 代入の左辺の式をパターンに変換する。
+    ///
+    /// `=` の左辺の配列リテラルとオブジェクトリテラルは分割代入のパターンになる。
+    pub(crate) fn expression_to_assignment_target(
+        &mut self,
+        expression: Expression,
+        operator: AssignmentOperator,
+    ) -> ParseResult<Pattern> {
+        let is_literal = matches!(expression, Expression::Array(_) | Expression::Object(_));
+
+        if operator == AssignmentOperator::Assign
+            && is_literal
+            && !self.is_parenthesized(&expression)
+        {
+            self.expression_to_pattern(expression, PatternKind::Assignment)
+        } else {
+            self.simple_assignment_target(expression)
+        }
+    }
+
+    /// 識別子かメンバー参照だけを代入の対象として受け付ける。
+    pub(crate) fn simple_assignment_target(&self, expression: Expression) -> ParseResult<Pattern> {
+        match expression {
+            Expression::Identifier(identifier) => {
+                if self.context.strict && matches!(identifier.name.as_str(), "eval" | "arguments") {
+                    return self.error(
+                        identifier.span,
+                        format!("cannot assign to '{}' in strict mode", identifier.name),
+                    );
+                }
+                Ok(Pattern::Identifier(identifier))
+            }
+            expression @ Expression::Member(_) => Ok(Pattern::Expression(Box::new(expression))),
+            expression => self.error(expression.span(), "invalid assignment target"),
+        }
+    }
+
+    /// ConditionalExpression
+    fn parse_conditional_expression(&mut self) -> ParseResult<Expression> {
+        let start = self.start();
+        let test = self.parse_binary_expression(0)?;
+
+        if self.is_bare_arrow_function(&test) || !self.eat(Punctuator::TernaryQuestion)? {
+            return Ok(test);
+        }
+
+        let consequent = self.with_allow_in(true, Self::parse_assignment_expression)?;
+        self.expect(Punctuator::TernaryColon)?;
+        let alternate = self.parse_assignment_expression()?;
+
+        Ok(Expression::Conditional(Box::new(ConditionalExpression {
+            span: self.span_from(start),
+            test,
+            consequent,
+            alternate,
+        })))
+    }
+
+    fn infix_operator(&self) -> Option<InfixOperator> {
+        use InfixOperator::{Binary, Logical};
+
+        let operator = match self.token_type() {
+            TokenType::Punctuator(punctuator) => match punctuator {
+                Punctuator::NullishCoalesce => Logical(LogicalOperator::NullishCoalesce),
+                Punctuator::LogicalOr => Logical(LogicalOperator::Or),
+                Punctuator::LogicalAnd => Logical(LogicalOperator::And),
+                Punctuator::BitOr => Binary(BinaryOperator::BitOr),
+                Punctuator::BitXor => Binary(BinaryOperator::BitXor),
+                Punctuator::BitAnd => Binary(BinaryOperator::BitAnd),
+                Punctuator::Equal => Binary(BinaryOperator::Equal),
+                Punctuator::NotEqual => Binary(BinaryOperator::NotEqual),
+                Punctuator::StrictEqual => Binary(BinaryOperator::StrictEqual),
+                Punctuator::StrictNotEqual => Binary(BinaryOperator::StrictNotEqual),
+                Punctuator::LessThan => Binary(BinaryOperator::LessThan),
+                Punctuator::LessThanEqual => Binary(BinaryOperator::LessThanEqual),
+                Punctuator::GreaterThan => Binary(BinaryOperator::GreaterThan),
+                Punctuator::GreaterThanEqual => Binary(BinaryOperator::GreaterThanEqual),
+                Punctuator::LeftShift => Binary(BinaryOperator::LeftShift),
+                Punctuator::RightShift => Binary(BinaryOperator::RightShift),
+                Punctuator::UnsignedRightShift => Binary(BinaryOperator::UnsignedRightShift),
+                Punctuator::Plus => Binary(BinaryOperator::Plus),
+                Punctuator::Minus => Binary(BinaryOperator::Minus),
+                Punctuator::Multiply => Binary(BinaryOperator::Multiply),
+                Punctuator::Divide => Binary(BinaryOperator::Divide),
+                Punctuator::Remainder => Binary(BinaryOperator::Remainder),
+                _ => return None,
+            },
+            TokenType::Identifier if self.is_keyword("in") && self.context.allow_in => {
+                Binary(BinaryOperator::In)
+            }
+            TokenType::Identifier if self.is_keyword("instanceof") => {
+                Binary(BinaryOperator::Instanceof)
+            }
+            _ => return None,
+        };
+
+        Some(operator)
+    }
+
+    /// ShortCircuitExpression
+    ///
+    /// 優先順位が `min_precedence` より大きい演算子だけを読む。
+    fn parse_binary_expression(&mut self, min_precedence: u8) -> ParseResult<Expression> {
+        let start = self.start();
+
+        let mut left = if self.token_type() == TokenType::PrivateIdentifier {
+            self.parse_private_in_left(min_precedence)?
+        } else {
+            self.parse_exponentiation_expression()?
+        };
+        if self.is_bare_arrow_function(&left) {
+            return Ok(left);
+        }
+
+        while let Some(operator) = self.infix_operator() {
+            let precedence = operator.precedence();
+            if precedence <= min_precedence {
+                break;
+            }
+
+            self.advance()?;
+            let right = self.parse_binary_expression(precedence)?;
+            let span = self.span_from(start);
+
+            left = match operator {
+                InfixOperator::Binary(operator) => Expression::Binary(Box::new(BinaryExpression {
+                    span,
+                    operator,
+                    left,
+                    right,
+                })),
+                InfixOperator::Logical(operator) => {
+                    self.check_nullish_mixing(operator, &left, &right)?;
+                    Expression::Logical(Box::new(LogicalExpression {
+                        span,
+                        operator,
+                        left,
+                        right,
+                    }))
+                }
+            };
+        }
+
+        Ok(left)
+    }
+
+    /// `??` と `||` `&&` は括弧なしで混ぜて使えない。
+    fn check_nullish_mixing(
+        &self,
+        operator: LogicalOperator,
+        left: &Expression,
+        right: &Expression,
+    ) -> ParseResult<()> {
+        for operand in [left, right] {
+            let Expression::Logical(logical) = operand else {
+                continue;
+            };
+            if self.is_parenthesized(operand) {
+                continue;
+            }
+
+            let is_nullish = operator == LogicalOperator::NullishCoalesce;
+            let is_operand_nullish = logical.operator == LogicalOperator::NullishCoalesce;
+
+            if is_nullish != is_operand_nullish {
+                return self.error(
+                    operand.span(),
+                    "cannot mix '??' with '||' or '&&' without parentheses",
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// `#a in b` の左辺
+    fn parse_private_in_left(&mut self, min_precedence: u8) -> ParseResult<Expression> {
+        let identifier = self.parse_private_identifier()?;
+        let in_precedence = InfixOperator::Binary(BinaryOperator::In).precedence();
+
+        if !self.is_keyword("in") || !self.context.allow_in || in_precedence <= min_precedence {
+            return self.error(identifier.span, "private name must be followed by 'in'");
+        }
+
+        Ok(Expression::PrivateIdentifier(identifier))
+    }
+
+    pub(crate) fn parse_private_identifier(&mut self) -> ParseResult<PrivateIdentifier> {
+        if self.token_type() != TokenType::PrivateIdentifier {
+            return self.unexpected();
+        }
+
+        let span = self.token_span();
+        let name = super::literal::identifier_value(&self.text()[1..])
+            .map_err(|message| ParseError::new(span, message))?;
+        self.advance()?;
+
+        Ok(PrivateIdentifier { span, name })
+    }
+
+    /// ExponentiationExpression (右結合)
+    fn parse_exponentiation_expression(&mut self) -> ParseResult<Expression> {
+        let start = self.start();
+        let left = self.parse_unary_expression()?;
+
+        if self.is_bare_arrow_function(&left) || !self.is(Punctuator::Exponentiation) {
+            return Ok(left);
+        }
+
+        if matches!(left, Expression::Unary(_) | Expression::Await(_))
+            && !self.is_parenthesized(&left)
+        {
+            return self.error(
+                left.span(),
+                "unary operator before '**' must be parenthesized",
+            );
+        }
+
+        self.advance()?;
+        let right = self.parse_exponentiation_expression()?;
+
+        Ok(Expression::Binary(Box::new(BinaryExpression {
+            span: self.span_from(start),
+            operator: BinaryOperator::Exponentiation,
+            left,
+            right,
+        })))
+    }
+
+    fn unary_operator(&self) -> Option<UnaryOperator> {
+        let operator = match self.token_type() {
+            TokenType::Punctuator(Punctuator::Minus) => UnaryOperator::Minus,
+            TokenType::Punctuator(Punctuator::Plus) => UnaryOperator::Plus,
+            TokenType::Punctuator(Punctuator::LogicalNot) => UnaryOperator::LogicalNot,
+            TokenType::Punctuator(Punctuator::BitNot) => UnaryOperator::BitNot,
+            TokenType::Identifier => match self.text() {
+                "typeof" => UnaryOperator::Typeof,
+                "void" => UnaryOperator::Void,
+                "delete" => UnaryOperator::Delete,
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        Some(operator)
+    }
+
+    /// UnaryExpression
+    fn parse_unary_expression(&mut self) -> ParseResult<Expression> {
+        let start = self.start();
+
+        if self.context.allow_await && self.is_keyword("await") {
+            self.advance()?;
+            let argument = self.parse_unary_expression()?;
+
+            let span = self.span_from(start);
+            self.yield_await.push(YieldAwait::Await(span));
+
+            return Ok(Expression::Await(Box::new(AwaitExpression {
+                span,
+                argument,
+            })));
+        }
+
+        if let Some(operator) = self.unary_operator() {
+            self.advance()?;
+            let argument = self.parse_unary_expression()?;
+
+            return Ok(Expression::Unary(Box::new(UnaryExpression {
+                span: self.span_from(start),
+                operator,
+                argument,
+            })));
+        }
+
+        let update_operator = match self.token_type() {
+            TokenType::Punctuator(Punctuator::Increment) => Some(UpdateOperator::Increment),
+            TokenType::Punctuator(Punctuator::Decrement) => Some(UpdateOperator::Decrement),
+            _ => None,
+        };
+
+        if let Some(operator) = update_operator {
+            self.advance()?;
+            let argument = self.parse_unary_expression()?;
+            self.check_update_target(&argument)?;
+
+            return Ok(Expression::Update(Box::new(UpdateExpression {
+                span: self.span_from(start),
+                operator,
+                prefix: true,
+                argument,
+            })));
+        }
+
+        self.parse_postfix_expression()
+    }
+
+    /// UpdateExpression (後置)
+    fn parse_postfix_expression(&mut self) -> ParseResult<Expression> {
+        let start = self.start();
+        let argument = self.parse_left_hand_side_expression()?;
+
+        // 後置演算子の前に改行を置くことはできない
+        if self.has_line_terminator_here() || self.is_bare_arrow_function(&argument) {
+            return Ok(argument);
+        }
+
+        let operator = match self.token_type() {
+            TokenType::Punctuator(Punctuator::Increment) => UpdateOperator::Increment,
+            TokenType::Punctuator(Punctuator::Decrement) => UpdateOperator::Decrement,
+            _ => return Ok(argument),
+        };
+
+        self.check_update_target(&argument)?;
+        self.advance()?;
+
+        Ok(Expression::Update(Box::new(UpdateExpression {
+            span: self.span_from(start),
+            operator,
+            prefix: false,
+            argument,
+        })))
+    }
+
+    /// `++` `--` の対象は識別子かメンバー参照でなければいけない。
+    fn check_update_target(&self, argument: &Expression) -> ParseResult<()> {
+        match argument {
+            Expression::Identifier(identifier)
+                if self.context.strict
+                    && matches!(identifier.name.as_str(), "eval" | "arguments") =>
+            {
+                self.error(
+                    identifier.span,
+                    format!("cannot assign to '{}' in strict mode", identifier.name),
+                )
+            }
+            Expression::Identifier(_) | Expression::Member(_) => Ok(()),
+            argument => self.error(argument.span(), "invalid update target"),
+        }
+    }
+
+    /// LeftHandSideExpression
+    pub(crate) fn parse_left_hand_side_expression(&mut self) -> ParseResult<Expression> {
+        let start = self.start();
+
+        let expression = if self.is_keyword("new") {
+            self.parse_new_expression()?
+        } else if self.is_keyword("super") {
+            self.parse_super()?
+        } else if self.is_keyword("import") {
+            self.parse_import_meta_or_call()?
+        } else {
+            self.parse_primary_expression()?
+        };
+
+        self.parse_call_tail(start, expression, true)
+    }
+
+    /// `new a(b)` と `new.target`
+    fn parse_new_expression(&mut self) -> ParseResult<Expression> {
+        let start = self.start();
+        let meta_span = self.token_span();
+        self.expect_keyword("new")?;
+
+        if self.eat(Punctuator::Dot)? {
+            let property = self.parse_identifier_name()?;
+            if property.name != "target" {
+                return self.error(property.span, "'new.' must be followed by 'target'");
+            }
+
+            return Ok(Expression::MetaProperty(MetaProperty {
+                span: self.span_from(start),
+                meta: Identifier::new(meta_span, "new"),
+                property,
+            }));
+        }
+
+        let callee_start = self.start();
+        let callee = if self.is_keyword("new") {
+            self.parse_new_expression()?
+        } else if self.is_keyword("super") {
+            self.parse_super()?
+        } else if self.is_keyword("import") {
+            return self.error(self.token_span(), "cannot use 'new' with 'import'");
+        } else {
+            self.parse_primary_expression()?
+        };
+        let callee = self.parse_call_tail(callee_start, callee, false)?;
+
+        let arguments = if self.is(Punctuator::LeftParen) {
+            self.parse_arguments()?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Expression::New(Box::new(NewExpression {
+            span: self.span_from(start),
+            callee,
+            arguments,
+        })))
+    }
+
+    /// `super` はメンバー参照か呼び出しの対象としてのみ使える。
+    fn parse_super(&mut self) -> ParseResult<Expression> {
+        let span = self.token_span();
+        self.expect_keyword("super")?;
+
+        if !matches!(
+            self.token_type(),
+            TokenType::Punctuator(
+                Punctuator::LeftParen | Punctuator::Dot | Punctuator::LeftBracket
+            )
+        ) {
+            return self.error(
+                span,
+                "'super' must be followed by an argument list or member access",
+            );
+        }
+
+        Ok(Expression::Super(Super { span }))
+    }
+
+    /// `import.meta` と `import(a)`
+    fn parse_import_meta_or_call(&mut self) -> ParseResult<Expression> {
+        let start = self.start();
+        let meta_span = self.token_span();
+        self.expect_keyword("import")?;
+
+        if self.eat(Punctuator::Dot)? {
+            let property = self.parse_identifier_name()?;
+            if property.name != "meta" {
+                return self.error(property.span, "'import.' must be followed by 'meta'");
+            }
+            if !self.is_module() {
+                return self.error(
+                    self.span_from(start),
+                    "'import.meta' may only appear in a module",
+                );
+            }
+
+            return Ok(Expression::MetaProperty(MetaProperty {
+                span: self.span_from(start),
+                meta: Identifier::new(meta_span, "import"),
+                property,
+            }));
+        }
+
+        self.expect(Punctuator::LeftParen)?;
+        let (source, options) = self.with_allow_in(true, |parser| {
+            let source = parser.parse_assignment_expression()?;
+            let mut options = None;
+
+            if parser.eat(Punctuator::Comma)? && !parser.is(Punctuator::RightParen) {
+                options = Some(parser.parse_assignment_expression()?);
+                parser.eat(Punctuator::Comma)?;
+            }
+            Ok((source, options))
+        })?;
+        self.expect_closing(Punctuator::RightParen)?;
+
+        Ok(Expression::Import(Box::new(
+            crate::ast::structs::expression::ImportExpression {
+                span: self.span_from(start),
+                source,
+                options,
+            },
+        )))
+    }
+
+    /// メンバー参照、呼び出し、タグ付きテンプレートを続けて読む。
+    ///
+    /// `allow_call` が `false` の場合は `new` の対象として呼び出しの前で止まる。
+    fn parse_call_tail(
+        &mut self,
+        start: usize,
+        mut expression: Expression,
+        allow_call: bool,
+    ) -> ParseResult<Expression> {
+        let mut is_in_chain = false;
+
+        if self.is_bare_arrow_function(&expression) {
+            return Ok(expression);
+        }
+
+        loop {
+            self.warn_continued_line();
+
+            match self.token_type() {
+                TokenType::Punctuator(Punctuator::Dot) => {
+                    self.advance()?;
+                    let property = self.parse_member_property_name()?;
+                    expression = self.member(start, expression, property, false);
+                }
+
+                TokenType::Punctuator(Punctuator::OptionalChain) => {
+                    if !allow_call {
+                        return self.error(
+                            self.token_span(),
+                            "optional chain cannot be used in 'new' expression",
+                        );
+                    }
+                    is_in_chain = true;
+                    self.advance()?;
+
+                    match self.token_type() {
+                        TokenType::Punctuator(Punctuator::LeftParen) => {
+                            let arguments = self.parse_arguments()?;
+                            expression = self.call(start, expression, arguments, true);
+                        }
+                        TokenType::Punctuator(Punctuator::LeftBracket) => {
+                            let property = self.parse_computed_member_property()?;
+                            expression = self.member(start, expression, property, true);
+                        }
+                        TokenType::Template(_) => {
+                            return self.error(
+                                self.token_span(),
+                                "tagged template cannot be used in optional chain",
+                            );
+                        }
+                        _ => {
+                            let property = self.parse_member_property_name()?;
+                            expression = self.member(start, expression, property, true);
+                        }
+                    }
+                }
+
+                TokenType::Punctuator(Punctuator::LeftBracket) => {
+                    let property = self.parse_computed_member_property()?;
+                    expression = self.member(start, expression, property, false);
+                }
+
+                TokenType::Punctuator(Punctuator::LeftParen) if allow_call => {
+                    let arguments = self.parse_arguments()?;
+                    expression = self.call(start, expression, arguments, false);
+                }
+
+                TokenType::Template(Template::NoSubstitution | Template::Head) => {
+                    if is_in_chain {
+                        return self.error(
+                            self.token_span(),
+                            "tagged template cannot be used in optional chain",
+                        );
+                    }
+
+                    let quasi = self.parse_template_literal(true)?;
+                    expression = Expression::TaggedTemplate(Box::new(TaggedTemplateExpression {
+                        span: self.span_from(start),
+                        tag: expression,
+                        quasi,
+                    }));
+                }
+
+                _ => break,
+            }
+        }
+
+        if is_in_chain {
+            expression = Expression::Chain(Box::new(ChainExpression {
+                span: self.span_from(start),
+                expression,
+            }));
+        }
+
+        Ok(expression)
+    }
+
+    fn member(
+        &self,
+        start: usize,
+        object: Expression,
+        property: MemberProperty,
+        optional: bool,
+    ) -> Expression {
+        Expression::Member(Box::new(MemberExpression {
+            span: self.span_from(start),
+            object,
+            property,
+            optional,
+        }))
+    }
+
+    fn call(
+        &self,
+        start: usize,
+        callee: Expression,
+        arguments: Vec<ExpressionOrSpread>,
+        optional: bool,
+    ) -> Expression {
+        Expression::Call(Box::new(CallExpression {
+            span: self.span_from(start),
+            callee,
+            arguments,
+            optional,
+        }))
+    }
+
+    /// `.b` と `.#b` のプロパティ名
+    fn parse_member_property_name(&mut self) -> ParseResult<MemberProperty> {
+        if self.token_type() == TokenType::PrivateIdentifier {
+            Ok(MemberProperty::PrivateIdentifier(
+                self.parse_private_identifier()?,
+            ))
+        } else {
+            Ok(MemberProperty::Identifier(self.parse_identifier_name()?))
+        }
+    }
+
+    /// `[b]`
+    fn parse_computed_member_property(&mut self) -> ParseResult<MemberProperty> {
+        self.expect(Punctuator::LeftBracket)?;
+        let property = self.with_allow_in(true, Self::parse_expression)?;
+        self.expect(Punctuator::RightBracket)?;
+
+        Ok(MemberProperty::Computed(property))
+    }
+
+    /// Arguments `(a, ...b)`
+    pub(crate) fn parse_arguments(&mut self) -> ParseResult<Vec<ExpressionOrSpread>> {
+        self.expect(Punctuator::LeftParen)?;
+
+        let arguments = self.with_allow_in(true, |parser| {
+            let mut arguments = Vec::new();
+
+            while !parser.is(Punctuator::RightParen) {
+                arguments.push(parser.parse_expression_or_spread()?);
+
+                // `,` がなければ `)` が欠けているものとして引数を終える
+                if !parser.is(Punctuator::RightParen) && !parser.eat(Punctuator::Comma)? {
+                    break;
+                }
+            }
+
+            Ok(arguments)
+        })?;
+
+        self.expect_closing(Punctuator::RightParen)?;
+        Ok(arguments)
+    }
+
+    fn parse_expression_or_spread(&mut self) -> ParseResult<ExpressionOrSpread> {
+        if self.is(Punctuator::DotThree) {
+            Ok(ExpressionOrSpread::Spread(self.parse_spread_element()?))
+        } else {
+            Ok(ExpressionOrSpread::Expression(
+                self.parse_assignment_expression()?,
+            ))
+        }
+    }
+
+    fn parse_spread_element(&mut self) -> ParseResult<SpreadElement> {
+        let start = self.start();
+        let spread = self.parse_spread_element_cover()?;
+        self.check_cover_initialized_names(start)?;
+        Ok(spread)
+    }
+
+    /// パターンに変換される可能性がある `...a`
+    pub(crate) fn parse_spread_element_cover(&mut self) -> ParseResult<SpreadElement> {
+        let start = self.start();
+        self.expect(Punctuator::DotThree)?;
+        let argument = self.parse_assignment_expression_cover()?;
+
+        Ok(SpreadElement {
+            span: self.span_from(start),
+            argument,
+        })
+    }
+
+    /// PrimaryExpression
+    fn parse_primary_expression(&mut self) -> ParseResult<Expression> {
+        let start = self.start();
+
+        match self.token_type() {
+            TokenType::Identifier => match self.text() {
+                "this" => {
+                    self.advance()?;
+                    Ok(Expression::This(ThisExpression {
+                        span: self.span_from(start),
+                    }))
+                }
+                "null" | "true" | "false" => Ok(Expression::Literal(self.parse_literal()?)),
+                "function" => self.parse_function_expression(),
+                "async" if self.is_async_function_start()? => self.parse_function_expression(),
+                "class" => self.parse_class_expression(),
+                "async" if self.is_async_call_start()? => self.parse_async_call_or_arrow_function(),
+                _ => Ok(Expression::Identifier(self.parse_identifier_reference()?)),
+            },
+
+            TokenType::Literal(_) => Ok(Expression::Literal(self.parse_literal()?)),
+
+            // 式の始まりの `/` は正規表現
+            TokenType::Punctuator(Punctuator::Divide | Punctuator::DivideAssign) => {
+                self.rescan(DivContext::RegExp, BraceContext::Block)?;
+                Ok(Expression::Literal(self.parse_literal()?))
+            }
+
+            TokenType::Template(Template::NoSubstitution | Template::Head) => Ok(
+                Expression::TemplateLiteral(self.parse_template_literal(false)?),
+            ),
+
+            TokenType::Punctuator(Punctuator::LeftParen) => {
+                self.parse_parenthesized_or_arrow_function()
+            }
+            TokenType::Punctuator(Punctuator::LeftBracket) => self.parse_array_literal(),
+            TokenType::Punctuator(Punctuator::LeftBrace) => self.parse_object_literal(),
+
+            _ => match self.missing_expression() {
+                Some(expression) => Ok(expression),
+                None => self.unexpected(),
+            },
+        }
+    }
+
+    /// `async (` で始まるかを返す。
+    ///
+    /// `async` と `(` の間に改行がある場合は、`async` という変数の呼び出しになる。
+    fn is_async_call_start(&self) -> ParseResult<bool> {
+        let (next, has_newline) = self.lookahead()?;
+        Ok(next.token_type() == TokenType::Punctuator(Punctuator::LeftParen) && !has_newline)
+    }
+
+    /// Literal
+    pub(crate) fn parse_literal(&mut self) -> ParseResult<Literal> {
+        let span = self.token_span();
+        let raw = self.text();
+
+        let value = match self.token_type() {
+            TokenType::Identifier => match raw {
+                "null" => LiteralValue::Null,
+                "true" => LiteralValue::Boolean(true),
+                "false" => LiteralValue::Boolean(false),
+                _ => return self.unexpected(),
+            },
+
+            TokenType::Literal(LiteralToken::SingleString | LiteralToken::DoubleString) => {
+                let cooked = cook_string(&raw[1..raw.len() - 1])
+                    .map_err(|message| ParseError::new(span, message))?;
+
+                if cooked.has_legacy_escape && self.context.strict {
+                    return self.error(span, "octal escape sequence is not allowed in strict mode");
+                }
+                LiteralValue::String(cooked.value)
+            }
+
+            TokenType::Literal(LiteralToken::RegularExpression) => {
+                let (pattern, flags) = split_regexp(raw);
+                check_regexp_flags(flags).map_err(|message| ParseError::new(span, message))?;
+
+                LiteralValue::RegExp(RegExpLiteral {
+                    pattern: pattern.to_string(),
+                    flags: flags.to_string(),
+                })
+            }
+
+            TokenType::Literal(
+                literal @ (LiteralToken::DecimalBigInteger
+                | LiteralToken::BinaryBigInteger
+                | LiteralToken::OctalBigInteger
+                | LiteralToken::HexBigInteger),
+            ) => LiteralValue::BigInt(bigint_value(raw, literal)),
+
+            TokenType::Literal(literal) => {
+                let is_legacy = literal == LiteralToken::LegacyOctalInteger
+                    || (raw.len() > 1
+                        && raw.starts_with('0')
+                        && raw.as_bytes()[1].is_ascii_digit());
+
+                if is_legacy && self.context.strict {
+                    return self.error(span, "legacy octal literal is not allowed in strict mode");
+                }
+                LiteralValue::Number(numeric_value(raw, literal))
+            }
+
+            _ => return self.unexpected(),
+        };
+
+        self.advance()?;
+
+        Ok(Literal {
+            span,
+            value,
+            raw: raw.to_string(),
+        })
+    }
+
+    /// TemplateLiteral
+    ///
+    /// タグ付きテンプレートでは不正なエスケープを許す。
+    pub(crate) fn parse_template_literal(&mut self, tagged: bool) -> ParseResult<TemplateLiteral> {
+        let start = self.start();
+        let mut quasis = Vec::new();
+        let mut expressions = Vec::new();
+
+        loop {
+            let TokenType::Template(part) = self.token_type() else {
+                return self.unexpected();
+            };
+
+            let tail = matches!(part, Template::NoSubstitution | Template::Tail);
+            let text = self.text();
+            let raw_text = &text[1..text.len() - if tail { 1 } else { 2 }];
+            let span = Span::new(
+                self.token.start_index() + 1,
+                self.token.start_index() + 1 + raw_text.len(),
+            );
+
+            let cooked = match cook_template(raw_text) {
+                Ok(cooked) => Some(cooked),
+                Err(_) if tagged => None,
+                Err(message) => return self.error(span, message),
+            };
+
+            quasis.push(TemplateElement {
+                span,
+                raw: normalize_template_raw(raw_text),
+                cooked,
+                tail,
+            });
+
+            self.advance()?;
+            if tail {
+                break;
+            }
+
+            expressions.push(self.with_allow_in(true, Self::parse_expression)?);
+
+            if !self.is(Punctuator::RightBrace) {
+                return self.unexpected();
+            }
+            self.rescan(DivContext::Divide, BraceContext::Template)?;
+        }
+
+        Ok(TemplateLiteral {
+            span: self.span_from(start),
+            quasis,
+            expressions,
+        })
+    }
+
+    /// ArrayLiteral `[a, , ...b]`
+    fn parse_array_literal(&mut self) -> ParseResult<Expression> {
+        let start = self.start();
+        self.expect(Punctuator::LeftBracket)?;
+
+        let elements = self.with_allow_in(true, |parser| {
+            let mut elements = Vec::new();
+
+            while !parser.is(Punctuator::RightBracket) {
+                if parser.eat(Punctuator::Comma)? {
+                    elements.push(None);
+                    continue;
+                }
+
+                let element = if parser.is(Punctuator::DotThree) {
+                    let spread = parser.parse_spread_element_cover()?;
+
+                    // [...a,] はパターンに変換できない
+                    if parser.is(Punctuator::Comma) {
+                        parser.trailing_comma_spreads.insert(spread.span);
+                    }
+                    ExpressionOrSpread::Spread(spread)
+                } else {
+                    ExpressionOrSpread::Expression(parser.parse_assignment_expression_cover()?)
+                };
+                elements.push(Some(element));
+
+                if !parser.is(Punctuator::RightBracket) {
+                    parser.expect(Punctuator::Comma)?;
+                }
+            }
+
+            Ok(elements)
+        })?;
+
+        self.expect(Punctuator::RightBracket)?;
+
+        Ok(Expression::Array(ArrayExpression {
+            span: self.span_from(start),
+            elements,
+        }))
+    }
+
+    /// ObjectLiteral `{ a, b: c, ...d }`
+    fn parse_object_literal(&mut self) -> ParseResult<Expression> {
+        let start = self.start();
+        self.expect(Punctuator::LeftBrace)?;
+
+        let properties = self.with_allow_in(true, |parser| {
+            let mut properties = Vec::new();
+
+            while !parser.is(Punctuator::RightBrace) {
+                properties.push(parser.parse_object_property()?);
+
+                if !parser.is(Punctuator::RightBrace) {
+                    parser.expect(Punctuator::Comma)?;
+                }
+            }
+
+            Ok(properties)
+        })?;
+
+        self.expect(Punctuator::RightBrace)?;
+
+        Ok(Expression::Object(ObjectExpression {
+            span: self.span_from(start),
+            properties,
+        }))
+    }
+
+    /// PropertyDefinition
+    fn parse_object_property(&mut self) -> ParseResult<ObjectProperty> {
+        if self.is(Punctuator::DotThree) {
+            return Ok(ObjectProperty::Spread(self.parse_spread_element_cover()?));
+        }
+
+        let start = self.start();
+        let modifier = self.parse_method_modifier()?;
+        let is_identifier = self.token_type() == TokenType::Identifier;
+        let has_escape = self.text().contains('\\');
+        let key = self.parse_property_key()?;
+
+        // { a() {} } { get a() {} } { set a(b) {} } { *a() {} }
+        if modifier != MethodModifier::None || self.is(Punctuator::LeftParen) {
+            let value = self.parse_method_function(modifier)?;
+            let kind = match modifier {
+                MethodModifier::Get => PropertyKind::Get,
+                MethodModifier::Set => PropertyKind::Set,
+                _ => PropertyKind::Init,
+            };
+
+            return Ok(ObjectProperty::Property(Property {
+                span: self.span_from(start),
+                key,
+                value: Expression::Function(Box::new(value)),
+                kind,
+                method: kind == PropertyKind::Init,
+                shorthand: false,
+            }));
+        }
+
+        if self.eat(Punctuator::TernaryColon)? {
+            let value = self.parse_assignment_expression_cover()?;
+
+            return Ok(ObjectProperty::Property(Property {
+                span: self.span_from(start),
+                key,
+                value,
+                kind: PropertyKind::Init,
+                method: false,
+                shorthand: false,
+            }));
+        }
+
+        // { a }
+        match key {
+            PropertyKey::Identifier(identifier) if is_identifier => {
+                self.check_reserved_word(&identifier, has_escape)?;
+
+                // { a = 1 } はパターンに変換される場合のみ使える
+                let value = if self.eat(Punctuator::Assign)? {
+                    let right = self.parse_assignment_expression_cover()?;
+                    let span = identifier.span.merge(right.span());
+                    self.cover_initialized_names.push(span);
+
+                    Expression::Assignment(Box::new(AssignmentExpression {
+                        span,
+                        operator: AssignmentOperator::Assign,
+                        left: Pattern::Identifier(identifier.clone()),
+                        right,
+                    }))
+                } else {
+                    Expression::Identifier(identifier.clone())
+                };
+
+                Ok(ObjectProperty::Property(Property {
+                    span: self.span_from(start),
+                    key: PropertyKey::Identifier(identifier),
+                    value,
+                    kind: PropertyKind::Init,
+                    method: false,
+                    shorthand: true,
+                }))
+            }
+            _ => self.unexpected(),
+        }
+    }
+
+    /// PropertyName
+    pub(crate) fn parse_property_key(&mut self) -> ParseResult<PropertyKey> {
+        match self.token_type() {
+            TokenType::Identifier => Ok(PropertyKey::Identifier(self.parse_identifier_name()?)),
+            TokenType::Literal(LiteralToken::RegularExpression) => self.unexpected(),
+            TokenType::Literal(_) => Ok(PropertyKey::Literal(self.parse_literal()?)),
+            TokenType::Punctuator(Punctuator::LeftBracket) => {
+                self.advance()?;
+                let key = self.with_allow_in(true, Self::parse_assignment_expression)?;
+                self.expect(Punctuator::RightBracket)?;
+                Ok(PropertyKey::Computed(key))
+            }
+            _ => self.unexpected(),
+        }
+    }
+
+    /// 範囲を指定して識別子を作る。
+    pub(crate) fn identifier_at(&self, span: Span) -> Identifier {
+        Identifier::new(span, span.source_text(self.source))
+    }
+}
+
+/// 正規表現リテラルをパターンとフラグに分ける。
+fn split_regexp(raw: &str) -> (&str, &str) {
+    let last_slash = raw.rfind('/').unwrap_or(0);
+    (&raw[1..last_slash], &raw[last_slash + 1..])
+}
+
+/// 正規表現のフラグを検査する。
+fn check_regexp_flags(flags: &str) -> Result<(), String> {
+    let mut seen = String::new();
+
+    for flag in flags.chars() {
+        if !"dgimsuvy".contains(flag) {
+            return Err(format!("invalid regular expression flag '{flag}'"));
+        }
+        if seen.contains(flag) {
+            return Err(format!("duplicate regular expression flag '{flag}'"));
+        }
+        seen.push(flag);
+    }
+
+    if seen.contains('u') && seen.contains('v') {
+        return Err("regular expression flags 'u' and 'v' cannot be used together".to_string());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test_helper::{parse_expression_error, parse_expression_sexp};
+
+    macro_rules! test_case {
+        ($test_name:ident, $source:expr, $expected:expr) => {
+            #[test]
+            fn $test_name() {
+                assert_eq!(parse_expression_sexp($source), Ok($expected.to_string()));
+            }
+        };
+    }
+
+    macro_rules! test_case_fail {
+        ($test_name:ident, $source:expr) => {
+            #[test]
+            fn $test_name() {
+                assert!(parse_expression_error($source).is_some());
+            }
+        };
+    }
+
+    test_case!(identifier, "abc", "abc");
+    test_case!(number, "1_000", "1000");
+    test_case!(string, r#""a\tb""#, r#""a\tb""#);
+    test_case!(regexp, "/ab+c/gi", "/ab+c/gi");
+    test_case!(division_after_identifier, "a / b / c", "(/ (/ a b) c)");
+    test_case!(regexp_in_call, "f(/=/)", "(call f /=/)");
+    test_case!(precedence_mul_add, "a + b * c", "(+ a (* b c))");
+    test_case!(precedence_add_mul, "a * b + c", "(+ (* a b) c)");
+    test_case!(left_assoc, "a - b - c", "(- (- a b) c)");
+    test_case!(exponent_right_assoc, "a ** b ** c", "(** a (** b c))");
+    test_case!(exponent_over_mul, "a * b ** c", "(* a (** b c))");
+    test_case!(parenthesized_unary_exponent, "(-a) ** b", "(** (- a) b)");
+    test_case!(exponent_unary_right, "a ** -b", "(** a (- b))");
+    test_case!(shift, "a << b >>> c >> d", "(>> (>>> (<< a b) c) d)");
+    test_case!(relational, "a < b == c > d", "(== (< a b) (> c d))");
+    test_case!(
+        in_instanceof,
+        "a in b instanceof c",
+        "(instanceof (in a b) c)"
+    );
+    test_case!(bitwise, "a | b ^ c & d", "(| a (^ b (& c d)))");
+    test_case!(logical, "a || b && c", "(|| a (&& b c))");
+    test_case!(nullish, "a ?? b ?? c", "(?? (?? a b) c)");
+    test_case!(nullish_parenthesized, "(a || b) ?? c", "(?? (|| a b) c)");
+    test_case!(
+        nullish_parenthesized_right,
+        "a ?? (b && c)",
+        "(?? a (&& b c))"
+    );
+    test_case!(conditional, "a ? b : c ? d : e", "(? a b (? c d e))");
+    test_case!(assignment_right_assoc, "a = b = c", "(= a (= b c))");
+    test_case!(compound_assignment, "a.b **= c", "(**= (. a b) c)");
+    test_case!(logical_assignment, "a ??= b ||= c", "(??= a (||= b c))");
+    test_case!(sequence, "a, b = c, d", "(, a (= b c) d)");
+    test_case!(unary, "!typeof void -a", "(! (typeof (void (- a))))");
+    test_case!(delete, "delete a[b]", "(delete ([] a b))");
+    test_case!(prefix_update, "++a.b", "(++prefix (. a b))");
+    test_case!(postfix_update, "a-- - b", "(- (--postfix a) b)");
+    test_case!(plus_plus, "a + +b", "(+ a (+ b))");
+    test_case!(member_call, "a.b(c)[d]", "([] (call (. a b) c) d)");
+    test_case!(private_member, "this.#a", "(. this #a)");
+    test_case!(private_in, "#a in b", "(in #a b)");
+    test_case!(keyword_property, "a.if.class", "(. (. a if) class)");
+    test_case!(new_with_arguments, "new a(b)", "(new a b)");
+    test_case!(new_without_arguments, "new a", "(new a)");
+    test_case!(new_member, "new a.b.c(d)", "(new (. (. a b) c) d)");
+    test_case!(new_call, "new a()()", "(call (new a))");
+    test_case!(new_member_after, "new a().b", "(. (new a) b)");
+    test_case!(new_new, "new new a()()", "(new (new a))");
+    test_case!(new_target, "new.target", "new.target");
+    test_case!(import_call, "import('a')", "(import 'a')");
+    test_case!(super_call, "super(a)", "(call super a)");
+    test_case!(optional_member, "a?.b.c", "(chain (. (?. a b) c))");
+    test_case!(optional_call, "a?.(b)", "(chain (?.call a b))");
+    test_case!(optional_computed, "a?.[b]", "(chain (?.[] a b))");
+    test_case!(optional_parenthesized, "(a?.b).c", "(. (chain (?. a b)) c)");
+    test_case!(tagged_template, "a`x${b}y`", "(tag a (` \"x\" b \"y\"))");
+    test_case!(tagged_template_invalid_escape, "a`\\u`", "(tag a (` null))");
+    test_case!(
+        template,
+        "`a${b + c}d${e}`",
+        "(` \"a\" (+ b c) \"d\" e \"\")"
+    );
+    test_case!(
+        nested_template,
+        "`a${`b${c}`}`",
+        "(` \"a\" (` \"b\" c \"\") \"\")"
+    );
+    test_case!(
+        template_object,
+        "`${{a}}`",
+        "(` \"\" (object (: a a)) \"\")"
+    );
+    test_case!(array, "[a, , ...b,]", "(array a _ (... b))");
+    test_case!(
+        object,
+        "({a, b: c, [d]: e, 'f': g, 1: h, ...i})",
+        "(object (: a a) (: b c) (: [d] e) (: 'f' g) (: 1 h) (... i))"
+    );
+    test_case!(spread_arguments, "f(a, ...b,)", "(call f a (... b))");
+    test_case!(in_inside_parentheses, "(a in b)", "(in a b)");
+
+    test_case_fail!(unary_exponent, "-a ** b");
+    test_case_fail!(typeof_exponent, "typeof a ** b");
+    test_case_fail!(nullish_or, "a ?? b || c");
+    test_case_fail!(or_nullish, "a || b ?? c");
+    test_case_fail!(and_nullish, "a && b ?? c");
+    test_case_fail!(nullish_and, "a ?? b && c");
+    test_case_fail!(optional_tagged_template, "a?.b`c`");
+    test_case_fail!(new_optional, "new a?.b()");
+    test_case_fail!(invalid_assignment, "a + b = c");
+    test_case_fail!(invalid_update, "++a()");
+    test_case_fail!(optional_assignment, "a?.b = c");
+    test_case_fail!(template_invalid_escape, "`\\u`");
+    test_case_fail!(lone_super, "super");
+    test_case_fail!(private_without_in, "#a + b");
+    test_case_fail!(reserved_word, "a + class");
+    test_case_fail!(escaped_keyword, "\\u0069f");
+    test_case_fail!(duplicate_regexp_flag, "/a/gg");
+    test_case_fail!(unclosed_paren, "(a + b");
+    test_case_fail!(import_meta_in_script, "import.meta.url");
+}
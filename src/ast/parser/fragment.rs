@@ -0,0 +1,163 @@
+//! スクリプトの一部分だけを解析するモジュールです。
+//!
+//! 設定値の式やコードのテンプレートのように、プログラム全体でない断片を読む。
+//! 断片の後ろに読めない入力が残っている場合はエラーにする。
+
+use super::Parser;
+use crate::ast::{
+    error::{ParseError, ParseResult},
+    structs::{expression::Expression, pattern::Pattern, span::Span, statement::Statement},
+};
+
+impl<'a> Parser<'a> {
+    /// 式 (カンマ区切りを含む) だけを読む。
+    pub fn parse_expression_fragment(&mut self) -> ParseResult<Expression> {
+        let expression = self.parse_expression();
+        self.finish_fragment(expression)
+    }
+
+    /// 文のリストを読む。
+    pub fn parse_statements_fragment(&mut self) -> ParseResult<Vec<Statement>> {
+        let statements = self.parse_statement_list(|_| false);
+        self.finish_fragment(statements)
+    }
+
+    /// 既定値を持つことができる束縛のパターン (`{ a, b = 1 }` や `[a, ...b]`) を読む。
+    pub fn parse_pattern_fragment(&mut self) -> ParseResult<Pattern> {
+        let pattern = self.parse_binding_element();
+        self.finish_fragment(pattern)
+    }
+
+    /// 断片の終わりを確かめる。
+    ///
+    /// 回復したエラーを含め、最も前にあるエラーを返す。
+    fn finish_fragment<T>(&mut self, result: ParseResult<T>) -> ParseResult<T> {
+        let mut errors = self.take_errors();
+
+        match result {
+            Ok(node) if errors.is_empty() => {
+                if self.is_end() {
+                    return Ok(node);
+                }
+                let span = Span::new(self.start(), self.source.len());
+                errors.push(ParseError::new(
+                    span,
+                    "unexpected input after the end of the fragment",
+                ));
+            }
+            Ok(_) => {}
+            Err(error) => errors.push(error),
+        }
+
+        Err(errors
+            .into_iter()
+            .min_by_key(|error| error.span)
+            .expect("at least one error"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test_helper::ToSexp;
+    use crate::ast::{
+        reader_struct::SourceCodeReader,
+        script::{parse_expression, parse_pattern, parse_statements},
+    };
+
+    fn expression_sexp(source: &str) -> Result<String, String> {
+        parse_expression(SourceCodeReader::new(source))
+            .map(|expression| expression.to_sexp())
+            .map_err(|error| error.message)
+    }
+
+    fn statements_sexp(source: &str) -> Result<String, String> {
+        parse_statements(SourceCodeReader::new(source))
+            .map(|statements| {
+                statements
+                    .iter()
+                    .map(ToSexp::to_sexp)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .map_err(|error| error.message)
+    }
+
+    fn pattern_sexp(source: &str) -> Result<String, String> {
+        parse_pattern(SourceCodeReader::new(source))
+            .map(|pattern| pattern.to_sexp())
+            .map_err(|error| error.message)
+    }
+
+    macro_rules! test_case {
+        ($test_name:ident, $parse:ident, $source:expr, $expected:expr) => {
+            #[test]
+            fn $test_name() {
+                assert_eq!($parse($source), Ok($expected.to_string()));
+            }
+        };
+    }
+
+    macro_rules! test_case_fail {
+        ($test_name:ident, $parse:ident, $source:expr, $expected:expr) => {
+            #[test]
+            fn $test_name() {
+                assert_eq!($parse($source), Err($expected.to_string()));
+            }
+        };
+    }
+
+    const TRAILING: &str = "unexpected input after the end of the fragment";
+
+    test_case!(expression, expression_sexp, "a + b * c", "(+ a (* b c))");
+    test_case!(sequence, expression_sexp, "a, b", "(, a b)");
+    test_case!(object, expression_sexp, "{ a: 1 }", "(object (: a 1))");
+    test_case!(function, expression_sexp, "function () {}", "(function)");
+    test_case!(arrow, expression_sexp, " (a) => a ", "(=> a a)");
+    test_case_fail!(expression_semicolon, expression_sexp, "a;", TRAILING);
+    test_case_fail!(expression_trailing, expression_sexp, "a b", TRAILING);
+    test_case_fail!(
+        expression_statement,
+        expression_sexp,
+        "var a",
+        "unexpected keyword 'var'"
+    );
+    test_case_fail!(
+        empty_expression,
+        expression_sexp,
+        "",
+        "expected an expression"
+    );
+    test_case_fail!(missing_paren, expression_sexp, "f(a", "missing ')'");
+
+    test_case!(statements, statements_sexp, "a; b;", "a b");
+    test_case!(empty_statements, statements_sexp, "", "");
+    test_case!(
+        declarations,
+        statements_sexp,
+        "let a = 1; function f() {}",
+        "(let (= a 1)) (function f)"
+    );
+    test_case_fail!(
+        stray_brace,
+        statements_sexp,
+        "a; } b;",
+        "unexpected token '}'"
+    );
+    test_case_fail!(
+        statement_error,
+        statements_sexp,
+        "a = ;\nb;",
+        "expected an expression"
+    );
+
+    test_case!(identifier_pattern, pattern_sexp, "a", "a");
+    test_case!(
+        object_pattern,
+        pattern_sexp,
+        "{ a, b: [c, ...d] } = {}",
+        "(= (object-pattern (: a a) (: b (array-pattern c (... d)))) (object))"
+    );
+    test_case_fail!(pattern_trailing, pattern_sexp, "a, b", TRAILING);
+    test_case_fail!(pattern_expression, pattern_sexp, "a.b", TRAILING);
+    test_case_fail!(pattern_literal, pattern_sexp, "1", "unexpected token '1'");
+}
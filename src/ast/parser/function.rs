@@ -0,0 +1,294 @@
+use crate::token::structs::{punctuator::Punctuator, token::TokenType};
+
+use super::{Context, Parser, STRICT_RESERVED_WORDS};
+use crate::ast::{
+    error::{ParseError, ParseResult},
+    structs::{
+        expression::Expression,
+        function::{Function, FunctionBody},
+        literal::Identifier,
+        pattern::Pattern,
+        statement::Statement,
+    },
+};
+
+/// メソッドの前に置く語
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MethodModifier {
+    None,
+
+    /// get a() {}
+    Get,
+
+    /// set a(b) {}
+    Set,
+
+    /// *a() {}
+    Generator,
+
+    /// async a() {}
+    Async,
+
+    /// async *a() {}
+    AsyncGenerator,
+}
+
+impl MethodModifier {
+    pub(crate) fn is_async(self) -> bool {
+        matches!(self, MethodModifier::Async | MethodModifier::AsyncGenerator)
+    }
+
+    pub(crate) fn is_generator(self) -> bool {
+        matches!(
+            self,
+            MethodModifier::Generator | MethodModifier::AsyncGenerator
+        )
+    }
+}
+
+impl<'a> Parser<'a> {
+    /// `function a() {}`
+    pub(crate) fn parse_function_declaration(&mut self) -> ParseResult<Statement> {
+        let function = self.parse_function(true)?;
+        Ok(Statement::FunctionDeclaration(Box::new(function)))
+    }
+
+    /// `function () {}`
+    pub(crate) fn parse_function_expression(&mut self) -> ParseResult<Expression> {
+        let function = self.parse_function(false)?;
+        Ok(Expression::Function(Box::new(function)))
+    }
+
+    /// `async function a() {}` の始まりであるかを返す。
+    ///
+    /// `async` と `function` の間に改行がある場合は、`async` という変数の参照になる。
+    pub(crate) fn is_async_function_start(&self) -> ParseResult<bool> {
+        if !self.is_keyword("async") {
+            return Ok(false);
+        }
+
+        let (next, has_newline) = self.lookahead()?;
+        Ok(next.token_type() == TokenType::Identifier
+            && next.source_text(self.source) == "function"
+            && !has_newline)
+    }
+
+    /// `function` または `async function` から関数の終わりまでを読む。
+    ///
+    /// 関数式の名前は関数の中の文脈で、関数宣言の名前は外側の文脈で読む。
+    pub(crate) fn parse_function(&mut self, require_id: bool) -> ParseResult<Function> {
+        let start = self.start();
+        let is_async = self.is_async_function_start()?;
+        if is_async {
+            self.advance()?;
+        }
+        self.expect_keyword("function")?;
+        let generator = self.eat(Punctuator::Multiply)?;
+
+        let context = if require_id {
+            self.context
+        } else {
+            Context {
+                allow_await: is_async,
+                allow_yield: generator,
+                ..self.context
+            }
+        };
+
+        let id = if self.token_type() == TokenType::Identifier {
+            Some(self.with_context(context, Self::parse_binding_identifier)?)
+        } else if require_id {
+            return self.unexpected();
+        } else {
+            None
+        };
+
+        self.parse_function_rest(start, id, is_async, generator)
+    }
+
+    /// 引数リストから関数の終わりまでを読む。
+    pub(crate) fn parse_function_rest(
+        &mut self,
+        start: usize,
+        id: Option<Identifier>,
+        is_async: bool,
+        generator: bool,
+    ) -> ParseResult<Function> {
+        let context = Context {
+            allow_await: is_async,
+            allow_yield: generator,
+            in_static_block: false,
+            ..self.context
+        };
+
+        let (params, body, strict) = self.with_context(context, |parser| {
+            let params_start = parser.start();
+            let params = parser.parse_formal_parameters()?;
+            parser.check_parameters_yield_await(params_start, false)?;
+
+            let (body, strict) = parser.parse_function_body(&params)?;
+            Ok((params, body, strict))
+        })?;
+        self.discard_yield_await(start);
+
+        // 本体の `"use strict"` は関数名にも適用される
+        if let Some(id) = id.as_ref().filter(|_| strict && !self.context.strict) {
+            if STRICT_RESERVED_WORDS.contains(&id.name.as_str()) {
+                let message = format!("unexpected keyword '{}'", id.name);
+                self.report(ParseError::new(id.span, message));
+            }
+        }
+
+        Ok(Function {
+            span: self.span_from(start),
+            id,
+            params,
+            body,
+            is_async,
+            generator,
+            strict,
+        })
+    }
+
+    /// メソッドの前に置かれた `get` `set` `async` `*` を読む。
+    ///
+    /// `get` `set` `async` は後ろにプロパティ名が続く場合のみ前置きとみなす。
+    /// `async` の後ろには改行を置くことができない。
+    pub(crate) fn parse_method_modifier(&mut self) -> ParseResult<MethodModifier> {
+        if self.eat(Punctuator::Multiply)? {
+            return Ok(MethodModifier::Generator);
+        }
+
+        let modifier = match self.text() {
+            _ if self.token_type() != TokenType::Identifier => return Ok(MethodModifier::None),
+            "get" => MethodModifier::Get,
+            "set" => MethodModifier::Set,
+            "async" => MethodModifier::Async,
+            _ => return Ok(MethodModifier::None),
+        };
+
+        let (next, has_newline) = self.lookahead()?;
+        let is_key_start = matches!(
+            next.token_type(),
+            TokenType::Identifier
+                | TokenType::PrivateIdentifier
+                | TokenType::Literal(_)
+                | TokenType::Punctuator(Punctuator::LeftBracket)
+        );
+
+        if modifier == MethodModifier::Async {
+            let is_async = !has_newline
+                && (is_key_start
+                    || next.token_type() == TokenType::Punctuator(Punctuator::Multiply));
+            if !is_async {
+                return Ok(MethodModifier::None);
+            }
+
+            self.advance()?;
+            return if self.eat(Punctuator::Multiply)? {
+                Ok(MethodModifier::AsyncGenerator)
+            } else {
+                Ok(MethodModifier::Async)
+            };
+        }
+
+        if is_key_start {
+            self.advance()?;
+            Ok(modifier)
+        } else {
+            Ok(MethodModifier::None)
+        }
+    }
+
+    /// メソッドの引数リストと本体
+    ///
+    /// ゲッターは引数を持たず、セッターは1つの引数を持つ。
+    pub(crate) fn parse_method_function(
+        &mut self,
+        modifier: MethodModifier,
+    ) -> ParseResult<Function> {
+        let start = self.start();
+        let function =
+            self.parse_function_rest(start, None, modifier.is_async(), modifier.is_generator())?;
+
+        match modifier {
+            MethodModifier::Get if !function.params.is_empty() => self.report(ParseError::new(
+                function.span,
+                "getter must not have any parameters",
+            )),
+            MethodModifier::Set
+                if function.params.len() != 1 || matches!(function.params[0], Pattern::Rest(_)) =>
+            {
+                self.report(ParseError::new(
+                    function.span,
+                    "setter must have exactly one parameter",
+                ));
+            }
+            _ => {}
+        }
+
+        Ok(function)
+    }
+
+    /// `(a, b = 1, ...c)`
+    pub(crate) fn parse_formal_parameters(&mut self) -> ParseResult<Vec<Pattern>> {
+        self.expect(Punctuator::LeftParen)?;
+
+        let mut params = Vec::new();
+        while !self.is(Punctuator::RightParen) {
+            let is_rest = self.is(Punctuator::DotThree);
+            if is_rest {
+                let rest = self.parse_binding_rest_element()?;
+                params.push(Pattern::Rest(Box::new(rest)));
+            } else {
+                params.push(self.parse_binding_element()?);
+            }
+
+            if self.is(Punctuator::RightParen) {
+                break;
+            }
+            if is_rest {
+                self.report(ParseError::new(
+                    self.token_span(),
+                    "rest parameter must be last formal parameter",
+                ));
+            }
+            // `,` がなければ `)` が欠けているものとして引数を終える
+            if !self.eat(Punctuator::Comma)? {
+                break;
+            }
+        }
+        self.expect_closing(Punctuator::RightParen)?;
+
+        Ok(params)
+    }
+
+    /// `{ ... }`
+    ///
+    /// 本体と、本体が strict モードであるかを返す。
+    pub(crate) fn parse_function_body(
+        &mut self,
+        params: &[Pattern],
+    ) -> ParseResult<(FunctionBody, bool)> {
+        let context = Context {
+            allow_in: true,
+            allow_return: true,
+            ..self.context
+        };
+
+        self.with_context(context, |parser| {
+            let start = parser.start();
+            parser.expect(Punctuator::LeftBrace)?;
+            let body = parser.parse_directives_and_statements(params, |parser| {
+                parser.is(Punctuator::RightBrace)
+            })?;
+            parser.expect_closing(Punctuator::RightBrace)?;
+
+            let body = FunctionBody {
+                span: parser.span_from(start),
+                body,
+            };
+            Ok((body, parser.context.strict))
+        })
+    }
+}
@@ -0,0 +1,345 @@
+//! トークンの表記からリテラルの値を求めるモジュールです。
+
+use crate::character_patterns;
+use crate::token::{
+    structs::literal::Literal as LiteralToken,
+    tokenize::{is_identifier_part, is_identifier_start},
+};
+
+/// 文字列リテラルの値
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub(crate) struct CookedString {
+    pub value: String,
+
+    /// `\01` や `\8` のような strict モードで使えないエスケープを含むか
+    pub has_legacy_escape: bool,
+}
+
+/// 数値リテラルの値を求める。
+pub(crate) fn numeric_value(raw: &str, literal: LiteralToken) -> f64 {
+    let digits: String = raw.chars().filter(|char| *char != '_').collect();
+
+    match literal {
+        LiteralToken::BinaryInteger => radix_value(&digits[2..], 2),
+        LiteralToken::OctalInteger => radix_value(&digits[2..], 8),
+        LiteralToken::HexInteger => radix_value(&digits[2..], 16),
+        LiteralToken::LegacyOctalInteger => radix_value(&digits[1..], 8),
+        _ => digits.parse().unwrap_or(f64::NAN),
+    }
+}
+
+fn radix_value(digits: &str, radix: u32) -> f64 {
+    match u128::from_str_radix(digits, radix) {
+        Ok(value) => value as f64,
+        Err(_) => digits.chars().fold(0.0, |value, char| {
+            value * radix as f64 + char.to_digit(radix).unwrap_or(0) as f64
+        }),
+    }
+}
+
+/// BigInt リテラルの値を10進数の文字列で求める。
+pub(crate) fn bigint_value(raw: &str, literal: LiteralToken) -> String {
+    let digits: String = raw
+        .chars()
+        .filter(|char| *char != '_' && *char != 'n')
+        .collect();
+
+    let (digits, radix) = match literal {
+        LiteralToken::BinaryBigInteger => (&digits[2..], 2),
+        LiteralToken::OctalBigInteger => (&digits[2..], 8),
+        LiteralToken::HexBigInteger => (&digits[2..], 16),
+        _ => (&digits[..], 10),
+    };
+
+    // 10^9 を基数とした下位からの桁
+    const BASE: u64 = 1_000_000_000;
+    let mut limbs: Vec<u64> = vec![0];
+
+    for char in digits.chars() {
+        let mut carry = char.to_digit(radix).unwrap_or(0) as u64;
+        for limb in limbs.iter_mut() {
+            let value = *limb * radix as u64 + carry;
+            *limb = value % BASE;
+            carry = value / BASE;
+        }
+        if carry > 0 {
+            limbs.push(carry);
+        }
+    }
+
+    let mut result = limbs.last().map(u64::to_string).unwrap_or_default();
+    for limb in limbs.iter().rev().skip(1) {
+        result.push_str(&format!("{limb:09}"));
+    }
+    result
+}
+
+/// 引用符を除いた文字列リテラルの中身からエスケープを解釈する。
+pub(crate) fn cook_string(body: &str) -> Result<CookedString, String> {
+    let mut units = Vec::new();
+    let has_legacy_escape = decode_escapes(body, false, &mut units)?;
+
+    Ok(CookedString {
+        value: String::from_utf16_lossy(&units),
+        has_legacy_escape,
+    })
+}
+
+/// テンプレートの文字列部分からエスケープを解釈する。
+pub(crate) fn cook_template(raw: &str) -> Result<String, String> {
+    let mut units = Vec::new();
+    decode_escapes(raw, true, &mut units)?;
+
+    Ok(String::from_utf16_lossy(&units))
+}
+
+/// テンプレートの文字列部分の改行を <LF> に正規化する。
+pub(crate) fn normalize_template_raw(raw: &str) -> String {
+    raw.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+/// エスケープを解釈して UTF-16 の符号単位を追加する。
+///
+/// テンプレートでは 8 進数のエスケープをエラーにする。
+/// 文字列では 8 進数のエスケープを含むかを返す。
+fn decode_escapes(body: &str, is_template: bool, units: &mut Vec<u16>) -> Result<bool, String> {
+    let mut has_legacy_escape = false;
+    let mut chars = body.chars().peekable();
+    let mut buffer = [0; 2];
+
+    while let Some(char) = chars.next() {
+        match char {
+            '\\' => {}
+
+            // テンプレートの <CR><LF> と <CR> は <LF> とみなす
+            '\r' if is_template => {
+                if chars.peek() == Some(&'\n') {
+                    chars.next();
+                }
+                units.push('\n' as u16);
+                continue;
+            }
+
+            char => {
+                units.extend_from_slice(char.encode_utf16(&mut buffer));
+                continue;
+            }
+        }
+
+        let Some(escaped) = chars.next() else {
+            return Err("error escape sequence is not complete".to_string());
+        };
+
+        match escaped {
+            // 行継続
+            '\r' => {
+                if chars.peek() == Some(&'\n') {
+                    chars.next();
+                }
+            }
+            character_patterns!(LineTerminator without CR) => {}
+
+            'b' => units.push(0x08),
+            'f' => units.push(0x0C),
+            'n' => units.push(0x0A),
+            'r' => units.push(0x0D),
+            't' => units.push(0x09),
+            'v' => units.push(0x0B),
+
+            '0' if !matches!(chars.peek(), Some('0'..='9')) => units.push(0),
+
+            '0'..='7' => {
+                if is_template {
+                    return Err(
+                        "error octal escape sequence is not allowed in template".to_string()
+                    );
+                }
+                has_legacy_escape = true;
+
+                // \377 までを 8 進数として読む
+                let mut value = escaped.to_digit(8).unwrap_or(0);
+                let max_digits = if escaped <= '3' { 2 } else { 1 };
+                for _ in 0..max_digits {
+                    match chars.peek().and_then(|char| char.to_digit(8)) {
+                        Some(digit) => {
+                            value = value * 8 + digit;
+                            chars.next();
+                        }
+                        None => break,
+                    }
+                }
+                units.push(value as u16);
+            }
+
+            '8' | '9' => {
+                if is_template {
+                    return Err("error \\8 and \\9 are not allowed in template".to_string());
+                }
+                has_legacy_escape = true;
+                units.push(escaped as u16);
+            }
+
+            'x' => {
+                let value = read_hex_digits(&mut chars, 2)
+                    .ok_or_else(|| "error invalid hexadecimal escape sequence".to_string())?;
+                units.push(value as u16);
+            }
+
+            'u' => {
+                let value = read_unicode_escape_value(&mut chars)?;
+                push_code_point(units, value);
+            }
+
+            char => units.extend_from_slice(char.encode_utf16(&mut buffer)),
+        }
+    }
+
+    Ok(has_legacy_escape)
+}
+
+fn read_hex_digits(chars: &mut impl Iterator<Item = char>, count: usize) -> Option<u32> {
+    let mut value = 0;
+    for _ in 0..count {
+        value = value * 16 + chars.next()?.to_digit(16)?;
+    }
+    Some(value)
+}
+
+/// `\u` の後ろの `XXXX` か `{X...}` を読む。
+fn read_unicode_escape_value(
+    chars: &mut std::iter::Peekable<impl Iterator<Item = char>>,
+) -> Result<u32, String> {
+    if chars.peek() == Some(&'{') {
+        chars.next();
+        let mut value: u32 = 0;
+        let mut digits = 0;
+
+        loop {
+            match chars.next() {
+                Some('}') if digits > 0 => break,
+                Some(char) if char.is_ascii_hexdigit() => {
+                    value = value
+                        .saturating_mul(16)
+                        .saturating_add(char.to_digit(16).unwrap_or(0));
+                    digits += 1;
+                }
+                _ => return Err("error invalid unicode escape sequence".to_string()),
+            }
+        }
+
+        if value > 0x10FFFF {
+            return Err("error unicode escape sequence is out of range".to_string());
+        }
+        Ok(value)
+    } else {
+        read_hex_digits(chars, 4).ok_or_else(|| "error invalid unicode escape sequence".to_string())
+    }
+}
+
+fn push_code_point(units: &mut Vec<u16>, value: u32) {
+    match char::from_u32(value) {
+        Some(char) => {
+            let mut buffer = [0; 2];
+            units.extend_from_slice(char.encode_utf16(&mut buffer));
+        }
+        // サロゲートはそのまま符号単位として追加する
+        None => units.push(value as u16),
+    }
+}
+
+/// 識別子の表記からエスケープを解釈した名前を求める。
+pub(crate) fn identifier_value(raw: &str) -> Result<String, String> {
+    if !raw.contains('\\') {
+        return Ok(raw.to_string());
+    }
+
+    let mut name = String::new();
+    let mut chars = raw.chars().peekable();
+
+    while let Some(char) = chars.next() {
+        let char = if char == '\\' {
+            chars.next();
+            let value = read_unicode_escape_value(&mut chars)?;
+            char::from_u32(value).ok_or_else(|| "error invalid identifier escape".to_string())?
+        } else {
+            char
+        };
+
+        let is_valid = if name.is_empty() {
+            is_identifier_start(char)
+        } else {
+            is_identifier_part(char)
+        };
+        if !is_valid || char == '\\' {
+            return Err(format!("error '{char}' cannot be used in identifier"));
+        }
+
+        name.push(char);
+    }
+
+    Ok(name)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::token::structs::literal::Literal as LiteralToken;
+
+    use super::{bigint_value, cook_string, cook_template, identifier_value, numeric_value};
+
+    #[test]
+    fn numeric_values() {
+        assert_eq!(numeric_value("1_000", LiteralToken::DecimalInteger), 1000.0);
+        assert_eq!(numeric_value(".5", LiteralToken::DecimalPointNumber), 0.5);
+        assert_eq!(
+            numeric_value("1e3", LiteralToken::DecimalExponentNumber),
+            1000.0
+        );
+        assert_eq!(numeric_value("0x1F", LiteralToken::HexInteger), 31.0);
+        assert_eq!(numeric_value("0b101", LiteralToken::BinaryInteger), 5.0);
+        assert_eq!(numeric_value("017", LiteralToken::LegacyOctalInteger), 15.0);
+        assert_eq!(numeric_value("019", LiteralToken::DecimalInteger), 19.0);
+    }
+
+    #[test]
+    fn bigint_values() {
+        assert_eq!(bigint_value("0n", LiteralToken::DecimalBigInteger), "0");
+        assert_eq!(bigint_value("0xFFn", LiteralToken::HexBigInteger), "255");
+        assert_eq!(
+            bigint_value("0xFFFF_FFFF_FFFF_FFFF_FFFFn", LiteralToken::HexBigInteger),
+            "1208925819614629174706175"
+        );
+    }
+
+    #[test]
+    fn string_escapes() {
+        let cooked = cook_string(
+            r"a\nb\x41B\u{1F600}\
+c",
+        )
+        .unwrap();
+        assert_eq!(cooked.value, "a\nbAB\u{1F600}c");
+        assert!(!cooked.has_legacy_escape);
+
+        let cooked = cook_string(r"\101\0").unwrap();
+        assert_eq!(cooked.value, "A\0");
+        assert!(cooked.has_legacy_escape);
+
+        assert_eq!(cook_string(r"😀").unwrap().value, "\u{1F600}");
+        assert_eq!(cook_string(r"\uD83D").unwrap().value, "\u{FFFD}");
+        assert!(cook_string(r"\x4").is_err());
+    }
+
+    #[test]
+    fn template_escapes() {
+        assert_eq!(cook_template("a\r\nb").unwrap(), "a\nb");
+        assert!(cook_template(r"\01").is_err());
+        assert_eq!(cook_template(r"\0").unwrap(), "\0");
+    }
+
+    #[test]
+    fn identifier_escapes() {
+        assert_eq!(identifier_value(r"abc").unwrap(), "abc");
+        assert_eq!(identifier_value(r"a\u{62}").unwrap(), "ab");
+        assert!(identifier_value(r"\u0031").is_err());
+    }
+}
@@ -0,0 +1,483 @@
+use crate::token::structs::{punctuator::Punctuator, token::TokenType};
+
+use super::Parser;
+use crate::ast::{
+    error::ParseResult,
+    structs::{
+        expression::PropertyKey,
+        literal::Literal,
+        module::{
+            Declaration, ExportAllDeclaration, ExportDefaultDeclaration, ExportDefaultKind,
+            ExportNamedDeclaration, ExportSpecifier, ImportAttribute, ImportDeclaration,
+            ImportDeclarationSpecifier, ImportDefaultSpecifier, ImportNamespaceSpecifier,
+            ImportSpecifier, ModuleDeclaration, ModuleExportName, ModuleItem,
+        },
+        span::Spanned,
+        statement::Statement,
+    },
+};
+
+impl<'a> Parser<'a> {
+    /// ModuleItem
+    pub(crate) fn parse_module_item(&mut self) -> ParseResult<ModuleItem> {
+        if self.is_keyword("import") {
+            let (next, _) = self.lookahead()?;
+
+            // import(a) と import.meta は式
+            if !matches!(
+                next.token_type(),
+                TokenType::Punctuator(Punctuator::LeftParen | Punctuator::Dot)
+            ) {
+                let declaration = self.parse_import_declaration()?;
+                return Ok(ModuleItem::ModuleDeclaration(ModuleDeclaration::Import(
+                    declaration,
+                )));
+            }
+        }
+
+        if self.is_keyword("export") {
+            let declaration = self.parse_export_declaration()?;
+            return Ok(ModuleItem::ModuleDeclaration(declaration));
+        }
+
+        Ok(ModuleItem::Statement(self.parse_statement_list_item()?))
+    }
+
+    /// `import a, { b as c } from "d";` `import * as a from "b";` `import "a";`
+    fn parse_import_declaration(&mut self) -> ParseResult<ImportDeclaration> {
+        let start = self.start();
+        self.expect_keyword("import")?;
+
+        let mut specifiers = Vec::new();
+
+        if !self.is_string_literal() {
+            // import a
+            if self.token_type() == TokenType::Identifier {
+                let local = self.parse_binding_identifier()?;
+                specifiers.push(ImportDeclarationSpecifier::Default(
+                    ImportDefaultSpecifier {
+                        span: local.span,
+                        local,
+                    },
+                ));
+            }
+
+            // import a, * as b
+            // import a, { b }
+            if specifiers.is_empty() || self.eat(Punctuator::Comma)? {
+                if self.is(Punctuator::Multiply) {
+                    let namespace_start = self.start();
+                    self.advance()?;
+                    self.expect_keyword("as")?;
+                    let local = self.parse_binding_identifier()?;
+                    specifiers.push(ImportDeclarationSpecifier::Namespace(
+                        ImportNamespaceSpecifier {
+                            span: self.span_from(namespace_start),
+                            local,
+                        },
+                    ));
+                } else if self.is(Punctuator::LeftBrace) {
+                    self.parse_named_imports(&mut specifiers)?;
+                } else {
+                    return self.unexpected();
+                }
+            }
+
+            self.expect_keyword("from")?;
+        }
+
+        let source = self.parse_module_specifier()?;
+        let attributes = self.parse_import_attributes()?;
+        self.consume_semicolon()?;
+
+        Ok(ImportDeclaration {
+            span: self.span_from(start),
+            specifiers,
+            source,
+            attributes,
+        })
+    }
+
+    /// `{ a, b as c, "d" as e }`
+    fn parse_named_imports(
+        &mut self,
+        specifiers: &mut Vec<ImportDeclarationSpecifier>,
+    ) -> ParseResult<()> {
+        self.expect(Punctuator::LeftBrace)?;
+
+        while !self.eat(Punctuator::RightBrace)? {
+            let start = self.start();
+            let has_escape = self.text().contains('\\');
+            let imported = self.parse_module_export_name()?;
+
+            let local = if self.eat_keyword("as")? {
+                self.parse_binding_identifier()?
+            } else {
+                // { a } は a を束縛するため、文字列や予約語は使えない
+                let ModuleExportName::Identifier(identifier) = &imported else {
+                    return self.error(
+                        imported.span(),
+                        "string import name must be followed by 'as'",
+                    );
+                };
+                self.check_reserved_word(identifier, has_escape)?;
+                self.check_binding_name(identifier)?;
+                identifier.clone()
+            };
+
+            specifiers.push(ImportDeclarationSpecifier::Named(ImportSpecifier {
+                span: self.span_from(start),
+                imported,
+                local,
+            }));
+
+            if !self.is(Punctuator::RightBrace) {
+                self.expect(Punctuator::Comma)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// export 宣言
+    fn parse_export_declaration(&mut self) -> ParseResult<ModuleDeclaration> {
+        let start = self.start();
+        self.expect_keyword("export")?;
+
+        // export * from "a";
+        // export * as a from "b";
+        if self.eat(Punctuator::Multiply)? {
+            let exported = if self.eat_keyword("as")? {
+                Some(self.parse_module_export_name()?)
+            } else {
+                None
+            };
+            self.expect_keyword("from")?;
+            let source = self.parse_module_specifier()?;
+            let attributes = self.parse_import_attributes()?;
+            self.consume_semicolon()?;
+
+            return Ok(ModuleDeclaration::ExportAll(ExportAllDeclaration {
+                span: self.span_from(start),
+                exported,
+                source,
+                attributes,
+            }));
+        }
+
+        if self.eat_keyword("default")? {
+            let declaration = self.parse_export_default()?;
+            return Ok(ModuleDeclaration::ExportDefault(ExportDefaultDeclaration {
+                span: self.span_from(start),
+                declaration,
+            }));
+        }
+
+        // export { a, b as c };
+        // export { a } from "b";
+        if self.is(Punctuator::LeftBrace) {
+            let specifiers = self.parse_export_specifiers()?;
+
+            let (source, attributes) = if self.eat_keyword("from")? {
+                let source = self.parse_module_specifier()?;
+                (Some(source), self.parse_import_attributes()?)
+            } else {
+                // 再エクスポートでない場合は、ローカルの名前は変数を参照する
+                for specifier in &specifiers {
+                    match &specifier.local {
+                        ModuleExportName::Identifier(identifier) => {
+                            let has_escape =
+                                identifier.span.source_text(self.source).contains('\\');
+                            self.check_reserved_word(identifier, has_escape)?;
+                        }
+                        ModuleExportName::Literal(literal) => {
+                            return self.error(
+                                literal.span,
+                                "string export name must be used with 'from'",
+                            );
+                        }
+                    }
+                }
+                (None, Vec::new())
+            };
+            self.consume_semicolon()?;
+
+            return Ok(ModuleDeclaration::ExportNamed(ExportNamedDeclaration {
+                span: self.span_from(start),
+                declaration: None,
+                specifiers,
+                source,
+                attributes,
+            }));
+        }
+
+        // export var a; export function a() {} export async function a() {} export class A {}
+        let is_async_function = self.is_async_function_start()?;
+        let declaration = match self.text() {
+            _ if self.token_type() != TokenType::Identifier => return self.unexpected(),
+            "async" if !is_async_function => return self.unexpected(),
+            "var" | "let" | "const" | "function" | "class" | "async" => {
+                match self.parse_statement_list_item()? {
+                    Statement::VariableDeclaration(declaration) => {
+                        Declaration::Variable(declaration)
+                    }
+                    Statement::FunctionDeclaration(function) => Declaration::Function(function),
+                    Statement::ClassDeclaration(class) => Declaration::Class(class),
+                    statement => return self.error(statement.span(), "expected declaration"),
+                }
+            }
+            _ => return self.unexpected(),
+        };
+
+        Ok(ModuleDeclaration::ExportNamed(ExportNamedDeclaration {
+            span: self.span_from(start),
+            declaration: Some(declaration),
+            specifiers: Vec::new(),
+            source: None,
+            attributes: Vec::new(),
+        }))
+    }
+
+    /// `export default` の後ろ
+    fn parse_export_default(&mut self) -> ParseResult<ExportDefaultKind> {
+        if self.is_keyword("function") || self.is_async_function_start()? {
+            return Ok(ExportDefaultKind::Function(Box::new(
+                self.parse_function(false)?,
+            )));
+        }
+
+        if self.is_keyword("class") {
+            return Ok(ExportDefaultKind::Class(Box::new(self.parse_class(false)?)));
+        }
+
+        let expression = self.with_allow_in(true, Self::parse_assignment_expression)?;
+        self.consume_semicolon()?;
+        Ok(ExportDefaultKind::Expression(expression))
+    }
+
+    /// `{ a, b as c, d as "e" }`
+    fn parse_export_specifiers(&mut self) -> ParseResult<Vec<ExportSpecifier>> {
+        self.expect(Punctuator::LeftBrace)?;
+
+        let mut specifiers = Vec::new();
+        while !self.eat(Punctuator::RightBrace)? {
+            let start = self.start();
+            let local = self.parse_module_export_name()?;
+            let exported = if self.eat_keyword("as")? {
+                self.parse_module_export_name()?
+            } else {
+                local.clone()
+            };
+
+            specifiers.push(ExportSpecifier {
+                span: self.span_from(start),
+                local,
+                exported,
+            });
+
+            if !self.is(Punctuator::RightBrace) {
+                self.expect(Punctuator::Comma)?;
+            }
+        }
+
+        Ok(specifiers)
+    }
+
+    /// 識別子名か文字列リテラル
+    fn parse_module_export_name(&mut self) -> ParseResult<ModuleExportName> {
+        match self.token_type() {
+            TokenType::Identifier => {
+                Ok(ModuleExportName::Identifier(self.parse_identifier_name()?))
+            }
+            _ if self.is_string_literal() => Ok(ModuleExportName::Literal(self.parse_literal()?)),
+            _ => self.unexpected(),
+        }
+    }
+
+    /// `from` の後ろの文字列リテラル
+    fn parse_module_specifier(&mut self) -> ParseResult<Literal> {
+        if !self.is_string_literal() {
+            return self.unexpected();
+        }
+        self.parse_literal()
+    }
+
+    /// `with { type: "json" }`
+    fn parse_import_attributes(&mut self) -> ParseResult<Vec<ImportAttribute>> {
+        if !self.eat_keyword("with")? {
+            return Ok(Vec::new());
+        }
+        self.expect(Punctuator::LeftBrace)?;
+
+        let mut attributes: Vec<ImportAttribute> = Vec::new();
+        while !self.eat(Punctuator::RightBrace)? {
+            let start = self.start();
+            let key = match self.token_type() {
+                TokenType::Identifier => PropertyKey::Identifier(self.parse_identifier_name()?),
+                _ if self.is_string_literal() => PropertyKey::Literal(self.parse_literal()?),
+                _ => return self.unexpected(),
+            };
+
+            let name = key.static_name().unwrap_or_default();
+            if attributes
+                .iter()
+                .any(|attribute| attribute.key.static_name() == Some(name))
+            {
+                return self.error(key.span(), format!("duplicate import attribute '{name}'"));
+            }
+
+            self.expect(Punctuator::TernaryColon)?;
+            let value = self.parse_module_specifier()?;
+
+            attributes.push(ImportAttribute {
+                span: self.span_from(start),
+                key,
+                value,
+            });
+
+            if !self.is(Punctuator::RightBrace) {
+                self.expect(Punctuator::Comma)?;
+            }
+        }
+
+        Ok(attributes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test_helper::{parse_module_error, parse_module_sexp};
+
+    macro_rules! test_case {
+        ($test_name:ident, $source:expr, $expected:expr) => {
+            #[test]
+            fn $test_name() {
+                assert_eq!(parse_module_sexp($source), Ok($expected.to_string()));
+            }
+        };
+    }
+
+    macro_rules! test_case_fail {
+        ($test_name:ident, $source:expr) => {
+            #[test]
+            fn $test_name() {
+                assert!(parse_module_error($source).is_some());
+            }
+        };
+    }
+
+    test_case!(import_side_effect, "import 'a';", "(import 'a')");
+    test_case!(import_default, "import a from 'b';", "(import 'b' a)");
+    test_case!(
+        import_namespace,
+        "import * as a from 'b';",
+        "(import 'b' (* a))"
+    );
+    test_case!(
+        import_default_and_namespace,
+        "import a, * as b from 'c';",
+        "(import 'c' a (* b))"
+    );
+    test_case!(
+        import_named,
+        "import a, { b, c as d, default as e, 'f g' as h } from 'i';",
+        "(import 'i' a (as b b) (as c d) (as default e) (as 'f g' h))"
+    );
+    test_case!(import_empty_named, "import {} from 'a';", "(import 'a')");
+    test_case!(
+        import_attributes,
+        "import a from './a.json' with { type: 'json' };",
+        "(import './a.json' a (with type 'json'))"
+    );
+    test_case!(import_call, "import('a');", "(import 'a')");
+    test_case!(import_meta, "import.meta.url;", "(. import.meta url)");
+    test_case!(export_all, "export * from 'a';", "(export * 'a')");
+    test_case!(
+        export_all_as,
+        "export * as a from 'b';",
+        "(export * (as a) 'b')"
+    );
+    test_case!(
+        export_all_as_string,
+        "export * as 'a b' from 'c';",
+        "(export * (as 'a b') 'c')"
+    );
+    test_case!(
+        export_named,
+        "let a, b; export { a, b as c, a as 'd e' };",
+        "(let a b) (export (as a a) (as b c) (as a 'd e'))"
+    );
+    test_case!(
+        export_from,
+        "export { default, 'a b' as c, d as e } from 'e';",
+        "(export (as default default) (as 'a b' c) (as d e) (from 'e'))"
+    );
+    test_case!(export_var, "export var a = 1;", "(export (var (= a 1)))");
+    test_case!(
+        export_const,
+        "export const a = 1, b = 2;",
+        "(export (const (= a 1) (= b 2)))"
+    );
+    test_case!(
+        export_function,
+        "export function f() {}",
+        "(export (function f))"
+    );
+    test_case!(
+        export_async_function,
+        "export async function f() {}",
+        "(export (async function f))"
+    );
+    test_case!(export_class, "export class A {}", "(export (class A))");
+    test_case!(
+        export_default_expression,
+        "export default a + b;",
+        "(export-default (+ a b))"
+    );
+    test_case!(
+        export_default_function,
+        "export default function () {}",
+        "(export-default (function))"
+    );
+    test_case!(
+        export_default_async_function,
+        "export default async function () { await a; }",
+        "(export-default (async function (await a)))"
+    );
+    test_case!(
+        export_default_named_class,
+        "export default class A {}",
+        "(export-default (class A))"
+    );
+    test_case!(top_level_await, "await a;", "(await a)");
+    test_case!(
+        for_await_of,
+        "for await (const a of b) c;",
+        "(for-await-of (const a) b c)"
+    );
+    test_case!(
+        for_await_top_level,
+        "for await (const a of b);",
+        "(for-await-of (const a) b (empty))"
+    );
+
+    test_case_fail!(strict_with, "with (a) b;");
+    test_case_fail!(strict_octal, "017;");
+    test_case_fail!(await_identifier, "var await;");
+    test_case_fail!(await_in_function, "function f() { await a; }");
+    test_case_fail!(import_string_without_as, "import { 'a' } from 'b';");
+    test_case_fail!(import_reserved_without_as, "import { default } from 'a';");
+    test_case_fail!(import_missing_from, "import a 'b';");
+    test_case_fail!(import_nested, "{ import a from 'b'; }");
+    test_case_fail!(export_string_local, "export { 'a' };");
+    test_case_fail!(export_reserved_local, "export { default };");
+    test_case_fail!(export_expression, "export a;");
+    test_case_fail!(
+        duplicate_attribute,
+        "import a from 'b' with { type: 'json', type: 'json' };"
+    );
+    test_case_fail!(
+        attribute_not_string,
+        "import a from 'b' with { type: json };"
+    );
+}
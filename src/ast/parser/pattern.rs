@@ -0,0 +1,195 @@
+use crate::token::structs::{punctuator::Punctuator, token::TokenType};
+
+use super::Parser;
+use crate::ast::{
+    error::ParseResult,
+    structs::{
+        expression::PropertyKey,
+        literal::Identifier,
+        pattern::{
+            ArrayPattern, AssignmentPattern, AssignmentProperty, ObjectPattern,
+            ObjectPatternProperty, Pattern, RestElement,
+        },
+    },
+};
+
+impl<'a> Parser<'a> {
+    /// BindingIdentifier
+    pub(crate) fn parse_binding_identifier(&mut self) -> ParseResult<Identifier> {
+        let identifier = self.parse_identifier_reference()?;
+        self.check_binding_name(&identifier)?;
+        Ok(identifier)
+    }
+
+    /// strict モードでは `eval` と `arguments` を束縛できない。
+    pub(crate) fn check_binding_name(&self, identifier: &Identifier) -> ParseResult<()> {
+        if self.context.strict && matches!(identifier.name.as_str(), "eval" | "arguments") {
+            return self.error(
+                identifier.span,
+                format!("cannot bind '{}' in strict mode", identifier.name),
+            );
+        }
+        Ok(())
+    }
+
+    /// LabelIdentifier
+    pub(crate) fn parse_label_identifier(&mut self) -> ParseResult<Identifier> {
+        self.parse_identifier_reference()
+    }
+
+    /// 束縛の対象 (識別子、配列パターン、オブジェクトパターン)
+    pub(crate) fn parse_binding_target(&mut self) -> ParseResult<Pattern> {
+        match self.token_type() {
+            TokenType::Punctuator(Punctuator::LeftBracket) => self.parse_array_binding_pattern(),
+            TokenType::Punctuator(Punctuator::LeftBrace) => self.parse_object_binding_pattern(),
+            TokenType::Identifier => Ok(Pattern::Identifier(self.parse_binding_identifier()?)),
+            _ => self.unexpected(),
+        }
+    }
+
+    /// 既定値を持つことができる束縛の対象 `a = 1`
+    pub(crate) fn parse_binding_element(&mut self) -> ParseResult<Pattern> {
+        let start = self.start();
+        let target = self.parse_binding_target()?;
+        self.parse_binding_initializer(start, target)
+    }
+
+    /// 束縛の対象の後ろの `= a`
+    fn parse_binding_initializer(&mut self, start: usize, target: Pattern) -> ParseResult<Pattern> {
+        if !self.eat(Punctuator::Assign)? {
+            return Ok(target);
+        }
+
+        let right = self.with_allow_in(true, Self::parse_assignment_expression)?;
+        Ok(Pattern::Assignment(Box::new(AssignmentPattern {
+            span: self.span_from(start),
+            left: target,
+            right,
+        })))
+    }
+
+    /// `...a`
+    pub(crate) fn parse_binding_rest_element(&mut self) -> ParseResult<RestElement> {
+        let start = self.start();
+        self.expect(Punctuator::DotThree)?;
+        let argument = self.parse_binding_target()?;
+
+        if self.is(Punctuator::Assign) {
+            return self.error(
+                self.token_span(),
+                "rest element cannot have a default value",
+            );
+        }
+
+        Ok(RestElement {
+            span: self.span_from(start),
+            argument,
+        })
+    }
+
+    /// `[a, , ...b]`
+    fn parse_array_binding_pattern(&mut self) -> ParseResult<Pattern> {
+        let start = self.start();
+        self.expect(Punctuator::LeftBracket)?;
+
+        let mut elements = Vec::new();
+        while !self.eat(Punctuator::RightBracket)? {
+            if self.eat(Punctuator::Comma)? {
+                elements.push(None);
+                continue;
+            }
+
+            if self.is(Punctuator::DotThree) {
+                let rest = self.parse_binding_rest_element()?;
+                elements.push(Some(Pattern::Rest(Box::new(rest))));
+                self.expect_rest_end(Punctuator::RightBracket)?;
+                break;
+            }
+
+            elements.push(Some(self.parse_binding_element()?));
+            if !self.is(Punctuator::RightBracket) {
+                self.expect(Punctuator::Comma)?;
+            }
+        }
+
+        Ok(Pattern::Array(ArrayPattern {
+            span: self.span_from(start),
+            elements,
+        }))
+    }
+
+    /// `{ a, b: c, ...d }`
+    fn parse_object_binding_pattern(&mut self) -> ParseResult<Pattern> {
+        let start = self.start();
+        self.expect(Punctuator::LeftBrace)?;
+
+        let mut properties = Vec::new();
+        while !self.eat(Punctuator::RightBrace)? {
+            if self.is(Punctuator::DotThree) {
+                let rest_start = self.start();
+                self.advance()?;
+                let argument = Pattern::Identifier(self.parse_binding_identifier()?);
+                properties.push(ObjectPatternProperty::Rest(RestElement {
+                    span: self.span_from(rest_start),
+                    argument,
+                }));
+                self.expect_rest_end(Punctuator::RightBrace)?;
+                break;
+            }
+
+            properties.push(ObjectPatternProperty::Property(
+                self.parse_binding_property()?,
+            ));
+            if !self.is(Punctuator::RightBrace) {
+                self.expect(Punctuator::Comma)?;
+            }
+        }
+
+        Ok(Pattern::Object(ObjectPattern {
+            span: self.span_from(start),
+            properties,
+        }))
+    }
+
+    /// `a` `a = 1` `a: b`
+    fn parse_binding_property(&mut self) -> ParseResult<AssignmentProperty> {
+        let start = self.start();
+        let is_identifier = self.token_type() == TokenType::Identifier;
+        let has_escape = self.text().contains('\\');
+        let key = self.parse_property_key()?;
+
+        if self.eat(Punctuator::TernaryColon)? {
+            let value = self.parse_binding_element()?;
+            return Ok(AssignmentProperty {
+                span: self.span_from(start),
+                key,
+                value,
+                shorthand: false,
+            });
+        }
+
+        let identifier = match key {
+            PropertyKey::Identifier(identifier) if is_identifier => identifier,
+            _ => return self.unexpected(),
+        };
+        self.check_reserved_word(&identifier, has_escape)?;
+        self.check_binding_name(&identifier)?;
+
+        let value =
+            self.parse_binding_initializer(start, Pattern::Identifier(identifier.clone()))?;
+        Ok(AssignmentProperty {
+            span: self.span_from(start),
+            key: PropertyKey::Identifier(identifier),
+            value,
+            shorthand: true,
+        })
+    }
+
+    /// 残余要素の後ろは閉じ括弧でなければならない。
+    fn expect_rest_end(&mut self, close: Punctuator) -> ParseResult<()> {
+        if self.is(Punctuator::Comma) {
+            return self.error(self.token_span(), "rest element must be last element");
+        }
+        self.expect(close)
+    }
+}
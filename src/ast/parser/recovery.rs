@@ -0,0 +1,396 @@
+//! 構文エラーから回復して解析を続けるモジュールです。
+//!
+//! 文の中でエラーが起きた場合は、エラーを記録して次の文の始まりまで読み飛ばし、
+//! 読めなかった範囲を [`ErrorNode`] として構文木に残す。
+//! 閉じ括弧や式が欠けている場合は、補ったものとして解析を続ける。
+
+use crate::{
+    character_patterns,
+    token::structs::{context::DivContext, punctuator::Punctuator, token::TokenType},
+};
+
+use super::Parser;
+use crate::ast::{
+    error::{ParseError, ParseResult},
+    structs::{expression::Expression, span::Span, statement::Statement, ErrorNode},
+};
+
+/// 行頭にあっても前の行の式の続きになる語
+const CONTINUATION_KEYWORDS: &[&str] = &["in", "instanceof"];
+
+/// 式の途中で読んでしまっても、行頭にあれば新しい文の始まりとみなす語
+const STATEMENT_KEYWORDS: &[&str] = &[
+    "break", "class", "const", "continue", "debugger", "do", "export", "for", "function", "if",
+    "import", "let", "return", "switch", "throw", "try", "var", "while", "with",
+];
+
+impl<'a> Parser<'a> {
+    /// 解析中に見つけたエラーを取り出す。
+    pub fn take_errors(&mut self) -> Vec<ParseError> {
+        std::mem::take(&mut self.errors)
+    }
+
+    /// エラーを記録して解析を続ける。
+    ///
+    /// 直前のエラーと同じ範囲で同じメッセージのエラーは、同じ誤りによるものとして記録しない。
+    pub(crate) fn report(&mut self, error: ParseError) {
+        if self.errors.last() == Some(&error) {
+            return;
+        }
+        self.errors.push(error);
+    }
+
+    /// 文を読む。エラーの場合は記録して次の文まで読み飛ばし、エラーのノードを返す。
+    pub(crate) fn parse_or_recover<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> ParseResult<T>,
+        error_node: impl FnOnce(ErrorNode) -> T,
+    ) -> T {
+        let start = self.start();
+
+        // 対応のない閉じ括弧は欠けた式とみなさず、その1つだけを読み飛ばす
+        if matches!(
+            self.token_type(),
+            TokenType::Punctuator(
+                Punctuator::RightParen | Punctuator::RightBracket | Punctuator::RightBrace
+            )
+        ) {
+            if let Err(error) = self.unexpected::<()>() {
+                self.report(error);
+            }
+            self.skip_token();
+
+            return error_node(ErrorNode {
+                span: self.span_from(start),
+            });
+        }
+
+        match parse(self) {
+            Ok(node) => node,
+            Err(error) => {
+                let restart = self.statement_keyword_before(start, error.span);
+                self.report(error);
+                match restart {
+                    Some(position) => self.rewind(position),
+                    None => self.synchronize(start),
+                }
+
+                // 読み飛ばした文の中で保留していた `{a = 1}` は報告済みのエラーに含める
+                self.cover_initialized_names
+                    .retain(|span| span.start < start);
+
+                error_node(ErrorNode {
+                    span: self.span_from(start),
+                })
+            }
+        }
+    }
+
+    /// 文を読み、エラーの場合は [`Statement::Error`] を返す。
+    pub(crate) fn parse_statement_list_item_or_recover(&mut self) -> Statement {
+        self.parse_or_recover(Self::parse_statement_list_item, Statement::Error)
+    }
+
+    /// 次の文の始まりまで読み飛ばす。
+    ///
+    /// 括弧の外の `;` の後ろ、対応のない `}` の前、括弧の外で文を始められる行頭のトークンの前で止まる。
+    /// セミコロンを書かないコードでも、次の行の文を読み飛ばさないようにする。
+    fn synchronize(&mut self, start: usize) {
+        // 文の始まりでエラーになった場合は少なくとも1つ読み飛ばす
+        if self.start() == start && !self.is_end() {
+            self.skip_token();
+        }
+
+        // `{}` の深さと、`()` `[]` の深さ
+        let mut brace_depth = 0usize;
+        let mut bracket_depth = 0usize;
+
+        while !self.is_end() {
+            let is_outside = brace_depth == 0 && bracket_depth == 0;
+
+            match self.token_type() {
+                TokenType::Punctuator(Punctuator::SemiColon) if is_outside => {
+                    self.skip_token();
+                    return;
+                }
+                TokenType::Punctuator(Punctuator::RightBrace) if brace_depth == 0 => return,
+                TokenType::Punctuator(Punctuator::RightBrace) => brace_depth -= 1,
+                TokenType::Punctuator(Punctuator::LeftBrace) => brace_depth += 1,
+                TokenType::Punctuator(Punctuator::LeftParen | Punctuator::LeftBracket) => {
+                    bracket_depth += 1;
+                }
+                // 文の途中で開いた括弧の閉じ括弧は深さを変えずに読み飛ばす
+                TokenType::Punctuator(Punctuator::RightParen | Punctuator::RightBracket) => {
+                    bracket_depth = bracket_depth.saturating_sub(1);
+                }
+                _ if is_outside && self.has_newline_before && self.can_start_statement() => {
+                    return;
+                }
+                _ => {}
+            }
+
+            self.skip_token();
+        }
+    }
+
+    /// エラーの位置か、その直前に読んだ行頭の文の始まりの語の位置を返す。
+    ///
+    /// `foo(\nvar x = 1;` の `var` のように、閉じていない括弧の中で次の行の文を
+    /// 読んでしまった場合に、その文から読み直すために使う。
+    fn statement_keyword_before(&self, start: usize, error: Span) -> Option<usize> {
+        let index = self
+            .tokens
+            .iter()
+            .rposition(|token| token.start_index() <= error.start)?;
+
+        (index.saturating_sub(1)..=index).rev().find_map(|index| {
+            let token = &self.tokens[index];
+            let previous_end = index
+                .checked_sub(1)
+                .map_or(0, |previous| self.tokens[previous].end_index());
+            let is_line_start = self.source[previous_end..token.start_index()]
+                .chars()
+                .any(|char| matches!(char, character_patterns!(LineTerminator)));
+
+            (token.start_index() > start
+                && is_line_start
+                && token.token_type() == TokenType::Identifier
+                && STATEMENT_KEYWORDS.contains(&token.source_text(self.source)))
+            .then_some(token.start_index())
+        })
+    }
+
+    /// `position` のトークンまで戻って読み直す。
+    fn rewind(&mut self, position: usize) {
+        self.tokens.retain(|token| token.start_index() < position);
+        self.prev_token_end = self.tokens.last().map_or(0, |token| token.end_index());
+        self.reader.reset(position);
+        match self.read_token(DivContext::RegExp) {
+            Ok((token, _)) => {
+                self.token = token;
+                self.has_newline_before = true;
+            }
+            Err(error) => self.report(error),
+        }
+    }
+
+    /// 現在のトークンが、前の行の続きではなく文の始まりになりうるかを返す。
+    ///
+    /// 行頭の `(` `[` `+` などは前の行の式に続くことが多いため含めない。
+    fn can_start_statement(&self) -> bool {
+        match self.token_type() {
+            TokenType::Identifier => !CONTINUATION_KEYWORDS.contains(&self.text()),
+            TokenType::Literal(_) | TokenType::Template(_) => true,
+            TokenType::Punctuator(punctuator) => matches!(
+                punctuator,
+                Punctuator::LogicalNot
+                    | Punctuator::BitNot
+                    | Punctuator::Increment
+                    | Punctuator::Decrement
+            ),
+            _ => false,
+        }
+    }
+
+    /// 現在のトークンを読み飛ばす。
+    ///
+    /// 字句解析のエラーは [`Parser::advance`] の中で記録されて読み飛ばされる。
+    fn skip_token(&mut self) {
+        if let Err(error) = self.advance() {
+            self.report(error);
+        }
+    }
+
+    /// 閉じ括弧を読む。
+    ///
+    /// 欠けている場合はエラーを記録し、補ったものとして続ける。
+    pub(crate) fn expect_closing(&mut self, punctuator: Punctuator) -> ParseResult<()> {
+        if self.eat(punctuator)? {
+            return Ok(());
+        }
+
+        let expected = match punctuator {
+            Punctuator::RightParen => ")",
+            Punctuator::RightBracket => "]",
+            Punctuator::RightBrace => "}",
+            _ => return self.unexpected(),
+        };
+        let span = Span::new(self.prev_token_end, self.prev_token_end);
+        self.report(ParseError::new(span, format!("missing '{expected}'")));
+        Ok(())
+    }
+
+    /// 式が欠けている位置であれば、エラーを記録して [`Expression::Error`] を返す。
+    ///
+    /// 文や括弧の終わりで式が欠けている場合だけを補い、それ以外は `None` を返す。
+    pub(crate) fn missing_expression(&mut self) -> Option<Expression> {
+        let is_missing = self.is_end()
+            || matches!(
+                self.token_type(),
+                TokenType::Punctuator(
+                    Punctuator::SemiColon
+                        | Punctuator::RightParen
+                        | Punctuator::RightBracket
+                        | Punctuator::RightBrace
+                )
+            );
+        if !is_missing {
+            return None;
+        }
+
+        let span = Span::new(self.prev_token_end, self.prev_token_end);
+        self.report(ParseError::new(span, "expected an expression"));
+        Some(Expression::Error(ErrorNode { span }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ast::{
+        parser::test_helper::ToSexp, reader_struct::SourceCodeReader, script::parse_script,
+    };
+
+    /// 構文木と、エラーのメッセージを返す。
+    fn parse_recovered(source: &str) -> (String, Vec<String>) {
+        let output = parse_script(SourceCodeReader::new(source));
+        let messages = output
+            .errors
+            .iter()
+            .map(|error| error.message.clone())
+            .collect();
+
+        (output.ast.to_sexp(), messages)
+    }
+
+    macro_rules! test_case {
+        ($test_name:ident, $source:expr, $expected:expr, $errors:expr) => {
+            #[test]
+            fn $test_name() {
+                let (sexp, messages) = parse_recovered($source);
+                assert_eq!(sexp, $expected);
+                assert_eq!(messages, $errors as &[&str]);
+            }
+        };
+    }
+
+    test_case!(
+        missing_paren,
+        "if (a { b } c;",
+        "(if a (block b)) c",
+        &["missing ')'"]
+    );
+    test_case!(
+        missing_expression,
+        "a = ;\nb;",
+        "(= a (error)) b",
+        &["expected an expression"]
+    );
+    test_case!(
+        skip_statement,
+        "f(a b;\nc",
+        "(error) c",
+        &["missing ')'", "unexpected token 'b'"]
+    );
+    test_case!(
+        stray_brace,
+        "a; }\nb;",
+        "a (error) b",
+        &["unexpected token '}'"]
+    );
+    test_case!(
+        in_function,
+        "function f() { x( ; y }",
+        "(function f (call x (error)) y)",
+        &["expected an expression", "missing ')'"]
+    );
+    test_case!(
+        unterminated_body,
+        "function f() { a",
+        "(function f a)",
+        &["missing '}'"]
+    );
+    test_case!(
+        keyword_at_line_start,
+        "a b c\nvar d;",
+        "(error) (var d)",
+        &["unexpected token 'b'"]
+    );
+    test_case!(
+        multiple,
+        "a = ;\nb = ;\nc;",
+        "(= a (error)) (= b (error)) c",
+        &["expected an expression", "expected an expression"]
+    );
+    test_case!(
+        invalid_character,
+        "a @ b;\nc;",
+        "(error) c",
+        &["unexpected character '@' at 2"]
+    );
+    test_case!(
+        missing_bracket,
+        "[a, b;\nc;",
+        "(error) c",
+        &["unexpected token ';'"]
+    );
+    test_case!(
+        missing_call_paren,
+        "foo(1, 2\nbar()\nbaz()\nqux()\n",
+        "(call foo 1 2) (call bar) (call baz) (call qux)",
+        &["missing ')'"]
+    );
+    test_case!(
+        without_semicolons,
+        "x = [1, 2\ny = 3;",
+        "(error) (= y 3)",
+        &["unexpected token 'y'"]
+    );
+    test_case!(
+        missing_for_semicolon,
+        "for (let i = 0; i < 3 {}\nw();",
+        "(error) (call w)",
+        &["unexpected token '{'"]
+    );
+    test_case!(
+        missing_parameter_paren,
+        "class A { m( { } }\nx();",
+        "(error) (error) (call x)",
+        &["missing ')'", "unexpected token '}'"]
+    );
+    test_case!(
+        duplicate_private_name,
+        "class A { #x; #x }\nb;",
+        "(class A (field #x) (field #x)) b",
+        &["duplicate private name '#x'"]
+    );
+    test_case!(
+        duplicate_constructor,
+        "class A { constructor() {} constructor() {} m() {} }",
+        "(class A (constructor) (constructor) (method m))",
+        &["a class may only have one constructor"]
+    );
+    test_case!(
+        getter_with_parameter,
+        "class A { get a(b) {} c() {} }",
+        "(class A (get a) (method c))",
+        &["getter must not have any parameters"]
+    );
+    test_case!(
+        bracket_semicolon,
+        "a b = [c;\nd];\ne;",
+        "(error) e",
+        &["unexpected token 'b'"]
+    );
+    test_case!(
+        unclosed_call_before_declaration,
+        "foo(\nvar x = 1;\nx;",
+        "(error) (var (= x 1)) x",
+        &["unexpected keyword 'var'"]
+    );
+    test_case!(
+        unclosed_array_before_let,
+        "foo([1,\nlet x = 1;\nx;",
+        "(error) (let (= x 1)) x",
+        &["unexpected token 'x'"]
+    );
+}
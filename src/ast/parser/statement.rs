@@ -0,0 +1,831 @@
+use crate::token::structs::{punctuator::Punctuator, token::TokenType};
+
+use super::Parser;
+use crate::ast::{
+    error::ParseResult,
+    structs::{
+        expression::{AssignmentOperator, Expression},
+        literal::Identifier,
+        span::Spanned,
+        statement::{
+            BlockStatement, BreakStatement, CatchClause, ContinueStatement, DebuggerStatement,
+            DoWhileStatement, EmptyStatement, ExpressionStatement, ForInOfLeft, ForInStatement,
+            ForInit, ForOfStatement, ForStatement, IfStatement, LabeledStatement, ReturnStatement,
+            Statement, SwitchCase, SwitchStatement, ThrowStatement, TryStatement,
+            VariableDeclaration, VariableDeclarator, VariableKind, WhileStatement, WithStatement,
+        },
+    },
+};
+
+impl<'a> Parser<'a> {
+    /// StatementListItem (宣言を含む文)
+    pub(crate) fn parse_statement_list_item(&mut self) -> ParseResult<Statement> {
+        match self.token_type() {
+            TokenType::Identifier => match self.text() {
+                "function" => self.parse_function_declaration(),
+                "async" if self.is_async_function_start()? => self.parse_function_declaration(),
+                "class" => self.parse_class_declaration(),
+                "const" => self.parse_variable_statement(VariableKind::Const),
+                "let" if self.is_let_declaration(false)? => {
+                    self.parse_variable_statement(VariableKind::Let)
+                }
+                _ => self.parse_statement(),
+            },
+            _ => self.parse_statement(),
+        }
+    }
+
+    /// `let` が宣言の始まりであるかを返す。
+    ///
+    /// `let` の後に束縛する名前、`[`、`{` が続く場合は宣言とみなす。
+    /// 単一の文を置く場所では、改行の後の名前と `{` は宣言とみなさない。
+    fn is_let_declaration(&self, single_statement: bool) -> ParseResult<bool> {
+        let (next, has_newline) = self.lookahead()?;
+
+        Ok(match next.token_type() {
+            TokenType::Punctuator(Punctuator::LeftBracket) => true,
+            _ if single_statement && has_newline => false,
+            TokenType::Punctuator(Punctuator::LeftBrace) => true,
+            TokenType::Identifier => !matches!(next.source_text(self.source), "in" | "instanceof"),
+            _ => false,
+        })
+    }
+
+    /// 文を読み、文の終わりまで進む。
+    pub(crate) fn parse_statement_list(
+        &mut self,
+        is_end: impl Fn(&Self) -> bool,
+    ) -> ParseResult<Vec<Statement>> {
+        let mut statements = Vec::new();
+
+        while !is_end(self) && !self.is_end() {
+            statements.push(self.parse_statement_list_item_or_recover());
+        }
+
+        Ok(statements)
+    }
+
+    /// Statement (宣言を含まない文)
+    pub(crate) fn parse_statement(&mut self) -> ParseResult<Statement> {
+        match self.token_type() {
+            TokenType::Punctuator(Punctuator::LeftBrace) => {
+                Ok(Statement::Block(self.parse_block_statement()?))
+            }
+            TokenType::Punctuator(Punctuator::SemiColon) => {
+                let span = self.token_span();
+                self.advance()?;
+                Ok(Statement::Empty(EmptyStatement { span }))
+            }
+            TokenType::Identifier => match self.text() {
+                "var" => self.parse_variable_statement(VariableKind::Var),
+                "if" => self.parse_if_statement(),
+                "for" => self.parse_for_statement(),
+                "while" => self.parse_while_statement(),
+                "do" => self.parse_do_while_statement(),
+                "continue" => self.parse_continue_statement(),
+                "break" => self.parse_break_statement(),
+                "return" => self.parse_return_statement(),
+                "with" => self.parse_with_statement(),
+                "switch" => self.parse_switch_statement(),
+                "throw" => self.parse_throw_statement(),
+                "try" => self.parse_try_statement(),
+                "debugger" => self.parse_debugger_statement(),
+                "function" | "class" | "const" => self.error(
+                    self.token_span(),
+                    "declaration cannot appear in single-statement context",
+                ),
+                "async" if self.is_async_function_start()? => self.error(
+                    self.token_span(),
+                    "async function declaration cannot appear in single-statement context",
+                ),
+                "let" if self.is_let_declaration(true)? => self.error(
+                    self.token_span(),
+                    "lexical declaration cannot appear in single-statement context",
+                ),
+                _ => self.parse_expression_or_labeled_statement(),
+            },
+            _ => self.parse_expression_statement(),
+        }
+    }
+
+    /// 単一の文を置く場所 (if や反復の本体)
+    ///
+    /// strict モード以外では関数宣言を置くことができる (Annex B)。
+    fn parse_sub_statement(&mut self) -> ParseResult<Statement> {
+        if self.is_keyword("function") && !self.context.strict {
+            let (next, _) = self.lookahead()?;
+            if next.token_type() == TokenType::Punctuator(Punctuator::Multiply) {
+                return self.error(
+                    self.token_span(),
+                    "generator declaration cannot appear in single-statement context",
+                );
+            }
+            return self.parse_function_declaration();
+        }
+
+        self.parse_statement()
+    }
+
+    /// `{ ... }`
+    pub(crate) fn parse_block_statement(&mut self) -> ParseResult<BlockStatement> {
+        let start = self.start();
+        self.expect(Punctuator::LeftBrace)?;
+        let body = self.parse_statement_list(|parser| parser.is(Punctuator::RightBrace))?;
+        self.expect_closing(Punctuator::RightBrace)?;
+
+        Ok(BlockStatement {
+            span: self.span_from(start),
+            body,
+        })
+    }
+
+    /// 式文
+    pub(crate) fn parse_expression_statement(&mut self) -> ParseResult<Statement> {
+        let start = self.start();
+        let expression = self.parse_expression()?;
+        self.consume_semicolon()?;
+
+        Ok(Statement::Expression(ExpressionStatement {
+            span: self.span_from(start),
+            expression,
+            directive: None,
+        }))
+    }
+
+    /// 識別子で始まる式文とラベル付き文 `a: b`
+    fn parse_expression_or_labeled_statement(&mut self) -> ParseResult<Statement> {
+        let start = self.start();
+        let (next, _) = self.lookahead()?;
+
+        if next.token_type() != TokenType::Punctuator(Punctuator::TernaryColon) {
+            return self.parse_expression_statement();
+        }
+
+        let label = self.parse_label_identifier()?;
+        self.expect(Punctuator::TernaryColon)?;
+
+        // ラベル付きの関数宣言は strict モード以外で使える (Annex B)
+        let body = if self.is_keyword("function") {
+            if self.context.strict {
+                return self.error(
+                    self.token_span(),
+                    "labelled function declaration is not allowed in strict mode",
+                );
+            }
+            self.parse_function_declaration()?
+        } else {
+            self.parse_statement()?
+        };
+
+        Ok(Statement::Labeled(Box::new(LabeledStatement {
+            span: self.span_from(start),
+            label,
+            body,
+        })))
+    }
+
+    /// `var a = 1;` `let a;` `const a = 1;`
+    fn parse_variable_statement(&mut self, kind: VariableKind) -> ParseResult<Statement> {
+        let declaration =
+            self.with_allow_in(true, |parser| parser.parse_variable_declaration(kind))?;
+        self.check_declarators_initialized(&declaration)?;
+        self.consume_semicolon()?;
+
+        let span = self.span_from(declaration.span.start);
+        Ok(Statement::VariableDeclaration(VariableDeclaration {
+            span,
+            ..declaration
+        }))
+    }
+
+    /// 宣言の並び (`;` を含まない)
+    pub(crate) fn parse_variable_declaration(
+        &mut self,
+        kind: VariableKind,
+    ) -> ParseResult<VariableDeclaration> {
+        let start = self.start();
+        self.advance()?;
+
+        let mut declarations = Vec::new();
+        loop {
+            let declarator_start = self.start();
+            let id = self.parse_binding_target()?;
+
+            if kind.is_lexical() {
+                for name in id.bound_names() {
+                    if name.name == "let" {
+                        return self
+                            .error(name.span, "'let' cannot be used as a lexically bound name");
+                    }
+                }
+            }
+
+            let init = if self.eat(Punctuator::Assign)? {
+                Some(self.parse_assignment_expression()?)
+            } else {
+                None
+            };
+
+            declarations.push(VariableDeclarator {
+                span: self.span_from(declarator_start),
+                id,
+                init,
+            });
+
+            if !self.eat(Punctuator::Comma)? {
+                break;
+            }
+        }
+
+        Ok(VariableDeclaration {
+            span: self.span_from(start),
+            kind,
+            declarations,
+        })
+    }
+
+    /// `const` と分割代入の宣言は初期化が必要
+    fn check_declarators_initialized(&self, declaration: &VariableDeclaration) -> ParseResult<()> {
+        for declarator in &declaration.declarations {
+            if declarator.init.is_some() {
+                continue;
+            }
+            if declaration.kind == VariableKind::Const {
+                return self.error(declarator.span, "missing initializer in const declaration");
+            }
+            if !declarator.id.is_simple() {
+                return self.error(
+                    declarator.span,
+                    "missing initializer in destructuring declaration",
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// `(a)` の条件式
+    fn parse_parenthesized_condition(&mut self) -> ParseResult<Expression> {
+        self.expect(Punctuator::LeftParen)?;
+        let test = self.with_allow_in(true, Self::parse_expression)?;
+        self.expect_closing(Punctuator::RightParen)?;
+        Ok(test)
+    }
+
+    /// `if (a) b else c`
+    fn parse_if_statement(&mut self) -> ParseResult<Statement> {
+        let start = self.start();
+        self.expect_keyword("if")?;
+        let test = self.parse_parenthesized_condition()?;
+        let consequent = self.parse_sub_statement()?;
+
+        let alternate = if self.eat_keyword("else")? {
+            Some(self.parse_sub_statement()?)
+        } else {
+            None
+        };
+
+        Ok(Statement::If(Box::new(IfStatement {
+            span: self.span_from(start),
+            test,
+            consequent,
+            alternate,
+        })))
+    }
+
+    /// 反復の本体 (関数宣言は置けない)
+    fn parse_loop_body(&mut self) -> ParseResult<Statement> {
+        self.parse_statement()
+    }
+
+    /// `while (a) b`
+    fn parse_while_statement(&mut self) -> ParseResult<Statement> {
+        let start = self.start();
+        self.expect_keyword("while")?;
+        let test = self.parse_parenthesized_condition()?;
+        let body = self.parse_loop_body()?;
+
+        Ok(Statement::While(Box::new(WhileStatement {
+            span: self.span_from(start),
+            test,
+            body,
+        })))
+    }
+
+    /// `do a while (b)`
+    fn parse_do_while_statement(&mut self) -> ParseResult<Statement> {
+        let start = self.start();
+        self.expect_keyword("do")?;
+        let body = self.parse_loop_body()?;
+        self.expect_keyword("while")?;
+        let test = self.parse_parenthesized_condition()?;
+
+        // do-while の後の `;` は改行がなくても省略できる
+        self.eat(Punctuator::SemiColon)?;
+
+        Ok(Statement::DoWhile(Box::new(DoWhileStatement {
+            span: self.span_from(start),
+            body,
+            test,
+        })))
+    }
+
+    /// `for (a; b; c) d` `for (a in b) c` `for (a of b) c` `for await (a of b) c`
+    fn parse_for_statement(&mut self) -> ParseResult<Statement> {
+        let start = self.start();
+        self.expect_keyword("for")?;
+        let await_span = self.token_span();
+        let is_await = self.context.allow_await && self.eat_keyword("await")?;
+        self.expect(Punctuator::LeftParen)?;
+
+        // for (;
+        if self.is(Punctuator::SemiColon) {
+            if is_await {
+                return self.error(await_span, "'for await' requires 'of'");
+            }
+            return self.parse_for_rest(start, None);
+        }
+
+        let declaration_kind = match self.text() {
+            _ if self.token_type() != TokenType::Identifier => None,
+            "var" => Some(VariableKind::Var),
+            "const" => Some(VariableKind::Const),
+            "let" if self.is_let_declaration(false)? => Some(VariableKind::Let),
+            _ => None,
+        };
+
+        if let Some(kind) = declaration_kind {
+            let declaration =
+                self.with_allow_in(false, |parser| parser.parse_variable_declaration(kind))?;
+
+            let is_of = self.is_keyword("of");
+            if is_of || self.is_keyword("in") {
+                self.check_for_in_of_declaration(&declaration, is_of)?;
+                let left = ForInOfLeft::VariableDeclaration(declaration);
+                return self.parse_for_in_of_rest(start, left, is_of, is_await);
+            }
+
+            if is_await {
+                return self.error(await_span, "'for await' requires 'of'");
+            }
+            self.check_declarators_initialized(&declaration)?;
+            return self.parse_for_rest(start, Some(ForInit::VariableDeclaration(declaration)));
+        }
+
+        let starts_with_let = self.is_keyword("let");
+        let starts_with_async = self.is_keyword("async") && !self.text().contains('\\');
+        let init_start = self.start();
+        let init = self.with_allow_in(false, Self::parse_expression_cover)?;
+
+        let is_of = self.is_keyword("of");
+        if is_of || self.is_keyword("in") {
+            if is_of && starts_with_let {
+                return self.error(init.span(), "'for (let of ...)' is not allowed");
+            }
+            if is_of && starts_with_async && matches!(init, Expression::Identifier(_)) && !is_await
+            {
+                return self.error(init.span(), "'for (async of ...)' is not allowed");
+            }
+
+            let target = self.expression_to_assignment_target(init, AssignmentOperator::Assign)?;
+            let left = ForInOfLeft::Pattern(target);
+            return self.parse_for_in_of_rest(start, left, is_of, is_await);
+        }
+
+        if is_await {
+            return self.error(await_span, "'for await' requires 'of'");
+        }
+        self.check_cover_initialized_names(init_start)?;
+        self.parse_for_rest(start, Some(ForInit::Expression(init)))
+    }
+
+    /// for-in と for-of の左辺の宣言を検査する。
+    fn check_for_in_of_declaration(
+        &self,
+        declaration: &VariableDeclaration,
+        is_of: bool,
+    ) -> ParseResult<()> {
+        let [declarator] = declaration.declarations.as_slice() else {
+            return self.error(
+                declaration.span,
+                "only one variable can be declared in the head of for-in and for-of",
+            );
+        };
+
+        if let Some(init) = &declarator.init {
+            // for (var a = 1 in b) は strict モード以外で使える (Annex B)
+            let is_annex_b = !is_of
+                && !self.context.strict
+                && declaration.kind == VariableKind::Var
+                && declarator.id.is_simple();
+
+            if !is_annex_b {
+                return self.error(
+                    init.span(),
+                    "for-in and for-of variable declaration cannot have an initializer",
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// `for (a; b; c) d` の最初の `;` から後ろ
+    fn parse_for_rest(&mut self, start: usize, init: Option<ForInit>) -> ParseResult<Statement> {
+        self.expect(Punctuator::SemiColon)?;
+        let test = if self.is(Punctuator::SemiColon) {
+            None
+        } else {
+            Some(self.with_allow_in(true, Self::parse_expression)?)
+        };
+
+        self.expect(Punctuator::SemiColon)?;
+        let update = if self.is(Punctuator::RightParen) {
+            None
+        } else {
+            Some(self.with_allow_in(true, Self::parse_expression)?)
+        };
+
+        self.expect_closing(Punctuator::RightParen)?;
+        let body = self.parse_loop_body()?;
+
+        Ok(Statement::For(Box::new(ForStatement {
+            span: self.span_from(start),
+            init,
+            test,
+            update,
+            body,
+        })))
+    }
+
+    /// `for (a in b) c` と `for (a of b) c` の `in` `of` から後ろ
+    fn parse_for_in_of_rest(
+        &mut self,
+        start: usize,
+        left: ForInOfLeft,
+        is_of: bool,
+        is_await: bool,
+    ) -> ParseResult<Statement> {
+        self.advance()?;
+
+        if is_of {
+            let right = self.with_allow_in(true, Self::parse_assignment_expression)?;
+            self.expect_closing(Punctuator::RightParen)?;
+            let body = self.parse_loop_body()?;
+
+            Ok(Statement::ForOf(Box::new(ForOfStatement {
+                span: self.span_from(start),
+                left,
+                right,
+                body,
+                is_await,
+            })))
+        } else {
+            if is_await {
+                return self.error(self.span_from(start), "'for await' requires 'of'");
+            }
+            let right = self.with_allow_in(true, Self::parse_expression)?;
+            self.expect_closing(Punctuator::RightParen)?;
+            let body = self.parse_loop_body()?;
+
+            Ok(Statement::ForIn(Box::new(ForInStatement {
+                span: self.span_from(start),
+                left,
+                right,
+                body,
+            })))
+        }
+    }
+
+    /// `continue a;`
+    fn parse_continue_statement(&mut self) -> ParseResult<Statement> {
+        let start = self.start();
+        self.expect_keyword("continue")?;
+        let label = self.parse_jump_label()?;
+        self.consume_semicolon()?;
+
+        Ok(Statement::Continue(ContinueStatement {
+            span: self.span_from(start),
+            label,
+        }))
+    }
+
+    /// `break a;`
+    fn parse_break_statement(&mut self) -> ParseResult<Statement> {
+        let start = self.start();
+        self.expect_keyword("break")?;
+        let label = self.parse_jump_label()?;
+        self.consume_semicolon()?;
+
+        Ok(Statement::Break(BreakStatement {
+            span: self.span_from(start),
+            label,
+        }))
+    }
+
+    /// break と continue のラベル (改行の後は読まない)
+    fn parse_jump_label(&mut self) -> ParseResult<Option<Identifier>> {
+        if self.token_type() == TokenType::Identifier && !self.has_line_terminator_here() {
+            Ok(Some(self.parse_label_identifier()?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// `return a;`
+    fn parse_return_statement(&mut self) -> ParseResult<Statement> {
+        let start = self.start();
+        if !self.context.allow_return {
+            return self.error(self.token_span(), "'return' outside of function");
+        }
+        self.expect_keyword("return")?;
+
+        // return の後に改行がある場合は値を持たない
+        self.warn_return_newline();
+        let argument = if self.is_statement_end() {
+            None
+        } else {
+            Some(self.with_allow_in(true, Self::parse_expression)?)
+        };
+        self.consume_semicolon()?;
+
+        Ok(Statement::Return(ReturnStatement {
+            span: self.span_from(start),
+            argument,
+        }))
+    }
+
+    /// `with (a) b`
+    fn parse_with_statement(&mut self) -> ParseResult<Statement> {
+        let start = self.start();
+        if self.context.strict {
+            return self.error(self.token_span(), "'with' is not allowed in strict mode");
+        }
+        self.expect_keyword("with")?;
+        let object = self.parse_parenthesized_condition()?;
+        let body = self.parse_statement()?;
+
+        Ok(Statement::With(Box::new(WithStatement {
+            span: self.span_from(start),
+            object,
+            body,
+        })))
+    }
+
+    /// `switch (a) { case b: c default: d }`
+    fn parse_switch_statement(&mut self) -> ParseResult<Statement> {
+        let start = self.start();
+        self.expect_keyword("switch")?;
+        let discriminant = self.parse_parenthesized_condition()?;
+        self.expect(Punctuator::LeftBrace)?;
+
+        let mut cases = Vec::new();
+        let mut has_default = false;
+
+        while !self.eat(Punctuator::RightBrace)? {
+            let case_start = self.start();
+
+            let test = if self.eat_keyword("case")? {
+                Some(self.with_allow_in(true, Self::parse_expression)?)
+            } else if self.is_keyword("default") {
+                if has_default {
+                    return self.error(
+                        self.token_span(),
+                        "more than one default clause in switch statement",
+                    );
+                }
+                has_default = true;
+                self.advance()?;
+                None
+            } else {
+                return self.unexpected();
+            };
+
+            self.expect(Punctuator::TernaryColon)?;
+            let consequent = self.parse_statement_list(|parser| {
+                parser.is(Punctuator::RightBrace)
+                    || parser.is_keyword("case")
+                    || parser.is_keyword("default")
+            })?;
+
+            cases.push(SwitchCase {
+                span: self.span_from(case_start),
+                test,
+                consequent,
+            });
+        }
+
+        Ok(Statement::Switch(SwitchStatement {
+            span: self.span_from(start),
+            discriminant,
+            cases,
+        }))
+    }
+
+    /// `throw a;`
+    fn parse_throw_statement(&mut self) -> ParseResult<Statement> {
+        let start = self.start();
+        self.expect_keyword("throw")?;
+
+        if self.has_line_terminator_here() {
+            return self.error(self.token_span(), "illegal newline after 'throw'");
+        }
+
+        let argument = self.with_allow_in(true, Self::parse_expression)?;
+        self.consume_semicolon()?;
+
+        Ok(Statement::Throw(ThrowStatement {
+            span: self.span_from(start),
+            argument,
+        }))
+    }
+
+    /// `try {} catch (a) {} finally {}`
+    fn parse_try_statement(&mut self) -> ParseResult<Statement> {
+        let start = self.start();
+        self.expect_keyword("try")?;
+        let block = self.parse_block_statement()?;
+
+        let handler = if self.is_keyword("catch") {
+            let catch_start = self.start();
+            self.advance()?;
+
+            let param = if self.eat(Punctuator::LeftParen)? {
+                let param = self.parse_binding_target()?;
+                self.expect_closing(Punctuator::RightParen)?;
+                Some(param)
+            } else {
+                None
+            };
+            let body = self.parse_block_statement()?;
+
+            Some(CatchClause {
+                span: self.span_from(catch_start),
+                param,
+                body,
+            })
+        } else {
+            None
+        };
+
+        let finalizer = if self.eat_keyword("finally")? {
+            Some(self.parse_block_statement()?)
+        } else {
+            None
+        };
+
+        if handler.is_none() && finalizer.is_none() {
+            return self.error(self.span_from(start), "missing catch or finally after try");
+        }
+
+        Ok(Statement::Try(Box::new(TryStatement {
+            span: self.span_from(start),
+            block,
+            handler,
+            finalizer,
+        })))
+    }
+
+    /// `debugger;`
+    fn parse_debugger_statement(&mut self) -> ParseResult<Statement> {
+        let start = self.start();
+        self.expect_keyword("debugger")?;
+        self.consume_semicolon()?;
+
+        Ok(Statement::Debugger(DebuggerStatement {
+            span: self.span_from(start),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test_helper::{parse_script_error, parse_script_sexp};
+
+    macro_rules! test_case {
+        ($test_name:ident, $source:expr, $expected:expr) => {
+            #[test]
+            fn $test_name() {
+                assert_eq!(parse_script_sexp($source), Ok($expected.to_string()));
+            }
+        };
+    }
+
+    macro_rules! test_case_fail {
+        ($test_name:ident, $source:expr) => {
+            #[test]
+            fn $test_name() {
+                assert!(parse_script_error($source).is_some());
+            }
+        };
+    }
+
+    test_case!(var, "var a = 1, b;", "(var (= a 1) b)");
+    test_case!(let_const, "let a; const b = 2;", "(let a) (const (= b 2))");
+    test_case!(
+        destructuring_declaration,
+        "let {a, b: [c, , ...d] = e} = f;",
+        "(let (= (object-pattern (: a a) (: b (= (array-pattern c _ (... d)) e))) f))"
+    );
+    test_case!(let_as_identifier, "let = 1;", "(= let 1)");
+    test_case!(let_member, "let.a;", "(. let a)");
+    test_case!(block, "{ a; { b } }", "(block a (block b))");
+    test_case!(empty, ";", "(empty)");
+    test_case!(if_else, "if (a) b; else c;", "(if a b c)");
+    test_case!(
+        dangling_else,
+        "if (a) if (b) c; else d;",
+        "(if a (if b c d))"
+    );
+    test_case!(if_function, "if (a) function f() {}", "(if a (function f))");
+    test_case!(while_loop, "while (a) b;", "(while a b)");
+    test_case!(do_while, "do a; while (b) c;", "(do a b) c");
+    test_case!(
+        for_loop,
+        "for (var i = 0; i < n; i++) a;",
+        "(for (var (= i 0)) (< i n) (++postfix i) a)"
+    );
+    test_case!(for_empty, "for (;;) {}", "(for _ _ _ (block))");
+    test_case!(for_in, "for (const k in o) a;", "(for-in (const k) o a)");
+    test_case!(
+        for_of,
+        "for (let [k, v] of m) a;",
+        "(for-of (let (array-pattern k v)) m a)"
+    );
+    test_case!(for_of_member, "for (a.b of c) d;", "(for-of (. a b) c d)");
+    test_case!(
+        for_in_parenthesized,
+        "for (var a = (b in c); ;) d;",
+        "(for (var (= a (in b c))) _ _ d)"
+    );
+    test_case!(
+        for_var_in_initializer,
+        "for (var a = 1 in b) c;",
+        "(for-in (var (= a 1)) b c)"
+    );
+    test_case!(for_let_in, "for (let in a) b;", "(for-in let a b)");
+    test_case!(
+        switch,
+        "switch (a) { case 1: b; case 2: default: c }",
+        "(switch a (case 1 b) (case 2) (default c))"
+    );
+    test_case!(
+        labeled,
+        "a: for (;;) { break a; continue a; }",
+        "(label a (for _ _ _ (block (break a) (continue a))))"
+    );
+    test_case!(
+        try_catch_finally,
+        "try { a } catch (e) { b } finally { c }",
+        "(try (block a) (catch e (block b)) (block c))"
+    );
+    test_case!(
+        try_optional_catch_binding,
+        "try {} catch {}",
+        "(try (block) (catch _ (block)))"
+    );
+    test_case!(throw, "throw a;", "(throw a)");
+    test_case!(with, "with (a) b;", "(with a b)");
+    test_case!(debugger, "debugger;", "(debugger)");
+    test_case!(
+        function_declaration,
+        "function f(a, b = 1, ...c) { return a; }",
+        "(function f a (= b 1) (... c) (return a))"
+    );
+    test_case!(function_expression, "(function () {});", "(function)");
+    test_case!(generator_declaration, "function* g() {}", "(function* g)");
+    test_case!(
+        return_newline,
+        "function f() { return\na; }",
+        "(function f (return) a)"
+    );
+    test_case!(
+        class_declaration,
+        "class A extends B { constructor() {} static m() {} get a() {} set a(v) {} }",
+        "(class A (extends B) (constructor) (static-method m) (get a) (set a))"
+    );
+    test_case!(
+        object_methods,
+        "({ a() {}, get b() {}, set b(v) {}, *c() {}, get: 1, set() {} });",
+        "(object (method a) (get b) (set b) (method* c) (: get 1) (method set))"
+    );
+
+    test_case_fail!(return_outside_function, "return;");
+    test_case_fail!(const_without_initializer, "const a;");
+    test_case_fail!(destructuring_without_initializer, "let [a];");
+    test_case_fail!(let_let, "let let = 1;");
+    test_case_fail!(let_in_single_statement, "if (a) let b;");
+    test_case_fail!(class_in_single_statement, "if (a) class A {}");
+    test_case_fail!(lexical_in_label, "a: let b;");
+    test_case_fail!(for_of_initializer, "for (let a = 1 of b) c;");
+    test_case_fail!(for_in_multiple, "for (var a, b in c) d;");
+    test_case_fail!(for_let_of, "for (let of a) b;");
+    test_case_fail!(for_async_of, "for (async of a) b;");
+    test_case_fail!(for_await_in, "for await (a in b) c;");
+    test_case_fail!(for_await_in_script, "for await (a of b) c;");
+    test_case_fail!(duplicate_default, "switch (a) { default: default: }");
+    test_case_fail!(try_without_handler, "try {}");
+    test_case_fail!(throw_newline, "throw\na;");
+    test_case_fail!(missing_semicolon, "a b");
+    test_case_fail!(generator_in_if, "if (a) function* g() {}");
+}
@@ -0,0 +1,651 @@
+//! 構文木を S 式の文字列にしてテストで比較するためのモジュールです。
+
+use super::Parser;
+use crate::ast::{
+    error::ParseError,
+    reader_struct::SourceCodeReader,
+    script::{parse_module, parse_script, parse_with_options, ParseOptions},
+    structs::{
+        class::{Class, ClassElement, MethodKind},
+        expression::{
+            Expression, ExpressionOrSpread, MemberProperty, ObjectProperty, Property, PropertyKey,
+            PropertyKind,
+        },
+        function::{ArrowFunctionBody, Function},
+        literal::{Literal, LiteralValue},
+        module::{
+            Declaration, ExportDefaultKind, ImportAttribute, ImportDeclarationSpecifier,
+            ModuleDeclaration, ModuleExportName, ModuleItem,
+        },
+        pattern::{ObjectPatternProperty, Pattern},
+        statement::{ForInOfLeft, ForInit, Statement, VariableDeclaration},
+        template::TemplateLiteral,
+        JsAst, SourceType,
+    },
+};
+use crate::to_source_string::ToSourceString;
+
+/// S 式の文字列にする
+pub(crate) trait ToSexp {
+    fn to_sexp(&self) -> String;
+}
+
+/// 早期エラーを検査せずにスクリプトを解析する。
+///
+/// 式の文法だけを確かめるため、`new.target` や `super()` も関数の外で読める。
+fn parse_script_grammar(source: &str) -> Result<JsAst, ParseError> {
+    let mut parser = Parser::new(SourceCodeReader::new(source))?;
+    let ast = parser.parse_program(SourceType::Script);
+
+    match parser.take_errors().into_iter().next() {
+        Some(error) => Err(error),
+        None => Ok(ast),
+    }
+}
+
+/// スクリプトの最初の式文を S 式にする。
+pub(crate) fn parse_expression_sexp(source: &str) -> Result<String, ParseError> {
+    let ast = parse_script_grammar(source)?;
+
+    match ast.body.first() {
+        Some(ModuleItem::Statement(Statement::Expression(statement))) => {
+            Ok(statement.expression.to_sexp())
+        }
+        item => panic!("expected expression statement: {item:?}"),
+    }
+}
+
+/// 早期エラーを検査せずにスクリプトを解析してエラーを返す。
+pub(crate) fn parse_expression_error(source: &str) -> Option<ParseError> {
+    parse_script_grammar(source).err()
+}
+
+/// スクリプトの全ての文を S 式にして空白で区切る。
+pub(crate) fn parse_script_sexp(source: &str) -> Result<String, ParseError> {
+    let ast = parse_script(SourceCodeReader::new(source)).into_result()?;
+
+    Ok(ast.to_sexp())
+}
+
+/// スクリプトを解析してエラーを返す。
+pub(crate) fn parse_script_error(source: &str) -> Option<ParseError> {
+    parse_script(SourceCodeReader::new(source))
+        .into_result()
+        .err()
+}
+
+/// 自動セミコロン挿入の警告を有効にしてスクリプトを解析し、警告を返す。
+pub(crate) fn parse_script_warnings(source: &str) -> Vec<String> {
+    let options = ParseOptions {
+        asi_hazard_warnings: true,
+    };
+    let output = parse_with_options(SourceCodeReader::new(source), SourceType::Script, options);
+    assert_eq!(output.errors, Vec::new(), "parse error");
+
+    output.warnings.iter().map(ToString::to_string).collect()
+}
+
+/// モジュールの全ての要素を S 式にして空白で区切る。
+pub(crate) fn parse_module_sexp(source: &str) -> Result<String, ParseError> {
+    let ast = parse_module(SourceCodeReader::new(source)).into_result()?;
+    Ok(ast.to_sexp())
+}
+
+/// モジュールを解析してエラーを返す。
+pub(crate) fn parse_module_error(source: &str) -> Option<ParseError> {
+    parse_module(SourceCodeReader::new(source))
+        .into_result()
+        .err()
+}
+
+fn list(head: &str, items: impl IntoIterator<Item = String>) -> String {
+    let mut result = format!("({head}");
+    for item in items {
+        result.push(' ');
+        result.push_str(&item);
+    }
+    result.push(')');
+    result
+}
+
+impl ToSexp for Literal {
+    fn to_sexp(&self) -> String {
+        match &self.value {
+            LiteralValue::Number(value) => value.to_string(),
+            LiteralValue::BigInt(value) => format!("{value}n"),
+            _ => self.raw.clone(),
+        }
+    }
+}
+
+impl ToSexp for Expression {
+    fn to_sexp(&self) -> String {
+        match self {
+            Expression::Identifier(identifier) => identifier.name.clone(),
+            Expression::Literal(literal) => literal.to_sexp(),
+            Expression::This(_) => "this".to_string(),
+            Expression::Super(_) => "super".to_string(),
+            Expression::Array(array) => list(
+                "array",
+                array.elements.iter().map(|element| match element {
+                    Some(element) => element.to_sexp(),
+                    None => "_".to_string(),
+                }),
+            ),
+            Expression::Object(object) => list(
+                "object",
+                object.properties.iter().map(|property| match property {
+                    ObjectProperty::Property(property) => property.to_sexp(),
+                    ObjectProperty::Spread(spread) => list("...", [spread.argument.to_sexp()]),
+                }),
+            ),
+            Expression::Function(function) => function.to_sexp(),
+            Expression::ArrowFunction(arrow) => {
+                let head = if arrow.is_async { "async=>" } else { "=>" };
+                let body = match &arrow.body {
+                    ArrowFunctionBody::Expression(expression) => expression.to_sexp(),
+                    ArrowFunctionBody::FunctionBody(body) => {
+                        list("block", body.body.iter().map(ToSexp::to_sexp))
+                    }
+                };
+                list(
+                    head,
+                    arrow
+                        .params
+                        .iter()
+                        .map(ToSexp::to_sexp)
+                        .chain(std::iter::once(body)),
+                )
+            }
+            Expression::Class(class) => class.to_sexp(),
+            Expression::TemplateLiteral(template) => template.to_sexp(),
+            Expression::TaggedTemplate(tagged) => {
+                list("tag", [tagged.tag.to_sexp(), tagged.quasi.to_sexp()])
+            }
+            Expression::Member(member) => {
+                let head = match (&member.property, member.optional) {
+                    (MemberProperty::Computed(_), false) => "[]",
+                    (MemberProperty::Computed(_), true) => "?.[]",
+                    (_, false) => ".",
+                    (_, true) => "?.",
+                };
+                list(head, [member.object.to_sexp(), member.property.to_sexp()])
+            }
+            Expression::Call(call) => list(
+                if call.optional { "?.call" } else { "call" },
+                std::iter::once(call.callee.to_sexp())
+                    .chain(call.arguments.iter().map(ToSexp::to_sexp)),
+            ),
+            Expression::New(new) => list(
+                "new",
+                std::iter::once(new.callee.to_sexp())
+                    .chain(new.arguments.iter().map(ToSexp::to_sexp)),
+            ),
+            Expression::Chain(chain) => list("chain", [chain.expression.to_sexp()]),
+            Expression::Unary(unary) => list(
+                &unary.operator.to_source_string(),
+                [unary.argument.to_sexp()],
+            ),
+            Expression::Update(update) => list(
+                &format!(
+                    "{}{}",
+                    update.operator.to_source_string(),
+                    if update.prefix { "prefix" } else { "postfix" }
+                ),
+                [update.argument.to_sexp()],
+            ),
+            Expression::Binary(binary) => list(
+                &binary.operator.to_source_string(),
+                [binary.left.to_sexp(), binary.right.to_sexp()],
+            ),
+            Expression::Logical(logical) => list(
+                &logical.operator.to_source_string(),
+                [logical.left.to_sexp(), logical.right.to_sexp()],
+            ),
+            Expression::Conditional(conditional) => list(
+                "?",
+                [
+                    conditional.test.to_sexp(),
+                    conditional.consequent.to_sexp(),
+                    conditional.alternate.to_sexp(),
+                ],
+            ),
+            Expression::Assignment(assignment) => list(
+                &assignment.operator.to_source_string(),
+                [assignment.left.to_sexp(), assignment.right.to_sexp()],
+            ),
+            Expression::Sequence(sequence) => {
+                list(",", sequence.expressions.iter().map(ToSexp::to_sexp))
+            }
+            Expression::Yield(expression) => list(
+                if expression.delegate {
+                    "yield*"
+                } else {
+                    "yield"
+                },
+                expression.argument.iter().map(ToSexp::to_sexp),
+            ),
+            Expression::Await(expression) => list("await", [expression.argument.to_sexp()]),
+            Expression::MetaProperty(meta) => {
+                format!("{}.{}", meta.meta.name, meta.property.name)
+            }
+            Expression::Import(import) => list(
+                "import",
+                std::iter::once(import.source.to_sexp())
+                    .chain(import.options.iter().map(ToSexp::to_sexp)),
+            ),
+            Expression::PrivateIdentifier(identifier) => format!("#{}", identifier.name),
+            Expression::Error(_) => "(error)".to_string(),
+        }
+    }
+}
+
+impl ToSexp for TemplateLiteral {
+    fn to_sexp(&self) -> String {
+        let mut items = Vec::new();
+        for (index, quasi) in self.quasis.iter().enumerate() {
+            items.push(match &quasi.cooked {
+                Some(cooked) => format!("{cooked:?}"),
+                None => "null".to_string(),
+            });
+            if let Some(expression) = self.expressions.get(index) {
+                items.push(expression.to_sexp());
+            }
+        }
+        list("`", items)
+    }
+}
+
+impl ToSexp for ExpressionOrSpread {
+    fn to_sexp(&self) -> String {
+        match self {
+            ExpressionOrSpread::Expression(expression) => expression.to_sexp(),
+            ExpressionOrSpread::Spread(spread) => list("...", [spread.argument.to_sexp()]),
+        }
+    }
+}
+
+impl ToSexp for MemberProperty {
+    fn to_sexp(&self) -> String {
+        match self {
+            MemberProperty::Identifier(identifier) => identifier.name.clone(),
+            MemberProperty::PrivateIdentifier(identifier) => format!("#{}", identifier.name),
+            MemberProperty::Computed(expression) => expression.to_sexp(),
+        }
+    }
+}
+
+impl ToSexp for PropertyKey {
+    fn to_sexp(&self) -> String {
+        match self {
+            PropertyKey::Identifier(identifier) => identifier.name.clone(),
+            PropertyKey::Literal(literal) => literal.raw.clone(),
+            PropertyKey::PrivateIdentifier(identifier) => format!("#{}", identifier.name),
+            PropertyKey::Computed(expression) => format!("[{}]", expression.to_sexp()),
+        }
+    }
+}
+
+impl ToSexp for Pattern {
+    fn to_sexp(&self) -> String {
+        match self {
+            Pattern::Identifier(identifier) => identifier.name.clone(),
+            Pattern::Object(object) => list(
+                "object-pattern",
+                object.properties.iter().map(|property| match property {
+                    ObjectPatternProperty::Property(property) => {
+                        list(":", [property.key.to_sexp(), property.value.to_sexp()])
+                    }
+                    ObjectPatternProperty::Rest(rest) => list("...", [rest.argument.to_sexp()]),
+                }),
+            ),
+            Pattern::Array(array) => list(
+                "array-pattern",
+                array.elements.iter().map(|element| match element {
+                    Some(element) => element.to_sexp(),
+                    None => "_".to_string(),
+                }),
+            ),
+            Pattern::Rest(rest) => list("...", [rest.argument.to_sexp()]),
+            Pattern::Assignment(assignment) => {
+                list("=", [assignment.left.to_sexp(), assignment.right.to_sexp()])
+            }
+            Pattern::Expression(expression) => expression.to_sexp(),
+        }
+    }
+}
+
+impl ToSexp for Property {
+    fn to_sexp(&self) -> String {
+        match self.kind {
+            PropertyKind::Get => list("get", [self.key.to_sexp()]),
+            PropertyKind::Set => list("set", [self.key.to_sexp()]),
+            PropertyKind::Init if self.method => {
+                let Expression::Function(function) = &self.value else {
+                    unreachable!("method value must be a function");
+                };
+                list(&method_head("method", function), [self.key.to_sexp()])
+            }
+            PropertyKind::Init => list(":", [self.key.to_sexp(), self.value.to_sexp()]),
+        }
+    }
+}
+
+impl ToSexp for Function {
+    fn to_sexp(&self) -> String {
+        let head = match (self.is_async, self.generator) {
+            (false, false) => "function",
+            (false, true) => "function*",
+            (true, false) => "async function",
+            (true, true) => "async function*",
+        };
+
+        list(
+            head,
+            self.id
+                .iter()
+                .map(|id| id.name.clone())
+                .chain(self.params.iter().map(ToSexp::to_sexp))
+                .chain(self.body.body.iter().map(ToSexp::to_sexp)),
+        )
+    }
+}
+
+/// `async-method*` のように async とジェネレーターを表す見出し
+fn method_head(kind: &str, function: &Function) -> String {
+    let prefix = if function.is_async { "async-" } else { "" };
+    let suffix = if function.generator { "*" } else { "" };
+    format!("{prefix}{kind}{suffix}")
+}
+
+impl ToSexp for Class {
+    fn to_sexp(&self) -> String {
+        let id = self.id.iter().map(|id| id.name.clone());
+        let super_class = self
+            .super_class
+            .iter()
+            .map(|super_class| list("extends", [super_class.to_sexp()]));
+        let elements = self.body.body.iter().map(|element| match element {
+            ClassElement::Method(method) => {
+                let kind = match method.kind {
+                    MethodKind::Constructor => return "(constructor)".to_string(),
+                    MethodKind::Method => "method",
+                    MethodKind::Get => "get",
+                    MethodKind::Set => "set",
+                };
+                let head = method_head(kind, &method.value);
+                let head = if method.is_static {
+                    format!("static-{head}")
+                } else {
+                    head
+                };
+                list(&head, [method.key.to_sexp()])
+            }
+            ClassElement::Property(property) => list(
+                if property.is_static {
+                    "static-field"
+                } else {
+                    "field"
+                },
+                std::iter::once(property.key.to_sexp())
+                    .chain(property.value.iter().map(ToSexp::to_sexp)),
+            ),
+            ClassElement::StaticBlock(block) => {
+                list("static", block.body.iter().map(ToSexp::to_sexp))
+            }
+        });
+
+        list("class", id.chain(super_class).chain(elements))
+    }
+}
+
+impl ToSexp for VariableDeclaration {
+    fn to_sexp(&self) -> String {
+        list(
+            self.kind.as_str(),
+            self.declarations
+                .iter()
+                .map(|declarator| match &declarator.init {
+                    Some(init) => list("=", [declarator.id.to_sexp(), init.to_sexp()]),
+                    None => declarator.id.to_sexp(),
+                }),
+        )
+    }
+}
+
+impl ToSexp for ForInOfLeft {
+    fn to_sexp(&self) -> String {
+        match self {
+            ForInOfLeft::VariableDeclaration(declaration) => declaration.to_sexp(),
+            ForInOfLeft::Pattern(pattern) => pattern.to_sexp(),
+        }
+    }
+}
+
+fn optional(node: &Option<impl ToSexp>) -> String {
+    match node {
+        Some(node) => node.to_sexp(),
+        None => "_".to_string(),
+    }
+}
+
+impl ToSexp for Statement {
+    fn to_sexp(&self) -> String {
+        match self {
+            Statement::Expression(statement) => statement.expression.to_sexp(),
+            Statement::Block(block) => list("block", block.body.iter().map(ToSexp::to_sexp)),
+            Statement::Empty(_) => "(empty)".to_string(),
+            Statement::Error(_) => "(error)".to_string(),
+            Statement::Debugger(_) => "(debugger)".to_string(),
+            Statement::With(with) => list("with", [with.object.to_sexp(), with.body.to_sexp()]),
+            Statement::Return(statement) => {
+                list("return", statement.argument.iter().map(ToSexp::to_sexp))
+            }
+            Statement::Labeled(labeled) => list(
+                "label",
+                [labeled.label.name.clone(), labeled.body.to_sexp()],
+            ),
+            Statement::Break(statement) => list(
+                "break",
+                statement.label.iter().map(|label| label.name.clone()),
+            ),
+            Statement::Continue(statement) => list(
+                "continue",
+                statement.label.iter().map(|label| label.name.clone()),
+            ),
+            Statement::If(statement) => list(
+                "if",
+                [statement.test.to_sexp(), statement.consequent.to_sexp()]
+                    .into_iter()
+                    .chain(statement.alternate.iter().map(ToSexp::to_sexp)),
+            ),
+            Statement::Switch(statement) => list(
+                "switch",
+                std::iter::once(statement.discriminant.to_sexp()).chain(
+                    statement.cases.iter().map(|case| match &case.test {
+                        Some(test) => list(
+                            "case",
+                            std::iter::once(test.to_sexp())
+                                .chain(case.consequent.iter().map(ToSexp::to_sexp)),
+                        ),
+                        None => list("default", case.consequent.iter().map(ToSexp::to_sexp)),
+                    }),
+                ),
+            ),
+            Statement::Throw(statement) => list("throw", [statement.argument.to_sexp()]),
+            Statement::Try(statement) => {
+                let handler = statement.handler.iter().map(|handler| {
+                    list(
+                        "catch",
+                        [
+                            optional(&handler.param),
+                            Statement::Block(handler.body.clone()).to_sexp(),
+                        ],
+                    )
+                });
+                let finalizer = statement
+                    .finalizer
+                    .iter()
+                    .map(|finalizer| Statement::Block(finalizer.clone()).to_sexp());
+
+                list(
+                    "try",
+                    std::iter::once(Statement::Block(statement.block.clone()).to_sexp())
+                        .chain(handler)
+                        .chain(finalizer),
+                )
+            }
+            Statement::While(statement) => list(
+                "while",
+                [statement.test.to_sexp(), statement.body.to_sexp()],
+            ),
+            Statement::DoWhile(statement) => {
+                list("do", [statement.body.to_sexp(), statement.test.to_sexp()])
+            }
+            Statement::For(statement) => list(
+                "for",
+                [
+                    optional(&statement.init),
+                    optional(&statement.test),
+                    optional(&statement.update),
+                    statement.body.to_sexp(),
+                ],
+            ),
+            Statement::ForIn(statement) => list(
+                "for-in",
+                [
+                    statement.left.to_sexp(),
+                    statement.right.to_sexp(),
+                    statement.body.to_sexp(),
+                ],
+            ),
+            Statement::ForOf(statement) => list(
+                if statement.is_await {
+                    "for-await-of"
+                } else {
+                    "for-of"
+                },
+                [
+                    statement.left.to_sexp(),
+                    statement.right.to_sexp(),
+                    statement.body.to_sexp(),
+                ],
+            ),
+            Statement::FunctionDeclaration(function) => function.to_sexp(),
+            Statement::VariableDeclaration(declaration) => declaration.to_sexp(),
+            Statement::ClassDeclaration(class) => class.to_sexp(),
+        }
+    }
+}
+
+impl ToSexp for ForInit {
+    fn to_sexp(&self) -> String {
+        match self {
+            ForInit::VariableDeclaration(declaration) => declaration.to_sexp(),
+            ForInit::Expression(expression) => expression.to_sexp(),
+        }
+    }
+}
+
+impl ToSexp for JsAst {
+    fn to_sexp(&self) -> String {
+        let items: Vec<_> = self
+            .body
+            .iter()
+            .map(|item| match item {
+                ModuleItem::Statement(statement) => statement.to_sexp(),
+                ModuleItem::ModuleDeclaration(declaration) => declaration.to_sexp(),
+            })
+            .collect();
+
+        items.join(" ")
+    }
+}
+
+impl ToSexp for ModuleExportName {
+    fn to_sexp(&self) -> String {
+        match self {
+            ModuleExportName::Identifier(identifier) => identifier.name.clone(),
+            ModuleExportName::Literal(literal) => literal.raw.clone(),
+        }
+    }
+}
+
+impl ToSexp for ImportAttribute {
+    fn to_sexp(&self) -> String {
+        list("with", [self.key.to_sexp(), self.value.to_sexp()])
+    }
+}
+
+impl ToSexp for ModuleDeclaration {
+    fn to_sexp(&self) -> String {
+        match self {
+            ModuleDeclaration::Import(import) => {
+                let specifiers = import.specifiers.iter().map(|specifier| match specifier {
+                    ImportDeclarationSpecifier::Default(default) => default.local.name.clone(),
+                    ImportDeclarationSpecifier::Namespace(namespace) => {
+                        list("*", [namespace.local.name.clone()])
+                    }
+                    ImportDeclarationSpecifier::Named(named) => {
+                        list("as", [named.imported.to_sexp(), named.local.name.clone()])
+                    }
+                });
+
+                list(
+                    "import",
+                    std::iter::once(import.source.to_sexp())
+                        .chain(specifiers)
+                        .chain(import.attributes.iter().map(ToSexp::to_sexp)),
+                )
+            }
+            ModuleDeclaration::ExportNamed(export) => {
+                let declaration = export
+                    .declaration
+                    .iter()
+                    .map(|declaration| match declaration {
+                        Declaration::Function(function) => function.to_sexp(),
+                        Declaration::Variable(variable) => variable.to_sexp(),
+                        Declaration::Class(class) => class.to_sexp(),
+                    });
+                let specifiers = export.specifiers.iter().map(|specifier| {
+                    list(
+                        "as",
+                        [specifier.local.to_sexp(), specifier.exported.to_sexp()],
+                    )
+                });
+                let source = export
+                    .source
+                    .iter()
+                    .map(|source| list("from", [source.to_sexp()]));
+
+                list(
+                    "export",
+                    declaration
+                        .chain(specifiers)
+                        .chain(source)
+                        .chain(export.attributes.iter().map(ToSexp::to_sexp)),
+                )
+            }
+            ModuleDeclaration::ExportDefault(export) => list(
+                "export-default",
+                [match &export.declaration {
+                    ExportDefaultKind::Function(function) => function.to_sexp(),
+                    ExportDefaultKind::Class(class) => class.to_sexp(),
+                    ExportDefaultKind::Expression(expression) => expression.to_sexp(),
+                }],
+            ),
+            ModuleDeclaration::ExportAll(export) => list(
+                "export",
+                std::iter::once("*".to_string())
+                    .chain(
+                        export
+                            .exported
+                            .iter()
+                            .map(|exported| list("as", [exported.to_sexp()])),
+                    )
+                    .chain(std::iter::once(export.source.to_sexp()))
+                    .chain(export.attributes.iter().map(ToSexp::to_sexp)),
+            ),
+        }
+    }
+}
@@ -0,0 +1,453 @@
+//! `yield` 式と `await` 式を読むモジュールです。
+//!
+//! `yield` と `await` は文脈によって識別子にも演算子にもなる。
+//! ジェネレーターの中では `yield` が、async 関数の中では `await` が演算子になる。
+//! どちらも関数の引数リストには置けないため、関数の終わりまで位置を記録しておく。
+
+use crate::token::structs::{punctuator::Punctuator, token::TokenType};
+
+use super::Parser;
+use crate::ast::{
+    error::ParseResult,
+    structs::{
+        expression::{Expression, YieldExpression},
+        span::Span,
+    },
+};
+
+/// 引数リストに置けない `yield` と `await` の位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum YieldAwait {
+    /// yield a
+    Yield(Span),
+
+    /// await a
+    Await(Span),
+
+    /// 識別子の await (async アロー関数の引数にだけ置けない)
+    AwaitIdentifier(Span),
+}
+
+impl YieldAwait {
+    fn span(self) -> Span {
+        match self {
+            YieldAwait::Yield(span)
+            | YieldAwait::Await(span)
+            | YieldAwait::AwaitIdentifier(span) => span,
+        }
+    }
+}
+
+impl<'a> Parser<'a> {
+    /// `yield` `yield a` `yield* a`
+    ///
+    /// `yield` の後ろに改行や式の終わりが続く場合は引数を持たない。
+    pub(crate) fn parse_yield_expression(&mut self) -> ParseResult<Expression> {
+        let start = self.start();
+        self.expect_keyword("yield")?;
+
+        let delegate = !self.has_newline_before && self.eat(Punctuator::Multiply)?;
+        let has_argument = delegate
+            || !(self.has_newline_before
+                || self.is_end()
+                || matches!(
+                    self.token_type(),
+                    TokenType::Punctuator(
+                        Punctuator::RightParen
+                            | Punctuator::RightBracket
+                            | Punctuator::RightBrace
+                            | Punctuator::Comma
+                            | Punctuator::SemiColon
+                            | Punctuator::TernaryColon
+                    )
+                ));
+
+        let argument = if has_argument {
+            Some(self.parse_assignment_expression()?)
+        } else {
+            None
+        };
+
+        let span = self.span_from(start);
+        self.yield_await.push(YieldAwait::Yield(span));
+
+        Ok(Expression::Yield(Box::new(YieldExpression {
+            span,
+            argument,
+            delegate,
+        })))
+    }
+
+    /// `start` 以降の引数リストに `yield` 式と `await` 式がないかを検査する。
+    ///
+    /// async アロー関数の引数リストには、識別子の `await` も置けない。
+    pub(crate) fn check_parameters_yield_await(
+        &self,
+        start: usize,
+        is_async_arrow: bool,
+    ) -> ParseResult<()> {
+        for &item in &self.yield_await {
+            if item.span().start < start {
+                continue;
+            }
+
+            let message = match item {
+                YieldAwait::Yield(_) => "yield expression is not allowed in formal parameters",
+                YieldAwait::Await(_) => "await expression is not allowed in formal parameters",
+                YieldAwait::AwaitIdentifier(_) if is_async_arrow => {
+                    "'await' is not allowed in async arrow function parameters"
+                }
+                YieldAwait::AwaitIdentifier(_) => continue,
+            };
+            return self.error(item.span(), message);
+        }
+
+        Ok(())
+    }
+
+    /// `start` から始まる関数を読み終えたので、その中の `yield` と `await` の記録を捨てる。
+    pub(crate) fn discard_yield_await(&mut self, start: usize) {
+        self.yield_await.retain(|item| item.span().start < start);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test_helper::{
+        parse_module_error, parse_module_sexp, parse_script_error, parse_script_sexp,
+    };
+
+    macro_rules! test_case {
+        ($test_name:ident, $source:expr, $expected:expr) => {
+            #[test]
+            fn $test_name() {
+                assert_eq!(parse_script_sexp($source), Ok($expected.to_string()));
+            }
+        };
+    }
+
+    macro_rules! test_case_module {
+        ($test_name:ident, $source:expr, $expected:expr) => {
+            #[test]
+            fn $test_name() {
+                assert_eq!(parse_module_sexp($source), Ok($expected.to_string()));
+            }
+        };
+    }
+
+    macro_rules! test_case_fail {
+        ($test_name:ident, $source:expr, $expected:expr) => {
+            #[test]
+            fn $test_name() {
+                assert_eq!(
+                    parse_script_error($source).map(|error| error.message),
+                    Some($expected.to_string())
+                );
+            }
+        };
+    }
+
+    macro_rules! test_case_module_fail {
+        ($test_name:ident, $source:expr, $expected:expr) => {
+            #[test]
+            fn $test_name() {
+                assert_eq!(
+                    parse_module_error($source).map(|error| error.message),
+                    Some($expected.to_string())
+                );
+            }
+        };
+    }
+
+    // 関数の外
+    test_case!(yield_identifier, "yield = 1;", "(= yield 1)");
+    test_case!(await_identifier, "await = 1;", "(= await 1)");
+    test_case_fail!(
+        strict_yield_identifier,
+        "'use strict'; yield = 1;",
+        "unexpected keyword 'yield'"
+    );
+    test_case_module_fail!(
+        module_await_identifier,
+        "await = 1;",
+        "unexpected token '='"
+    );
+    test_case_module!(module_await, "await a;", "(await a)");
+
+    // ジェネレーター
+    test_case!(
+        yield_expression,
+        "function* g() { yield; yield a; yield* b; }",
+        "(function* g (yield) (yield a) (yield* b))"
+    );
+    test_case!(
+        yield_in_expressions,
+        "function* g() { a = yield b; f(yield, yield c); [yield]; ({ a: yield }); }",
+        "(function* g (= a (yield b)) (call f (yield) (yield c)) (array (yield)) (object (: a (yield))))"
+    );
+    test_case!(
+        yield_newline,
+        "function* g() { yield\na; }",
+        "(function* g (yield) a)"
+    );
+    test_case!(
+        yield_conditional,
+        "function* g() { a ? yield : yield b; }",
+        "(function* g (? a (yield) (yield b)))"
+    );
+    test_case!(
+        yield_nested_yield,
+        "function* g() { yield yield a; }",
+        "(function* g (yield (yield a)))"
+    );
+    test_case!(
+        generator_expression,
+        "(function* () { yield a; });",
+        "(function* (yield a))"
+    );
+    test_case!(
+        generator_method,
+        "({ *g() { yield a; } });",
+        "(object (method* g))"
+    );
+    test_case!(
+        generator_declaration_named_yield,
+        "function* yield() {}",
+        "(function* yield)"
+    );
+    test_case!(
+        yield_in_nested_function,
+        "function* g() { function f() { yield = 1; } }",
+        "(function* g (function f (= yield 1)))"
+    );
+    test_case!(
+        yield_in_arrow_body,
+        "function* g() { () => yield; }",
+        "(function* g (=> yield))"
+    );
+    test_case_fail!(
+        yield_operand,
+        "function* g() { a + yield; }",
+        "unexpected keyword 'yield'"
+    );
+    test_case_fail!(
+        yield_delegate_without_argument,
+        "function* g() { yield*; }",
+        "expected an expression"
+    );
+    test_case_fail!(
+        yield_binding_in_generator,
+        "function* g() { var yield; }",
+        "unexpected keyword 'yield'"
+    );
+    test_case_fail!(
+        generator_expression_named_yield,
+        "(function* yield() {});",
+        "unexpected keyword 'yield'"
+    );
+    test_case_fail!(
+        yield_in_parameters,
+        "function* g(a = yield) {}",
+        "yield expression is not allowed in formal parameters"
+    );
+    test_case_fail!(
+        yield_parameter_name,
+        "function* g(yield) {}",
+        "unexpected keyword 'yield'"
+    );
+    test_case_fail!(
+        yield_in_arrow_parameters,
+        "function* g() { (a = yield) => a; }",
+        "yield expression is not allowed in formal parameters"
+    );
+    test_case_fail!(
+        yield_in_method_parameters,
+        "({ *g(a = yield) {} });",
+        "yield expression is not allowed in formal parameters"
+    );
+    test_case_fail!(
+        yield_outside_generator,
+        "function f() { yield a; }",
+        "unexpected token 'a'"
+    );
+
+    // async 関数
+    test_case!(
+        async_function,
+        "async function f() { await a; }",
+        "(async function f (await a))"
+    );
+    test_case!(
+        async_function_expression,
+        "(async function () { await a; });",
+        "(async function (await a))"
+    );
+    test_case!(
+        async_newline,
+        "async\nfunction f() {}",
+        "async (function f)"
+    );
+    test_case!(
+        async_function_named_await,
+        "async function await() {}",
+        "(async function await)"
+    );
+    test_case!(
+        await_in_nested_function,
+        "async function f() { function g() { await = 1; } }",
+        "(async function f (function g (= await 1)))"
+    );
+    test_case!(
+        await_precedence,
+        "async function f() { await a + b; (await a) ** 2; }",
+        "(async function f (+ (await a) b) (** (await a) 2))"
+    );
+    test_case_fail!(
+        await_exponent,
+        "async function f() { await a ** 2; }",
+        "unary operator before '**' must be parenthesized"
+    );
+    test_case!(
+        for_await,
+        "async function f() { for await (const a of b) c; }",
+        "(async function f (for-await-of (const a) b c))"
+    );
+    test_case!(
+        async_method,
+        "({ async m() { await a; } });",
+        "(object (async-method m))"
+    );
+    test_case_fail!(
+        async_function_expression_named_await,
+        "(async function await() {});",
+        "unexpected keyword 'await'"
+    );
+    test_case_fail!(
+        await_in_parameters,
+        "async function f(a = await b) {}",
+        "await expression is not allowed in formal parameters"
+    );
+    test_case_fail!(
+        await_parameter_name,
+        "async function f(await) {}",
+        "unexpected keyword 'await'"
+    );
+    test_case_fail!(
+        await_binding_in_async,
+        "async function f() { let await; }",
+        "unexpected keyword 'await'"
+    );
+    test_case_fail!(
+        for_await_outside_async,
+        "function f() { for await (const a of b) c; }",
+        "unexpected token 'await'"
+    );
+    test_case_fail!(
+        for_await_in,
+        "async function f() { for await (const a in b) c; }",
+        "'for await' requires 'of'"
+    );
+    test_case_fail!(
+        async_function_in_if,
+        "if (a) async function f() {}",
+        "async function declaration cannot appear in single-statement context"
+    );
+
+    // async ジェネレーター
+    test_case!(
+        async_generator,
+        "async function* g() { yield await a; for await (b of c) yield* b; }",
+        "(async function* g (yield (await a)) (for-await-of b c (yield* b)))"
+    );
+    test_case!(
+        async_generator_method,
+        "({ async *g() { yield a; } });",
+        "(object (async-method* g))"
+    );
+    test_case_fail!(
+        async_generator_yield_parameter,
+        "async function* g(a = yield) {}",
+        "yield expression is not allowed in formal parameters"
+    );
+    test_case_fail!(
+        async_generator_await_parameter,
+        "async function* g(a = await b) {}",
+        "await expression is not allowed in formal parameters"
+    );
+
+    // async アロー関数
+    test_case!(
+        async_arrow,
+        "async (a) => await a;",
+        "(async=> a (await a))"
+    );
+    test_case!(
+        async_arrow_identifier,
+        "async a => await a;",
+        "(async=> a (await a))"
+    );
+    test_case!(async_call, "async(await);", "(call async await)");
+    test_case!(arrow_await_parameter, "(await) => 1;", "(=> await 1)");
+    test_case!(
+        arrow_in_async_function,
+        "async function f() { async () => await a; }",
+        "(async function f (async=> (await a)))"
+    );
+    test_case!(
+        async_arrow_nested_function,
+        "async (a = function await() {}) => a;",
+        "(async=> (= a (function await)) a)"
+    );
+    test_case_fail!(
+        async_arrow_await_parameter,
+        "async (await) => 1;",
+        "'await' is not allowed in async arrow function parameters"
+    );
+    test_case_fail!(
+        async_arrow_identifier_await,
+        "async await => 1;",
+        "'await' is not allowed in async arrow function parameters"
+    );
+    test_case_fail!(
+        async_arrow_await_default,
+        "async (a = await) => 1;",
+        "'await' is not allowed in async arrow function parameters"
+    );
+    test_case_fail!(
+        async_arrow_await_expression_parameter,
+        "async function f() { async (a = await b) => 1; }",
+        "await expression is not allowed in formal parameters"
+    );
+    test_case_fail!(
+        arrow_await_expression_parameter,
+        "async function f() { (a = await b) => 1; }",
+        "await expression is not allowed in formal parameters"
+    );
+    test_case_fail!(
+        await_in_arrow_body,
+        "async function f() { () => await a; }",
+        "unexpected token 'a'"
+    );
+    test_case_module_fail!(
+        module_arrow_await_parameter,
+        "(a = await b) => 1;",
+        "await expression is not allowed in formal parameters"
+    );
+
+    // クラス
+    test_case!(
+        class_generator_method,
+        "class A { *g() { yield a; } static async *h() { yield await b; } }",
+        "(class A (method* g) (static-async-method* h))"
+    );
+    test_case_fail!(
+        yield_in_field_initializer,
+        "function* g() { class A { a = yield; } }",
+        "unexpected keyword 'yield'"
+    );
+    test_case_fail!(
+        await_in_field_initializer,
+        "async function f() { class A { a = await b; } }",
+        "unexpected token 'b'"
+    );
+}
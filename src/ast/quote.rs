@@ -0,0 +1,405 @@
+//! JavaScript の断片のテンプレートから構文木を作るモジュールです。
+//!
+//! テンプレートの `$` で始まる識別子 (`$a` など) は穴で、指定した値で埋める。
+//!
+//! ```ignore
+//! let sum = quote_expr!("$a + $b", a = left, b = "c");
+//! let body = quote_stmts!("if ($test) $then; return;", test = test, then = statements);
+//! ```
+//!
+//! - 式の位置の穴は、式か識別子で埋める。
+//! - 束縛の名前やプロパティ名のように識別子しか置けない位置の穴は、識別子で埋める。
+//! - 文の位置の穴 (`$a;`) は、文の列で埋めることもできる。文のリストの中では展開し、
+//!   `if` の本体のように1つの文しか置けない位置では複数の文をブロックで囲む。
+//!
+//! 作ったノードの位置はテンプレートの中の位置になる。
+//! テンプレートが読めない場合や、穴と値が対応しない場合はエラーになる。
+//! マクロはエラーで panic するため、テンプレートはテストで確かめる。
+
+use std::fmt::{self, Display};
+
+use super::{
+    reader_struct::SourceCodeReader,
+    script::{parse_expression, parse_statements},
+    structs::{
+        expression::Expression,
+        literal::Identifier,
+        span::Span,
+        statement::{BlockStatement, ExpressionStatement, Statement},
+    },
+    visit::{Fold, FoldWith},
+};
+
+/// 穴を埋める値
+#[derive(Debug, PartialEq, Clone)]
+pub enum Hole {
+    Expression(Expression),
+    Identifier(Identifier),
+    Statements(Vec<Statement>),
+}
+
+impl From<Expression> for Hole {
+    fn from(expression: Expression) -> Hole {
+        Hole::Expression(expression)
+    }
+}
+
+impl From<Identifier> for Hole {
+    fn from(identifier: Identifier) -> Hole {
+        Hole::Identifier(identifier)
+    }
+}
+
+/// 文字列は識別子の名前として埋める。
+impl From<&str> for Hole {
+    fn from(name: &str) -> Hole {
+        Hole::Identifier(Identifier::new(Span::default(), name))
+    }
+}
+
+impl From<Statement> for Hole {
+    fn from(statement: Statement) -> Hole {
+        Hole::Statements(vec![statement])
+    }
+}
+
+impl From<Vec<Statement>> for Hole {
+    fn from(statements: Vec<Statement>) -> Hole {
+        Hole::Statements(statements)
+    }
+}
+
+/// テンプレートから構文木を作れなかった理由
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct QuoteError {
+    pub message: String,
+}
+
+impl Display for QuoteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl QuoteError {
+    fn new(message: impl Into<String>) -> QuoteError {
+        QuoteError {
+            message: message.into(),
+        }
+    }
+}
+
+/// 式のテンプレートの穴を埋める。
+pub fn quote_expression(
+    template: &str,
+    holes: Vec<(&str, Hole)>,
+) -> Result<Expression, QuoteError> {
+    let expression = parse_expression(SourceCodeReader::new(template))
+        .map_err(|error| QuoteError::new(format!("invalid template '{template}': {error}")))?;
+    Filler::new(holes)?.fill(expression)
+}
+
+/// 文のリストのテンプレートの穴を埋める。
+pub fn quote_statements(
+    template: &str,
+    holes: Vec<(&str, Hole)>,
+) -> Result<Vec<Statement>, QuoteError> {
+    let statements = parse_statements(SourceCodeReader::new(template))
+        .map_err(|error| QuoteError::new(format!("invalid template '{template}': {error}")))?;
+    Filler::new(holes)?.fill(statements)
+}
+
+/// 式のテンプレートから式を作る。テンプレートと値が合わない場合は panic する。
+macro_rules! quote_expr {
+    ($template:expr $(, $name:ident = $value:expr)* $(,)?) => {
+        $crate::ast::quote::quote_expression(
+            $template,
+            vec![$((stringify!($name), $crate::ast::quote::Hole::from($value))),*],
+        )
+        .unwrap_or_else(|error| panic!("{error}"))
+    };
+}
+
+/// 文のリストのテンプレートから文を作る。テンプレートと値が合わない場合は panic する。
+macro_rules! quote_stmts {
+    ($template:expr $(, $name:ident = $value:expr)* $(,)?) => {
+        $crate::ast::quote::quote_statements(
+            $template,
+            vec![$((stringify!($name), $crate::ast::quote::Hole::from($value))),*],
+        )
+        .unwrap_or_else(|error| panic!("{error}"))
+    };
+}
+
+pub(crate) use {quote_expr, quote_stmts};
+
+/// 穴を値で埋める。
+struct Filler {
+    holes: Vec<(String, Hole)>,
+    used: Vec<bool>,
+
+    /// 最初に見つけたエラー
+    error: Option<QuoteError>,
+}
+
+impl Filler {
+    fn new(holes: Vec<(&str, Hole)>) -> Result<Filler, QuoteError> {
+        for (index, (name, _)) in holes.iter().enumerate() {
+            if holes[..index].iter().any(|(other, _)| other == name) {
+                return Err(QuoteError::new(format!("duplicate value for '${name}'")));
+            }
+        }
+
+        Ok(Filler {
+            used: vec![false; holes.len()],
+            holes: holes
+                .into_iter()
+                .map(|(name, hole)| (name.to_string(), hole))
+                .collect(),
+            error: None,
+        })
+    }
+
+    fn fill<T: FoldWith>(mut self, node: T) -> Result<T, QuoteError> {
+        let node = node.fold_with(&mut self);
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        if let Some(index) = self.used.iter().position(|used| !used) {
+            let name = &self.holes[index].0;
+            return Err(QuoteError::new(format!(
+                "value '{name}' is not used in the template"
+            )));
+        }
+        Ok(node)
+    }
+
+    /// 識別子が穴であれば、埋める値を返す。
+    fn hole(&mut self, identifier: &Identifier) -> Option<Hole> {
+        let name = identifier
+            .name
+            .strip_prefix('$')
+            .filter(|name| !name.is_empty())?;
+        match self.holes.iter().position(|(hole, _)| hole == name) {
+            Some(index) => {
+                self.used[index] = true;
+                Some(self.holes[index].1.clone())
+            }
+            None => {
+                self.report(format!("no value for placeholder '${name}'"));
+                None
+            }
+        }
+    }
+
+    fn report(&mut self, message: String) {
+        if self.error.is_none() {
+            self.error = Some(QuoteError::new(message));
+        }
+    }
+
+    /// `$a;` の形の文の穴が文の列で埋まる場合は、その文の列を返す。
+    fn statements_hole(&mut self, statement: &Statement) -> Option<Vec<Statement>> {
+        let Statement::Expression(ExpressionStatement {
+            expression: Expression::Identifier(identifier),
+            directive: None,
+            ..
+        }) = statement
+        else {
+            return None;
+        };
+
+        let name = identifier.name.strip_prefix('$')?;
+        let index = self.holes.iter().position(|(hole, _)| hole == name)?;
+        match &self.holes[index].1 {
+            Hole::Statements(statements) => {
+                self.used[index] = true;
+                Some(statements.clone())
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Fold for Filler {
+    fn fold_expression(&mut self, node: Expression) -> Expression {
+        let Expression::Identifier(identifier) = &node else {
+            return node.fold_children_with(self);
+        };
+
+        match self.hole(identifier) {
+            Some(Hole::Expression(expression)) => expression,
+            Some(Hole::Identifier(identifier)) => Expression::Identifier(identifier),
+            Some(Hole::Statements(_)) => {
+                let message = format!(
+                    "statements cannot fill the expression '{}'",
+                    identifier.name
+                );
+                self.report(message);
+                node
+            }
+            None => node,
+        }
+    }
+
+    fn fold_identifier(&mut self, node: Identifier) -> Identifier {
+        match self.hole(&node) {
+            Some(Hole::Identifier(identifier)) => identifier,
+            Some(_) => {
+                self.report(format!("'{}' must be filled with an identifier", node.name));
+                node
+            }
+            None => node,
+        }
+    }
+
+    fn fold_statement(&mut self, node: Statement) -> Statement {
+        match self.statements_hole(&node) {
+            Some(mut statements) if statements.len() == 1 => statements.pop().expect("a statement"),
+            Some(statements) => Statement::Block(BlockStatement {
+                span: Span::default(),
+                body: statements,
+            }),
+            None => node.fold_children_with(self),
+        }
+    }
+
+    fn flat_fold_statement(&mut self, node: Statement) -> Vec<Statement> {
+        match self.statements_hole(&node) {
+            Some(statements) => statements,
+            None => vec![self.fold_statement(node)],
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{quote_expression, quote_statements, Hole, QuoteError};
+    use crate::{
+        ast::{
+            quote::{quote_expr, quote_stmts},
+            structs::statement::Statement,
+        },
+        to_source_string::ToSourceString,
+    };
+
+    fn source(statements: &[Statement]) -> String {
+        statements
+            .iter()
+            .map(|statement| statement.to_source_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn error(result: Result<impl std::fmt::Debug, QuoteError>) -> String {
+        result.expect_err("template should fail").message
+    }
+
+    #[test]
+    fn expression() {
+        let left = quote_expr!("x * 2");
+        let sum = quote_expr!("$a + $b", a = left, b = "c");
+        assert_eq!(sum.to_source_string(), "x * 2 + c");
+
+        // 優先順位は構文木で決まるため、括弧は出力時に補われる
+        let product = quote_expr!("$a * $a", a = quote_expr!("x + 1"));
+        assert_eq!(product.to_source_string(), "(x + 1) * (x + 1)");
+
+        let call = quote_expr!(
+            "$f($x, ...$rest)",
+            f = "g",
+            x = quote_expr!("1"),
+            rest = "r"
+        );
+        assert_eq!(call.to_source_string(), "g(1, ...r)");
+
+        // 値を指定しない識別子はそのまま残る
+        assert_eq!(quote_expr!("a + b").to_source_string(), "a + b");
+    }
+
+    #[test]
+    fn identifiers() {
+        let function = quote_expr!(
+            "function $name($param) { return $param.$key; }",
+            name = "f",
+            param = "value",
+            key = "length",
+        );
+        assert_eq!(
+            function.to_source_string(),
+            "function f(value) {\n  return value.length;\n}"
+        );
+
+        let object = quote_expr!("({ $key: $value })", key = "a", value = quote_expr!("1"));
+        assert_eq!(object.to_source_string(), "{ a: 1 }");
+    }
+
+    #[test]
+    fn statements() {
+        let body = quote_stmts!("a(); b();");
+        let statements = quote_stmts!("before(); $body; after();", body = body.clone());
+        assert_eq!(source(&statements), "before();\na();\nb();\nafter();");
+
+        // 1つの文しか置けない位置では、複数の文をブロックで囲む
+        let statements = quote_stmts!(
+            "if ($test) $then; else $other;",
+            test = "x",
+            then = body,
+            other = quote_stmts!("c();")
+        );
+        assert_eq!(source(&statements), "if (x) {\n  a();\n  b();\n} else c();");
+
+        // 文の位置の穴を式で埋めると式文になる
+        let statements = quote_stmts!("$a;", a = quote_expr!("f()"));
+        assert_eq!(source(&statements), "f();");
+
+        let statements = quote_stmts!("$a;", a = Vec::new());
+        assert_eq!(source(&statements), "");
+    }
+
+    #[test]
+    fn errors() {
+        assert!(error(quote_expression("$a +", vec![])).starts_with("invalid template '$a +'"));
+        assert!(error(quote_statements("if (", vec![])).starts_with("invalid template 'if ('"));
+        assert!(error(quote_expression("a; b", vec![])).starts_with("invalid template"));
+
+        assert_eq!(
+            error(quote_expression("$a + $b", vec![("a", "x".into())])),
+            "no value for placeholder '$b'"
+        );
+        assert_eq!(
+            error(quote_expression(
+                "$a",
+                vec![("a", "x".into()), ("b", "y".into())]
+            )),
+            "value 'b' is not used in the template"
+        );
+        assert_eq!(
+            error(quote_expression(
+                "$a",
+                vec![("a", "x".into()), ("a", "y".into())]
+            )),
+            "duplicate value for '$a'"
+        );
+        assert_eq!(
+            error(quote_expression(
+                "$a + 1",
+                vec![("a", Hole::from(quote_stmts!("b();")))]
+            )),
+            "statements cannot fill the expression '$a'"
+        );
+        assert_eq!(
+            error(quote_expression(
+                "function $a() {}",
+                vec![("a", quote_expr!("1").into())]
+            )),
+            "'$a' must be filled with an identifier"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "no value for placeholder '$b'")]
+    fn macro_panics() {
+        quote_expr!("$a + $b", a = "x");
+    }
+}
@@ -1,25 +1,67 @@
 use std::{iter::Peekable, str::CharIndices};
 
-use crate::token::structs::{
-    context::{BraceContext, DivContext},
-    token::Token,
+use crate::token::{
+    structs::{
+        context::{BraceContext, DivContext},
+        error::TokenizeError,
+        token::{Token, TokenType},
+    },
+    tokenize::next_token,
 };
 
 #[derive(Debug, Clone)]
 pub struct SourceCodeReader<'a> {
     source: &'a str,
+
+    /// `iter` の読み始めの位置
+    offset: usize,
     iter: Peekable<CharIndices<'a>>,
 }
 
 impl<'a> SourceCodeReader<'a> {
-    pub fn new(source: &'a str) -> SourceCodeReader {
+    pub fn new(source: &'a str) -> SourceCodeReader<'a> {
         SourceCodeReader {
             source,
+            offset: 0,
             iter: source.char_indices().peekable(),
         }
     }
 
-    pub fn read_next_token(&mut self, div: DivContext, brace: BraceContext) -> Token {
-        todo!()
+    pub fn source(&self) -> &'a str {
+        self.source
+    }
+
+    /// 次に読む文字の位置を返す。
+    pub fn position(&mut self) -> usize {
+        match self.iter.peek() {
+            Some((index, _)) => self.offset + index,
+            None => self.source.len(),
+        }
+    }
+
+    /// 指定した位置から読み直す。
+    ///
+    /// 文脈によって読み方が変わる `/` と `}` を読み直すために使う。
+    pub fn reset(&mut self, position: usize) {
+        self.offset = position;
+        self.iter = self.source[position..].char_indices().peekable();
+    }
+
+    /// 次のトークンを読む。
+    ///
+    /// 空白、改行、コメントもトークンとして返す。
+    /// 最後まで読んだ場合は [`TokenType::EndOfFile`] を返す。
+    pub fn read_next_token(
+        &mut self,
+        div: DivContext,
+        brace: BraceContext,
+    ) -> Result<Token, TokenizeError> {
+        if self.iter.peek().is_none() {
+            let end = self.source.len();
+            return Ok(Token::new(TokenType::EndOfFile, end, end));
+        }
+
+        let offset = self.offset;
+        next_token(&mut self.iter, div, brace).map(|token| token.offset(offset))
     }
 }
@@ -0,0 +1,763 @@
+//! JavaScript のコードで書いたパターンで構文木を探し、書き換えるモジュールです。
+//!
+//! ```ignore
+//! let rule = Rule::new("$X.bar($Y)", "baz($Y)")?;
+//! let rewritten = rule.rewrite("foo.bar(1 + 2);", SourceType::Script)?;
+//! assert_eq!(rewritten.source, "baz(1 + 2);");
+//! ```
+//!
+//! - パターンは式か1つの文で、`$X` のようなメタ変数 ([`matcher::is_metavariable`]) は
+//!   任意のノードと一致する。同じメタ変数が複数回現れる場合は、同じ構造のノードと一致する。
+//!   `$_` はどのノードとも一致し、束縛しない。
+//! - 文のパターンの `$A;` は任意の文と一致する。
+//! - 置き換えはパターンと同じ種類 (式か文の列) のテンプレートで、メタ変数を束縛したノードで
+//!   埋め ([`quote`](super::quote))、コード生成で文字列にする。
+//!
+//! 一致したノードの範囲だけを置き換え、それ以外のソースコードはそのまま残す。
+//! 一致したノードの中にある一致は、束縛したノードを書き換えてから埋める。
+
+mod matcher;
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+};
+
+use self::matcher::{find_matches, is_metavariable, metavariable, span, Binding, Match, Slot};
+use super::{
+    estree::{expression_to_estree, statement_to_estree, to_estree},
+    line_index::LineIndex,
+    quote::{quote_expression, quote_statements, Hole},
+    reader_struct::SourceCodeReader,
+    script::{parse_expression, parse_module, parse_script, parse_statements},
+    structs::{
+        expression::Expression,
+        literal::Identifier,
+        span::{Span, Spanned},
+        statement::{BlockStatement, ExpressionStatement, Statement},
+        JsAst, SourceType,
+    },
+    visit::{Fold, FoldWith, Visit, VisitWith},
+};
+use crate::{json::JsonValue, to_source_string::ToSourceString};
+
+/// 書き換えができなかった理由
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RewriteError {
+    pub message: String,
+}
+
+impl Display for RewriteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl RewriteError {
+    fn new(message: impl Into<String>) -> RewriteError {
+        RewriteError {
+            message: message.into(),
+        }
+    }
+}
+
+/// パターンの種類
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum PatternKind {
+    Expression,
+    Statement,
+}
+
+/// パターンと置き換えの組
+#[derive(Debug, Clone)]
+pub struct Rule {
+    kind: PatternKind,
+    pattern: JsonValue,
+    replacement: String,
+
+    /// 置き換えに現れる `$` で始まる識別子
+    placeholders: Vec<String>,
+}
+
+/// 書き換えた結果
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Rewrite {
+    pub source: String,
+
+    /// 書き換えた箇所の数 (一致したノードの中の一致は数えない)
+    pub count: usize,
+}
+
+impl Rule {
+    /// パターンと置き換えを読む。
+    ///
+    /// パターンは式として読めなければ文として読む。
+    pub fn new(pattern: &str, replacement: &str) -> Result<Rule, RewriteError> {
+        let (kind, pattern_json, replacement_json) =
+            match parse_expression(SourceCodeReader::new(pattern)) {
+                Ok(expression) => {
+                    let replacement_json = parse_expression(SourceCodeReader::new(replacement))
+                        .map(|expression| vec![expression_to_estree(&expression, replacement)])
+                        .map_err(|error| {
+                            RewriteError::new(format!(
+                                "invalid replacement '{replacement}': {error}"
+                            ))
+                        })?;
+                    let pattern_json = expression_to_estree(&expression, pattern);
+                    (PatternKind::Expression, pattern_json, replacement_json)
+                }
+                Err(_) => {
+                    let statement = single_statement(pattern)?;
+                    let replacement_json = parse_statements(SourceCodeReader::new(replacement))
+                        .map(|statements| {
+                            statements
+                                .iter()
+                                .map(|statement| statement_to_estree(statement, replacement))
+                                .collect()
+                        })
+                        .map_err(|error| {
+                            RewriteError::new(format!(
+                                "invalid replacement '{replacement}': {error}"
+                            ))
+                        })?;
+                    let pattern_json = statement_to_estree(&statement, pattern);
+                    (PatternKind::Statement, pattern_json, replacement_json)
+                }
+            };
+
+        let only_metavariable = match kind {
+            PatternKind::Expression => metavariable(&pattern_json).is_some(),
+            PatternKind::Statement => pattern_json
+                .get("expression")
+                .and_then(metavariable)
+                .is_some(),
+        };
+        if only_metavariable {
+            return Err(RewriteError::new(format!(
+                "pattern '{pattern}' matches everything"
+            )));
+        }
+
+        let mut bound = Vec::new();
+        collect_placeholders(&pattern_json, &mut bound);
+        let mut placeholders = Vec::new();
+        for json in &replacement_json {
+            collect_placeholders(json, &mut placeholders);
+        }
+        for name in placeholders.iter().filter(|name| is_metavariable(name)) {
+            if name == "$_" || !bound.contains(name) {
+                return Err(RewriteError::new(format!(
+                    "'{name}' in the replacement is not bound by the pattern"
+                )));
+            }
+        }
+
+        Ok(Rule {
+            kind,
+            pattern: pattern_json,
+            replacement: replacement.to_string(),
+            placeholders,
+        })
+    }
+
+    /// ソースコードの一致するノードを全て置き換える。
+    ///
+    /// 構文エラーのあるソースコードは書き換えない。書き換えた結果が読めない場合もエラーにする。
+    ///
+    /// 文の始まりを `(` などで始まる式に置き換えると、`;` のない前の文と続けて読まれてしまうため、
+    /// その場合は置き換えの前に `;` を置く。
+    pub fn rewrite(&self, source: &str, source_type: SourceType) -> Result<Rewrite, RewriteError> {
+        let ast = parse(source, source_type)
+            .map_err(|error| RewriteError::new(format!("syntax error: {error}")))?;
+        let program = to_estree(&ast, source);
+        let index = LineIndex::new(source);
+        let matches = find_matches(&self.pattern, &program, &index);
+        let mut previous_ends = HashMap::new();
+        collect_previous_ends(&program, &index, &mut previous_ends);
+        let rewriter = Rewriter {
+            rule: self,
+            ast: &ast,
+            matches: &matches,
+            index: &index,
+        };
+
+        let mut output = String::with_capacity(source.len());
+        let mut position = 0;
+        let mut count = 0;
+        for found in &matches {
+            // 外側の一致の中にある一致は、束縛したノードの書き換えで扱う
+            if found.span.start < position {
+                continue;
+            }
+            let mut text = rewriter.replacement_text(found, source)?;
+            if let Some(&previous_end) = previous_ends.get(&found.span.start) {
+                if continues_previous(&text) && !source[..previous_end].ends_with(';') {
+                    text.insert(0, ';');
+                }
+            }
+            output.push_str(&source[position..found.span.start]);
+            output.push_str(&text);
+            position = found.span.end;
+            count += 1;
+        }
+        output.push_str(&source[position..]);
+
+        if count > 0 {
+            parse(&output, source_type).map_err(|error| {
+                RewriteError::new(format!("rewritten source has a syntax error: {error}"))
+            })?;
+        }
+        Ok(Rewrite {
+            source: output,
+            count,
+        })
+    }
+}
+
+fn parse(source: &str, source_type: SourceType) -> Result<JsAst, String> {
+    let reader = SourceCodeReader::new(source);
+    let output = match source_type {
+        SourceType::Script => parse_script(reader),
+        SourceType::Module => parse_module(reader),
+    };
+    output.into_result().map_err(|error| error.to_string())
+}
+
+fn single_statement(pattern: &str) -> Result<Statement, RewriteError> {
+    let mut statements = parse_statements(SourceCodeReader::new(pattern))
+        .map_err(|error| RewriteError::new(format!("invalid pattern '{pattern}': {error}")))?;
+    match statements.len() {
+        1 => Ok(statements.pop().expect("a statement")),
+        _ => Err(RewriteError::new(format!(
+            "pattern '{pattern}' must be an expression or a single statement"
+        ))),
+    }
+}
+
+/// 文の並びの2つ目以降の文について、文の開始位置から前の文の終了位置への対応を集める。
+fn collect_previous_ends(value: &JsonValue, index: &LineIndex, ends: &mut HashMap<usize, usize>) {
+    match value {
+        JsonValue::Object(entries) => {
+            let kind = value.get("type").and_then(JsonValue::as_str);
+            let list_key = match kind {
+                Some("Program" | "BlockStatement" | "StaticBlock") => Some("body"),
+                Some("SwitchCase") => Some("consequent"),
+                _ => None,
+            };
+            if let Some(JsonValue::Array(statements)) = list_key.and_then(|key| value.get(key)) {
+                for pair in statements.windows(2) {
+                    let previous = span(&pair[0], index);
+                    let next = span(&pair[1], index);
+                    ends.insert(next.start, previous.end);
+                }
+            }
+            for (_, value) in entries {
+                collect_previous_ends(value, index, ends);
+            }
+        }
+        JsonValue::Array(values) => {
+            for value in values {
+                collect_previous_ends(value, index, ends);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 文の始まりに置くと、`;` のない前の文の続きとして読まれる文字列かを返す。
+fn continues_previous(text: &str) -> bool {
+    text.starts_with(['(', '[', '`', '+', '-', '/'])
+}
+
+/// `$` で始まる識別子 (`$` だけのものを除く) の名前を集める。
+fn collect_placeholders(value: &JsonValue, names: &mut Vec<String>) {
+    match value {
+        JsonValue::Object(entries) => {
+            let kind = value.get("type").and_then(JsonValue::as_str);
+            let name = value.get("name").and_then(JsonValue::as_str);
+            if let (Some("Identifier"), Some(name)) = (kind, name) {
+                if name.len() > 1
+                    && name.starts_with('$')
+                    && !names.iter().any(|other| other == name)
+                {
+                    names.push(name.to_string());
+                }
+            }
+            for (_, value) in entries {
+                collect_placeholders(value, names);
+            }
+        }
+        JsonValue::Array(values) => {
+            for value in values {
+                collect_placeholders(value, names);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 置き換えたノード
+enum Replacement {
+    Expression(Expression),
+    Statements(Vec<Statement>),
+}
+
+/// 一致から置き換えを作る。
+struct Rewriter<'a> {
+    rule: &'a Rule,
+    ast: &'a JsAst,
+    matches: &'a [Match<'a>],
+    index: &'a LineIndex,
+}
+
+impl Rewriter<'_> {
+    fn replacement(&self, found: &Match) -> Result<Replacement, RewriteError> {
+        let mut holes = Vec::new();
+        for name in &self.rule.placeholders {
+            let hole = match is_metavariable(name) {
+                true => {
+                    let (_, binding) = found
+                        .bindings
+                        .iter()
+                        .find(|(bound, _)| bound == name)
+                        .expect("metavariables in the replacement are bound");
+                    self.hole(name, binding, found.span)?
+                }
+                // メタ変数でない `$el` などはそのまま残す
+                false => Hole::Identifier(Identifier::new(Span::default(), name)),
+            };
+            holes.push((&name[1..], hole));
+        }
+
+        let replacement = &self.rule.replacement;
+        let result = match self.rule.kind {
+            PatternKind::Expression => {
+                quote_expression(replacement, holes).map(Replacement::Expression)
+            }
+            PatternKind::Statement => {
+                quote_statements(replacement, holes).map(Replacement::Statements)
+            }
+        };
+        result.map_err(|error| RewriteError::new(error.message))
+    }
+
+    /// 束縛したノードを、中の一致を書き換えてから穴を埋める値にする。
+    ///
+    /// `outer` は束縛した一致の範囲。
+    fn hole(&self, name: &str, binding: &Binding, outer: Span) -> Result<Hole, RewriteError> {
+        let node = binding.node;
+        let kind = node
+            .get("type")
+            .and_then(JsonValue::as_str)
+            .unwrap_or_default();
+        if kind == "Identifier" {
+            let name = node
+                .get("name")
+                .and_then(JsonValue::as_str)
+                .unwrap_or_default();
+            return Ok(Hole::Identifier(Identifier::new(Span::default(), name)));
+        }
+
+        let span = span(node, self.index);
+        let mut finder = Finder {
+            span,
+            statement: binding.statement,
+            expression: None,
+            statements: None,
+        };
+        self.ast.visit_with(&mut finder);
+
+        let mut replacer = Replacer {
+            rewriter: self,
+            outer,
+            error: None,
+        };
+        let hole = match (finder.expression, finder.statements) {
+            (Some(expression), _) => Hole::Expression(expression.clone().fold_with(&mut replacer)),
+            (_, Some(statement)) => {
+                Hole::Statements(statement.clone().flat_fold_with(&mut replacer))
+            }
+            _ => {
+                return Err(RewriteError::new(format!(
+                    "'{name}' matched {kind}, which cannot be used in the replacement"
+                )))
+            }
+        };
+        match replacer.error {
+            Some(error) => Err(error),
+            None => Ok(hole),
+        }
+    }
+
+    /// 一致したノードの範囲に置く文字列を作る。
+    fn replacement_text(&self, found: &Match, source: &str) -> Result<String, RewriteError> {
+        match self.replacement(found)? {
+            Replacement::Expression(expression) => Ok(expression_text(expression, &found.slot)),
+            Replacement::Statements(statements) => {
+                let line_start = source[..found.span.start]
+                    .rfind('\n')
+                    .map_or(0, |index| index + 1);
+                let indent = &source[line_start..found.span.start];
+                let indent = match indent.trim().is_empty() {
+                    true => indent,
+                    false => "",
+                };
+                Ok(statements_text(statements, &found.slot, indent))
+            }
+        }
+    }
+}
+
+/// 範囲が一致するノードを探す。
+struct Finder<'ast> {
+    span: Span,
+    statement: bool,
+    expression: Option<&'ast Expression>,
+    statements: Option<&'ast Statement>,
+}
+
+impl<'ast> Visit<'ast> for Finder<'ast> {
+    fn visit_expression(&mut self, node: &'ast Expression) {
+        let span = node.span();
+        if self.expression.is_some() || span.start > self.span.start || span.end < self.span.end {
+            return;
+        }
+        if !self.statement && span == self.span {
+            self.expression = Some(node);
+            return;
+        }
+        node.visit_children_with(self);
+    }
+
+    fn visit_statement(&mut self, node: &'ast Statement) {
+        let span = node.span();
+        if self.statements.is_some() || span.start > self.span.start || span.end < self.span.end {
+            return;
+        }
+        if self.statement && span == self.span {
+            self.statements = Some(node);
+            return;
+        }
+        node.visit_children_with(self);
+    }
+}
+
+/// 束縛したノードの中の一致を置き換える。
+struct Replacer<'a, 'b> {
+    rewriter: &'b Rewriter<'a>,
+
+    /// 外側の一致の範囲 (同じ範囲の一致は置き換えない)
+    outer: Span,
+    error: Option<RewriteError>,
+}
+
+impl Replacer<'_, '_> {
+    fn find(&self, span: Span) -> Option<&Match<'_>> {
+        if span == self.outer {
+            return None;
+        }
+        self.rewriter
+            .matches
+            .iter()
+            .find(|found| found.span == span)
+    }
+
+    fn replace(&mut self, span: Span) -> Option<Replacement> {
+        let found = self.find(span)?;
+        match self.rewriter.replacement(found) {
+            Ok(replacement) => Some(replacement),
+            Err(error) => {
+                self.error.get_or_insert(error);
+                None
+            }
+        }
+    }
+}
+
+impl Fold for Replacer<'_, '_> {
+    fn fold_expression(&mut self, node: Expression) -> Expression {
+        if self.rewriter.rule.kind == PatternKind::Expression {
+            if let Some(Replacement::Expression(expression)) = self.replace(node.span()) {
+                return expression;
+            }
+        }
+        node.fold_children_with(self)
+    }
+
+    fn fold_statement(&mut self, node: Statement) -> Statement {
+        match self.flat_fold_statement(node) {
+            statements if statements.len() == 1 => {
+                statements.into_iter().next().expect("a statement")
+            }
+            statements => Statement::Block(BlockStatement {
+                span: Span::default(),
+                body: statements,
+            }),
+        }
+    }
+
+    fn flat_fold_statement(&mut self, node: Statement) -> Vec<Statement> {
+        if self.rewriter.rule.kind == PatternKind::Statement {
+            if let Some(Replacement::Statements(statements)) = self.replace(node.span()) {
+                return statements;
+            }
+        }
+        vec![node.fold_children_with(self)]
+    }
+}
+
+/// 置き換えた式を、一致した位置に置ける文字列にする。
+fn expression_text(expression: Expression, slot: &Slot) -> String {
+    // 文の始まりの `{` や `function` は、文として出力すれば括弧で囲まれる
+    if slot.parent == "ExpressionStatement" {
+        let statement = Statement::Expression(ExpressionStatement {
+            span: Span::default(),
+            expression,
+            directive: None,
+        });
+        let text = statement.to_source_string();
+        return text.strip_suffix(';').unwrap_or(&text).to_string();
+    }
+
+    let text = expression.to_source_string();
+    match needs_parentheses(&expression, slot) {
+        true => format!("({text})"),
+        false => text,
+    }
+}
+
+/// 置き換えた式を括弧で囲む必要があるかを返す。
+///
+/// 周りの演算子の優先順位は見ずに、位置ごとに囲まなくてよい式を決める (余分に囲むことがある)。
+fn needs_parentheses(expression: &Expression, slot: &Slot) -> bool {
+    use Expression as E;
+
+    let is_sequence = matches!(expression, E::Sequence(_));
+    match (slot.parent.as_str(), slot.key.as_str()) {
+        // 任意の式を置ける位置
+        ("ReturnStatement" | "ThrowStatement", "argument")
+        | ("IfStatement" | "WhileStatement" | "DoWhileStatement" | "SwitchCase", "test")
+        | ("SwitchStatement", "discriminant")
+        | ("ForStatement", "test" | "update")
+        | ("MemberExpression", "property")
+        | ("TemplateLiteral", "expressions") => false,
+
+        // 代入式を置ける位置
+        ("CallExpression" | "NewExpression", "arguments")
+        | ("ArrayExpression", "elements")
+        | ("VariableDeclarator", "init")
+        | ("Property" | "PropertyDefinition", "value")
+        | ("AssignmentExpression" | "AssignmentPattern", "right")
+        | ("ConditionalExpression", "consequent" | "alternate")
+        | ("SpreadElement" | "YieldExpression", "argument")
+        | ("SequenceExpression", "expressions") => is_sequence,
+        ("ArrowFunctionExpression", "body") => is_sequence || matches!(expression, E::Object(_)),
+        ("ExportDefaultDeclaration", "declaration") => {
+            is_sequence || matches!(expression, E::Function(_) | E::Class(_))
+        }
+
+        // 呼び出しや `new` の対象は、`?.` や呼び出しを含むと意味が変わる
+        ("NewExpression", "callee") => !matches!(expression, E::Identifier(_)),
+
+        _ => !matches!(
+            expression,
+            E::Identifier(_)
+                | E::This(_)
+                | E::Array(_)
+                | E::TemplateLiteral(_)
+                | E::TaggedTemplate(_)
+                | E::Member(_)
+                | E::Call(_)
+                | E::MetaProperty(_)
+                | E::Import(_)
+        ),
+    }
+}
+
+/// 置き換えた文を、一致した位置に置ける文字列にする。
+///
+/// 2行目以降は、一致した文の行の字下げに合わせる。
+fn statements_text(statements: Vec<Statement>, slot: &Slot, indent: &str) -> String {
+    let statements = match (slot.in_list, statements.len()) {
+        (true, _) | (false, 1) => statements,
+        (false, _) => vec![Statement::Block(BlockStatement {
+            span: Span::default(),
+            body: statements,
+        })],
+    };
+    statements
+        .iter()
+        .map(|statement| statement.to_source_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .replace('\n', &format!("\n{indent}"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Rewrite, Rule};
+    use crate::ast::structs::SourceType;
+
+    fn rewrite(pattern: &str, replacement: &str, source: &str) -> String {
+        Rule::new(pattern, replacement)
+            .and_then(|rule| rule.rewrite(source, SourceType::Script))
+            .unwrap_or_else(|error| panic!("{pattern} -> {replacement}: {error}"))
+            .source
+    }
+
+    fn error(pattern: &str, replacement: &str, source: &str) -> String {
+        Rule::new(pattern, replacement)
+            .and_then(|rule| rule.rewrite(source, SourceType::Script))
+            .expect_err("rewrite should fail")
+            .message
+    }
+
+    #[test]
+    fn expressions() {
+        let source = "// keep\nfoo.bar(x);\nlet y = foo . bar ( 1 + 2 ) ;\nfoo.baz(x);\n";
+        assert_eq!(
+            rewrite("$X.bar($Y)", "baz($Y)", source),
+            "// keep\nbaz(x);\nlet y = baz(1 + 2) ;\nfoo.baz(x);\n"
+        );
+
+        // 引用符や括弧の違いは一致に影響しない
+        assert_eq!(
+            rewrite(
+                "require('a')",
+                "load('b')",
+                "require(\"a\"); (require)(('a'));"
+            ),
+            "load('b'); load('b');"
+        );
+        assert_eq!(
+            Rule::new("$X.bar($Y)", "baz($Y)")
+                .unwrap()
+                .rewrite("foo.bar(a, b); bar(a);", SourceType::Script),
+            Ok(Rewrite {
+                source: "foo.bar(a, b); bar(a);".to_string(),
+                count: 0
+            })
+        );
+    }
+
+    #[test]
+    fn metavariables() {
+        // 同じメタ変数は同じ構造と一致する
+        let source = "a = a + 1; b = c + 1; d.e = d . e + 1;";
+        assert_eq!(
+            rewrite("$X = $X + 1", "$X++", source),
+            "a++; b = c + 1; d.e++;"
+        );
+
+        assert_eq!(rewrite("f($_, $_)", "g()", "f(1, 2); f(1);"), "g(); f(1);");
+        assert_eq!(
+            rewrite("f(...$ARGS)", "g(...$ARGS)", "f(...a); f(a);"),
+            "g(...a); f(a);"
+        );
+
+        // メタ変数でない `$` の識別子はそのまま比べ、そのまま残す
+        assert_eq!(
+            rewrite("$el.hide()", "$el.show()", "$el.hide(); $other.hide();"),
+            "$el.show(); $other.hide();"
+        );
+        assert_eq!(rewrite("jQuery($S)", "$($S)", "jQuery('a');"), "$('a');");
+    }
+
+    #[test]
+    fn parentheses() {
+        let rule = ("$A.plus($B)", "$A + $B");
+        assert_eq!(rewrite(rule.0, rule.1, "x * a.plus(b);"), "x * (a + b);");
+        assert_eq!(
+            rewrite(rule.0, rule.1, "f(a.plus(b), c.plus(d) * 2);"),
+            "f(a + b, (c + d) * 2);"
+        );
+        assert_eq!(
+            rewrite(rule.0, rule.1, "a.plus(b).toString();"),
+            "(a + b).toString();"
+        );
+        assert_eq!(
+            rewrite(
+                "make($X)",
+                "({ value: $X })",
+                "make(1); f(make(2)); g = () => make(3);"
+            ),
+            "({ value: 1 }); f({ value: 2 }); g = () => ({ value: 3 });"
+        );
+        assert_eq!(
+            rewrite(
+                "pair($A, $B)",
+                "($A, $B)",
+                "f(pair(1, 2)); return_ = pair(1, 2);"
+            ),
+            "f((1, 2)); return_ = (1, 2);"
+        );
+    }
+
+    #[test]
+    fn nested() {
+        assert_eq!(
+            rewrite("$X.bar($Y)", "baz($Y)", "foo.bar(foo.bar(x) + 1);"),
+            "baz(baz(x) + 1);"
+        );
+        assert_eq!(
+            rewrite("$A.plus($B)", "$A + $B", "a.plus(b).plus(c.plus(d));"),
+            "a + b + (c + d);"
+        );
+    }
+
+    #[test]
+    fn statements() {
+        let source = "function f() {\n  if (x) {\n    a();\n  }\n  if (y) b();\n}\n";
+        assert_eq!(
+            rewrite("if ($T) $S;", "$S; log($T);", source),
+            "function f() {\n  {\n    a();\n  }\n  log(x);\n  b();\n  log(y);\n}\n"
+        );
+
+        // 1つの文しか置けない位置では、ブロックで囲む
+        assert_eq!(
+            rewrite("debug($X);", "log($X); flush();", "if (a) debug(1);"),
+            "if (a) {\n  log(1);\n  flush();\n}"
+        );
+        assert_eq!(
+            rewrite("debug($X);", "", "f();\ndebug(1);\ng();"),
+            "f();\n\ng();"
+        );
+    }
+
+    #[test]
+    fn semicolons() {
+        // `;` のない前の文と続けて読まれないように `;` を置く
+        assert_eq!(
+            rewrite("$X.bar($Y)", "$Y + $Y * 2", "x.bar(y)\nfoo.bar(z)(w);"),
+            "y + y * 2\n;(z + z * 2)(w);"
+        );
+        assert_eq!(rewrite("foo($Y)", "[$Y]", "a = 1\nfoo(b)"), "a = 1\n;[b]");
+
+        // 前の文が `;` で終わる場合や、文の並びの先頭では置かない
+        assert_eq!(rewrite("foo($Y)", "[$Y]", "a = 1;\nfoo(b)"), "a = 1;\n[b]");
+        assert_eq!(
+            rewrite("foo($Y)", "[$Y]", "{\n  foo(b)\n}\nif (a) foo(c)"),
+            "{\n  [b]\n}\nif (a) [c]"
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert!(error("f(", "g()", "").starts_with("invalid pattern 'f('"));
+        assert!(error("f()", "g(", "").starts_with("invalid replacement 'g('"));
+        assert_eq!(
+            error("a; b;", "c;", ""),
+            "pattern 'a; b;' must be an expression or a single statement"
+        );
+        assert_eq!(error("$X", "f($X)", ""), "pattern '$X' matches everything");
+        assert_eq!(
+            error("f($X)", "g($Y)", ""),
+            "'$Y' in the replacement is not bound by the pattern"
+        );
+        assert!(error("f()", "g()", "let = ;").starts_with("syntax error: "));
+        assert_eq!(
+            error("(function ($X) {})", "g($X)", "(function ({ a }) {});"),
+            "'$X' matched ObjectPattern, which cannot be used in the replacement"
+        );
+    }
+}
@@ -0,0 +1,237 @@
+//! パターンと構文木を ESTree の JSON で比べるモジュールです。
+//!
+//! 位置と `raw` は比べないため、空白や括弧、引用符の違いは一致に影響しない。
+
+use crate::{
+    ast::{line_index::LineIndex, structs::span::Span},
+    json::JsonValue,
+};
+
+/// 比べないプロパティ (位置と表記)
+const IGNORED_KEYS: [&str; 5] = ["start", "end", "loc", "range", "raw"];
+
+/// どのノードとも一致し、束縛しないメタ変数
+const WILDCARD: &str = "$_";
+
+/// メタ変数であるかを返す。
+///
+/// `$` と大文字、数字、`_` からなる名前 (`$X`、`$ARG_1` など) をメタ変数にする。
+/// `$el` のような名前は普通の識別子として比べる。
+pub(super) fn is_metavariable(name: &str) -> bool {
+    let Some(rest) = name.strip_prefix('$') else {
+        return false;
+    };
+    rest.starts_with(|char: char| char.is_ascii_uppercase() || char == '_')
+        && rest
+            .chars()
+            .all(|char| char.is_ascii_uppercase() || char.is_ascii_digit() || char == '_')
+}
+
+/// ノードがメタ変数であれば、その名前を返す。
+pub(super) fn metavariable(node: &JsonValue) -> Option<&str> {
+    if node.get("type")?.as_str()? != "Identifier" {
+        return None;
+    }
+    let name = node.get("name")?.as_str()?;
+    is_metavariable(name).then_some(name)
+}
+
+/// ノードの親と、親のどのプロパティにあるか
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub(super) struct Slot {
+    /// 親の `type` (根の場合は空)
+    pub(super) parent: String,
+    pub(super) key: String,
+
+    /// 配列の要素であるか
+    pub(super) in_list: bool,
+}
+
+/// メタ変数に束縛したノード
+#[derive(Debug, Clone)]
+pub(super) struct Binding<'a> {
+    pub(super) node: &'a JsonValue,
+
+    /// 文の位置のメタ変数 (`$A;`) が文に一致したか
+    pub(super) statement: bool,
+}
+
+/// パターンと一致したノード
+#[derive(Debug, Clone)]
+pub(super) struct Match<'a> {
+    pub(super) span: Span,
+    pub(super) slot: Slot,
+
+    /// メタ変数の名前 (`$` を含む) と束縛したノード
+    pub(super) bindings: Vec<(&'a str, Binding<'a>)>,
+}
+
+/// パターンと一致するノードを前順に全て探す (一致したノードの中も探す)。
+pub(super) fn find_matches<'a>(
+    pattern: &'a JsonValue,
+    program: &'a JsonValue,
+    index: &LineIndex,
+) -> Vec<Match<'a>> {
+    let mut matches = Vec::new();
+    let root = Slot {
+        parent: String::new(),
+        key: String::new(),
+        in_list: false,
+    };
+    walk(pattern, program, root, index, &mut matches);
+    matches
+}
+
+fn walk<'a>(
+    pattern: &'a JsonValue,
+    node: &'a JsonValue,
+    slot: Slot,
+    index: &LineIndex,
+    matches: &mut Vec<Match<'a>>,
+) {
+    let mut bindings = Vec::new();
+    if match_node(pattern, node, &mut bindings) {
+        matches.push(Match {
+            span: span(node, index),
+            slot,
+            bindings,
+        });
+    }
+
+    let JsonValue::Object(entries) = node else {
+        return;
+    };
+    let kind = node
+        .get("type")
+        .and_then(JsonValue::as_str)
+        .unwrap_or_default();
+    for (key, value) in entries {
+        let slot = |in_list| Slot {
+            parent: kind.to_string(),
+            key: key.clone(),
+            in_list,
+        };
+        match value {
+            JsonValue::Array(values) => {
+                for value in values.iter().filter(|value| is_node(value)) {
+                    walk(pattern, value, slot(true), index, matches);
+                }
+            }
+            value if is_node(value) => walk(pattern, value, slot(false), index, matches),
+            _ => {}
+        }
+    }
+}
+
+/// ノードのソースコード上の範囲 (バイト位置)
+pub(super) fn span(node: &JsonValue, index: &LineIndex) -> Span {
+    let position = |key| {
+        let offset = node.get(key).and_then(JsonValue::as_f64).unwrap_or(0.0);
+        index.byte_offset(offset as usize)
+    };
+    Span::new(position("start"), position("end"))
+}
+
+fn is_node(value: &JsonValue) -> bool {
+    matches!(value, JsonValue::Object(_)) && value.get("type").is_some()
+}
+
+fn is_statement(node: &JsonValue) -> bool {
+    node.get("type")
+        .and_then(JsonValue::as_str)
+        .is_some_and(|kind| kind.ends_with("Statement") || kind.ends_with("Declaration"))
+}
+
+fn match_node<'a>(
+    pattern: &'a JsonValue,
+    node: &'a JsonValue,
+    bindings: &mut Vec<(&'a str, Binding<'a>)>,
+) -> bool {
+    // `...a` と `...rest` は `...$X` で一致させる
+    if let Some(name) = metavariable(pattern) {
+        let kind = node.get("type").and_then(JsonValue::as_str);
+        return is_node(node)
+            && !matches!(kind, Some("SpreadElement" | "RestElement"))
+            && bind(name, node, false, bindings);
+    }
+
+    // 文の位置の `$A;` は式文以外の文とも一致する
+    if let Some(name) = statement_metavariable(pattern) {
+        let kind = node.get("type").and_then(JsonValue::as_str);
+        if is_statement(node) && kind != Some("ExpressionStatement") {
+            return bind(name, node, true, bindings);
+        }
+    }
+
+    match (pattern, node) {
+        (JsonValue::Object(pattern_entries), JsonValue::Object(_)) => {
+            pattern_entries.iter().all(|(key, value)| {
+                IGNORED_KEYS.contains(&key.as_str())
+                    || node
+                        .get(key)
+                        .is_some_and(|other| match_node(value, other, bindings))
+            })
+        }
+        (JsonValue::Array(patterns), JsonValue::Array(nodes)) => {
+            patterns.len() == nodes.len()
+                && patterns
+                    .iter()
+                    .zip(nodes)
+                    .all(|(pattern, node)| match_node(pattern, node, bindings))
+        }
+        (pattern, node) => pattern == node,
+    }
+}
+
+/// `$A;` の形の文であれば、メタ変数の名前を返す。
+fn statement_metavariable(pattern: &JsonValue) -> Option<&str> {
+    if pattern.get("type")?.as_str()? != "ExpressionStatement" {
+        return None;
+    }
+    if pattern.get("directive").is_some() {
+        return None;
+    }
+    metavariable(pattern.get("expression")?)
+}
+
+/// メタ変数にノードを束縛する。束縛済みの場合は同じ構造であるかを比べる。
+fn bind<'a>(
+    name: &'a str,
+    node: &'a JsonValue,
+    statement: bool,
+    bindings: &mut Vec<(&'a str, Binding<'a>)>,
+) -> bool {
+    if name == WILDCARD {
+        return true;
+    }
+    match bindings.iter().find(|(bound, _)| *bound == name) {
+        Some((_, binding)) => same_structure(binding.node, node),
+        None => {
+            bindings.push((name, Binding { node, statement }));
+            true
+        }
+    }
+}
+
+/// 位置と表記を除いて同じ構造であるかを返す。
+pub(super) fn same_structure(a: &JsonValue, b: &JsonValue) -> bool {
+    match (a, b) {
+        (JsonValue::Object(a_entries), JsonValue::Object(b_entries)) => {
+            let keys = |entries: &[(String, JsonValue)]| {
+                entries
+                    .iter()
+                    .filter(|(key, _)| !IGNORED_KEYS.contains(&key.as_str()))
+                    .count()
+            };
+            keys(a_entries) == keys(b_entries)
+                && a_entries.iter().all(|(key, value)| {
+                    IGNORED_KEYS.contains(&key.as_str())
+                        || b.get(key).is_some_and(|other| same_structure(value, other))
+                })
+        }
+        (JsonValue::Array(a), JsonValue::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| same_structure(a, b))
+        }
+        (a, b) => a == b,
+    }
+}
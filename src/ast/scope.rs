@@ -0,0 +1,531 @@
+//! 構文木のスコープを解析し、識別子の参照を宣言に結び付けるモジュールです。
+//!
+//! ECMAScript のスコープ (グローバル、モジュール、関数、ブロック、catch、クラス、`with`) を作り、
+//! 宣言を束縛として登録してから、参照をスコープの外側へ向かって解決する。
+//!
+//! - `var` と関数宣言は最も近い関数 (またはグローバル、モジュール、static ブロック) に巻き上げる。
+//! - `let` `const` `class` は宣言より前の参照を TDZ (初期化前の参照) として記録する。
+//! - strict モードでないブロックの関数宣言は、Annex B に従って関数スコープにも `var` の束縛を作る。
+//! - `with` と direct eval を通る参照は、実行時に別の束縛を指しうるため動的な参照として記録する。
+
+mod builder;
+
+use std::collections::{HashMap, HashSet};
+
+use self::builder::Builder;
+use super::structs::{span::Span, JsAst};
+
+/// スコープの番号 ([`SymbolTable::scopes`] の添字)
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub struct ScopeId(pub usize);
+
+/// 束縛の番号 ([`SymbolTable::bindings`] の添字)
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub struct BindingId(pub usize);
+
+/// 参照の番号 ([`SymbolTable::references`] の添字)
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub struct ReferenceId(pub usize);
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ScopeKind {
+    /// スクリプトのトップレベル (モジュールの外側にもある)
+    Global,
+
+    /// モジュールのトップレベル
+    Module,
+
+    /// 関数の引数と本体
+    Function {
+        /// アロー関数であるか (`arguments` を持たない)
+        arrow: bool,
+    },
+
+    /// ブロック、`let` `const` で宣言する for 文、switch 文のケース
+    Block,
+
+    /// catch 節の引数 (本体は子の [`ScopeKind::Block`])
+    Catch,
+
+    /// クラスの名前 (クラス式の中からだけ見える) と本体
+    Class,
+
+    /// with 文の本体
+    With,
+
+    /// クラスの static ブロック
+    StaticBlock,
+}
+
+/// スコープ
+#[derive(Debug, Clone)]
+pub struct Scope {
+    pub kind: ScopeKind,
+    pub span: Span,
+    pub parent: Option<ScopeId>,
+    pub children: Vec<ScopeId>,
+    pub strict: bool,
+
+    /// このスコープに宣言された束縛 (宣言順)
+    pub bindings: Vec<BindingId>,
+
+    /// このスコープで direct eval (`eval(...)`) を呼んでいるか (内側の関数は含まない)
+    pub direct_eval: bool,
+}
+
+impl Scope {
+    /// `var` の宣言を受け取るスコープであるかを返す。
+    pub fn is_var_scope(&self) -> bool {
+        matches!(
+            self.kind,
+            ScopeKind::Global
+                | ScopeKind::Module
+                | ScopeKind::Function { .. }
+                | ScopeKind::StaticBlock
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BindingKind {
+    Var,
+    Let,
+    Const,
+
+    /// クラス宣言
+    Class,
+
+    /// 関数宣言 (Annex B で関数スコープに作る束縛を含む)
+    Function,
+    Parameter,
+    CatchParameter,
+    Import,
+
+    /// 名前付き関数式の名前 (関数の中からだけ見える)
+    FunctionName,
+
+    /// クラスの名前 (クラスの中からだけ見える)
+    ClassName,
+
+    /// 関数の暗黙の `arguments`
+    Arguments,
+}
+
+impl BindingKind {
+    /// 宣言より前に参照できない (TDZ がある) 束縛であるかを返す。
+    pub fn has_tdz(&self) -> bool {
+        matches!(
+            self,
+            BindingKind::Let | BindingKind::Const | BindingKind::Class | BindingKind::ClassName
+        )
+    }
+}
+
+/// スコープに宣言された名前
+#[derive(Debug, Clone)]
+pub struct Binding {
+    pub name: String,
+    pub kind: BindingKind,
+    pub scope: ScopeId,
+
+    /// 宣言した識別子の範囲 (`var` と関数は同じ名前を何度も宣言できる)
+    pub declarations: Vec<Span>,
+
+    /// この束縛に解決された参照 (ソースコードの順)
+    pub references: Vec<ReferenceId>,
+}
+
+/// 参照が値を読むか書くか
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Access {
+    Read,
+    Write,
+
+    /// 複合代入と `++` `--`
+    ReadWrite,
+}
+
+impl Access {
+    pub fn is_read(&self) -> bool {
+        matches!(self, Access::Read | Access::ReadWrite)
+    }
+
+    pub fn is_write(&self) -> bool {
+        matches!(self, Access::Write | Access::ReadWrite)
+    }
+}
+
+/// 識別子の参照
+#[derive(Debug, Clone)]
+pub struct Reference {
+    pub name: String,
+    pub span: Span,
+
+    /// 参照している位置のスコープ
+    pub scope: ScopeId,
+    pub access: Access,
+
+    /// 解決した束縛 (グローバル変数などの見つからなかった参照は `None`)
+    pub binding: Option<BindingId>,
+
+    /// 初期化より前に参照しているか (実行すると ReferenceError になる)
+    pub in_tdz: bool,
+
+    /// `with` や direct eval のために、実行時には別の束縛を指しうるか
+    pub dynamic: bool,
+}
+
+/// スコープ解析の結果
+#[derive(Debug, Clone)]
+pub struct SymbolTable {
+    /// 前順に並べたスコープ (先頭はグローバルスコープ)
+    pub scopes: Vec<Scope>,
+    pub bindings: Vec<Binding>,
+
+    /// ソースコードの順に並べた参照
+    pub references: Vec<Reference>,
+
+    /// スコープと名前から束縛を引く
+    names: HashMap<(ScopeId, String), BindingId>,
+}
+
+impl SymbolTable {
+    /// グローバルスコープ
+    pub fn root(&self) -> ScopeId {
+        ScopeId(0)
+    }
+
+    pub fn scope(&self, id: ScopeId) -> &Scope {
+        &self.scopes[id.0]
+    }
+
+    pub fn binding(&self, id: BindingId) -> &Binding {
+        &self.bindings[id.0]
+    }
+
+    pub fn reference(&self, id: ReferenceId) -> &Reference {
+        &self.references[id.0]
+    }
+
+    /// スコープに直接宣言された名前の束縛を返す。
+    pub fn own_binding(&self, scope: ScopeId, name: &str) -> Option<BindingId> {
+        self.names.get(&(scope, name.to_string())).copied()
+    }
+
+    /// スコープから外側へ向かって名前を探す。
+    pub fn lookup(&self, scope: ScopeId, name: &str) -> Option<BindingId> {
+        self.ancestors(scope)
+            .find_map(|scope| self.own_binding(scope, name))
+    }
+
+    /// 自身からグローバルスコープまでのスコープ
+    pub fn ancestors(&self, scope: ScopeId) -> impl Iterator<Item = ScopeId> + '_ {
+        std::iter::successors(Some(scope), |&scope| self.scope(scope).parent)
+    }
+
+    /// 宣言または参照の識別子の範囲から束縛を探す。
+    pub fn binding_at(&self, span: Span) -> Option<BindingId> {
+        let declared = self
+            .bindings
+            .iter()
+            .position(|binding| binding.declarations.contains(&span))
+            .map(BindingId);
+        declared.or_else(|| {
+            self.references
+                .iter()
+                .find(|reference| reference.span == span)
+                .and_then(|reference| reference.binding)
+        })
+    }
+
+    /// 束縛が見つからなかった参照
+    pub fn unresolved(&self) -> impl Iterator<Item = &Reference> + '_ {
+        self.references
+            .iter()
+            .filter(|reference| reference.binding.is_none())
+    }
+
+    /// 解決できなかった名前 (グローバル変数) を最初に現れた順に返す。
+    pub fn globals(&self) -> Vec<&str> {
+        let mut seen = HashSet::new();
+        self.unresolved()
+            .map(|reference| reference.name.as_str())
+            .filter(|name| seen.insert(*name))
+            .collect()
+    }
+}
+
+/// 構文木のスコープを解析する。
+pub fn analyze(ast: &JsAst) -> SymbolTable {
+    Builder::build(ast)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{analyze, Access, BindingKind, ScopeKind, SymbolTable};
+    use crate::ast::{
+        reader_struct::SourceCodeReader,
+        script::{parse_module, parse_script},
+        structs::span::Span,
+    };
+
+    fn script(source: &str) -> SymbolTable {
+        let ast = parse_script(SourceCodeReader::new(source))
+            .into_result()
+            .unwrap_or_else(|error| panic!("{source}: {error:?}"));
+        analyze(&ast)
+    }
+
+    fn module(source: &str) -> SymbolTable {
+        let ast = parse_module(SourceCodeReader::new(source))
+            .into_result()
+            .unwrap_or_else(|error| panic!("{source}: {error:?}"));
+        analyze(&ast)
+    }
+
+    /// 参照を `名前@位置 -> 束縛の種類@宣言の位置` の形で並べる。
+    fn resolved(table: &SymbolTable) -> Vec<String> {
+        table
+            .references
+            .iter()
+            .map(|reference| {
+                let mut text = format!("{}@{}", reference.name, reference.span.start);
+                match reference.binding {
+                    Some(binding) => {
+                        let binding = table.binding(binding);
+                        text += &format!(" -> {:?}", binding.kind);
+                        if let Some(span) = binding.declarations.first() {
+                            text += &format!("@{}", span.start);
+                        }
+                    }
+                    None => text += " -> global",
+                }
+                if reference.in_tdz {
+                    text += " tdz";
+                }
+                if reference.dynamic {
+                    text += " dynamic";
+                }
+                text
+            })
+            .collect()
+    }
+
+    #[test]
+    fn hoisting() {
+        let table = script("f(x);\nfunction f() { if (a) { var x = 1; } }\nvar x;");
+        assert_eq!(
+            resolved(&table),
+            ["f@0 -> Function@15", "x@2 -> Var@49", "a@25 -> global"]
+        );
+        let x = table.own_binding(table.root(), "x").unwrap();
+        assert_eq!(table.binding(x).declarations, [Span::new(49, 50)]);
+        assert_eq!(table.globals(), ["a"]);
+
+        // 関数の中の var は関数スコープに入る
+        let table = script("function f() { { var y; } y; }\ny;");
+        assert_eq!(resolved(&table), ["y@26 -> Var@21", "y@31 -> global"]);
+    }
+
+    #[test]
+    fn lexical_scopes() {
+        let table = script("let a = 1;\n{ let a = 2; a; }\na;");
+        assert_eq!(resolved(&table), ["a@24 -> Let@17", "a@29 -> Let@4"]);
+
+        let table = script("for (let i = 0; i < 3; i++) {}\ni;");
+        assert_eq!(
+            resolved(&table),
+            ["i@16 -> Let@9", "i@23 -> Let@9", "i@31 -> global"]
+        );
+        let for_scope = table.scope(table.scopes[0].children[0]);
+        assert_eq!(for_scope.kind, ScopeKind::Block);
+
+        let table = script("switch (x) { case 1: let x; x; }");
+        assert_eq!(resolved(&table), ["x@8 -> global", "x@28 -> Let@25"]);
+    }
+
+    #[test]
+    fn temporal_dead_zone() {
+        let table = script("x; let x = x; x;");
+        assert_eq!(
+            resolved(&table),
+            ["x@0 -> Let@7 tdz", "x@11 -> Let@7 tdz", "x@14 -> Let@7"]
+        );
+
+        // 後で呼ばれる関数の中は TDZ にならない
+        let table = script("function f() { return c; }\nconst c = 1;");
+        assert_eq!(resolved(&table), ["c@22 -> Const@33"]);
+
+        let table = script("new C();\nclass C extends C { x = C; static { C; } }");
+        assert_eq!(
+            resolved(&table),
+            [
+                "C@4 -> Class@15 tdz",
+                "C@25 -> ClassName@15 tdz",
+                "C@33 -> ClassName@15",
+                "C@45 -> ClassName@15",
+            ]
+        );
+    }
+
+    #[test]
+    fn functions() {
+        let table = script("function f(a, b = a) { return arguments[0] + b; }");
+        assert_eq!(
+            resolved(&table),
+            [
+                "a@18 -> Parameter@11",
+                "arguments@30 -> Arguments",
+                "b@45 -> Parameter@14"
+            ]
+        );
+
+        // アロー関数は外側の arguments を使う
+        let table = script("function f() { return () => arguments; }\n() => arguments;");
+        assert_eq!(
+            resolved(&table),
+            ["arguments@28 -> Arguments", "arguments@47 -> global"]
+        );
+
+        // 名前付き関数式の名前は関数の中だけで見える
+        let table = script("(function g() { g; })\ng;\n(function h(h) { h; })");
+        assert_eq!(
+            resolved(&table),
+            [
+                "g@16 -> FunctionName@10",
+                "g@22 -> global",
+                "h@42 -> Parameter@37"
+            ]
+        );
+    }
+
+    #[test]
+    fn catch_and_class() {
+        let table = script("try {} catch (e) { let f; e; f; }\ne;");
+        assert_eq!(
+            resolved(&table),
+            [
+                "e@26 -> CatchParameter@14",
+                "f@29 -> Let@23",
+                "e@34 -> global"
+            ]
+        );
+        let catch = table.scope(table.scopes[0].children[1]);
+        assert_eq!(catch.kind, ScopeKind::Catch);
+        assert_eq!(table.scope(catch.children[0]).kind, ScopeKind::Block);
+
+        let table = script("const K = class Inner { m() { return Inner; } };\nInner;");
+        assert_eq!(
+            resolved(&table),
+            ["Inner@37 -> ClassName@16", "Inner@49 -> global"]
+        );
+    }
+
+    #[test]
+    fn annex_b_block_functions() {
+        // strict モードでなければ、ブロックの外からも関数を呼べる
+        let table = script("{ function f() {} }\nf();");
+        assert_eq!(resolved(&table), ["f@20 -> Function@11"]);
+        let f = table.own_binding(table.root(), "f").unwrap();
+        assert_eq!(table.binding(f).kind, BindingKind::Function);
+
+        let table = script("'use strict';\n{ function f() {} }\nf();");
+        assert_eq!(resolved(&table), ["f@34 -> global"]);
+
+        // var に置き換えると宣言が衝突する場合は作らない
+        let table = script("let f;\n{ function f() {} }\nf;");
+        assert_eq!(resolved(&table), ["f@27 -> Let@4"]);
+        let table = script("function g(f) { { function f() {} } f; }");
+        assert_eq!(resolved(&table), ["f@36 -> Parameter@11"]);
+
+        // ブロックの中ではブロックの束縛を使う
+        let table = script("{ f; function f() {} }");
+        let reference = &table.references[0];
+        let binding = table.binding(reference.binding.unwrap());
+        assert_eq!(table.scope(binding.scope).kind, ScopeKind::Block);
+        assert!(!reference.in_tdz);
+    }
+
+    #[test]
+    fn with_and_eval() {
+        let table = script("var a;\nwith (o) { a; b; }\na;");
+        assert_eq!(
+            resolved(&table),
+            [
+                "o@13 -> global",
+                "a@18 -> Var@4 dynamic",
+                "b@21 -> global dynamic",
+                "a@26 -> Var@4",
+            ]
+        );
+        assert_eq!(table.scopes[1].kind, ScopeKind::With);
+
+        let table = script("var x;\nfunction f() { eval(s); return x; }");
+        assert_eq!(
+            resolved(&table),
+            [
+                "eval@22 -> global dynamic",
+                "s@27 -> global dynamic",
+                "x@38 -> Var@4 dynamic"
+            ]
+        );
+        assert!(table.scopes[1].direct_eval);
+
+        // strict モードの eval は外側に変数を作らない
+        let table = script("var x;\nfunction f() { 'use strict'; eval(s); return x; }");
+        assert!(!table.references[2].dynamic);
+    }
+
+    #[test]
+    fn access() {
+        let table = script("a = 1; b += 1; c++; [d, e = f] = g; for (h of i);");
+        let accesses: Vec<(&str, Access)> = table
+            .references
+            .iter()
+            .map(|reference| (reference.name.as_str(), reference.access))
+            .collect();
+        assert_eq!(
+            accesses,
+            [
+                ("a", Access::Write),
+                ("b", Access::ReadWrite),
+                ("c", Access::ReadWrite),
+                ("d", Access::Write),
+                ("e", Access::Write),
+                ("f", Access::Read),
+                ("g", Access::Read),
+                ("h", Access::Write),
+                ("i", Access::Read),
+            ]
+        );
+    }
+
+    #[test]
+    fn non_references() {
+        let table = script("a: for (;;) { o.p; ({ k: v, [c]: 1 }); break a; }");
+        assert_eq!(table.globals(), ["o", "v", "c"]);
+    }
+
+    #[test]
+    fn modules() {
+        let table = module(
+            "import d, { a as b } from 'm';\nimport * as ns from 'n';\nexport { b };\nexport { r } from 'r';\nexport default function main() { return ns.x + d + g; }\nvar top;",
+        );
+        assert_eq!(table.scopes[1].kind, ScopeKind::Module);
+        assert!(table.scopes[1].strict);
+        assert_eq!(
+            resolved(&table),
+            [
+                "b@65 -> Import@17",
+                "ns@133 -> Import@43",
+                "d@140 -> Import@7",
+                "g@144 -> global"
+            ]
+        );
+        assert!(table
+            .own_binding(table.scopes[1].parent.unwrap(), "top")
+            .is_none());
+        assert!(table.own_binding(super::ScopeId(1), "main").is_some());
+        assert_eq!(table.globals(), ["g"]);
+    }
+}
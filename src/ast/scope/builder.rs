@@ -0,0 +1,608 @@
+//! 構文木を辿ってスコープと束縛と参照を集め、参照を解決するモジュールです。
+
+use std::collections::HashMap;
+
+use super::{
+    Access, Binding, BindingId, BindingKind, Reference, ReferenceId, Scope, ScopeId, ScopeKind,
+    SymbolTable,
+};
+use crate::ast::{
+    structs::{
+        class::{Class, PropertyDefinition, StaticBlock},
+        expression::{AssignmentExpression, AssignmentOperator, Expression, UpdateExpression},
+        function::{ArrowFunctionBody, ArrowFunctionExpression, Function},
+        literal::Identifier,
+        module::{
+            Declaration, ExportDefaultKind, ExportNamedDeclaration, ImportDeclaration,
+            ImportDeclarationSpecifier, ModuleExportName, ModuleItem,
+        },
+        pattern::Pattern,
+        span::{Span, Spanned},
+        statement::{
+            BlockStatement, CatchClause, ForInOfLeft, ForInStatement, ForInit, ForOfStatement,
+            ForStatement, Statement, SwitchStatement, VariableDeclaration, VariableKind,
+            WithStatement,
+        },
+        JsAst, SourceType,
+    },
+    visit::{Visit, VisitWith},
+};
+
+/// パターンの識別子を宣言とするか参照とするか
+#[derive(Debug, Clone, Copy)]
+enum PatternMode {
+    Declare(BindingKind),
+    Assign(Access),
+}
+
+pub(super) struct Builder<'ast> {
+    table: SymbolTable,
+    current: ScopeId,
+    mode: PatternMode,
+
+    /// 束縛が初期化される位置 (TDZ がない束縛は `None`)
+    initialized: Vec<Option<usize>>,
+
+    /// 参照がクラスのフィールド初期化子の中にあれば、そのクラスのスコープ
+    field_initializers: Vec<Option<ScopeId>>,
+    field_initializer: Option<ScopeId>,
+
+    /// strict モードでないブロックの関数宣言 (Annex B の候補)
+    block_functions: Vec<(&'ast Identifier, ScopeId)>,
+}
+
+impl<'ast> Builder<'ast> {
+    pub(super) fn build(ast: &'ast JsAst) -> SymbolTable {
+        let mut builder = Builder {
+            table: SymbolTable {
+                scopes: Vec::new(),
+                bindings: Vec::new(),
+                references: Vec::new(),
+                names: HashMap::new(),
+            },
+            current: ScopeId(0),
+            mode: PatternMode::Assign(Access::Write),
+            initialized: Vec::new(),
+            field_initializers: Vec::new(),
+            field_initializer: None,
+            block_functions: Vec::new(),
+        };
+
+        let module = ast.source_type == SourceType::Module;
+        builder.push_scope(ScopeKind::Global, ast.span, !module && has_use_strict(ast));
+        if module {
+            builder.push_scope(ScopeKind::Module, ast.span, true);
+        }
+        for item in &ast.body {
+            builder.visit_module_item(item);
+        }
+
+        builder.declare_annex_b_functions();
+        builder.resolve();
+        builder.table
+    }
+
+    fn scope(&self, id: ScopeId) -> &Scope {
+        &self.table.scopes[id.0]
+    }
+
+    fn push_scope(&mut self, kind: ScopeKind, span: Span, strict: bool) -> ScopeId {
+        let id = ScopeId(self.table.scopes.len());
+        let parent = (id.0 > 0).then_some(self.current);
+        if let Some(parent) = parent {
+            self.table.scopes[parent.0].children.push(id);
+        }
+        self.table.scopes.push(Scope {
+            kind,
+            span,
+            parent,
+            children: Vec::new(),
+            strict,
+            bindings: Vec::new(),
+            direct_eval: false,
+        });
+        self.current = id;
+        id
+    }
+
+    fn pop_scope(&mut self) {
+        self.current = self.scope(self.current).parent.unwrap_or(ScopeId(0));
+    }
+
+    /// 現在のスコープを引き継いだ strict モードで子のスコープを作る。
+    fn push_child_scope(&mut self, kind: ScopeKind, span: Span) -> ScopeId {
+        let strict = self.scope(self.current).strict;
+        self.push_scope(kind, span, strict)
+    }
+
+    fn var_scope(&self, scope: ScopeId) -> ScopeId {
+        self.table
+            .ancestors(scope)
+            .find(|&scope| self.scope(scope).is_var_scope())
+            .unwrap_or(ScopeId(0))
+    }
+
+    fn with_mode(&mut self, mode: PatternMode, f: impl FnOnce(&mut Self)) {
+        let saved = std::mem::replace(&mut self.mode, mode);
+        f(self);
+        self.mode = saved;
+    }
+
+    /// 束縛を宣言する。同じスコープに同じ名前があれば、その束縛に宣言を加える。
+    fn declare_in(
+        &mut self,
+        scope: ScopeId,
+        identifier: &Identifier,
+        kind: BindingKind,
+    ) -> BindingId {
+        let key = (scope, identifier.name.clone());
+        if let Some(&id) = self.table.names.get(&key) {
+            self.table.bindings[id.0].declarations.push(identifier.span);
+            return id;
+        }
+        let id = self.add_binding(scope, &identifier.name, kind);
+        self.table.bindings[id.0].declarations.push(identifier.span);
+        id
+    }
+
+    fn add_binding(&mut self, scope: ScopeId, name: &str, kind: BindingKind) -> BindingId {
+        let id = BindingId(self.table.bindings.len());
+        self.table.bindings.push(Binding {
+            name: name.to_string(),
+            kind,
+            scope,
+            declarations: Vec::new(),
+            references: Vec::new(),
+        });
+        self.table.scopes[scope.0].bindings.push(id);
+        self.table.names.insert((scope, name.to_string()), id);
+        self.initialized.push(None);
+        id
+    }
+
+    fn declare(&mut self, identifier: &Identifier, kind: BindingKind) -> BindingId {
+        let scope = match kind {
+            BindingKind::Var => self.var_scope(self.current),
+            _ => self.current,
+        };
+        self.declare_in(scope, identifier, kind)
+    }
+
+    /// `let` `const` `class` の束縛に、初期化される位置を記録する。
+    fn declare_lexical(&mut self, identifier: &Identifier, kind: BindingKind, initialized: usize) {
+        let id = self.declare(identifier, kind);
+        self.initialized[id.0] = Some(initialized);
+    }
+
+    fn reference(&mut self, identifier: &Identifier, access: Access) {
+        self.table.references.push(Reference {
+            name: identifier.name.clone(),
+            span: identifier.span,
+            scope: self.current,
+            access,
+            binding: None,
+            in_tdz: false,
+            dynamic: false,
+        });
+        self.field_initializers.push(self.field_initializer);
+    }
+
+    /// 関数宣言の名前を宣言する。
+    ///
+    /// 関数のトップレベルでは `var` と同じく関数スコープに、ブロックの中ではブロックに入る。
+    fn declare_function(&mut self, function: &'ast Function) {
+        let Some(id) = &function.id else {
+            return;
+        };
+        self.declare(id, BindingKind::Function);
+        let scope = self.scope(self.current);
+        if !scope.is_var_scope() && !scope.strict && !function.is_async && !function.generator {
+            self.block_functions.push((id, self.current));
+        }
+    }
+
+    fn function(&mut self, function: &'ast Function, expression: bool) {
+        let saved = self.field_initializer.take();
+        let scope = self.push_scope(
+            ScopeKind::Function { arrow: false },
+            function.span,
+            function.strict,
+        );
+        self.with_mode(PatternMode::Declare(BindingKind::Parameter), |builder| {
+            for param in &function.params {
+                builder.visit_pattern(param);
+            }
+        });
+        for statement in &function.body.body {
+            self.visit_statement(statement);
+        }
+
+        // 引数や本体で同じ名前を宣言した場合は、そちらが優先される
+        if let Some(id) = function.id.as_ref().filter(|_| expression) {
+            if !self.table.names.contains_key(&(scope, id.name.clone())) {
+                self.declare_in(scope, id, BindingKind::FunctionName);
+            }
+        }
+        self.pop_scope();
+        self.field_initializer = saved;
+    }
+
+    /// クラス宣言の名前を宣言し、クラスをたどる。
+    fn class_declaration(&mut self, class: &'ast Class) {
+        if let Some(id) = &class.id {
+            self.declare_lexical(id, BindingKind::Class, class.span.end);
+        }
+        self.visit_class(class);
+    }
+
+    /// Annex B (B.3.2): ブロックの関数宣言を `var` に置き換えても宣言が衝突しなければ、
+    /// 関数スコープにも同じ名前の束縛を作る。
+    fn declare_annex_b_functions(&mut self) {
+        for (id, block) in std::mem::take(&mut self.block_functions) {
+            let var_scope = self.var_scope(block);
+            let mut conflict = false;
+            for scope in self.table.ancestors(block).skip(1) {
+                if let Some(binding) = self.table.own_binding(scope, &id.name) {
+                    let kind = self.table.binding(binding).kind;
+                    conflict |= kind.has_tdz()
+                        || kind == BindingKind::Parameter
+                        || (kind == BindingKind::Function && scope != var_scope);
+                }
+                if scope == var_scope {
+                    break;
+                }
+            }
+            if !conflict {
+                self.declare_in(var_scope, id, BindingKind::Function);
+            }
+        }
+    }
+
+    /// 参照をスコープの外側へ向かって解決する。
+    fn resolve(&mut self) {
+        // strict モードでない direct eval は、関数スコープに変数を作りうる
+        let mut injectable = vec![false; self.table.scopes.len()];
+        for (id, scope) in self.table.scopes.iter().enumerate() {
+            if scope.direct_eval && !scope.strict {
+                injectable[self.var_scope(ScopeId(id)).0] = true;
+            }
+        }
+
+        for index in 0..self.table.references.len() {
+            let reference = &self.table.references[index];
+            let name = reference.name.clone();
+            let start = reference.span.start;
+            let mut scope = Some(reference.scope);
+            let mut dynamic = false;
+            let mut deferred = false;
+            let mut found = None;
+            while let Some(id) = scope {
+                if let Some(binding) = self.table.own_binding(id, &name) {
+                    found = Some(binding);
+                    break;
+                }
+                let kind = self.scope(id).kind;
+                if name == "arguments" && kind == (ScopeKind::Function { arrow: false }) {
+                    found = Some(self.add_binding(id, &name, BindingKind::Arguments));
+                    break;
+                }
+                dynamic |= kind == ScopeKind::With || injectable[id.0];
+                deferred |= matches!(kind, ScopeKind::Function { .. } | ScopeKind::StaticBlock);
+                scope = self.scope(id).parent;
+            }
+
+            let in_tdz = found.is_some_and(|binding| {
+                // フィールド初期化子はクラスの定義が終わった後に評価される
+                let binding_scope = self.table.binding(binding).scope;
+                let in_field = self.field_initializers[index].is_some_and(|class| {
+                    self.table
+                        .ancestors(class)
+                        .any(|scope| scope == binding_scope)
+                });
+                !deferred && !in_field && self.initialized[binding.0].is_some_and(|end| start < end)
+            });
+
+            let reference = &mut self.table.references[index];
+            reference.binding = found;
+            reference.dynamic = dynamic;
+            reference.in_tdz = in_tdz;
+            if let Some(binding) = found {
+                self.table.bindings[binding.0]
+                    .references
+                    .push(ReferenceId(index));
+            }
+        }
+    }
+}
+
+/// スクリプトの先頭に `"use strict"` ディレクティブがあるかを返す。
+fn has_use_strict(ast: &JsAst) -> bool {
+    ast.body
+        .iter()
+        .map_while(|item| match item {
+            ModuleItem::Statement(Statement::Expression(statement)) => {
+                statement.directive.as_deref()
+            }
+            _ => None,
+        })
+        .any(|directive| directive == "use strict")
+}
+
+impl<'ast> Visit<'ast> for Builder<'ast> {
+    // ラベル、プロパティ名、宣言の名前などは参照ではない (参照は式とパターンで記録する)
+    fn visit_identifier(&mut self, _: &'ast Identifier) {}
+
+    fn visit_expression(&mut self, node: &'ast Expression) {
+        match node {
+            Expression::Identifier(identifier) => self.reference(identifier, Access::Read),
+            Expression::Call(call) => {
+                if let Expression::Identifier(callee) = &call.callee {
+                    if callee.name == "eval" && !call.optional {
+                        self.table.scopes[self.current.0].direct_eval = true;
+                    }
+                }
+                node.visit_children_with(self);
+            }
+            _ => node.visit_children_with(self),
+        }
+    }
+
+    fn visit_statement(&mut self, node: &'ast Statement) {
+        match node {
+            Statement::FunctionDeclaration(function) => {
+                self.declare_function(function);
+                self.function(function, false);
+            }
+            Statement::ClassDeclaration(class) => self.class_declaration(class),
+            _ => node.visit_children_with(self),
+        }
+    }
+
+    fn visit_pattern(&mut self, node: &'ast Pattern) {
+        match (node, self.mode) {
+            (Pattern::Identifier(identifier), PatternMode::Declare(kind)) => {
+                self.declare(identifier, kind);
+            }
+            (Pattern::Identifier(identifier), PatternMode::Assign(access)) => {
+                self.reference(identifier, access);
+            }
+            _ => node.visit_children_with(self),
+        }
+    }
+
+    fn visit_function(&mut self, node: &'ast Function) {
+        // メソッドの関数 (名前を持たない)
+        self.function(node, true);
+    }
+
+    fn visit_arrow_function_expression(&mut self, node: &'ast ArrowFunctionExpression) {
+        let saved = self.field_initializer.take();
+        self.push_scope(ScopeKind::Function { arrow: true }, node.span, node.strict);
+        self.with_mode(PatternMode::Declare(BindingKind::Parameter), |builder| {
+            for param in &node.params {
+                builder.visit_pattern(param);
+            }
+        });
+        match &node.body {
+            ArrowFunctionBody::Expression(expression) => self.visit_expression(expression),
+            ArrowFunctionBody::FunctionBody(body) => {
+                for statement in &body.body {
+                    self.visit_statement(statement);
+                }
+            }
+        }
+        self.pop_scope();
+        self.field_initializer = saved;
+    }
+
+    fn visit_class(&mut self, node: &'ast Class) {
+        self.push_scope(ScopeKind::Class, node.span, true);
+        if let Some(id) = &node.id {
+            self.declare_lexical(id, BindingKind::ClassName, node.span.end);
+        }
+        if let Some(super_class) = &node.super_class {
+            self.visit_expression(super_class);
+        }
+        self.visit_class_body(&node.body);
+        self.pop_scope();
+    }
+
+    fn visit_property_definition(&mut self, node: &'ast PropertyDefinition) {
+        self.visit_property_key(&node.key);
+        if let Some(value) = &node.value {
+            let saved = self.field_initializer.replace(self.current);
+            self.visit_expression(value);
+            self.field_initializer = saved;
+        }
+    }
+
+    fn visit_static_block(&mut self, node: &'ast StaticBlock) {
+        let saved = self.field_initializer.take();
+        self.push_child_scope(ScopeKind::StaticBlock, node.span);
+        for statement in &node.body {
+            self.visit_statement(statement);
+        }
+        self.pop_scope();
+        self.field_initializer = saved;
+    }
+
+    fn visit_block_statement(&mut self, node: &'ast BlockStatement) {
+        self.push_child_scope(ScopeKind::Block, node.span);
+        node.visit_children_with(self);
+        self.pop_scope();
+    }
+
+    fn visit_variable_declaration(&mut self, node: &'ast VariableDeclaration) {
+        let kind = match node.kind {
+            VariableKind::Var => BindingKind::Var,
+            VariableKind::Let => BindingKind::Let,
+            VariableKind::Const => BindingKind::Const,
+        };
+        for declarator in &node.declarations {
+            self.with_mode(PatternMode::Declare(kind), |builder| {
+                builder.visit_pattern(&declarator.id);
+            });
+            if kind.has_tdz() {
+                for name in declarator.id.bound_names() {
+                    if let Some(id) = self.table.own_binding(self.current, &name.name) {
+                        self.initialized[id.0] = Some(declarator.span.end);
+                    }
+                }
+            }
+            if let Some(init) = &declarator.init {
+                self.visit_expression(init);
+            }
+        }
+    }
+
+    fn visit_for_statement(&mut self, node: &'ast ForStatement) {
+        let lexical = matches!(
+            &node.init,
+            Some(ForInit::VariableDeclaration(declaration)) if declaration.kind.is_lexical()
+        );
+        if lexical {
+            self.push_child_scope(ScopeKind::Block, node.span);
+        }
+        node.visit_children_with(self);
+        if lexical {
+            self.pop_scope();
+        }
+    }
+
+    fn visit_for_in_statement(&mut self, node: &'ast ForInStatement) {
+        self.for_in_of(&node.left, node.span, |builder| {
+            node.visit_children_with(builder)
+        });
+    }
+
+    fn visit_for_of_statement(&mut self, node: &'ast ForOfStatement) {
+        self.for_in_of(&node.left, node.span, |builder| {
+            node.visit_children_with(builder)
+        });
+    }
+
+    fn visit_for_in_of_left(&mut self, node: &'ast ForInOfLeft) {
+        match node {
+            ForInOfLeft::Pattern(pattern) => {
+                self.with_mode(PatternMode::Assign(Access::Write), |builder| {
+                    builder.visit_pattern(pattern);
+                });
+            }
+            ForInOfLeft::VariableDeclaration(declaration) => {
+                self.visit_variable_declaration(declaration)
+            }
+        }
+    }
+
+    fn visit_switch_statement(&mut self, node: &'ast SwitchStatement) {
+        self.visit_expression(&node.discriminant);
+        self.push_child_scope(ScopeKind::Block, node.span);
+        for case in &node.cases {
+            self.visit_switch_case(case);
+        }
+        self.pop_scope();
+    }
+
+    fn visit_with_statement(&mut self, node: &'ast WithStatement) {
+        self.visit_expression(&node.object);
+        self.push_child_scope(ScopeKind::With, node.body.span());
+        self.visit_statement(&node.body);
+        self.pop_scope();
+    }
+
+    fn visit_catch_clause(&mut self, node: &'ast CatchClause) {
+        self.push_child_scope(ScopeKind::Catch, node.span);
+        if let Some(param) = &node.param {
+            self.with_mode(
+                PatternMode::Declare(BindingKind::CatchParameter),
+                |builder| {
+                    builder.visit_pattern(param);
+                },
+            );
+        }
+        self.visit_block_statement(&node.body);
+        self.pop_scope();
+    }
+
+    fn visit_assignment_expression(&mut self, node: &'ast AssignmentExpression) {
+        let access = match node.operator {
+            AssignmentOperator::Assign => Access::Write,
+            _ => Access::ReadWrite,
+        };
+        self.with_mode(PatternMode::Assign(access), |builder| {
+            builder.visit_pattern(&node.left);
+        });
+        self.visit_expression(&node.right);
+    }
+
+    fn visit_update_expression(&mut self, node: &'ast UpdateExpression) {
+        match &node.argument {
+            Expression::Identifier(identifier) => self.reference(identifier, Access::ReadWrite),
+            argument => self.visit_expression(argument),
+        }
+    }
+
+    fn visit_import_declaration(&mut self, node: &'ast ImportDeclaration) {
+        for specifier in &node.specifiers {
+            let local = match specifier {
+                ImportDeclarationSpecifier::Named(specifier) => &specifier.local,
+                ImportDeclarationSpecifier::Default(specifier) => &specifier.local,
+                ImportDeclarationSpecifier::Namespace(specifier) => &specifier.local,
+            };
+            self.declare(local, BindingKind::Import);
+        }
+    }
+
+    fn visit_export_named_declaration(&mut self, node: &'ast ExportNamedDeclaration) {
+        if let Some(declaration) = &node.declaration {
+            self.visit_declaration(declaration);
+        }
+        // `export { a } from "m"` は他のモジュールの名前
+        if node.source.is_none() {
+            for specifier in &node.specifiers {
+                if let ModuleExportName::Identifier(local) = &specifier.local {
+                    self.reference(local, Access::Read);
+                }
+            }
+        }
+    }
+
+    fn visit_declaration(&mut self, node: &'ast Declaration) {
+        match node {
+            Declaration::Function(function) => {
+                self.declare_function(function);
+                self.function(function, false);
+            }
+            Declaration::Variable(declaration) => self.visit_variable_declaration(declaration),
+            Declaration::Class(class) => self.class_declaration(class),
+        }
+    }
+
+    fn visit_export_default_kind(&mut self, node: &'ast ExportDefaultKind) {
+        match node {
+            ExportDefaultKind::Function(function) => {
+                self.declare_function(function);
+                self.function(function, false);
+            }
+            ExportDefaultKind::Class(class) => self.class_declaration(class),
+            ExportDefaultKind::Expression(expression) => self.visit_expression(expression),
+        }
+    }
+}
+
+impl<'ast> Builder<'ast> {
+    /// `let` `const` の for-in/of 文は、左辺の束縛のためのスコープを作る。
+    fn for_in_of(&mut self, left: &'ast ForInOfLeft, span: Span, visit: impl FnOnce(&mut Self)) {
+        let lexical = matches!(
+            left,
+            ForInOfLeft::VariableDeclaration(declaration) if declaration.kind.is_lexical()
+        );
+        if lexical {
+            self.push_child_scope(ScopeKind::Block, span);
+        }
+        visit(self);
+        if lexical {
+            self.pop_scope();
+        }
+    }
+}
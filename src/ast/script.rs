@@ -1,9 +1,118 @@
-use super::{reader_struct::SourceCodeReader, structs::JsAst};
+use crate::token::structs::token::Token;
 
-pub fn parse_script(n: SourceCodeReader<'_>) -> JsAst {
-    JsAst::new()
+use super::{
+    early_errors::check_early_errors,
+    error::{ParseError, ParseResult, ParseWarning},
+    parser::Parser,
+    reader_struct::SourceCodeReader,
+    structs::{expression::Expression, pattern::Pattern, statement::Statement, JsAst, SourceType},
+};
+
+/// 解析の設定
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// 自動セミコロン挿入で誤解しやすい箇所を警告する
+    pub asi_hazard_warnings: bool,
+}
+
+/// 解析の結果
+///
+/// 構文エラーがあっても、読めた部分の構文木を返す。
+/// 読めなかった文や欠けている式はエラーのノードになる。
+#[derive(Debug, PartialEq, Clone)]
+pub struct ParseOutput {
+    pub ast: JsAst,
+
+    /// 構文エラーと早期エラー (位置の順)
+    pub errors: Vec<ParseError>,
+    pub warnings: Vec<ParseWarning>,
+}
+
+impl ParseOutput {
+    /// エラーがなければ構文木を、あれば最初のエラーを返す。
+    pub fn into_result(self) -> ParseResult<JsAst> {
+        match self.errors.into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(self.ast),
+        }
+    }
+}
+
+/// スクリプトとして解析する。
+pub fn parse_script(n: SourceCodeReader<'_>) -> ParseOutput {
+    parse_with_options(n, SourceType::Script, ParseOptions::default())
+}
+
+/// モジュールとして解析する。
+pub fn parse_module(n: SourceCodeReader<'_>) -> ParseOutput {
+    parse_with_options(n, SourceType::Module, ParseOptions::default())
+}
+
+/// 設定を指定して解析し、エラーと警告と共に返す。
+pub fn parse_with_options(
+    n: SourceCodeReader<'_>,
+    source_type: SourceType,
+    options: ParseOptions,
+) -> ParseOutput {
+    parse_with_tokens(n, source_type, options).0
+}
+
+/// 解析した結果と、読み進めたトークン (空白、改行、コメントを除く) を返す。
+pub(crate) fn parse_with_tokens(
+    n: SourceCodeReader<'_>,
+    source_type: SourceType,
+    options: ParseOptions,
+) -> (ParseOutput, Vec<Token>) {
+    let mut parser = match Parser::with_options(n, options) {
+        Ok(parser) => parser,
+        Err(error) => {
+            let output = ParseOutput {
+                ast: JsAst {
+                    source_type,
+                    ..JsAst::new()
+                },
+                errors: vec![error],
+                warnings: Vec::new(),
+            };
+            return (output, Vec::new());
+        }
+    };
+
+    let ast = parser.parse_program(source_type);
+    let mut errors = parser.take_errors();
+
+    // 構文エラーがない場合だけ早期エラーを検査する
+    if errors.is_empty() {
+        errors = check_early_errors(&ast);
+    }
+    errors.sort_by_key(|error| error.span);
+
+    let output = ParseOutput {
+        ast,
+        errors,
+        warnings: parser.take_warnings(),
+    };
+    (output, parser.take_tokens())
+}
+
+/// 1つの式を解析する。カンマ区切りの式も1つの式として読む。
+///
+/// 断片はスクリプトの最上位として読み、早期エラーは検査しない。
+/// 式の後ろに入力が残っている場合はエラーになる。
+pub fn parse_expression(n: SourceCodeReader<'_>) -> ParseResult<Expression> {
+    Parser::new(n)?.parse_expression_fragment()
+}
+
+/// 文のリストを解析する。
+///
+/// 断片はスクリプトの最上位として読み、早期エラーは検査しない。
+pub fn parse_statements(n: SourceCodeReader<'_>) -> ParseResult<Vec<Statement>> {
+    Parser::new(n)?.parse_statements_fragment()
 }
 
-pub fn parse_module(n: SourceCodeReader<'_>) -> JsAst {
-    JsAst::new()
+/// 束縛のパターン (`{ a, b = 1 }` など) を解析する。
+///
+/// パターンの後ろに入力が残っている場合はエラーになる。
+pub fn parse_pattern(n: SourceCodeReader<'_>) -> ParseResult<Pattern> {
+    Parser::new(n)?.parse_pattern_fragment()
 }
@@ -1,8 +1,73 @@
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
-pub struct JsAst {}
+//! 構文木のノードを定義するモジュールです。
+//!
+//! ノードの形は ESTree に近づけています。
+//! 全てのノードはソースコード上のバイト位置を [`Span`] として持ちます。
+
+pub mod class;
+pub mod expression;
+pub mod function;
+pub mod literal;
+pub mod module;
+pub mod pattern;
+pub mod span;
+pub mod statement;
+pub mod template;
+
+use self::{
+    module::ModuleItem,
+    span::{Span, Spanned},
+};
+
+/// ソースコードの解析目標
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SourceType {
+    /// スクリプトとして解析する
+    Script,
+
+    /// モジュールとして解析する
+    Module,
+}
+
+/// 構文木のルート (ESTree の Program)
+#[derive(Debug, PartialEq, Clone)]
+pub struct JsAst {
+    pub span: Span,
+    pub source_type: SourceType,
+    pub body: Vec<ModuleItem>,
+}
+
+/// 構文エラーのために読めなかった範囲
+///
+/// 文や式が欠けている位置に置き、その後ろの解析を続ける。
+#[derive(Debug, PartialEq, Clone)]
+pub struct ErrorNode {
+    pub span: Span,
+}
 
 impl JsAst {
     pub fn new() -> JsAst {
-        JsAst {}
+        JsAst {
+            span: Span::default(),
+            source_type: SourceType::Script,
+            body: Vec::new(),
+        }
+    }
+}
+
+impl Default for JsAst {
+    fn default() -> Self {
+        JsAst::new()
+    }
+}
+
+impl Spanned for JsAst {
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl Spanned for ErrorNode {
+    fn span(&self) -> Span {
+        self.span
     }
 }
@@ -0,0 +1,95 @@
+use super::{
+    expression::{Expression, PropertyKey},
+    function::Function,
+    literal::Identifier,
+    span::{impl_spanned, impl_spanned_enum, Span, Spanned},
+    statement::Statement,
+};
+
+/// クラス宣言とクラス式
+#[derive(Debug, PartialEq, Clone)]
+pub struct Class {
+    pub span: Span,
+    pub id: Option<Identifier>,
+
+    /// extends の右辺
+    pub super_class: Option<Expression>,
+    pub body: ClassBody,
+}
+
+/// { ... }
+#[derive(Debug, PartialEq, Clone)]
+pub struct ClassBody {
+    pub span: Span,
+    pub body: Vec<ClassElement>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ClassElement {
+    /// a() {}
+    Method(MethodDefinition),
+
+    /// a = 1;
+    Property(PropertyDefinition),
+
+    /// static {}
+    StaticBlock(StaticBlock),
+}
+
+/// メソッド、アクセサ、コンストラクタ
+#[derive(Debug, PartialEq, Clone)]
+pub struct MethodDefinition {
+    pub span: Span,
+    pub key: PropertyKey,
+    pub value: Function,
+    pub kind: MethodKind,
+
+    /// static a() {}
+    pub is_static: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MethodKind {
+    /// constructor() {}
+    Constructor,
+
+    /// a() {}
+    Method,
+
+    /// get a() {}
+    Get,
+
+    /// set a(b) {}
+    Set,
+}
+
+/// フィールド定義
+#[derive(Debug, PartialEq, Clone)]
+pub struct PropertyDefinition {
+    pub span: Span,
+    pub key: PropertyKey,
+    pub value: Option<Expression>,
+
+    /// static a = 1;
+    pub is_static: bool,
+}
+
+/// static { ... }
+#[derive(Debug, PartialEq, Clone)]
+pub struct StaticBlock {
+    pub span: Span,
+    pub body: Vec<Statement>,
+}
+
+impl_spanned!(
+    Class,
+    ClassBody,
+    MethodDefinition,
+    PropertyDefinition,
+    StaticBlock
+);
+impl_spanned_enum!(ClassElement {
+    Method,
+    Property,
+    StaticBlock,
+});
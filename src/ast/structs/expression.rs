@@ -0,0 +1,694 @@
+use crate::to_source_string::ToSourceString;
+
+use super::{
+    class::Class,
+    function::{ArrowFunctionExpression, Function},
+    literal::{Identifier, Literal, PrivateIdentifier},
+    pattern::Pattern,
+    span::{impl_spanned, impl_spanned_enum, Span, Spanned},
+    template::{TaggedTemplateExpression, TemplateLiteral},
+    ErrorNode,
+};
+
+/// 式
+#[derive(Debug, PartialEq, Clone)]
+pub enum Expression {
+    /// abc
+    Identifier(Identifier),
+
+    /// 123
+    Literal(Literal),
+
+    /// this
+    This(ThisExpression),
+
+    /// super (メンバー参照と呼び出しの対象としてのみ使う)
+    Super(Super),
+
+    /// [a, b]
+    Array(ArrayExpression),
+
+    /// { a: b }
+    Object(ObjectExpression),
+
+    /// function () {}
+    Function(Box<Function>),
+
+    /// () => {}
+    ArrowFunction(Box<ArrowFunctionExpression>),
+
+    /// class {}
+    Class(Box<Class>),
+
+    /// `a${b}c`
+    TemplateLiteral(TemplateLiteral),
+
+    /// tag`a${b}c`
+    TaggedTemplate(Box<TaggedTemplateExpression>),
+
+    /// a.b
+    Member(Box<MemberExpression>),
+
+    /// a(b)
+    Call(Box<CallExpression>),
+
+    /// new a(b)
+    New(Box<NewExpression>),
+
+    /// a?.b
+    Chain(Box<ChainExpression>),
+
+    /// -a
+    Unary(Box<UnaryExpression>),
+
+    /// a++
+    Update(Box<UpdateExpression>),
+
+    /// a + b
+    Binary(Box<BinaryExpression>),
+
+    /// a && b
+    Logical(Box<LogicalExpression>),
+
+    /// a ? b : c
+    Conditional(Box<ConditionalExpression>),
+
+    /// a = b
+    Assignment(Box<AssignmentExpression>),
+
+    /// a, b
+    Sequence(SequenceExpression),
+
+    /// yield a
+    Yield(Box<YieldExpression>),
+
+    /// await a
+    Await(Box<AwaitExpression>),
+
+    /// new.target
+    MetaProperty(MetaProperty),
+
+    /// import(a)
+    Import(Box<ImportExpression>),
+
+    /// #a (`#a in b` の左辺としてのみ使う)
+    PrivateIdentifier(PrivateIdentifier),
+
+    /// 構文エラーで欠けている式
+    Error(ErrorNode),
+}
+
+/// this
+#[derive(Debug, PartialEq, Clone)]
+pub struct ThisExpression {
+    pub span: Span,
+}
+
+/// super
+#[derive(Debug, PartialEq, Clone)]
+pub struct Super {
+    pub span: Span,
+}
+
+/// [a, , ...b]
+#[derive(Debug, PartialEq, Clone)]
+pub struct ArrayExpression {
+    pub span: Span,
+
+    /// 省略された要素は `None`
+    pub elements: Vec<Option<ExpressionOrSpread>>,
+}
+
+/// 配列の要素と関数の引数
+#[derive(Debug, PartialEq, Clone)]
+pub enum ExpressionOrSpread {
+    /// a
+    Expression(Expression),
+
+    /// ...a
+    Spread(SpreadElement),
+}
+
+/// ...a
+#[derive(Debug, PartialEq, Clone)]
+pub struct SpreadElement {
+    pub span: Span,
+    pub argument: Expression,
+}
+
+/// { a, b: c, ...d }
+#[derive(Debug, PartialEq, Clone)]
+pub struct ObjectExpression {
+    pub span: Span,
+    pub properties: Vec<ObjectProperty>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ObjectProperty {
+    /// a: b
+    Property(Property),
+
+    /// ...a
+    Spread(SpreadElement),
+}
+
+/// オブジェクトリテラルのプロパティ
+#[derive(Debug, PartialEq, Clone)]
+pub struct Property {
+    pub span: Span,
+    pub key: PropertyKey,
+
+    /// メソッドとアクセサの場合は関数式
+    pub value: Expression,
+    pub kind: PropertyKind,
+
+    /// a() {}
+    pub method: bool,
+
+    /// { a }
+    pub shorthand: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PropertyKind {
+    /// a: b
+    Init,
+
+    /// get a() {}
+    Get,
+
+    /// set a(b) {}
+    Set,
+}
+
+/// プロパティ名
+#[derive(Debug, PartialEq, Clone)]
+pub enum PropertyKey {
+    /// a
+    Identifier(Identifier),
+
+    /// "a", 1
+    Literal(Literal),
+
+    /// #a (クラスの要素のみ)
+    PrivateIdentifier(PrivateIdentifier),
+
+    /// [a]
+    Computed(Expression),
+}
+
+/// a.b
+#[derive(Debug, PartialEq, Clone)]
+pub struct MemberExpression {
+    pub span: Span,
+    pub object: Expression,
+    pub property: MemberProperty,
+
+    /// a?.b
+    pub optional: bool,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum MemberProperty {
+    /// a.b
+    Identifier(Identifier),
+
+    /// a.#b
+    PrivateIdentifier(PrivateIdentifier),
+
+    /// a[b]
+    Computed(Expression),
+}
+
+/// a(b)
+#[derive(Debug, PartialEq, Clone)]
+pub struct CallExpression {
+    pub span: Span,
+    pub callee: Expression,
+    pub arguments: Vec<ExpressionOrSpread>,
+
+    /// a?.(b)
+    pub optional: bool,
+}
+
+/// new a(b)
+#[derive(Debug, PartialEq, Clone)]
+pub struct NewExpression {
+    pub span: Span,
+    pub callee: Expression,
+
+    /// `new a` のように括弧がない場合は空
+    pub arguments: Vec<ExpressionOrSpread>,
+}
+
+/// オプショナルチェーンの全体 `a?.b.c`
+#[derive(Debug, PartialEq, Clone)]
+pub struct ChainExpression {
+    pub span: Span,
+
+    /// メンバー参照か呼び出し
+    pub expression: Expression,
+}
+
+/// -a
+#[derive(Debug, PartialEq, Clone)]
+pub struct UnaryExpression {
+    pub span: Span,
+    pub operator: UnaryOperator,
+    pub argument: Expression,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum UnaryOperator {
+    /// -
+    Minus,
+
+    /// +
+    Plus,
+
+    /// !
+    LogicalNot,
+
+    /// ~
+    BitNot,
+
+    /// typeof
+    Typeof,
+
+    /// void
+    Void,
+
+    /// delete
+    Delete,
+}
+
+/// a++, --a
+#[derive(Debug, PartialEq, Clone)]
+pub struct UpdateExpression {
+    pub span: Span,
+    pub operator: UpdateOperator,
+
+    /// 前置の場合は `true`
+    pub prefix: bool,
+    pub argument: Expression,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum UpdateOperator {
+    /// ++
+    Increment,
+
+    /// --
+    Decrement,
+}
+
+/// a + b
+#[derive(Debug, PartialEq, Clone)]
+pub struct BinaryExpression {
+    pub span: Span,
+    pub operator: BinaryOperator,
+
+    /// `#a in b` の場合は [`Expression::PrivateIdentifier`]
+    pub left: Expression,
+    pub right: Expression,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BinaryOperator {
+    /// ==
+    Equal,
+
+    /// !=
+    NotEqual,
+
+    /// ===
+    StrictEqual,
+
+    /// !==
+    StrictNotEqual,
+
+    /// <
+    LessThan,
+
+    /// <=
+    LessThanEqual,
+
+    /// \>
+    GreaterThan,
+
+    /// \>=
+    GreaterThanEqual,
+
+    /// <<
+    LeftShift,
+
+    /// \>>
+    RightShift,
+
+    /// \>>>
+    UnsignedRightShift,
+
+    /// +
+    Plus,
+
+    /// -
+    Minus,
+
+    /// *
+    Multiply,
+
+    /// /
+    Divide,
+
+    /// %
+    Remainder,
+
+    /// **
+    Exponentiation,
+
+    /// |
+    BitOr,
+
+    /// ^
+    BitXor,
+
+    /// &
+    BitAnd,
+
+    /// in
+    In,
+
+    /// instanceof
+    Instanceof,
+}
+
+/// a && b
+#[derive(Debug, PartialEq, Clone)]
+pub struct LogicalExpression {
+    pub span: Span,
+    pub operator: LogicalOperator,
+    pub left: Expression,
+    pub right: Expression,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LogicalOperator {
+    /// ||
+    Or,
+
+    /// &&
+    And,
+
+    /// ??
+    NullishCoalesce,
+}
+
+/// a ? b : c
+#[derive(Debug, PartialEq, Clone)]
+pub struct ConditionalExpression {
+    pub span: Span,
+    pub test: Expression,
+    pub consequent: Expression,
+    pub alternate: Expression,
+}
+
+/// a = b
+#[derive(Debug, PartialEq, Clone)]
+pub struct AssignmentExpression {
+    pub span: Span,
+    pub operator: AssignmentOperator,
+
+    /// 複合代入の場合は識別子かメンバー参照
+    pub left: Pattern,
+    pub right: Expression,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum AssignmentOperator {
+    /// =
+    Assign,
+
+    /// +=
+    PlusAssign,
+
+    /// -=
+    MinusAssign,
+
+    /// *=
+    MultiplyAssign,
+
+    /// /=
+    DivideAssign,
+
+    /// %=
+    RemainderAssign,
+
+    /// **=
+    ExponentiationAssign,
+
+    /// <<=
+    LeftShiftAssign,
+
+    /// \>>=
+    RightShiftAssign,
+
+    /// \>>>=
+    UnsignedRightShiftAssign,
+
+    /// &=
+    BitAndAssign,
+
+    /// |=
+    BitOrAssign,
+
+    /// ^=
+    BitXorAssign,
+
+    /// &&=
+    LogicalAndAssign,
+
+    /// ||=
+    LogicalOrAssign,
+
+    /// ??=
+    NullishCoalesceAssign,
+}
+
+/// a, b
+#[derive(Debug, PartialEq, Clone)]
+pub struct SequenceExpression {
+    pub span: Span,
+    pub expressions: Vec<Expression>,
+}
+
+/// yield a, yield* a
+#[derive(Debug, PartialEq, Clone)]
+pub struct YieldExpression {
+    pub span: Span,
+    pub argument: Option<Expression>,
+
+    /// yield* の場合は `true`
+    pub delegate: bool,
+}
+
+/// await a
+#[derive(Debug, PartialEq, Clone)]
+pub struct AwaitExpression {
+    pub span: Span,
+    pub argument: Expression,
+}
+
+/// new.target, import.meta
+#[derive(Debug, PartialEq, Clone)]
+pub struct MetaProperty {
+    pub span: Span,
+    pub meta: Identifier,
+    pub property: Identifier,
+}
+
+/// import(a), import(a, b)
+#[derive(Debug, PartialEq, Clone)]
+pub struct ImportExpression {
+    pub span: Span,
+    pub source: Expression,
+    pub options: Option<Expression>,
+}
+
+impl_spanned!(
+    ThisExpression,
+    Super,
+    ArrayExpression,
+    SpreadElement,
+    ObjectExpression,
+    Property,
+    MemberExpression,
+    CallExpression,
+    NewExpression,
+    ChainExpression,
+    UnaryExpression,
+    UpdateExpression,
+    BinaryExpression,
+    LogicalExpression,
+    ConditionalExpression,
+    AssignmentExpression,
+    SequenceExpression,
+    YieldExpression,
+    AwaitExpression,
+    MetaProperty,
+    ImportExpression,
+);
+
+impl_spanned_enum!(Expression {
+    Identifier,
+    Literal,
+    This,
+    Super,
+    Array,
+    Object,
+    Function,
+    ArrowFunction,
+    Class,
+    TemplateLiteral,
+    TaggedTemplate,
+    Member,
+    Call,
+    New,
+    Chain,
+    Unary,
+    Update,
+    Binary,
+    Logical,
+    Conditional,
+    Assignment,
+    Sequence,
+    Yield,
+    Await,
+    MetaProperty,
+    Import,
+    PrivateIdentifier,
+    Error,
+});
+
+impl_spanned_enum!(ExpressionOrSpread { Expression, Spread });
+impl_spanned_enum!(ObjectProperty { Property, Spread });
+impl_spanned_enum!(PropertyKey {
+    Identifier,
+    Literal,
+    PrivateIdentifier,
+    Computed,
+});
+impl_spanned_enum!(MemberProperty {
+    Identifier,
+    PrivateIdentifier,
+    Computed,
+});
+
+impl PropertyKey {
+    /// 計算されないプロパティ名の文字列を返す。
+    ///
+    /// 数値リテラルは正規化しないため、`1` と `1.0` は別の名前になる。
+    pub fn static_name(&self) -> Option<&str> {
+        match self {
+            PropertyKey::Identifier(identifier) => Some(&identifier.name),
+            PropertyKey::Literal(literal) => match &literal.value {
+                super::literal::LiteralValue::String(value) => Some(value),
+                _ => Some(&literal.raw),
+            },
+            PropertyKey::PrivateIdentifier(_) | PropertyKey::Computed(_) => None,
+        }
+    }
+
+    pub fn is_computed(&self) -> bool {
+        matches!(self, PropertyKey::Computed(_))
+    }
+}
+
+impl ToSourceString for UnaryOperator {
+    fn to_source_string(&self) -> String {
+        match self {
+            UnaryOperator::Minus => "-",
+            UnaryOperator::Plus => "+",
+            UnaryOperator::LogicalNot => "!",
+            UnaryOperator::BitNot => "~",
+            UnaryOperator::Typeof => "typeof",
+            UnaryOperator::Void => "void",
+            UnaryOperator::Delete => "delete",
+        }
+        .to_string()
+    }
+}
+
+impl ToSourceString for UpdateOperator {
+    fn to_source_string(&self) -> String {
+        match self {
+            UpdateOperator::Increment => "++",
+            UpdateOperator::Decrement => "--",
+        }
+        .to_string()
+    }
+}
+
+impl ToSourceString for BinaryOperator {
+    fn to_source_string(&self) -> String {
+        match self {
+            BinaryOperator::Equal => "==",
+            BinaryOperator::NotEqual => "!=",
+            BinaryOperator::StrictEqual => "===",
+            BinaryOperator::StrictNotEqual => "!==",
+            BinaryOperator::LessThan => "<",
+            BinaryOperator::LessThanEqual => "<=",
+            BinaryOperator::GreaterThan => ">",
+            BinaryOperator::GreaterThanEqual => ">=",
+            BinaryOperator::LeftShift => "<<",
+            BinaryOperator::RightShift => ">>",
+            BinaryOperator::UnsignedRightShift => ">>>",
+            BinaryOperator::Plus => "+",
+            BinaryOperator::Minus => "-",
+            BinaryOperator::Multiply => "*",
+            BinaryOperator::Divide => "/",
+            BinaryOperator::Remainder => "%",
+            BinaryOperator::Exponentiation => "**",
+            BinaryOperator::BitOr => "|",
+            BinaryOperator::BitXor => "^",
+            BinaryOperator::BitAnd => "&",
+            BinaryOperator::In => "in",
+            BinaryOperator::Instanceof => "instanceof",
+        }
+        .to_string()
+    }
+}
+
+impl ToSourceString for LogicalOperator {
+    fn to_source_string(&self) -> String {
+        match self {
+            LogicalOperator::Or => "||",
+            LogicalOperator::And => "&&",
+            LogicalOperator::NullishCoalesce => "??",
+        }
+        .to_string()
+    }
+}
+
+impl ToSourceString for AssignmentOperator {
+    fn to_source_string(&self) -> String {
+        match self {
+            AssignmentOperator::Assign => "=",
+            AssignmentOperator::PlusAssign => "+=",
+            AssignmentOperator::MinusAssign => "-=",
+            AssignmentOperator::MultiplyAssign => "*=",
+            AssignmentOperator::DivideAssign => "/=",
+            AssignmentOperator::RemainderAssign => "%=",
+            AssignmentOperator::ExponentiationAssign => "**=",
+            AssignmentOperator::LeftShiftAssign => "<<=",
+            AssignmentOperator::RightShiftAssign => ">>=",
+            AssignmentOperator::UnsignedRightShiftAssign => ">>>=",
+            AssignmentOperator::BitAndAssign => "&=",
+            AssignmentOperator::BitOrAssign => "|=",
+            AssignmentOperator::BitXorAssign => "^=",
+            AssignmentOperator::LogicalAndAssign => "&&=",
+            AssignmentOperator::LogicalOrAssign => "||=",
+            AssignmentOperator::NullishCoalesceAssign => "??=",
+        }
+        .to_string()
+    }
+}
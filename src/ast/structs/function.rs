@@ -0,0 +1,77 @@
+use super::{
+    expression::Expression,
+    literal::Identifier,
+    pattern::Pattern,
+    span::{impl_spanned, impl_spanned_enum, Span, Spanned},
+    statement::Statement,
+};
+
+/// 関数宣言、関数式、メソッドの関数
+#[derive(Debug, PartialEq, Clone)]
+pub struct Function {
+    pub span: Span,
+    pub id: Option<Identifier>,
+    pub params: Vec<Pattern>,
+    pub body: FunctionBody,
+
+    /// async function
+    pub is_async: bool,
+
+    /// function*
+    pub generator: bool,
+
+    /// 本体が strict モードであるか (外側から引き継いだ場合と `"use strict"` の場合)
+    pub strict: bool,
+}
+
+/// 関数本体 `{ ... }`
+///
+/// ディレクティブは `directive` を持つ式文として先頭に並ぶ。
+#[derive(Debug, PartialEq, Clone)]
+pub struct FunctionBody {
+    pub span: Span,
+    pub body: Vec<Statement>,
+}
+
+/// (a, b) => c
+#[derive(Debug, PartialEq, Clone)]
+pub struct ArrowFunctionExpression {
+    pub span: Span,
+    pub params: Vec<Pattern>,
+    pub body: ArrowFunctionBody,
+
+    /// async () => {}
+    pub is_async: bool,
+
+    /// 本体が strict モードであるか
+    pub strict: bool,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ArrowFunctionBody {
+    /// () => a
+    Expression(Expression),
+
+    /// () => { a }
+    FunctionBody(FunctionBody),
+}
+
+impl Function {
+    /// 引数が全て単純な識別子であるかを返す。
+    pub fn has_simple_parameters(&self) -> bool {
+        self.params.iter().all(Pattern::is_simple)
+    }
+}
+
+impl ArrowFunctionExpression {
+    /// 本体が式であるかを返す。
+    pub fn is_expression(&self) -> bool {
+        matches!(self.body, ArrowFunctionBody::Expression(_))
+    }
+}
+
+impl_spanned!(Function, FunctionBody, ArrowFunctionExpression);
+impl_spanned_enum!(ArrowFunctionBody {
+    Expression,
+    FunctionBody,
+});
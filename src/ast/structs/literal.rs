@@ -0,0 +1,81 @@
+use super::span::{impl_spanned, Span};
+
+/// 識別子
+///
+/// 変数の参照、束縛、ラベル、プロパティ名に使う。
+#[derive(Debug, PartialEq, Clone)]
+pub struct Identifier {
+    pub span: Span,
+    pub name: String,
+}
+
+/// プライベート名 `#name`
+///
+/// `name` には `#` を含まない。
+#[derive(Debug, PartialEq, Clone)]
+pub struct PrivateIdentifier {
+    pub span: Span,
+    pub name: String,
+}
+
+/// リテラル
+#[derive(Debug, PartialEq, Clone)]
+pub struct Literal {
+    pub span: Span,
+    pub value: LiteralValue,
+
+    /// ソースコード上の表記
+    pub raw: String,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum LiteralValue {
+    /// null
+    Null,
+
+    /// true
+    Boolean(bool),
+
+    /// 12.3
+    Number(f64),
+
+    /// "abc"
+    ///
+    /// 対になっていないサロゲートは U+FFFD に置き換える。
+    String(String),
+
+    /// 123n
+    ///
+    /// 10進数の数字列で持つ。
+    BigInt(String),
+
+    /// /abc/g
+    RegExp(RegExpLiteral),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct RegExpLiteral {
+    pub pattern: String,
+    pub flags: String,
+}
+
+impl Identifier {
+    pub fn new(span: Span, name: impl Into<String>) -> Identifier {
+        Identifier {
+            span,
+            name: name.into(),
+        }
+    }
+}
+
+impl Literal {
+    /// 文字列リテラルの値を返す。
+    pub fn as_str(&self) -> Option<&str> {
+        match &self.value {
+            LiteralValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl_spanned!(Identifier, PrivateIdentifier, Literal);
@@ -0,0 +1,218 @@
+use super::{
+    class::Class,
+    expression::{Expression, PropertyKey},
+    function::Function,
+    literal::{Identifier, Literal},
+    span::{impl_spanned, impl_spanned_enum, Span, Spanned},
+    statement::{Statement, VariableDeclaration},
+};
+
+/// プログラムの最上位に並ぶ要素
+#[derive(Debug, PartialEq, Clone)]
+pub enum ModuleItem {
+    /// 文と宣言
+    Statement(Statement),
+
+    /// import と export (モジュールのみ)
+    ModuleDeclaration(ModuleDeclaration),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ModuleDeclaration {
+    /// import a from "b";
+    Import(ImportDeclaration),
+
+    /// export { a }; export const a = 1;
+    ExportNamed(ExportNamedDeclaration),
+
+    /// export default a;
+    ExportDefault(ExportDefaultDeclaration),
+
+    /// export * from "a";
+    ExportAll(ExportAllDeclaration),
+}
+
+/// import a, { b as c } from "d" with { type: "json" };
+#[derive(Debug, PartialEq, Clone)]
+pub struct ImportDeclaration {
+    pub span: Span,
+    pub specifiers: Vec<ImportDeclarationSpecifier>,
+    pub source: Literal,
+    pub attributes: Vec<ImportAttribute>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ImportDeclarationSpecifier {
+    /// { a as b }
+    Named(ImportSpecifier),
+
+    /// a
+    Default(ImportDefaultSpecifier),
+
+    /// * as a
+    Namespace(ImportNamespaceSpecifier),
+}
+
+/// { a as b }
+#[derive(Debug, PartialEq, Clone)]
+pub struct ImportSpecifier {
+    pub span: Span,
+    pub imported: ModuleExportName,
+    pub local: Identifier,
+}
+
+/// import a from "b";
+#[derive(Debug, PartialEq, Clone)]
+pub struct ImportDefaultSpecifier {
+    pub span: Span,
+    pub local: Identifier,
+}
+
+/// import * as a from "b";
+#[derive(Debug, PartialEq, Clone)]
+pub struct ImportNamespaceSpecifier {
+    pub span: Span,
+    pub local: Identifier,
+}
+
+/// with { type: "json" } の要素
+#[derive(Debug, PartialEq, Clone)]
+pub struct ImportAttribute {
+    pub span: Span,
+
+    /// 識別子か文字列リテラル
+    pub key: PropertyKey,
+    pub value: Literal,
+}
+
+/// モジュールが公開する名前
+#[derive(Debug, PartialEq, Clone)]
+pub enum ModuleExportName {
+    /// a
+    Identifier(Identifier),
+
+    /// "a"
+    Literal(Literal),
+}
+
+/// export { a as b } from "c";
+#[derive(Debug, PartialEq, Clone)]
+pub struct ExportNamedDeclaration {
+    pub span: Span,
+    pub declaration: Option<Declaration>,
+    pub specifiers: Vec<ExportSpecifier>,
+    pub source: Option<Literal>,
+    pub attributes: Vec<ImportAttribute>,
+}
+
+/// export の対象の宣言
+#[derive(Debug, PartialEq, Clone)]
+pub enum Declaration {
+    /// export function a() {}
+    Function(Box<Function>),
+
+    /// export const a = 1;
+    Variable(VariableDeclaration),
+
+    /// export class A {}
+    Class(Box<Class>),
+}
+
+/// { a as b }
+#[derive(Debug, PartialEq, Clone)]
+pub struct ExportSpecifier {
+    pub span: Span,
+    pub local: ModuleExportName,
+    pub exported: ModuleExportName,
+}
+
+/// export default a;
+#[derive(Debug, PartialEq, Clone)]
+pub struct ExportDefaultDeclaration {
+    pub span: Span,
+    pub declaration: ExportDefaultKind,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ExportDefaultKind {
+    /// export default function () {}
+    Function(Box<Function>),
+
+    /// export default class {}
+    Class(Box<Class>),
+
+    /// export default a;
+    Expression(Expression),
+}
+
+/// export * as a from "b";
+#[derive(Debug, PartialEq, Clone)]
+pub struct ExportAllDeclaration {
+    pub span: Span,
+    pub exported: Option<ModuleExportName>,
+    pub source: Literal,
+    pub attributes: Vec<ImportAttribute>,
+}
+
+impl ModuleExportName {
+    /// 公開名の文字列を返す。
+    pub fn name(&self) -> &str {
+        match self {
+            ModuleExportName::Identifier(identifier) => &identifier.name,
+            ModuleExportName::Literal(literal) => literal.as_str().unwrap_or(&literal.raw),
+        }
+    }
+}
+
+impl Declaration {
+    /// 宣言を文に変換する。
+    pub fn into_statement(self) -> Statement {
+        match self {
+            Declaration::Function(function) => Statement::FunctionDeclaration(function),
+            Declaration::Variable(variable) => Statement::VariableDeclaration(variable),
+            Declaration::Class(class) => Statement::ClassDeclaration(class),
+        }
+    }
+}
+
+impl_spanned!(
+    ImportDeclaration,
+    ImportSpecifier,
+    ImportDefaultSpecifier,
+    ImportNamespaceSpecifier,
+    ImportAttribute,
+    ExportNamedDeclaration,
+    ExportSpecifier,
+    ExportDefaultDeclaration,
+    ExportAllDeclaration,
+);
+
+impl_spanned_enum!(ModuleItem {
+    Statement,
+    ModuleDeclaration,
+});
+impl_spanned_enum!(ModuleDeclaration {
+    Import,
+    ExportNamed,
+    ExportDefault,
+    ExportAll,
+});
+impl_spanned_enum!(ImportDeclarationSpecifier {
+    Named,
+    Default,
+    Namespace,
+});
+impl_spanned_enum!(ModuleExportName {
+    Identifier,
+    Literal,
+});
+impl_spanned_enum!(Declaration {
+    Function,
+    Variable,
+    Class,
+});
+impl_spanned_enum!(ExportDefaultKind {
+    Function,
+    Class,
+    Expression,
+});
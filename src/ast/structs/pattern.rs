@@ -0,0 +1,136 @@
+use super::{
+    expression::{Expression, PropertyKey},
+    literal::Identifier,
+    span::{impl_spanned, impl_spanned_enum, Span, Spanned},
+};
+
+/// 束縛と代入の対象
+#[derive(Debug, PartialEq, Clone)]
+pub enum Pattern {
+    /// a
+    Identifier(Identifier),
+
+    /// { a, b: c }
+    Object(ObjectPattern),
+
+    /// [a, b]
+    Array(ArrayPattern),
+
+    /// ...a
+    Rest(Box<RestElement>),
+
+    /// a = 1
+    Assignment(Box<AssignmentPattern>),
+
+    /// a.b (代入の対象のみ)
+    Expression(Box<Expression>),
+}
+
+/// { a, b: c, ...d }
+#[derive(Debug, PartialEq, Clone)]
+pub struct ObjectPattern {
+    pub span: Span,
+    pub properties: Vec<ObjectPatternProperty>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ObjectPatternProperty {
+    /// b: c
+    Property(AssignmentProperty),
+
+    /// ...d
+    Rest(RestElement),
+}
+
+/// オブジェクトパターンのプロパティ
+#[derive(Debug, PartialEq, Clone)]
+pub struct AssignmentProperty {
+    pub span: Span,
+    pub key: PropertyKey,
+    pub value: Pattern,
+
+    /// { a } と { a = 1 }
+    pub shorthand: bool,
+}
+
+/// [a, , ...b]
+#[derive(Debug, PartialEq, Clone)]
+pub struct ArrayPattern {
+    pub span: Span,
+
+    /// 省略された要素は `None`
+    pub elements: Vec<Option<Pattern>>,
+}
+
+/// ...a
+#[derive(Debug, PartialEq, Clone)]
+pub struct RestElement {
+    pub span: Span,
+    pub argument: Pattern,
+}
+
+/// a = 1
+#[derive(Debug, PartialEq, Clone)]
+pub struct AssignmentPattern {
+    pub span: Span,
+    pub left: Pattern,
+    pub right: Expression,
+}
+
+impl Pattern {
+    /// パターンが束縛する識別子を出現順に集める。
+    pub fn bound_names(&self) -> Vec<&Identifier> {
+        let mut names = Vec::new();
+        self.collect_bound_names(&mut names);
+        names
+    }
+
+    fn collect_bound_names<'a>(&'a self, names: &mut Vec<&'a Identifier>) {
+        match self {
+            Pattern::Identifier(identifier) => names.push(identifier),
+            Pattern::Object(object) => {
+                for property in &object.properties {
+                    match property {
+                        ObjectPatternProperty::Property(property) => {
+                            property.value.collect_bound_names(names)
+                        }
+                        ObjectPatternProperty::Rest(rest) => {
+                            rest.argument.collect_bound_names(names)
+                        }
+                    }
+                }
+            }
+            Pattern::Array(array) => {
+                for element in array.elements.iter().flatten() {
+                    element.collect_bound_names(names);
+                }
+            }
+            Pattern::Rest(rest) => rest.argument.collect_bound_names(names),
+            Pattern::Assignment(assignment) => assignment.left.collect_bound_names(names),
+            Pattern::Expression(_) => {}
+        }
+    }
+
+    /// 分割代入や既定値を含まない単純な識別子であるかを返す。
+    pub fn is_simple(&self) -> bool {
+        matches!(self, Pattern::Identifier(_))
+    }
+}
+
+impl_spanned!(
+    ObjectPattern,
+    AssignmentProperty,
+    ArrayPattern,
+    RestElement,
+    AssignmentPattern
+);
+
+impl_spanned_enum!(Pattern {
+    Identifier,
+    Object,
+    Array,
+    Rest,
+    Assignment,
+    Expression,
+});
+impl_spanned_enum!(ObjectPatternProperty { Property, Rest });
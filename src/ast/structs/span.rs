@@ -0,0 +1,96 @@
+/// ソースコード上のバイト位置の範囲
+///
+/// `start` は含み、`end` は含まない。
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Span {
+        Span { start, end }
+    }
+
+    /// 両方の範囲を含む最小の範囲を返す。
+    pub fn merge(self, other: Span) -> Span {
+        Span {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// 位置が範囲の中にあるかを返す。
+    pub fn contains(&self, position: usize) -> bool {
+        self.start <= position && position < self.end
+    }
+
+    /// 範囲のソースコードを切り出す。
+    pub fn source_text<'a>(&self, source: &'a str) -> &'a str {
+        &source[self.start..self.end]
+    }
+}
+
+/// ソースコード上の範囲を持つもの
+pub trait Spanned {
+    fn span(&self) -> Span;
+}
+
+impl<T: Spanned> Spanned for Box<T> {
+    fn span(&self) -> Span {
+        self.as_ref().span()
+    }
+}
+
+/// 構造体の `span` フィールドを返す [`Spanned`] を実装する。
+macro_rules! impl_spanned {
+    ($($name:ty),* $(,)?) => {
+        $(
+            impl $crate::ast::structs::span::Spanned for $name {
+                fn span(&self) -> $crate::ast::structs::span::Span {
+                    self.span
+                }
+            }
+        )*
+    };
+}
+
+/// 全てのバリアントが [`Spanned`] を持つ列挙型に [`Spanned`] を実装する。
+macro_rules! impl_spanned_enum {
+    ($name:ident { $($variant:ident),* $(,)? }) => {
+        impl $crate::ast::structs::span::Spanned for $name {
+            fn span(&self) -> $crate::ast::structs::span::Span {
+                match self {
+                    $($name::$variant(node) => node.span(),)*
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use impl_spanned;
+pub(crate) use impl_spanned_enum;
+
+#[cfg(test)]
+mod test {
+    use super::Span;
+
+    #[test]
+    fn merge_spans() {
+        assert_eq!(Span::new(3, 5).merge(Span::new(8, 10)), Span::new(3, 10));
+        assert_eq!(Span::new(8, 10).merge(Span::new(3, 5)), Span::new(3, 10));
+    }
+
+    #[test]
+    fn source_text() {
+        assert_eq!(Span::new(4, 7).source_text("let abc = 1;"), "abc");
+    }
+}
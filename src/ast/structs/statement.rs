@@ -0,0 +1,364 @@
+use super::{
+    class::Class,
+    expression::Expression,
+    function::Function,
+    literal::Identifier,
+    pattern::Pattern,
+    span::{impl_spanned, impl_spanned_enum, Span, Spanned},
+    ErrorNode,
+};
+
+/// 文と宣言
+#[derive(Debug, PartialEq, Clone)]
+pub enum Statement {
+    /// a;
+    Expression(ExpressionStatement),
+
+    /// { a }
+    Block(BlockStatement),
+
+    /// ;
+    Empty(EmptyStatement),
+
+    /// debugger;
+    Debugger(DebuggerStatement),
+
+    /// with (a) b
+    With(Box<WithStatement>),
+
+    /// return a;
+    Return(ReturnStatement),
+
+    /// a: b
+    Labeled(Box<LabeledStatement>),
+
+    /// break a;
+    Break(BreakStatement),
+
+    /// continue a;
+    Continue(ContinueStatement),
+
+    /// if (a) b else c
+    If(Box<IfStatement>),
+
+    /// switch (a) { case b: c }
+    Switch(SwitchStatement),
+
+    /// throw a;
+    Throw(ThrowStatement),
+
+    /// try {} catch (a) {} finally {}
+    Try(Box<TryStatement>),
+
+    /// while (a) b
+    While(Box<WhileStatement>),
+
+    /// do a while (b)
+    DoWhile(Box<DoWhileStatement>),
+
+    /// for (a; b; c) d
+    For(Box<ForStatement>),
+
+    /// for (a in b) c
+    ForIn(Box<ForInStatement>),
+
+    /// for (a of b) c
+    ForOf(Box<ForOfStatement>),
+
+    /// function a() {}
+    FunctionDeclaration(Box<Function>),
+
+    /// var a = 1;
+    VariableDeclaration(VariableDeclaration),
+
+    /// class A {}
+    ClassDeclaration(Box<Class>),
+
+    /// 構文エラーで読めなかった文
+    Error(ErrorNode),
+}
+
+/// a;
+#[derive(Debug, PartialEq, Clone)]
+pub struct ExpressionStatement {
+    pub span: Span,
+    pub expression: Expression,
+
+    /// ディレクティブの場合は引用符を除いたソースコード上の表記
+    pub directive: Option<String>,
+}
+
+/// { a }
+#[derive(Debug, PartialEq, Clone)]
+pub struct BlockStatement {
+    pub span: Span,
+    pub body: Vec<Statement>,
+}
+
+/// ;
+#[derive(Debug, PartialEq, Clone)]
+pub struct EmptyStatement {
+    pub span: Span,
+}
+
+/// debugger;
+#[derive(Debug, PartialEq, Clone)]
+pub struct DebuggerStatement {
+    pub span: Span,
+}
+
+/// with (a) b
+#[derive(Debug, PartialEq, Clone)]
+pub struct WithStatement {
+    pub span: Span,
+    pub object: Expression,
+    pub body: Statement,
+}
+
+/// return a;
+#[derive(Debug, PartialEq, Clone)]
+pub struct ReturnStatement {
+    pub span: Span,
+    pub argument: Option<Expression>,
+}
+
+/// a: b
+#[derive(Debug, PartialEq, Clone)]
+pub struct LabeledStatement {
+    pub span: Span,
+    pub label: Identifier,
+    pub body: Statement,
+}
+
+/// break a;
+#[derive(Debug, PartialEq, Clone)]
+pub struct BreakStatement {
+    pub span: Span,
+    pub label: Option<Identifier>,
+}
+
+/// continue a;
+#[derive(Debug, PartialEq, Clone)]
+pub struct ContinueStatement {
+    pub span: Span,
+    pub label: Option<Identifier>,
+}
+
+/// if (a) b else c
+#[derive(Debug, PartialEq, Clone)]
+pub struct IfStatement {
+    pub span: Span,
+    pub test: Expression,
+    pub consequent: Statement,
+    pub alternate: Option<Statement>,
+}
+
+/// switch (a) { case b: c }
+#[derive(Debug, PartialEq, Clone)]
+pub struct SwitchStatement {
+    pub span: Span,
+    pub discriminant: Expression,
+    pub cases: Vec<SwitchCase>,
+}
+
+/// case a: b
+#[derive(Debug, PartialEq, Clone)]
+pub struct SwitchCase {
+    pub span: Span,
+
+    /// default の場合は `None`
+    pub test: Option<Expression>,
+    pub consequent: Vec<Statement>,
+}
+
+/// throw a;
+#[derive(Debug, PartialEq, Clone)]
+pub struct ThrowStatement {
+    pub span: Span,
+    pub argument: Expression,
+}
+
+/// try {} catch (a) {} finally {}
+#[derive(Debug, PartialEq, Clone)]
+pub struct TryStatement {
+    pub span: Span,
+    pub block: BlockStatement,
+    pub handler: Option<CatchClause>,
+    pub finalizer: Option<BlockStatement>,
+}
+
+/// catch (a) {}
+#[derive(Debug, PartialEq, Clone)]
+pub struct CatchClause {
+    pub span: Span,
+
+    /// `catch {}` の場合は `None`
+    pub param: Option<Pattern>,
+    pub body: BlockStatement,
+}
+
+/// while (a) b
+#[derive(Debug, PartialEq, Clone)]
+pub struct WhileStatement {
+    pub span: Span,
+    pub test: Expression,
+    pub body: Statement,
+}
+
+/// do a while (b)
+#[derive(Debug, PartialEq, Clone)]
+pub struct DoWhileStatement {
+    pub span: Span,
+    pub body: Statement,
+    pub test: Expression,
+}
+
+/// for (a; b; c) d
+#[derive(Debug, PartialEq, Clone)]
+pub struct ForStatement {
+    pub span: Span,
+    pub init: Option<ForInit>,
+    pub test: Option<Expression>,
+    pub update: Option<Expression>,
+    pub body: Statement,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ForInit {
+    /// for (let a = 1; ; )
+    VariableDeclaration(VariableDeclaration),
+
+    /// for (a = 1; ; )
+    Expression(Expression),
+}
+
+/// for (a in b) c
+#[derive(Debug, PartialEq, Clone)]
+pub struct ForInStatement {
+    pub span: Span,
+    pub left: ForInOfLeft,
+    pub right: Expression,
+    pub body: Statement,
+}
+
+/// for (a of b) c
+#[derive(Debug, PartialEq, Clone)]
+pub struct ForOfStatement {
+    pub span: Span,
+    pub left: ForInOfLeft,
+    pub right: Expression,
+    pub body: Statement,
+
+    /// for await (a of b) c
+    pub is_await: bool,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ForInOfLeft {
+    /// for (let a of b)
+    VariableDeclaration(VariableDeclaration),
+
+    /// for (a.b of c)
+    Pattern(Pattern),
+}
+
+/// var a = 1, b;
+#[derive(Debug, PartialEq, Clone)]
+pub struct VariableDeclaration {
+    pub span: Span,
+    pub kind: VariableKind,
+    pub declarations: Vec<VariableDeclarator>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum VariableKind {
+    /// var
+    Var,
+
+    /// let
+    Let,
+
+    /// const
+    Const,
+}
+
+/// a = 1
+#[derive(Debug, PartialEq, Clone)]
+pub struct VariableDeclarator {
+    pub span: Span,
+    pub id: Pattern,
+    pub init: Option<Expression>,
+}
+
+impl VariableKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VariableKind::Var => "var",
+            VariableKind::Let => "let",
+            VariableKind::Const => "const",
+        }
+    }
+
+    /// ブロックスコープの宣言であるかを返す。
+    pub fn is_lexical(&self) -> bool {
+        !matches!(self, VariableKind::Var)
+    }
+}
+
+impl_spanned!(
+    ExpressionStatement,
+    BlockStatement,
+    EmptyStatement,
+    DebuggerStatement,
+    WithStatement,
+    ReturnStatement,
+    LabeledStatement,
+    BreakStatement,
+    ContinueStatement,
+    IfStatement,
+    SwitchStatement,
+    SwitchCase,
+    ThrowStatement,
+    TryStatement,
+    CatchClause,
+    WhileStatement,
+    DoWhileStatement,
+    ForStatement,
+    ForInStatement,
+    ForOfStatement,
+    VariableDeclaration,
+    VariableDeclarator,
+);
+
+impl_spanned_enum!(Statement {
+    Expression,
+    Block,
+    Empty,
+    Debugger,
+    With,
+    Return,
+    Labeled,
+    Break,
+    Continue,
+    If,
+    Switch,
+    Throw,
+    Try,
+    While,
+    DoWhile,
+    For,
+    ForIn,
+    ForOf,
+    FunctionDeclaration,
+    VariableDeclaration,
+    ClassDeclaration,
+    Error,
+});
+impl_spanned_enum!(ForInit {
+    VariableDeclaration,
+    Expression,
+});
+impl_spanned_enum!(ForInOfLeft {
+    VariableDeclaration,
+    Pattern,
+});
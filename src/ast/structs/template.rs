@@ -0,0 +1,39 @@
+use super::{
+    expression::Expression,
+    span::{impl_spanned, Span},
+};
+
+/// `a${b}c`
+#[derive(Debug, PartialEq, Clone)]
+pub struct TemplateLiteral {
+    pub span: Span,
+
+    /// 文字列部分 (常に `expressions` より1つ多い)
+    pub quasis: Vec<TemplateElement>,
+    pub expressions: Vec<Expression>,
+}
+
+/// テンプレートの文字列部分
+#[derive(Debug, PartialEq, Clone)]
+pub struct TemplateElement {
+    pub span: Span,
+
+    /// ソースコード上の表記 (改行は <LF> に正規化する)
+    pub raw: String,
+
+    /// エスケープを解釈した値
+    ///
+    /// タグ付きテンプレートで不正なエスケープがある場合は `None` になる。
+    pub cooked: Option<String>,
+    pub tail: bool,
+}
+
+/// tag`a${b}c`
+#[derive(Debug, PartialEq, Clone)]
+pub struct TaggedTemplateExpression {
+    pub span: Span,
+    pub tag: Expression,
+    pub quasi: TemplateLiteral,
+}
+
+impl_spanned!(TemplateLiteral, TemplateElement, TaggedTemplateExpression);
@@ -0,0 +1,718 @@
+//! 構文木をたどる Visitor と、構文木を作り変える Fold を定義するモジュールです。
+//!
+//! - [`Visit`] はノードを参照でたどる。
+//! - [`VisitMut`] はノードを可変参照でたどり、その場で書き換える。
+//! - [`Fold`] はノードを受け取って新しいノードを返す。リストの中の文などは
+//!   [`Fold::flat_fold_statement`] で複数のノードに置き換えることができる。
+//!
+//! どのトレイトもノードの型ごとのメソッドを持ち、既定では子ノードをたどる。
+//! メソッドを上書きした場合は `visit_children_with` などで子ノードをたどる。
+//!
+//! メソッドと子ノードのたどり方は、`define_visitors!` の一覧から生成する。
+//! 構造体は全てのフィールドを分解するため、ノードにフィールドを追加して
+//! 一覧を直し忘れるとコンパイルエラーになる。
+
+use super::structs::{
+    class::{Class, ClassBody, ClassElement, MethodDefinition, PropertyDefinition, StaticBlock},
+    expression::{
+        ArrayExpression, AssignmentExpression, AwaitExpression, BinaryExpression, CallExpression,
+        ChainExpression, ConditionalExpression, Expression, ExpressionOrSpread, ImportExpression,
+        LogicalExpression, MemberExpression, MemberProperty, MetaProperty, NewExpression,
+        ObjectExpression, ObjectProperty, Property, PropertyKey, SequenceExpression, SpreadElement,
+        Super, ThisExpression, UnaryExpression, UpdateExpression, YieldExpression,
+    },
+    function::{ArrowFunctionBody, ArrowFunctionExpression, Function, FunctionBody},
+    literal::{Identifier, Literal, PrivateIdentifier},
+    module::{
+        Declaration, ExportAllDeclaration, ExportDefaultDeclaration, ExportDefaultKind,
+        ExportNamedDeclaration, ExportSpecifier, ImportAttribute, ImportDeclaration,
+        ImportDeclarationSpecifier, ImportDefaultSpecifier, ImportNamespaceSpecifier,
+        ImportSpecifier, ModuleDeclaration, ModuleExportName, ModuleItem,
+    },
+    pattern::{
+        ArrayPattern, AssignmentPattern, AssignmentProperty, ObjectPattern, ObjectPatternProperty,
+        Pattern, RestElement,
+    },
+    statement::{
+        BlockStatement, BreakStatement, CatchClause, ContinueStatement, DebuggerStatement,
+        DoWhileStatement, EmptyStatement, ExpressionStatement, ForInOfLeft, ForInStatement,
+        ForInit, ForOfStatement, ForStatement, IfStatement, LabeledStatement, ReturnStatement,
+        Statement, SwitchCase, SwitchStatement, ThrowStatement, TryStatement, VariableDeclaration,
+        VariableDeclarator, WhileStatement, WithStatement,
+    },
+    template::{TaggedTemplateExpression, TemplateElement, TemplateLiteral},
+    ErrorNode, JsAst,
+};
+
+/// 参照でたどることができるノード
+pub trait VisitWith<'ast> {
+    /// このノードに対応する [`Visit`] のメソッドを呼ぶ。
+    fn visit_with<V: Visit<'ast> + ?Sized>(&'ast self, visitor: &mut V);
+
+    /// 子ノードをたどる。
+    fn visit_children_with<V: Visit<'ast> + ?Sized>(&'ast self, visitor: &mut V);
+}
+
+/// 可変参照でたどることができるノード
+pub trait VisitMutWith {
+    /// このノードに対応する [`VisitMut`] のメソッドを呼ぶ。
+    fn visit_mut_with<V: VisitMut + ?Sized>(&mut self, visitor: &mut V);
+
+    /// 子ノードをたどる。
+    fn visit_mut_children_with<V: VisitMut + ?Sized>(&mut self, visitor: &mut V);
+}
+
+/// 作り変えることができるノード
+pub trait FoldWith: Sized {
+    /// このノードに対応する [`Fold`] のメソッドを呼ぶ。
+    fn fold_with<F: Fold + ?Sized>(self, folder: &mut F) -> Self;
+
+    /// 子ノードを作り変える。
+    fn fold_children_with<F: Fold + ?Sized>(self, folder: &mut F) -> Self;
+
+    /// リストの要素として作り変える。既定では1つのノードになる。
+    fn flat_fold_with<F: Fold + ?Sized>(self, folder: &mut F) -> Vec<Self> {
+        vec![self.fold_with(folder)]
+    }
+}
+
+impl<'ast, T: VisitWith<'ast>> VisitWith<'ast> for Box<T> {
+    fn visit_with<V: Visit<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+        self.as_ref().visit_with(visitor);
+    }
+
+    fn visit_children_with<V: Visit<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+        self.as_ref().visit_children_with(visitor);
+    }
+}
+
+impl<'ast, T: VisitWith<'ast>> VisitWith<'ast> for Option<T> {
+    fn visit_with<V: Visit<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+        if let Some(node) = self {
+            node.visit_with(visitor);
+        }
+    }
+
+    fn visit_children_with<V: Visit<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+        if let Some(node) = self {
+            node.visit_children_with(visitor);
+        }
+    }
+}
+
+impl<'ast, T: VisitWith<'ast>> VisitWith<'ast> for Vec<T> {
+    fn visit_with<V: Visit<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+        for node in self {
+            node.visit_with(visitor);
+        }
+    }
+
+    fn visit_children_with<V: Visit<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+        for node in self {
+            node.visit_children_with(visitor);
+        }
+    }
+}
+
+impl<T: VisitMutWith> VisitMutWith for Box<T> {
+    fn visit_mut_with<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+        self.as_mut().visit_mut_with(visitor);
+    }
+
+    fn visit_mut_children_with<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+        self.as_mut().visit_mut_children_with(visitor);
+    }
+}
+
+impl<T: VisitMutWith> VisitMutWith for Option<T> {
+    fn visit_mut_with<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+        if let Some(node) = self {
+            node.visit_mut_with(visitor);
+        }
+    }
+
+    fn visit_mut_children_with<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+        if let Some(node) = self {
+            node.visit_mut_children_with(visitor);
+        }
+    }
+}
+
+impl<T: VisitMutWith> VisitMutWith for Vec<T> {
+    fn visit_mut_with<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+        for node in self {
+            node.visit_mut_with(visitor);
+        }
+    }
+
+    fn visit_mut_children_with<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+        for node in self {
+            node.visit_mut_children_with(visitor);
+        }
+    }
+}
+
+impl<T: FoldWith> FoldWith for Box<T> {
+    fn fold_with<F: Fold + ?Sized>(self, folder: &mut F) -> Self {
+        Box::new((*self).fold_with(folder))
+    }
+
+    fn fold_children_with<F: Fold + ?Sized>(self, folder: &mut F) -> Self {
+        Box::new((*self).fold_children_with(folder))
+    }
+}
+
+impl<T: FoldWith> FoldWith for Option<T> {
+    fn fold_with<F: Fold + ?Sized>(self, folder: &mut F) -> Self {
+        self.map(|node| node.fold_with(folder))
+    }
+
+    fn fold_children_with<F: Fold + ?Sized>(self, folder: &mut F) -> Self {
+        self.map(|node| node.fold_children_with(folder))
+    }
+}
+
+/// リストの要素は [`FoldWith::flat_fold_with`] で作り変え、0個以上のノードに置き換える。
+impl<T: FoldWith> FoldWith for Vec<T> {
+    fn fold_with<F: Fold + ?Sized>(self, folder: &mut F) -> Self {
+        self.into_iter()
+            .flat_map(|node| node.flat_fold_with(folder))
+            .collect()
+    }
+
+    fn fold_children_with<F: Fold + ?Sized>(self, folder: &mut F) -> Self {
+        self.into_iter()
+            .map(|node| node.fold_children_with(folder))
+            .collect()
+    }
+}
+
+/// トップレベルの文を [`Fold::flat_fold_statement`] で作り変える。
+///
+/// 文のリストと同じように、トップレベルの文も複数の文に置き換えられる。
+fn flat_fold_module_item_statement<F: Fold + ?Sized>(
+    folder: &mut F,
+    node: ModuleItem,
+) -> Vec<ModuleItem> {
+    match node {
+        ModuleItem::Statement(statement) => folder
+            .flat_fold_statement(statement)
+            .into_iter()
+            .map(ModuleItem::Statement)
+            .collect(),
+        node => vec![folder.fold_module_item(node)],
+    }
+}
+
+/// ノードの一覧から [`Visit`] [`VisitMut`] [`Fold`] と、各ノードのたどり方を生成する。
+///
+/// 構造体は子ノードのフィールドと、たどらないフィールド (`skip`) を全て並べる。
+/// 列挙型は全てのバリアントを並べる。バリアントはどれも1つのノードを持つ。
+/// 列挙型に3つ目のメソッド名を書くと、リストの中で複数のノードに置き換えられる。
+/// その既定の実装は `= 関数` で変えられる。
+macro_rules! define_visitors {
+    (@flat_fold $folder:expr, $node:expr, $fold:ident) => {
+        vec![$folder.$fold($node)]
+    };
+    (@flat_fold $folder:expr, $node:expr, $fold:ident, $flat_default:path) => {
+        $flat_default($folder, $node)
+    };
+    (
+        structs {
+            $(
+                $struct:ident [$struct_visit:ident, $struct_fold:ident]
+                    { $($field:ident),* } skip { $($skip:ident),* }
+            )*
+        }
+        enums {
+            $(
+                $enum:ident [$enum_visit:ident, $enum_fold:ident
+                    $(, $enum_flat_fold:ident $(= $flat_default:path)?)?]
+                    { $($variant:ident),* }
+            )*
+        }
+    ) => {
+        /// 構文木を参照でたどる。
+        ///
+        /// 既定のメソッドは子ノードをたどる。
+        pub trait Visit<'ast> {
+            $(
+                fn $struct_visit(&mut self, node: &'ast $struct) {
+                    node.visit_children_with(self);
+                }
+            )*
+            $(
+                fn $enum_visit(&mut self, node: &'ast $enum) {
+                    node.visit_children_with(self);
+                }
+            )*
+        }
+
+        /// 構文木を可変参照でたどる。
+        ///
+        /// 既定のメソッドは子ノードをたどる。
+        pub trait VisitMut {
+            $(
+                fn $struct_visit(&mut self, node: &mut $struct) {
+                    node.visit_mut_children_with(self);
+                }
+            )*
+            $(
+                fn $enum_visit(&mut self, node: &mut $enum) {
+                    node.visit_mut_children_with(self);
+                }
+            )*
+        }
+
+        /// 構文木を作り変える。
+        ///
+        /// 既定のメソッドは子ノードを作り変える。
+        pub trait Fold {
+            $(
+                fn $struct_fold(&mut self, node: $struct) -> $struct {
+                    node.fold_children_with(self)
+                }
+            )*
+            $(
+                fn $enum_fold(&mut self, node: $enum) -> $enum {
+                    node.fold_children_with(self)
+                }
+
+                $(
+                    /// リストの中のノードを0個以上のノードに置き換える。
+                    fn $enum_flat_fold(&mut self, node: $enum) -> Vec<$enum> {
+                        define_visitors!(@flat_fold self, node, $enum_fold $(, $flat_default)?)
+                    }
+                )?
+            )*
+        }
+
+        $(
+            impl<'ast> VisitWith<'ast> for $struct {
+                fn visit_with<V: Visit<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+                    visitor.$struct_visit(self);
+                }
+
+                #[allow(unused_variables)]
+                fn visit_children_with<V: Visit<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+                    let $struct { $($field,)* $($skip: _,)* } = self;
+                    $($field.visit_with(visitor);)*
+                }
+            }
+
+            impl VisitMutWith for $struct {
+                fn visit_mut_with<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+                    visitor.$struct_visit(self);
+                }
+
+                #[allow(unused_variables)]
+                fn visit_mut_children_with<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+                    let $struct { $($field,)* $($skip: _,)* } = self;
+                    $($field.visit_mut_with(visitor);)*
+                }
+            }
+
+            impl FoldWith for $struct {
+                fn fold_with<F: Fold + ?Sized>(self, folder: &mut F) -> Self {
+                    folder.$struct_fold(self)
+                }
+
+                #[allow(unused_variables)]
+                fn fold_children_with<F: Fold + ?Sized>(self, folder: &mut F) -> Self {
+                    let $struct { $($field,)* $($skip,)* } = self;
+                    $struct {
+                        $($field: $field.fold_with(folder),)*
+                        $($skip,)*
+                    }
+                }
+            }
+        )*
+
+        $(
+            impl<'ast> VisitWith<'ast> for $enum {
+                fn visit_with<V: Visit<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+                    visitor.$enum_visit(self);
+                }
+
+                fn visit_children_with<V: Visit<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+                    match self {
+                        $($enum::$variant(node) => node.visit_with(visitor),)*
+                    }
+                }
+            }
+
+            impl VisitMutWith for $enum {
+                fn visit_mut_with<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+                    visitor.$enum_visit(self);
+                }
+
+                fn visit_mut_children_with<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+                    match self {
+                        $($enum::$variant(node) => node.visit_mut_with(visitor),)*
+                    }
+                }
+            }
+
+            impl FoldWith for $enum {
+                fn fold_with<F: Fold + ?Sized>(self, folder: &mut F) -> Self {
+                    folder.$enum_fold(self)
+                }
+
+                fn fold_children_with<F: Fold + ?Sized>(self, folder: &mut F) -> Self {
+                    match self {
+                        $($enum::$variant(node) => $enum::$variant(node.fold_with(folder)),)*
+                    }
+                }
+
+                $(
+                    fn flat_fold_with<F: Fold + ?Sized>(self, folder: &mut F) -> Vec<Self> {
+                        folder.$enum_flat_fold(self)
+                    }
+                )?
+            }
+        )*
+    };
+}
+
+define_visitors! {
+    structs {
+        JsAst [visit_js_ast, fold_js_ast] { body } skip { span, source_type }
+        ErrorNode [visit_error_node, fold_error_node] {} skip { span }
+
+        // 文
+        ExpressionStatement [visit_expression_statement, fold_expression_statement]
+            { expression } skip { span, directive }
+        BlockStatement [visit_block_statement, fold_block_statement] { body } skip { span }
+        EmptyStatement [visit_empty_statement, fold_empty_statement] {} skip { span }
+        DebuggerStatement [visit_debugger_statement, fold_debugger_statement] {} skip { span }
+        WithStatement [visit_with_statement, fold_with_statement]
+            { object, body } skip { span }
+        ReturnStatement [visit_return_statement, fold_return_statement]
+            { argument } skip { span }
+        LabeledStatement [visit_labeled_statement, fold_labeled_statement]
+            { label, body } skip { span }
+        BreakStatement [visit_break_statement, fold_break_statement] { label } skip { span }
+        ContinueStatement [visit_continue_statement, fold_continue_statement]
+            { label } skip { span }
+        IfStatement [visit_if_statement, fold_if_statement]
+            { test, consequent, alternate } skip { span }
+        SwitchStatement [visit_switch_statement, fold_switch_statement]
+            { discriminant, cases } skip { span }
+        SwitchCase [visit_switch_case, fold_switch_case] { test, consequent } skip { span }
+        ThrowStatement [visit_throw_statement, fold_throw_statement] { argument } skip { span }
+        TryStatement [visit_try_statement, fold_try_statement]
+            { block, handler, finalizer } skip { span }
+        CatchClause [visit_catch_clause, fold_catch_clause] { param, body } skip { span }
+        WhileStatement [visit_while_statement, fold_while_statement]
+            { test, body } skip { span }
+        DoWhileStatement [visit_do_while_statement, fold_do_while_statement]
+            { body, test } skip { span }
+        ForStatement [visit_for_statement, fold_for_statement]
+            { init, test, update, body } skip { span }
+        ForInStatement [visit_for_in_statement, fold_for_in_statement]
+            { left, right, body } skip { span }
+        ForOfStatement [visit_for_of_statement, fold_for_of_statement]
+            { left, right, body } skip { span, is_await }
+        VariableDeclaration [visit_variable_declaration, fold_variable_declaration]
+            { declarations } skip { span, kind }
+        VariableDeclarator [visit_variable_declarator, fold_variable_declarator]
+            { id, init } skip { span }
+
+        // 式
+        ThisExpression [visit_this_expression, fold_this_expression] {} skip { span }
+        Super [visit_super, fold_super] {} skip { span }
+        ArrayExpression [visit_array_expression, fold_array_expression]
+            { elements } skip { span }
+        SpreadElement [visit_spread_element, fold_spread_element] { argument } skip { span }
+        ObjectExpression [visit_object_expression, fold_object_expression]
+            { properties } skip { span }
+        Property [visit_property, fold_property]
+            { key, value } skip { span, kind, method, shorthand }
+        MemberExpression [visit_member_expression, fold_member_expression]
+            { object, property } skip { span, optional }
+        CallExpression [visit_call_expression, fold_call_expression]
+            { callee, arguments } skip { span, optional }
+        NewExpression [visit_new_expression, fold_new_expression]
+            { callee, arguments } skip { span }
+        ChainExpression [visit_chain_expression, fold_chain_expression]
+            { expression } skip { span }
+        UnaryExpression [visit_unary_expression, fold_unary_expression]
+            { argument } skip { span, operator }
+        UpdateExpression [visit_update_expression, fold_update_expression]
+            { argument } skip { span, operator, prefix }
+        BinaryExpression [visit_binary_expression, fold_binary_expression]
+            { left, right } skip { span, operator }
+        LogicalExpression [visit_logical_expression, fold_logical_expression]
+            { left, right } skip { span, operator }
+        ConditionalExpression [visit_conditional_expression, fold_conditional_expression]
+            { test, consequent, alternate } skip { span }
+        AssignmentExpression [visit_assignment_expression, fold_assignment_expression]
+            { left, right } skip { span, operator }
+        SequenceExpression [visit_sequence_expression, fold_sequence_expression]
+            { expressions } skip { span }
+        YieldExpression [visit_yield_expression, fold_yield_expression]
+            { argument } skip { span, delegate }
+        AwaitExpression [visit_await_expression, fold_await_expression]
+            { argument } skip { span }
+        MetaProperty [visit_meta_property, fold_meta_property]
+            { meta, property } skip { span }
+        ImportExpression [visit_import_expression, fold_import_expression]
+            { source, options } skip { span }
+
+        // 関数とクラス
+        Function [visit_function, fold_function]
+            { id, params, body } skip { span, is_async, generator, strict }
+        FunctionBody [visit_function_body, fold_function_body] { body } skip { span }
+        ArrowFunctionExpression [visit_arrow_function_expression, fold_arrow_function_expression]
+            { params, body } skip { span, is_async, strict }
+        Class [visit_class, fold_class] { id, super_class, body } skip { span }
+        ClassBody [visit_class_body, fold_class_body] { body } skip { span }
+        MethodDefinition [visit_method_definition, fold_method_definition]
+            { key, value } skip { span, kind, is_static }
+        PropertyDefinition [visit_property_definition, fold_property_definition]
+            { key, value } skip { span, is_static }
+        StaticBlock [visit_static_block, fold_static_block] { body } skip { span }
+
+        // パターン
+        ObjectPattern [visit_object_pattern, fold_object_pattern] { properties } skip { span }
+        AssignmentProperty [visit_assignment_property, fold_assignment_property]
+            { key, value } skip { span, shorthand }
+        ArrayPattern [visit_array_pattern, fold_array_pattern] { elements } skip { span }
+        RestElement [visit_rest_element, fold_rest_element] { argument } skip { span }
+        AssignmentPattern [visit_assignment_pattern, fold_assignment_pattern]
+            { left, right } skip { span }
+
+        // テンプレートとリテラル
+        TemplateLiteral [visit_template_literal, fold_template_literal]
+            { quasis, expressions } skip { span }
+        TemplateElement [visit_template_element, fold_template_element]
+            {} skip { span, raw, cooked, tail }
+        TaggedTemplateExpression [visit_tagged_template_expression, fold_tagged_template_expression]
+            { tag, quasi } skip { span }
+        Identifier [visit_identifier, fold_identifier] {} skip { span, name }
+        PrivateIdentifier [visit_private_identifier, fold_private_identifier]
+            {} skip { span, name }
+        Literal [visit_literal, fold_literal] {} skip { span, value, raw }
+
+        // モジュール
+        ImportDeclaration [visit_import_declaration, fold_import_declaration]
+            { specifiers, source, attributes } skip { span }
+        ImportSpecifier [visit_import_specifier, fold_import_specifier]
+            { imported, local } skip { span }
+        ImportDefaultSpecifier [visit_import_default_specifier, fold_import_default_specifier]
+            { local } skip { span }
+        ImportNamespaceSpecifier [visit_import_namespace_specifier, fold_import_namespace_specifier]
+            { local } skip { span }
+        ImportAttribute [visit_import_attribute, fold_import_attribute]
+            { key, value } skip { span }
+        ExportNamedDeclaration [visit_export_named_declaration, fold_export_named_declaration]
+            { declaration, specifiers, source, attributes } skip { span }
+        ExportSpecifier [visit_export_specifier, fold_export_specifier]
+            { local, exported } skip { span }
+        ExportDefaultDeclaration [visit_export_default_declaration, fold_export_default_declaration]
+            { declaration } skip { span }
+        ExportAllDeclaration [visit_export_all_declaration, fold_export_all_declaration]
+            { exported, source, attributes } skip { span }
+    }
+    enums {
+        Statement [visit_statement, fold_statement, flat_fold_statement] {
+            Expression, Block, Empty, Debugger, With, Return, Labeled, Break, Continue, If,
+            Switch, Throw, Try, While, DoWhile, For, ForIn, ForOf, FunctionDeclaration,
+            VariableDeclaration, ClassDeclaration, Error
+        }
+        ForInit [visit_for_init, fold_for_init] { VariableDeclaration, Expression }
+        ForInOfLeft [visit_for_in_of_left, fold_for_in_of_left] { VariableDeclaration, Pattern }
+        Expression [visit_expression, fold_expression] {
+            Identifier, Literal, This, Super, Array, Object, Function, ArrowFunction, Class,
+            TemplateLiteral, TaggedTemplate, Member, Call, New, Chain, Unary, Update, Binary,
+            Logical, Conditional, Assignment, Sequence, Yield, Await, MetaProperty, Import,
+            PrivateIdentifier, Error
+        }
+        ExpressionOrSpread [visit_expression_or_spread, fold_expression_or_spread]
+            { Expression, Spread }
+        ObjectProperty [visit_object_property, fold_object_property] { Property, Spread }
+        PropertyKey [visit_property_key, fold_property_key]
+            { Identifier, Literal, PrivateIdentifier, Computed }
+        MemberProperty [visit_member_property, fold_member_property]
+            { Identifier, PrivateIdentifier, Computed }
+        ArrowFunctionBody [visit_arrow_function_body, fold_arrow_function_body]
+            { Expression, FunctionBody }
+        ClassElement [visit_class_element, fold_class_element, flat_fold_class_element]
+            { Method, Property, StaticBlock }
+        Pattern [visit_pattern, fold_pattern]
+            { Identifier, Object, Array, Rest, Assignment, Expression }
+        ObjectPatternProperty [visit_object_pattern_property, fold_object_pattern_property]
+            { Property, Rest }
+        ModuleItem [
+            visit_module_item,
+            fold_module_item,
+            flat_fold_module_item = flat_fold_module_item_statement
+        ]
+            { Statement, ModuleDeclaration }
+        ModuleDeclaration [visit_module_declaration, fold_module_declaration]
+            { Import, ExportNamed, ExportDefault, ExportAll }
+        ImportDeclarationSpecifier [visit_import_declaration_specifier, fold_import_declaration_specifier]
+            { Named, Default, Namespace }
+        ModuleExportName [visit_module_export_name, fold_module_export_name]
+            { Identifier, Literal }
+        Declaration [visit_declaration, fold_declaration] { Function, Variable, Class }
+        ExportDefaultKind [visit_export_default_kind, fold_export_default_kind]
+            { Function, Class, Expression }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Fold, FoldWith, Visit, VisitMut, VisitMutWith, VisitWith};
+    use crate::ast::{
+        parser::test_helper::ToSexp,
+        reader_struct::SourceCodeReader,
+        script::parse_script,
+        structs::{
+            expression::Expression,
+            function::FunctionBody,
+            literal::Identifier,
+            statement::{ExpressionStatement, Statement},
+            JsAst,
+        },
+    };
+
+    fn parse(source: &str) -> JsAst {
+        parse_script(SourceCodeReader::new(source))
+            .into_result()
+            .expect("parse error")
+    }
+
+    /// 出現順に識別子の名前を集める。
+    #[derive(Default)]
+    struct CollectIdentifiers<'ast> {
+        names: Vec<&'ast str>,
+    }
+
+    impl<'ast> Visit<'ast> for CollectIdentifiers<'ast> {
+        fn visit_identifier(&mut self, node: &'ast Identifier) {
+            self.names.push(&node.name);
+        }
+    }
+
+    fn identifiers(source: &str) -> Vec<String> {
+        let ast = parse(source);
+        let mut visitor = CollectIdentifiers::default();
+        ast.visit_with(&mut visitor);
+        visitor.names.into_iter().map(str::to_string).collect()
+    }
+
+    /// 関数の中をたどらずに識別子を集める。
+    #[derive(Default)]
+    struct SkipFunctions<'ast>(CollectIdentifiers<'ast>);
+
+    impl<'ast> Visit<'ast> for SkipFunctions<'ast> {
+        fn visit_identifier(&mut self, node: &'ast Identifier) {
+            self.0.visit_identifier(node);
+        }
+
+        fn visit_function_body(&mut self, _: &'ast FunctionBody) {}
+    }
+
+    /// 識別子 `from` を `to` に書き換える。
+    struct Rename {
+        from: &'static str,
+        to: &'static str,
+    }
+
+    impl VisitMut for Rename {
+        fn visit_identifier(&mut self, node: &mut Identifier) {
+            if node.name == self.from {
+                node.name = self.to.to_string();
+            }
+        }
+    }
+
+    /// カンマ区切りの式文を、式ごとの文に分ける。
+    struct SplitSequence;
+
+    impl Fold for SplitSequence {
+        fn flat_fold_statement(&mut self, node: Statement) -> Vec<Statement> {
+            match self.fold_statement(node) {
+                Statement::Expression(ExpressionStatement {
+                    span,
+                    expression: Expression::Sequence(sequence),
+                    directive: None,
+                }) => sequence
+                    .expressions
+                    .into_iter()
+                    .map(|expression| {
+                        Statement::Expression(ExpressionStatement {
+                            span,
+                            expression,
+                            directive: None,
+                        })
+                    })
+                    .collect(),
+                node => vec![node],
+            }
+        }
+    }
+
+    /// `debugger` 文を取り除く。
+    struct RemoveDebugger;
+
+    impl Fold for RemoveDebugger {
+        fn flat_fold_statement(&mut self, node: Statement) -> Vec<Statement> {
+            match node {
+                Statement::Debugger(_) => Vec::new(),
+                node => vec![self.fold_statement(node)],
+            }
+        }
+    }
+
+    #[test]
+    fn visit_identifiers() {
+        assert_eq!(
+            identifiers("let a = b + c.d; function f(g, { h = i }) { return `${j}` }"),
+            vec!["a", "b", "c", "d", "f", "g", "h", "h", "i", "j"]
+        );
+        assert_eq!(
+            identifiers("class A extends B { #c = d; static { e } m() { f } }"),
+            vec!["A", "B", "d", "e", "m", "f"]
+        );
+    }
+
+    #[test]
+    fn visit_override_stops_recursion() {
+        let ast = parse("a; function f(b) { c } d;");
+        let mut visitor = SkipFunctions::default();
+        ast.visit_with(&mut visitor);
+        assert_eq!(visitor.0.names, vec!["a", "f", "b", "d"]);
+    }
+
+    #[test]
+    fn visit_mut_rename() {
+        let mut ast = parse("a = a + b; function f(a) { return a.a }");
+        ast.visit_mut_with(&mut Rename { from: "a", to: "x" });
+        assert_eq!(
+            ast.to_sexp(),
+            "(= x (+ x b)) (function f x (return (. x x)))"
+        );
+    }
+
+    #[test]
+    fn fold_expand_statement() {
+        let ast = parse("a, b; if (c) { d, e, f; } g;").fold_with(&mut SplitSequence);
+        assert_eq!(ast.to_sexp(), "a b (if c (block d e f)) g");
+    }
+
+    #[test]
+    fn fold_remove_statement() {
+        let ast =
+            parse("debugger; a; function f() { debugger; b; }").fold_with(&mut RemoveDebugger);
+        assert_eq!(ast.to_sexp(), "a (function f b)");
+    }
+
+    #[test]
+    fn fold_single_statement_position() {
+        // 単独の文の位置では要素を増やせないので、そのままにする
+        let ast = parse("if (a) b, c;").fold_with(&mut SplitSequence);
+        assert_eq!(ast.to_sexp(), "(if a (, b c))");
+    }
+}
@@ -0,0 +1,149 @@
+//! 開発用ツール dwji のコマンドラインを扱うモジュールです。
+//!
+//! 終了コードは、成功が0、差分や指摘があれば1、引数やファイルの誤りは2にする。
+
+mod diff;
+mod lint;
+mod rewrite;
+mod text_diff;
+
+#[cfg(test)]
+mod test_helper;
+
+use std::{fs, io::Write, path::Path, process::ExitCode};
+
+use crate::{
+    ast::{
+        line_index::LineIndex,
+        reader_struct::SourceCodeReader,
+        script::{parse_with_tokens, ParseOptions},
+        structs::{JsAst, SourceType},
+    },
+    token::structs::token::Token,
+};
+
+const USAGE: &str = "\
+usage: dwji <command> [options]
+
+commands:
+  diff [--module] <old> <new>    show structural changes between two files
+  lint [--module] [--fix | --fix-suggested] [--rule=<id>:<off|warn|error>]... <files...>
+                                 report problems found by the lint rules
+                                 (and apply their fixes)
+  rewrite [--module] [--write] <pattern> <replacement> <files...>
+                                 rewrite code matching a pattern (dry run by default)
+";
+
+/// コマンドの結果
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Status {
+    Success,
+
+    /// 差分や指摘がある
+    Found,
+}
+
+/// コマンドラインの引数 (プログラム名を除く) を実行する。
+pub fn run(args: &[String]) -> ExitCode {
+    let mut stdout = std::io::stdout().lock();
+    match execute(args, &mut stdout) {
+        Ok(Status::Success) => ExitCode::SUCCESS,
+        Ok(Status::Found) => ExitCode::from(1),
+        Err(message) => {
+            eprintln!("dwji: {message}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn execute(args: &[String], output: &mut impl Write) -> Result<Status, String> {
+    let result = match args.first().map(String::as_str) {
+        Some("diff") => diff::run(&args[1..], output),
+        Some("lint") => lint::run(&args[1..], output),
+        Some("rewrite") => rewrite::run(&args[1..], output),
+        Some("-h" | "--help") => write!(output, "{USAGE}")
+            .map(|_| Status::Success)
+            .map_err(|error| error.to_string()),
+        Some(command) => Err(format!("unknown command '{command}'\n\n{USAGE}")),
+        None => Err(format!("no command\n\n{USAGE}")),
+    }?;
+    output.flush().map_err(|error| error.to_string())?;
+    Ok(result)
+}
+
+/// ファイルの読み方を返す。
+///
+/// `.mjs` のファイルと `module` が真の場合はモジュールとして読む。
+fn source_type(path: &str, module: bool) -> SourceType {
+    match module || Path::new(path).extension().is_some_and(|ext| ext == "mjs") {
+        true => SourceType::Module,
+        false => SourceType::Script,
+    }
+}
+
+/// ファイルを読み、構文木と読み進めたトークンにする。
+fn parse_file(path: &str, module: bool) -> Result<(String, JsAst, Vec<Token>), String> {
+    let source = fs::read_to_string(path).map_err(|error| format!("{path}: {error}"))?;
+    let source_type = source_type(path, module);
+
+    let (output, tokens) = parse_with_tokens(
+        SourceCodeReader::new(&source),
+        source_type,
+        ParseOptions::default(),
+    );
+    match output.into_result() {
+        Ok(ast) => Ok((source, ast, tokens)),
+        Err(error) => {
+            let position = LineIndex::new(&source).line_column(error.span.start);
+            Err(format!(
+                "{path}:{}:{}: {}",
+                position.line,
+                position.column + 1,
+                error.message
+            ))
+        }
+    }
+}
+
+/// 引数をオプションとそれ以外に分ける。
+///
+/// `flags` にないオプションはエラーにする。
+fn split_options<'a>(
+    args: &'a [String],
+    flags: &[&str],
+) -> Result<(Vec<&'a str>, Vec<&'a str>), String> {
+    let (options, positional): (Vec<&str>, Vec<&str>) = args
+        .iter()
+        .map(String::as_str)
+        .partition(|arg| arg.starts_with("--"));
+    match options.iter().find(|option| !flags.contains(option)) {
+        Some(option) => Err(format!("unknown option '{option}'")),
+        None => Ok((options, positional)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{execute, Status};
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn commands() {
+        let mut output = Vec::new();
+        assert_eq!(
+            execute(&args(&["--help"]), &mut output),
+            Ok(Status::Success)
+        );
+        assert!(String::from_utf8(output)
+            .unwrap()
+            .starts_with("usage: dwji"));
+
+        let error = execute(&args(&["format"]), &mut Vec::new()).unwrap_err();
+        assert!(error.starts_with("unknown command 'format'"));
+        let error = execute(&args(&[]), &mut Vec::new()).unwrap_err();
+        assert!(error.starts_with("no command"));
+    }
+}
@@ -0,0 +1,88 @@
+//! `dwji diff`: 2つのファイルの構造の差分を表示する。
+
+use std::io::Write;
+
+use super::{parse_file, split_options, Status};
+use crate::ast::diff::{diff, format_edits};
+
+pub(super) fn run(args: &[String], output: &mut impl Write) -> Result<Status, String> {
+    let (options, paths) = split_options(args, &["--module"])?;
+    let [old_path, new_path] = paths[..] else {
+        return Err("diff needs two files: dwji diff [--module] <old> <new>".to_string());
+    };
+    let module = options.contains(&"--module");
+
+    let (old_source, old, _) = parse_file(old_path, module)?;
+    let (new_source, new, _) = parse_file(new_path, module)?;
+    let edits = diff(&old, &old_source, &new, &new_source);
+    if edits.is_empty() {
+        return Ok(Status::Success);
+    }
+
+    let text = format_edits(&edits, (old_path, &old_source), (new_path, &new_source));
+    output
+        .write_all(text.as_bytes())
+        .map_err(|error| error.to_string())?;
+    Ok(Status::Found)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cli::{
+        test_helper::{file, run},
+        Status,
+    };
+
+    #[test]
+    fn files() {
+        let old = file("old.js", "let a = 1;\nf(a);\n");
+        let new = file("new.js", "let a = 1;\n\nf( a );\n");
+        assert_eq!(
+            run("diff", &[&old, &new]),
+            (Ok(Status::Success), String::new())
+        );
+
+        let new = file("changed.js", "let b = 1;\nf(a);\n");
+        assert_eq!(
+            run("diff", &[&old, &new]),
+            (
+                Ok(Status::Found),
+                format!("--- {old}\n+++ {new}\nupdate Identifier 1:5-1:6 -> 1:5-1:6: `a` -> `b`\n")
+            )
+        );
+    }
+
+    #[test]
+    fn modules() {
+        let old = file("old.mjs", "import a from 'a';");
+        let new = file("new.mjs", "import b from 'a';");
+        assert_eq!(run("diff", &[&old, &new]).0, Ok(Status::Found));
+
+        let old = file("module.js", "export {};");
+        assert!(run("diff", &[&old, &old]).0.is_err());
+        assert_eq!(
+            run("diff", &["--module", &old, &old]).0,
+            Ok(Status::Success)
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let old = file("error.js", "let a = 1;\nlet = ;\n");
+        let (result, _) = run("diff", &[&old, &old]);
+        assert!(result.unwrap_err().starts_with(&format!("{old}:2:")));
+
+        assert!(run("diff", &[&old])
+            .0
+            .unwrap_err()
+            .starts_with("diff needs two files"));
+        assert_eq!(
+            run("diff", &["--verbose", &old, &old]).0,
+            Err("unknown option '--verbose'".to_string())
+        );
+        assert!(run("diff", &["missing.js", &old])
+            .0
+            .unwrap_err()
+            .starts_with("missing.js: "));
+    }
+}
@@ -0,0 +1,257 @@
+//! `dwji lint`: ファイルに規則を当てはめ、問題を指摘する。
+//!
+//! `--rule=<id>:<重大度>` で規則ごとの重大度を変える。エラーの指摘があれば終了コードを1にする
+//! (警告だけなら0)。
+//!
+//! `// dwji-ignore-next-line` などのコメントで抑えた指摘は表示しない。
+//! 使われない抑制は `unused-suppression` として指摘する。
+//!
+//! `--fix` は安全な修正を、`--fix-suggested` は提案の修正も適用してファイルを書き換え、
+//! 残った指摘を表示する。
+
+use std::{fs, io::Write};
+
+use super::{parse_file, split_options, Status};
+use crate::{
+    ast::line_index::LineIndex,
+    lint::{Applicability, Linter, Severity},
+};
+
+const USAGE: &str =
+    "dwji lint [--module] [--fix | --fix-suggested] [--rule=<id>:<off|warn|error>]... <files...>";
+
+pub(super) fn run(args: &[String], output: &mut impl Write) -> Result<Status, String> {
+    let (rules, args): (Vec<String>, Vec<String>) = args
+        .iter()
+        .cloned()
+        .partition(|arg| arg.starts_with("--rule="));
+    let (options, paths) = split_options(&args, &["--module", "--fix", "--fix-suggested"])?;
+    if paths.is_empty() {
+        return Err(format!("lint needs files: {USAGE}"));
+    }
+    let module = options.contains(&"--module");
+    let fix = match (
+        options.contains(&"--fix"),
+        options.contains(&"--fix-suggested"),
+    ) {
+        (_, true) => Some(Applicability::Suggested),
+        (true, false) => Some(Applicability::Safe),
+        (false, false) => None,
+    };
+
+    let mut linter = Linter::new();
+    for rule in &rules {
+        let setting = &rule["--rule=".len()..];
+        let (id, severity) = setting
+            .split_once(':')
+            .and_then(|(id, severity)| Some((id, Severity::parse(severity)?)))
+            .ok_or_else(|| format!("invalid rule setting '{setting}': {USAGE}"))?;
+        linter
+            .set_severity(id, severity)
+            .map_err(|error| error.to_string())?;
+    }
+
+    let mut errors = 0;
+    let mut warnings = 0;
+    let mut safe = 0;
+    let mut suggested = 0;
+    let mut failures = 0;
+    for &path in &paths {
+        let (source, ast, tokens) = match parse_file(path, module) {
+            Ok(parsed) => parsed,
+            Err(message) => {
+                eprintln!("dwji: {message}");
+                failures += 1;
+                continue;
+            }
+        };
+        let (source, diagnostics) = match fix {
+            Some(applicability) => {
+                let fixed = linter.fix(&ast, &source, &tokens, applicability);
+                if fixed.count > 0 {
+                    fs::write(path, &fixed.source).map_err(|error| format!("{path}: {error}"))?;
+                    writeln!(output, "{path}: fixed {}", count(fixed.count, "problem"))
+                        .map_err(|error| error.to_string())?;
+                }
+                (fixed.source, fixed.diagnostics)
+            }
+            None => {
+                let diagnostics = linter.lint(&ast, &source, &tokens);
+                (source, diagnostics)
+            }
+        };
+
+        let index = LineIndex::new(&source);
+        for diagnostic in diagnostics {
+            match diagnostic.severity {
+                Severity::Error => errors += 1,
+                _ => warnings += 1,
+            }
+            match diagnostic.fix.as_ref().map(|fix| fix.applicability) {
+                Some(Applicability::Safe) => safe += 1,
+                Some(Applicability::Suggested) => suggested += 1,
+                None => {}
+            }
+            let position = index.line_column(diagnostic.span.start);
+            writeln!(
+                output,
+                "{path}:{}:{}: {}: {} ({})",
+                position.line,
+                position.column + 1,
+                diagnostic.severity.name(),
+                diagnostic.message,
+                diagnostic.rule
+            )
+            .map_err(|error| error.to_string())?;
+        }
+    }
+    if errors + warnings > 0 {
+        writeln!(
+            output,
+            "\n{} ({}, {})",
+            count(errors + warnings, "problem"),
+            count(errors, "error"),
+            count(warnings, "warning")
+        )
+        .map_err(|error| error.to_string())?;
+    }
+    for (fixable, option) in [(safe, "--fix"), (suggested, "--fix-suggested")] {
+        if fixable > 0 {
+            writeln!(
+                output,
+                "{} fixable with {option}",
+                count(fixable, "problem")
+            )
+            .map_err(|error| error.to_string())?;
+        }
+    }
+
+    match (failures, errors) {
+        (0, 0) => Ok(Status::Success),
+        (0, _) => Ok(Status::Found),
+        (1, _) => Err("1 file could not be linted".to_string()),
+        (failures, _) => Err(format!("{failures} files could not be linted")),
+    }
+}
+
+fn count(count: usize, noun: &str) -> String {
+    match count {
+        1 => format!("1 {noun}"),
+        count => format!("{count} {noun}s"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use crate::cli::{
+        test_helper::{file, run},
+        Status,
+    };
+
+    #[test]
+    fn diagnostics() {
+        let path = file("diagnostics.js", "let a = 1;\nif (a == b) {}\n");
+        assert_eq!(
+            run("lint", &[&path]),
+            (
+                Ok(Status::Found),
+                format!(
+                    "{path}:2:7: warning: expected '===' and instead saw '==' (eqeqeq)\n{path}:2:10: error: 'b' is not defined (no-undef)\n\n2 problems (1 error, 1 warning)\n1 problem fixable with --fix-suggested\n"
+                )
+            )
+        );
+        assert_eq!(
+            run("lint", &["--rule=no-undef:off", &path]),
+            (
+                Ok(Status::Success),
+                format!(
+                    "{path}:2:7: warning: expected '===' and instead saw '==' (eqeqeq)\n\n1 problem (0 errors, 1 warning)\n1 problem fixable with --fix-suggested\n"
+                )
+            )
+        );
+        let clean = file("clean.mjs", "export const a = 1;\n");
+        assert_eq!(run("lint", &[&clean]), (Ok(Status::Success), String::new()));
+    }
+
+    #[test]
+    fn fix() {
+        let source = "import { a, b } from 'm'\nif (typeof a == 'string' || a == 1) {}\n";
+        let path = file("fix.mjs", source);
+        assert_eq!(
+            run("lint", &["--fix", "--rule=semi:warn", &path]),
+            (
+                Ok(Status::Success),
+                format!(
+                    "{path}: fixed 3 problems\n{path}:2:32: warning: expected '===' and instead saw '==' (eqeqeq)\n\n1 problem (0 errors, 1 warning)\n1 problem fixable with --fix-suggested\n"
+                )
+            )
+        );
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "import { a } from 'm';\nif (typeof a === 'string' || a == 1) {}\n"
+        );
+        assert_eq!(
+            run("lint", &["--fix-suggested", &path]),
+            (Ok(Status::Success), format!("{path}: fixed 1 problem\n"))
+        );
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "import { a } from 'm';\nif (typeof a === 'string' || a === 1) {}\n"
+        );
+    }
+
+    #[test]
+    fn suppressions() {
+        let path = file(
+            "suppressions.js",
+            "// dwji-ignore-next-line no-undef\nf();\n// dwji-ignore-next-line eqeqeq\ng();\n",
+        );
+        assert_eq!(
+            run("lint", &[&path]),
+            (
+                Ok(Status::Found),
+                format!(
+                    "{path}:3:1: warning: unused suppression for 'eqeqeq' (unused-suppression)\n{path}:4:1: error: 'g' is not defined (no-undef)\n\n2 problems (1 error, 1 warning)\n"
+                )
+            )
+        );
+        assert_eq!(
+            run(
+                "lint",
+                &[
+                    "--rule=unused-suppression:off",
+                    "--rule=no-undef:off",
+                    &path
+                ]
+            ),
+            (Ok(Status::Success), String::new())
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let broken = file("broken.js", "let = ;");
+        let good = file("good.js", "f();");
+        assert_eq!(
+            run("lint", &["--rule=no-undef:warn", &broken, &good]),
+            (
+                Err("1 file could not be linted".to_string()),
+                format!("{good}:1:1: warning: 'f' is not defined (no-undef)\n\n1 problem (0 errors, 1 warning)\n")
+            )
+        );
+        assert_eq!(
+            run("lint", &["--rule=no-such-rule:error", &good]).0,
+            Err("unknown rule 'no-such-rule'".to_string())
+        );
+        assert!(run("lint", &["--rule=eqeqeq", &good])
+            .0
+            .unwrap_err()
+            .starts_with("invalid rule setting 'eqeqeq'"));
+        assert!(run("lint", &[])
+            .0
+            .unwrap_err()
+            .starts_with("lint needs files"));
+    }
+}
@@ -0,0 +1,137 @@
+//! `dwji rewrite`: パターンに一致するコードを書き換える。
+//!
+//! 既定では書き換えの差分を表示するだけで、`--write` を指定するとファイルを書き換える。
+
+use std::{fs, io::Write};
+
+use super::{source_type, split_options, text_diff::unified_diff, Status};
+use crate::ast::rewrite::Rule;
+
+const USAGE: &str = "dwji rewrite [--module] [--write] <pattern> <replacement> <files...>";
+
+pub(super) fn run(args: &[String], output: &mut impl Write) -> Result<Status, String> {
+    let (options, positional) = split_options(args, &["--module", "--write"])?;
+    let [pattern, replacement, paths @ ..] = &positional[..] else {
+        return Err(format!(
+            "rewrite needs a pattern and a replacement: {USAGE}"
+        ));
+    };
+    if paths.is_empty() {
+        return Err(format!("rewrite needs files: {USAGE}"));
+    }
+    let module = options.contains(&"--module");
+    let write = options.contains(&"--write");
+    let rule = Rule::new(pattern, replacement).map_err(|error| error.to_string())?;
+
+    let mut changed = false;
+    let mut failures = 0;
+    for &path in paths {
+        let result = fs::read_to_string(path)
+            .map_err(|error| error.to_string())
+            .and_then(|source| {
+                let rewritten = rule
+                    .rewrite(&source, source_type(path, module))
+                    .map_err(|error| error.to_string())?;
+                Ok((source, rewritten))
+            });
+        let (source, rewritten) = match result {
+            Ok(result) => result,
+            Err(message) => {
+                eprintln!("dwji: {path}: {message}");
+                failures += 1;
+                continue;
+            }
+        };
+        if rewritten.count == 0 {
+            continue;
+        }
+        changed = true;
+
+        if write {
+            fs::write(path, &rewritten.source).map_err(|error| format!("{path}: {error}"))?;
+            writeln!(output, "{path}: rewrote {}", plural(rewritten.count))
+        } else {
+            let diff = unified_diff(path, path, &source, &rewritten.source);
+            write!(output, "{diff}")
+        }
+        .map_err(|error| error.to_string())?;
+    }
+
+    match (failures, changed && !write) {
+        (0, true) => Ok(Status::Found),
+        (0, false) => Ok(Status::Success),
+        (1, _) => Err("1 file could not be rewritten".to_string()),
+        (failures, _) => Err(format!("{failures} files could not be rewritten")),
+    }
+}
+
+fn plural(count: usize) -> String {
+    match count {
+        1 => "1 match".to_string(),
+        count => format!("{count} matches"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use crate::cli::{
+        test_helper::{file, run},
+        Status,
+    };
+
+    #[test]
+    fn dry_run() {
+        let source = "// a comment\nfoo.bar(x);\n\nfoo.bar( y ); // trailing\n";
+        let path = file("dry_run.js", source);
+        let unchanged = file("unchanged.js", "bar(x);\n");
+
+        assert_eq!(
+            run("rewrite", &["$X.bar($Y)", "baz($Y)", &path, &unchanged]),
+            (
+                Ok(Status::Found),
+                format!(
+                    "--- {path}\n+++ {path}\n@@ -1,4 +1,4 @@\n // a comment\n-foo.bar(x);\n+baz(x);\n \n-foo.bar( y ); // trailing\n+baz(y); // trailing\n"
+                )
+            )
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), source);
+        assert_eq!(
+            run("rewrite", &["$X.bar($Y)", "baz($Y)", &unchanged]),
+            (Ok(Status::Success), String::new())
+        );
+    }
+
+    #[test]
+    fn write() {
+        let path = file("write.js", "a.plus(b) * c.plus(d);\n");
+        assert_eq!(
+            run("rewrite", &["--write", "$A.plus($B)", "$A + $B", &path]),
+            (Ok(Status::Success), format!("{path}: rewrote 2 matches\n"))
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "(a + b) * (c + d);\n");
+    }
+
+    #[test]
+    fn errors() {
+        let broken = file("broken.js", "let = ;");
+        let good = file("good.js", "f(1);");
+        assert_eq!(
+            run("rewrite", &["f($X)", "g($X)", &broken, &good]),
+            (Err("1 file could not be rewritten".to_string()), format!("--- {good}\n+++ {good}\n@@ -1 +1 @@\n-f(1);\n\\ No newline at end of file\n+g(1);\n\\ No newline at end of file\n"))
+        );
+        assert!(run("rewrite", &["f($X)"])
+            .0
+            .unwrap_err()
+            .starts_with("rewrite needs a pattern"));
+        assert!(run("rewrite", &["f($X)", "g($X)"])
+            .0
+            .unwrap_err()
+            .starts_with("rewrite needs files"));
+        assert_eq!(
+            run("rewrite", &["f($X)", "g($Y)", &good]).0,
+            Err("'$Y' in the replacement is not bound by the pattern".to_string())
+        );
+    }
+}
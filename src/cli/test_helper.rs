@@ -0,0 +1,30 @@
+//! コマンドのテストで使う一時ファイルと実行の補助をするモジュールです。
+
+use std::fs;
+
+use super::{execute, Status};
+
+/// テスト用の一時ファイルを作る。
+///
+/// 並列に実行されるテスト同士で同じ名前のファイルを上書きしないように、
+/// テストごとのディレクトリに置く。
+pub(super) fn file(name: &str, source: &str) -> String {
+    let thread = std::thread::current();
+    let test_name = thread.name().unwrap_or("main").replace("::", "-");
+    let directory = std::env::temp_dir().join("dwji-cli").join(test_name);
+    fs::create_dir_all(&directory).unwrap();
+    let path = directory.join(name);
+    fs::write(&path, source).unwrap();
+    path.to_string_lossy().into_owned()
+}
+
+/// コマンドを実行し、結果と出力を返す。
+pub(super) fn run(command: &str, args: &[&str]) -> (Result<Status, String>, String) {
+    let args: Vec<String> = std::iter::once(command)
+        .chain(args.iter().copied())
+        .map(str::to_string)
+        .collect();
+    let mut output = Vec::new();
+    let result = execute(&args, &mut output);
+    (result, String::from_utf8(output).unwrap())
+}
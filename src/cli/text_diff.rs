@@ -0,0 +1,191 @@
+//! 2つのテキストの行の差分を unified diff の形式にするモジュールです。
+
+/// 変更の前後に表示する行の数
+const CONTEXT: usize = 3;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Line<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// `diff -u` と同じ形式の差分を返す。同じテキストの場合は空文字列を返す。
+pub(super) fn unified_diff(old_name: &str, new_name: &str, old: &str, new: &str) -> String {
+    if old == new {
+        return String::new();
+    }
+    let lines = diff_lines(old, new);
+
+    let mut output = format!("--- {old_name}\n+++ {new_name}\n");
+    let changed: Vec<usize> = (0..lines.len())
+        .filter(|&index| !matches!(lines[index], Line::Same(_)))
+        .collect();
+
+    // 間の変わらない行が少ない変更を1つの塊にまとめる
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for &index in &changed {
+        let start = index.saturating_sub(CONTEXT);
+        let end = (index + CONTEXT + 1).min(lines.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    for (start, end) in hunks {
+        let count = |lines: &[Line], old: bool| {
+            lines
+                .iter()
+                .filter(|line| match line {
+                    Line::Same(_) => true,
+                    Line::Removed(_) => old,
+                    Line::Added(_) => !old,
+                })
+                .count()
+        };
+        let old_start = count(&lines[..start], true) + 1;
+        let new_start = count(&lines[..start], false) + 1;
+        let old_count = count(&lines[start..end], true);
+        let new_count = count(&lines[start..end], false);
+        output.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            range(old_start, old_count),
+            range(new_start, new_count)
+        ));
+
+        for line in &lines[start..end] {
+            let (prefix, text) = match line {
+                Line::Same(text) => (' ', text),
+                Line::Removed(text) => ('-', text),
+                Line::Added(text) => ('+', text),
+            };
+            output.push(prefix);
+            output.push_str(text);
+            if !text.ends_with('\n') {
+                output.push_str("\n\\ No newline at end of file\n");
+            }
+        }
+    }
+    output
+}
+
+/// 塊の範囲の表記 (行がない場合は前の行の番号になる)
+fn range(start: usize, count: usize) -> String {
+    match count {
+        0 => format!("{},0", start - 1),
+        1 => start.to_string(),
+        count => format!("{start},{count}"),
+    }
+}
+
+/// 行ごとの差分を求める。
+///
+/// 前後の同じ行を除いてから、残りの行の最長共通部分列を求める。
+fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<Line<'a>> {
+    let old: Vec<&str> = old.split_inclusive('\n').collect();
+    let new: Vec<&str> = new.split_inclusive('\n').collect();
+
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let (old_middle, new_middle) = (
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    );
+
+    // lengths[i][j] は old_middle[i..] と new_middle[j..] の最長共通部分列の長さ
+    let mut lengths = vec![vec![0; new_middle.len() + 1]; old_middle.len() + 1];
+    for i in (0..old_middle.len()).rev() {
+        for j in (0..new_middle.len()).rev() {
+            lengths[i][j] = match old_middle[i] == new_middle[j] {
+                true => lengths[i + 1][j + 1] + 1,
+                false => lengths[i + 1][j].max(lengths[i][j + 1]),
+            };
+        }
+    }
+
+    let mut lines: Vec<Line> = old[..prefix].iter().map(|line| Line::Same(line)).collect();
+    let (mut i, mut j) = (0, 0);
+    while i < old_middle.len() || j < new_middle.len() {
+        if i < old_middle.len() && j < new_middle.len() && old_middle[i] == new_middle[j] {
+            lines.push(Line::Same(old_middle[i]));
+            i += 1;
+            j += 1;
+        } else if j == new_middle.len()
+            || (i < old_middle.len() && lengths[i + 1][j] >= lengths[i][j + 1])
+        {
+            lines.push(Line::Removed(old_middle[i]));
+            i += 1;
+        } else {
+            lines.push(Line::Added(new_middle[j]));
+            j += 1;
+        }
+    }
+    lines.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|line| Line::Same(line)),
+    );
+    lines
+}
+
+#[cfg(test)]
+mod test {
+    use super::unified_diff;
+
+    #[test]
+    fn unified() {
+        assert_eq!(unified_diff("a", "b", "x\n", "x\n"), "");
+        assert_eq!(
+            unified_diff("a", "b", "1\n2\n3\n", "1\nTWO\n3\n"),
+            "--- a\n+++ b\n@@ -1,3 +1,3 @@\n 1\n-2\n+TWO\n 3\n"
+        );
+
+        let mut lines: Vec<String> = (1..=20).map(|line| format!("{line}\n")).collect();
+        let old = lines.concat();
+        lines[1] = "two\n".to_string();
+        lines.insert(15, "15.5\n".to_string());
+        let new = lines.concat();
+        assert_eq!(
+            unified_diff("a", "b", &old, &new),
+            [
+                "--- a",
+                "+++ b",
+                "@@ -1,5 +1,5 @@",
+                " 1",
+                "-2",
+                "+two",
+                " 3",
+                " 4",
+                " 5",
+                "@@ -13,6 +13,7 @@",
+                " 13",
+                " 14",
+                " 15",
+                "+15.5",
+                " 16",
+                " 17",
+                " 18",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn edges() {
+        assert_eq!(
+            unified_diff("a", "b", "", "x\n"),
+            "--- a\n+++ b\n@@ -0,0 +1 @@\n+x\n"
+        );
+        assert_eq!(
+            unified_diff("a", "b", "x", "y"),
+            "--- a\n+++ b\n@@ -1 +1 @@\n-x\n\\ No newline at end of file\n+y\n\\ No newline at end of file\n"
+        );
+    }
+}
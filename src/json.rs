@@ -0,0 +1,563 @@
+//! JSON の値と、その読み書きを定義するモジュールです。
+//!
+//! 書き出しは JavaScript の `JSON.stringify` と同じ表記にする。
+//! オブジェクトのキーは書かれた順に保つ。
+
+use std::fmt::{self, Display};
+
+/// JSON の値
+#[derive(Debug, PartialEq, Clone)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+
+    /// キーと値の組 (書かれた順)
+    Object(Vec<(String, JsonValue)>),
+}
+
+/// JSON の構文エラー
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct JsonError {
+    /// エラーの位置 (バイト位置)
+    pub position: usize,
+    pub message: String,
+}
+
+impl Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (at {})", self.message, self.position)
+    }
+}
+
+impl JsonValue {
+    /// オブジェクトのキーに対応する値を返す。
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(entries) => entries
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            JsonValue::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            JsonValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[JsonValue]> {
+        match self {
+            JsonValue::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, JsonValue::Null)
+    }
+
+    /// 空白を入れずに書き出す (`JSON.stringify(value)`)。
+    pub fn to_json_string(&self) -> String {
+        let mut output = String::new();
+        self.write(&mut output, None, 0);
+        output
+    }
+
+    /// 2文字ずつ字下げして書き出す (`JSON.stringify(value, null, 2)`)。
+    pub fn to_pretty_string(&self) -> String {
+        let mut output = String::new();
+        self.write(&mut output, Some(2), 0);
+        output
+    }
+
+    fn write(&self, output: &mut String, indent: Option<usize>, depth: usize) {
+        match self {
+            JsonValue::Null => output.push_str("null"),
+            JsonValue::Bool(value) => output.push_str(if *value { "true" } else { "false" }),
+            JsonValue::Number(value) => output.push_str(&format_number(*value)),
+            JsonValue::String(value) => write_string(output, value),
+            JsonValue::Array(values) => {
+                write_list(output, '[', ']', values, indent, depth, |output, value| {
+                    value.write(output, indent, depth + 1)
+                });
+            }
+            JsonValue::Object(entries) => {
+                write_list(
+                    output,
+                    '{',
+                    '}',
+                    entries,
+                    indent,
+                    depth,
+                    |output, (key, value)| {
+                        write_string(output, key);
+                        output.push(':');
+                        if indent.is_some() {
+                            output.push(' ');
+                        }
+                        value.write(output, indent, depth + 1);
+                    },
+                );
+            }
+        }
+    }
+}
+
+impl From<bool> for JsonValue {
+    fn from(value: bool) -> Self {
+        JsonValue::Bool(value)
+    }
+}
+
+impl From<usize> for JsonValue {
+    fn from(value: usize) -> Self {
+        JsonValue::Number(value as f64)
+    }
+}
+
+impl From<f64> for JsonValue {
+    fn from(value: f64) -> Self {
+        JsonValue::Number(value)
+    }
+}
+
+impl From<&str> for JsonValue {
+    fn from(value: &str) -> Self {
+        JsonValue::String(value.to_string())
+    }
+}
+
+impl From<String> for JsonValue {
+    fn from(value: String) -> Self {
+        JsonValue::String(value)
+    }
+}
+
+fn write_list<T>(
+    output: &mut String,
+    open: char,
+    close: char,
+    items: &[T],
+    indent: Option<usize>,
+    depth: usize,
+    mut write_item: impl FnMut(&mut String, &T),
+) {
+    output.push(open);
+    if items.is_empty() {
+        output.push(close);
+        return;
+    }
+
+    for (index, item) in items.iter().enumerate() {
+        if index > 0 {
+            output.push(',');
+        }
+        if let Some(indent) = indent {
+            output.push('\n');
+            output.push_str(&" ".repeat(indent * (depth + 1)));
+        }
+        write_item(output, item);
+    }
+
+    if let Some(indent) = indent {
+        output.push('\n');
+        output.push_str(&" ".repeat(indent * depth));
+    }
+    output.push(close);
+}
+
+fn write_string(output: &mut String, value: &str) {
+    output.push('"');
+    for char in value.chars() {
+        match char {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\u{8}' => output.push_str("\\b"),
+            '\u{c}' => output.push_str("\\f"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            '\0'..='\u{1f}' => output.push_str(&format!("\\u{:04x}", char as u32)),
+            _ => output.push(char),
+        }
+    }
+    output.push('"');
+}
+
+/// 数値を JavaScript の `Number.prototype.toString` と同じ表記にする。
+///
+/// JSON で表せない `NaN` と `Infinity` は `null` にする。
+pub fn format_number(value: f64) -> String {
+    if !value.is_finite() {
+        return "null".to_string();
+    }
+    if value == 0.0 {
+        return "0".to_string();
+    }
+
+    // 最短の桁と指数を求める ("1.25e-7" のような表記)
+    let scientific = format!("{:e}", value.abs());
+    let (mantissa, exponent) = scientific.split_once('e').expect("exponent");
+    let digits = mantissa.replace('.', "");
+    let exponent: i32 = exponent.parse().expect("exponent");
+
+    let length = digits.len() as i32;
+    let point = exponent + 1;
+    let sign = if value < 0.0 { "-" } else { "" };
+
+    let body = if length <= point && point <= 21 {
+        format!("{digits}{}", "0".repeat((point - length) as usize))
+    } else if 0 < point && point <= 21 {
+        format!(
+            "{}.{}",
+            &digits[..point as usize],
+            &digits[point as usize..]
+        )
+    } else if -6 < point && point <= 0 {
+        format!("0.{}{digits}", "0".repeat(-point as usize))
+    } else {
+        let exponent_sign = if exponent < 0 { '-' } else { '+' };
+        let (first, rest) = digits.split_at(1);
+        let fraction = if rest.is_empty() {
+            String::new()
+        } else {
+            format!(".{rest}")
+        };
+        format!("{first}{fraction}e{exponent_sign}{}", exponent.abs())
+    };
+
+    format!("{sign}{body}")
+}
+
+/// JSON の文字列を読む。
+pub fn parse_json(source: &str) -> Result<JsonValue, JsonError> {
+    let mut reader = JsonReader {
+        source,
+        position: 0,
+    };
+    let value = reader.value()?;
+    reader.skip_whitespace();
+    if reader.position < source.len() {
+        return Err(reader.error("unexpected input after the end of the JSON value"));
+    }
+    Ok(value)
+}
+
+struct JsonReader<'a> {
+    source: &'a str,
+    position: usize,
+}
+
+impl JsonReader<'_> {
+    fn error(&self, message: impl Into<String>) -> JsonError {
+        JsonError {
+            position: self.position,
+            message: message.into(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.source[self.position..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(' ' | '\t' | '\n' | '\r') = self.peek() {
+            self.position += 1;
+        }
+    }
+
+    fn eat(&mut self, char: char) -> bool {
+        if self.peek() == Some(char) {
+            self.position += char.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, char: char) -> Result<(), JsonError> {
+        if self.eat(char) {
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn unexpected(&self) -> JsonError {
+        match self.peek() {
+            Some(char) => self.error(format!("unexpected character '{char}'")),
+            None => self.error("unexpected end of input"),
+        }
+    }
+
+    fn value(&mut self) -> Result<JsonValue, JsonError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.object(),
+            Some('[') => self.array(),
+            Some('"') => Ok(JsonValue::String(self.string()?)),
+            Some('-' | '0'..='9') => self.number(),
+            Some('n') => self.keyword("null", JsonValue::Null),
+            Some('t') => self.keyword("true", JsonValue::Bool(true)),
+            Some('f') => self.keyword("false", JsonValue::Bool(false)),
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str, value: JsonValue) -> Result<JsonValue, JsonError> {
+        if self.source[self.position..].starts_with(keyword) {
+            self.position += keyword.len();
+            Ok(value)
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn object(&mut self) -> Result<JsonValue, JsonError> {
+        self.expect('{')?;
+        let mut entries = Vec::new();
+
+        self.skip_whitespace();
+        if self.eat('}') {
+            return Ok(JsonValue::Object(entries));
+        }
+
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some('"') {
+                return Err(self.unexpected());
+            }
+            let key = self.string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            entries.push((key, self.value()?));
+
+            self.skip_whitespace();
+            if self.eat('}') {
+                return Ok(JsonValue::Object(entries));
+            }
+            self.expect(',')?;
+        }
+    }
+
+    fn array(&mut self) -> Result<JsonValue, JsonError> {
+        self.expect('[')?;
+        let mut values = Vec::new();
+
+        self.skip_whitespace();
+        if self.eat(']') {
+            return Ok(JsonValue::Array(values));
+        }
+
+        loop {
+            values.push(self.value()?);
+            self.skip_whitespace();
+            if self.eat(']') {
+                return Ok(JsonValue::Array(values));
+            }
+            self.expect(',')?;
+        }
+    }
+
+    fn number(&mut self) -> Result<JsonValue, JsonError> {
+        let start = self.position;
+        self.eat('-');
+
+        if !self.eat('0') {
+            if !matches!(self.peek(), Some('1'..='9')) {
+                return Err(self.unexpected());
+            }
+            self.digits();
+        }
+        if self.eat('.') {
+            if !matches!(self.peek(), Some('0'..='9')) {
+                return Err(self.unexpected());
+            }
+            self.digits();
+        }
+        if self.eat('e') || self.eat('E') {
+            let _ = self.eat('+') || self.eat('-');
+            if !matches!(self.peek(), Some('0'..='9')) {
+                return Err(self.unexpected());
+            }
+            self.digits();
+        }
+
+        let value = self.source[start..self.position]
+            .parse()
+            .map_err(|_| self.error("invalid number"))?;
+        Ok(JsonValue::Number(value))
+    }
+
+    fn digits(&mut self) {
+        while let Some('0'..='9') = self.peek() {
+            self.position += 1;
+        }
+    }
+
+    fn string(&mut self) -> Result<String, JsonError> {
+        self.expect('"')?;
+        let mut units: Vec<u16> = Vec::new();
+
+        loop {
+            let Some(char) = self.peek() else {
+                return Err(self.error("unterminated string"));
+            };
+            self.position += char.len_utf8();
+
+            match char {
+                '"' => break,
+                '\\' => {
+                    let Some(escape) = self.peek() else {
+                        return Err(self.error("unterminated string"));
+                    };
+                    self.position += escape.len_utf8();
+
+                    let unit = match escape {
+                        '"' => '"' as u16,
+                        '\\' => '\\' as u16,
+                        '/' => '/' as u16,
+                        'b' => 0x8,
+                        'f' => 0xc,
+                        'n' => '\n' as u16,
+                        'r' => '\r' as u16,
+                        't' => '\t' as u16,
+                        'u' => self.hex_unit()?,
+                        _ => return Err(self.error(format!("invalid escape '\\{escape}'"))),
+                    };
+                    units.push(unit);
+                }
+                '\0'..='\u{1f}' => return Err(self.error("control character in string")),
+                _ => {
+                    let mut buffer = [0; 2];
+                    units.extend_from_slice(char.encode_utf16(&mut buffer));
+                }
+            }
+        }
+
+        // 対になっていないサロゲートは U+FFFD に置き換える
+        Ok(String::from_utf16_lossy(&units))
+    }
+
+    fn hex_unit(&mut self) -> Result<u16, JsonError> {
+        let digits = self
+            .source
+            .get(self.position..self.position + 4)
+            .filter(|digits| digits.chars().all(|char| char.is_ascii_hexdigit()))
+            .ok_or_else(|| self.error("invalid unicode escape"))?;
+        self.position += 4;
+        Ok(u16::from_str_radix(digits, 16).expect("hex digits"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{format_number, parse_json, JsonValue};
+
+    #[test]
+    fn numbers() {
+        assert_eq!(format_number(0.0), "0");
+        assert_eq!(format_number(-0.0), "0");
+        assert_eq!(format_number(1.0), "1");
+        assert_eq!(format_number(-12.5), "-12.5");
+        assert_eq!(format_number(0.1), "0.1");
+        assert_eq!(format_number(0.000001), "0.000001");
+        assert_eq!(format_number(1e-7), "1e-7");
+        assert_eq!(format_number(1.5e-7), "1.5e-7");
+        assert_eq!(format_number(1e21), "1e+21");
+        assert_eq!(format_number(123e18), "123000000000000000000");
+        assert_eq!(format_number(f64::INFINITY), "null");
+    }
+
+    #[test]
+    fn write_compact() {
+        let value = JsonValue::Object(vec![
+            (
+                "a".to_string(),
+                JsonValue::Array(vec![1.0.into(), JsonValue::Null]),
+            ),
+            ("b".to_string(), "x\"\n\u{1}".into()),
+            ("c".to_string(), JsonValue::Object(Vec::new())),
+        ]);
+        assert_eq!(
+            value.to_json_string(),
+            r#"{"a":[1,null],"b":"x\"\n\u0001","c":{}}"#
+        );
+    }
+
+    #[test]
+    fn write_pretty() {
+        let value = JsonValue::Object(vec![
+            ("a".to_string(), JsonValue::Array(vec![true.into()])),
+            ("b".to_string(), JsonValue::Array(Vec::new())),
+        ]);
+        assert_eq!(
+            value.to_pretty_string(),
+            "{\n  \"a\": [\n    true\n  ],\n  \"b\": []\n}"
+        );
+    }
+
+    #[test]
+    fn read() {
+        let source = r#" { "a" : [1, -2.5e1, true, null], "b": "\u3042\ud83d\ude00\/", "c": {} } "#;
+        assert_eq!(
+            parse_json(source),
+            Ok(JsonValue::Object(vec![
+                (
+                    "a".to_string(),
+                    JsonValue::Array(vec![
+                        1.0.into(),
+                        (-25.0).into(),
+                        true.into(),
+                        JsonValue::Null
+                    ])
+                ),
+                ("b".to_string(), "あ😀/".into()),
+                ("c".to_string(), JsonValue::Object(Vec::new())),
+            ]))
+        );
+    }
+
+    #[test]
+    fn read_round_trip() {
+        let source = r#"{"a":[1,0.5,"\\\t"],"b":{"c":false}}"#;
+        assert_eq!(parse_json(source).unwrap().to_json_string(), source);
+    }
+
+    #[test]
+    fn read_errors() {
+        let message = |source| parse_json(source).map_err(|error| error.message);
+        assert_eq!(message("[1,]"), Err("unexpected character ']'".to_string()));
+        assert_eq!(
+            message("01"),
+            Err("unexpected input after the end of the JSON value".to_string())
+        );
+        assert_eq!(message("\"a"), Err("unterminated string".to_string()));
+        assert_eq!(
+            message("{\"a\" 1}"),
+            Err("unexpected character '1'".to_string())
+        );
+        assert_eq!(message("\"\\x\""), Err("invalid escape '\\x'".to_string()));
+        assert_eq!(message(""), Err("unexpected end of input".to_string()));
+    }
+}
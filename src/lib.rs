@@ -0,0 +1,12 @@
+#![allow(dead_code, unused)]
+
+pub mod ast;
+pub mod cli;
+mod json;
+mod lint;
+pub mod token {
+    pub mod structs;
+    pub mod tokenize;
+}
+mod pattern_macros;
+mod to_source_string;
@@ -0,0 +1,605 @@
+//! 構文木とスコープの情報に規則を当てはめ、問題を指摘するモジュールです。
+//!
+//! 規則 ([`Rule`]) は [`Context`] から構文木、スコープ、制御フローグラフを受け取り、
+//! 問題の範囲とメッセージを報告する。[`Linter`] は規則ごとの重大度を持ち、
+//! 無効にした規則は実行しない。
+//!
+//! 指摘には修正 ([`Fix`]) を付けられる。[`Linter::fix`] は重ならない修正をまとめて適用し、
+//! 修正がなくなるまで解析と適用を繰り返す。重なった修正は次の繰り返しで適用する。
+//!
+//! `// dwji-ignore-next-line` などのコメントで指摘を抑えられる ([`suppression`])。
+
+mod rules;
+mod suppression;
+
+use std::fmt;
+
+pub use self::suppression::UNUSED_SUPPRESSION;
+use crate::{
+    ast::{
+        cfg::{control_flow_graphs, ControlFlowGraph},
+        cst::comments,
+        reader_struct::SourceCodeReader,
+        scope::{analyze, SymbolTable},
+        script::{parse_with_tokens, ParseOptions},
+        structs::{span::Span, JsAst, SourceType},
+    },
+    token::structs::{comment::Comment, token::Token},
+};
+
+/// [`Linter::fix`] で解析と修正を繰り返す最大の回数
+const MAX_FIX_PASSES: usize = 10;
+
+/// 指摘の重大度
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Severity {
+    /// 規則を実行しない
+    Off,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    /// `off` `warn` `error` (または `0` `1` `2`) を読む。
+    pub fn parse(text: &str) -> Option<Severity> {
+        match text {
+            "off" | "0" => Some(Severity::Off),
+            "warn" | "1" => Some(Severity::Warning),
+            "error" | "2" => Some(Severity::Error),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Severity::Off => "off",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+/// 規則による指摘
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Diagnostic {
+    /// 規則の名前 (`no-undef` など)
+    pub rule: &'static str,
+    pub severity: Severity,
+    pub span: Span,
+    pub message: String,
+    pub fix: Option<Fix>,
+}
+
+/// ソースコードの範囲を `text` に置き換える編集 (空の範囲なら挿入)
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TextEdit {
+    pub span: Span,
+    pub text: String,
+}
+
+impl TextEdit {
+    pub fn replace(span: Span, text: impl Into<String>) -> TextEdit {
+        TextEdit {
+            span,
+            text: text.into(),
+        }
+    }
+
+    pub fn insert(position: usize, text: impl Into<String>) -> TextEdit {
+        TextEdit::replace(Span::new(position, position), text)
+    }
+
+    pub fn delete(span: Span) -> TextEdit {
+        TextEdit::replace(span, "")
+    }
+}
+
+/// 修正を自動で適用してよいか
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Applicability {
+    /// 動作を変えない修正 (`--fix` で適用する)
+    Safe,
+
+    /// 動作が変わりうる修正 (`--fix-suggested` で適用する)
+    Suggested,
+}
+
+/// 指摘を直す編集の組
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Fix {
+    pub applicability: Applicability,
+
+    /// 互いに重ならない編集
+    pub edits: Vec<TextEdit>,
+}
+
+impl Fix {
+    pub fn safe(edits: Vec<TextEdit>) -> Fix {
+        Fix {
+            applicability: Applicability::Safe,
+            edits,
+        }
+    }
+
+    pub fn suggested(edits: Vec<TextEdit>) -> Fix {
+        Fix {
+            applicability: Applicability::Suggested,
+            edits,
+        }
+    }
+
+    /// 全ての編集を含む範囲
+    fn span(&self) -> Span {
+        self.edits
+            .iter()
+            .map(|edit| edit.span)
+            .reduce(Span::merge)
+            .unwrap_or_default()
+    }
+}
+
+/// [`Linter::fix`] の結果
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Fixed {
+    pub source: String,
+
+    /// 適用した修正の数
+    pub count: usize,
+
+    /// 修正した後のソースコードへの指摘
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// 規則に渡す解析の結果
+pub struct Context<'a> {
+    pub source: &'a str,
+    pub ast: &'a JsAst,
+    pub scopes: &'a SymbolTable,
+
+    /// トップレベルと全ての関数の制御フローグラフ
+    pub graphs: &'a [ControlFlowGraph<'a>],
+
+    /// 構文木と同じ解析で読んだトークン (コメントを含まない) と、コメントの種類と範囲
+    pub tokens: &'a [Token],
+    pub comments: &'a [(Comment, Span)],
+    reports: Vec<(Span, String, Option<Fix>)>,
+}
+
+impl Context<'_> {
+    pub fn report(&mut self, span: Span, message: impl Into<String>) {
+        self.reports.push((span, message.into(), None));
+    }
+
+    pub fn report_with_fix(&mut self, span: Span, message: impl Into<String>, fix: Fix) {
+        self.reports.push((span, message.into(), Some(fix)));
+    }
+}
+
+/// 指摘の規則
+pub trait Rule {
+    /// 規則の名前 (`no-undef` など)
+    fn id(&self) -> &'static str;
+
+    /// 設定がない場合の重大度
+    fn default_severity(&self) -> Severity;
+
+    fn check(&self, context: &mut Context<'_>);
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LintError {
+    pub message: String,
+}
+
+impl fmt::Display for LintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// 登録された規則と、その重大度
+pub struct Linter {
+    rules: Vec<(Box<dyn Rule>, Severity)>,
+
+    /// 使われない抑制の指示 ([`UNUSED_SUPPRESSION`]) の重大度
+    unused_suppression: Severity,
+}
+
+impl Linter {
+    /// 全ての規則を既定の重大度で登録する。
+    pub fn new() -> Linter {
+        Linter {
+            rules: rules::all()
+                .into_iter()
+                .map(|rule| {
+                    let severity = rule.default_severity();
+                    (rule, severity)
+                })
+                .collect(),
+            unused_suppression: Severity::Warning,
+        }
+    }
+
+    /// 登録された規則の名前と重大度
+    pub fn rules(&self) -> impl Iterator<Item = (&'static str, Severity)> + '_ {
+        self.rules
+            .iter()
+            .map(|(rule, severity)| (rule.id(), *severity))
+    }
+
+    /// 規則 (または [`UNUSED_SUPPRESSION`]) の重大度を変える。
+    pub fn set_severity(&mut self, id: &str, severity: Severity) -> Result<(), LintError> {
+        if id == UNUSED_SUPPRESSION {
+            self.unused_suppression = severity;
+            return Ok(());
+        }
+        match self.rules.iter_mut().find(|(rule, _)| rule.id() == id) {
+            Some((_, current)) => {
+                *current = severity;
+                Ok(())
+            }
+            None => Err(LintError {
+                message: format!("unknown rule '{id}'"),
+            }),
+        }
+    }
+
+    /// 有効な規則を全て実行し、コメントの指示で抑えた残りの指摘を位置の順に並べて返す。
+    ///
+    /// `tokens` は構文木と同じ解析で読んだトークン ([`parse_with_tokens`] の結果) である。
+    pub fn lint(&self, ast: &JsAst, source: &str, tokens: &[Token]) -> Vec<Diagnostic> {
+        let scopes = analyze(ast);
+        let graphs = control_flow_graphs(ast);
+        let comments = comments(source, tokens);
+
+        let mut diagnostics = Vec::new();
+        for (rule, severity) in &self.rules {
+            if *severity == Severity::Off {
+                continue;
+            }
+            let mut context = Context {
+                source,
+                ast,
+                scopes: &scopes,
+                graphs: &graphs,
+                tokens,
+                comments: &comments,
+                reports: Vec::new(),
+            };
+            rule.check(&mut context);
+            diagnostics.extend(context.reports.into_iter().map(|(span, message, fix)| {
+                Diagnostic {
+                    rule: rule.id(),
+                    severity: *severity,
+                    span,
+                    message,
+                    fix,
+                }
+            }));
+        }
+        diagnostics.sort_by(|a, b| a.span.cmp(&b.span).then(a.rule.cmp(b.rule)));
+
+        let known: Vec<&str> = self.rules().map(|(id, _)| id).collect();
+        let enabled: Vec<&str> = self
+            .rules()
+            .filter(|(_, severity)| *severity != Severity::Off)
+            .map(|(id, _)| id)
+            .collect();
+        suppression::apply(
+            source,
+            &comments,
+            diagnostics,
+            &known,
+            &enabled,
+            self.unused_suppression,
+        )
+    }
+
+    /// `applicability` 以下の修正を、修正がなくなるまで (最大 [`MAX_FIX_PASSES`] 回) 適用する。
+    ///
+    /// 修正した結果が読めない場合は、その回の修正を捨てて終える。
+    pub fn fix(
+        &self,
+        ast: &JsAst,
+        source: &str,
+        tokens: &[Token],
+        applicability: Applicability,
+    ) -> Fixed {
+        let mut fixed = Fixed {
+            source: source.to_string(),
+            count: 0,
+            diagnostics: self.lint(ast, source, tokens),
+        };
+        let source_type = ast.source_type;
+        for _ in 0..MAX_FIX_PASSES {
+            let fixes: Vec<&Fix> = fixed
+                .diagnostics
+                .iter()
+                .filter_map(|diagnostic| diagnostic.fix.as_ref())
+                .filter(|fix| fix.applicability <= applicability)
+                .collect();
+            let (source, count) = apply_fixes(&fixed.source, &fixes);
+            if count == 0 {
+                break;
+            }
+            let Ok((ast, tokens)) = parse(&source, source_type) else {
+                break;
+            };
+            fixed.diagnostics = self.lint(&ast, &source, &tokens);
+            fixed.source = source;
+            fixed.count += count;
+        }
+        fixed
+    }
+}
+
+/// 前の修正と重ならない修正を位置の順に適用し、結果と適用した数を返す。
+fn apply_fixes(source: &str, fixes: &[&Fix]) -> (String, usize) {
+    let mut fixes = fixes.to_vec();
+    fixes.sort_by_key(|fix| fix.span());
+
+    let mut output = String::with_capacity(source.len());
+    let mut position = 0;
+    let mut count = 0;
+    for fix in fixes {
+        if fix.span().start < position {
+            continue;
+        }
+        let mut edits: Vec<&TextEdit> = fix.edits.iter().collect();
+        edits.sort_by_key(|edit| edit.span);
+        for edit in edits {
+            output.push_str(&source[position..edit.span.start]);
+            output.push_str(&edit.text);
+            position = edit.span.end;
+        }
+        count += 1;
+    }
+    output.push_str(&source[position..]);
+    (output, count)
+}
+
+/// 構文木と、同じ解析で読んだトークンを返す。
+fn parse(source: &str, source_type: SourceType) -> Result<(JsAst, Vec<Token>), String> {
+    let (output, tokens) = parse_with_tokens(
+        SourceCodeReader::new(source),
+        source_type,
+        ParseOptions::default(),
+    );
+    let ast = output.into_result().map_err(|error| error.to_string())?;
+    Ok((ast, tokens))
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Linter::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{apply_fixes, parse, Applicability, Diagnostic, Fix, Linter, Severity, TextEdit};
+    use crate::ast::{
+        line_index::LineIndex,
+        structs::{span::Span, SourceType},
+    };
+
+    fn lint(linter: &Linter, source: &str) -> Vec<Diagnostic> {
+        let (ast, tokens) = parse(source, SourceType::Script).unwrap();
+        linter.lint(&ast, source, &tokens)
+    }
+
+    #[test]
+    fn registered_rules() {
+        let linter = Linter::new();
+        let ids: Vec<&str> = linter.rules().map(|(id, _)| id).collect();
+        assert_eq!(
+            ids,
+            [
+                "eqeqeq",
+                "no-dupe-keys",
+                "no-fallthrough",
+                "no-self-assign",
+                "no-undef",
+                "no-unreachable",
+                "no-unused-vars",
+                "semi",
+            ]
+        );
+    }
+
+    #[test]
+    fn severities() {
+        let source = "let unused = a == b;";
+        let mut linter = Linter::new();
+        assert_eq!(
+            lint(&linter, source),
+            [
+                Diagnostic {
+                    rule: "no-unused-vars",
+                    severity: Severity::Warning,
+                    span: Span::new(4, 10),
+                    message: "'unused' is defined but never used".to_string(),
+                    fix: None,
+                },
+                Diagnostic {
+                    rule: "no-undef",
+                    severity: Severity::Error,
+                    span: Span::new(13, 14),
+                    message: "'a' is not defined".to_string(),
+                    fix: None,
+                },
+                Diagnostic {
+                    rule: "eqeqeq",
+                    severity: Severity::Warning,
+                    span: Span::new(15, 17),
+                    message: "expected '===' and instead saw '=='".to_string(),
+                    fix: Some(Fix::suggested(vec![TextEdit::replace(
+                        Span::new(15, 17),
+                        "==="
+                    )])),
+                },
+                Diagnostic {
+                    rule: "no-undef",
+                    severity: Severity::Error,
+                    span: Span::new(18, 19),
+                    message: "'b' is not defined".to_string(),
+                    fix: None,
+                },
+            ]
+        );
+
+        linter.set_severity("no-undef", Severity::Off).unwrap();
+        linter.set_severity("eqeqeq", Severity::Error).unwrap();
+        let rules: Vec<(&str, Severity)> = lint(&linter, source)
+            .iter()
+            .map(|diagnostic| (diagnostic.rule, diagnostic.severity))
+            .collect();
+        assert_eq!(
+            rules,
+            [
+                ("no-unused-vars", Severity::Warning),
+                ("eqeqeq", Severity::Error)
+            ]
+        );
+
+        assert_eq!(
+            linter
+                .set_severity("no-such-rule", Severity::Error)
+                .unwrap_err()
+                .to_string(),
+            "unknown rule 'no-such-rule'"
+        );
+        assert_eq!(Severity::parse("warn"), Some(Severity::Warning));
+        assert_eq!(Severity::parse("2"), Some(Severity::Error));
+        assert_eq!(Severity::parse("fatal"), None);
+    }
+
+    #[test]
+    fn overlapping_fixes() {
+        let source = "abcdef";
+        let first = Fix::safe(vec![
+            TextEdit::replace(Span::new(0, 1), "A"),
+            TextEdit::replace(Span::new(3, 4), "D"),
+        ]);
+        let overlapping = Fix::safe(vec![TextEdit::delete(Span::new(2, 3))]);
+        let adjacent = Fix::safe(vec![TextEdit::insert(4, "-")]);
+        assert_eq!(
+            apply_fixes(source, &[&overlapping, &adjacent, &first]),
+            ("AbcD-ef".to_string(), 2)
+        );
+        assert_eq!(apply_fixes(source, &[]), (source.to_string(), 0));
+    }
+
+    #[test]
+    fn fix_passes() {
+        let source = "var a = 1, b = 2\nif (a == 1 || typeof b != 'string') {}\n";
+        let (ast, tokens) = parse(source, SourceType::Script).unwrap();
+        let mut linter = Linter::new();
+        linter.set_severity("semi", Severity::Warning).unwrap();
+
+        let fixed = linter.fix(&ast, source, &tokens, Applicability::Safe);
+        assert_eq!(
+            fixed.source,
+            "var a = 1, b = 2;\nif (a == 1 || typeof b !== 'string') {}\n"
+        );
+        assert_eq!(fixed.count, 2);
+        assert_eq!(
+            fixed
+                .diagnostics
+                .iter()
+                .map(|diagnostic| diagnostic.fix.as_ref().map(|fix| fix.applicability))
+                .collect::<Vec<_>>(),
+            [Some(Applicability::Suggested)]
+        );
+
+        let fixed = linter.fix(&ast, source, &tokens, Applicability::Suggested);
+        assert_eq!(
+            fixed.source,
+            "var a = 1, b = 2;\nif (a === 1 || typeof b !== 'string') {}\n"
+        );
+        assert_eq!((fixed.count, fixed.diagnostics.len()), (3, 0));
+    }
+
+    #[test]
+    fn suppressions() {
+        let source = "\
+// dwji-ignore-next-line no-undef
+a == b;
+/* dwji-disable eqeqeq */
+c == d;
+/* dwji-enable eqeqeq */
+e == f; // dwji-ignore-next-line
+/* dwji-disable-file no-unused-vars -- generated */
+let unused;
+// dwji-ignore-next-line semi, no-undef
+g();
+";
+        let mut linter = Linter::new();
+        let diagnostics = |linter: &Linter| -> Vec<String> {
+            let index = LineIndex::new(source);
+            lint(linter, source)
+                .iter()
+                .map(|diagnostic| {
+                    let position = index.line_column(diagnostic.span.start);
+                    format!(
+                        "{}:{} {} ({})",
+                        position.line,
+                        position.column + 1,
+                        diagnostic.message,
+                        diagnostic.rule
+                    )
+                })
+                .collect()
+        };
+        assert_eq!(
+            diagnostics(&linter),
+            [
+                "2:3 expected '===' and instead saw '==' (eqeqeq)",
+                "4:1 'c' is not defined (no-undef)",
+                "4:6 'd' is not defined (no-undef)",
+                "6:1 'e' is not defined (no-undef)",
+                "6:3 expected '===' and instead saw '==' (eqeqeq)",
+                "6:6 'f' is not defined (no-undef)",
+                "6:9 unused suppression (unused-suppression)",
+            ]
+        );
+
+        linter.set_severity("semi", Severity::Error).unwrap();
+        linter
+            .set_severity("unused-suppression", Severity::Error)
+            .unwrap();
+        let found = diagnostics(&linter);
+        assert_eq!(found.len(), 8);
+        assert_eq!(
+            found[7],
+            "9:1 unused suppression for 'semi' (unused-suppression)"
+        );
+
+        linter
+            .set_severity("unused-suppression", Severity::Off)
+            .unwrap();
+        assert_eq!(diagnostics(&linter).len(), 6);
+    }
+
+    #[test]
+    fn unknown_rules_in_suppressions() {
+        let source =
+            "// dwji-ignore-next-line no-undefined, no-undef\na;\r\n// dwji-ignore-next-line\r\nb;";
+        let mut linter = Linter::new();
+        let found: Vec<(Span, String)> = lint(&linter, source)
+            .into_iter()
+            .map(|diagnostic| (diagnostic.span, diagnostic.message))
+            .collect();
+        assert_eq!(
+            found,
+            [(
+                Span::new(0, 47),
+                "unknown rule 'no-undefined' in suppression".to_string()
+            )]
+        );
+
+        // 実行しない規則でも、知らない規則の名前は指摘する
+        linter.set_severity("no-undef", Severity::Off).unwrap();
+        assert_eq!(lint(&linter, source).len(), 2);
+    }
+}
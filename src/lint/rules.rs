@@ -0,0 +1,90 @@
+//! 組み込みの規則です。
+
+mod eqeqeq;
+mod no_dupe_keys;
+mod no_fallthrough;
+mod no_self_assign;
+mod no_undef;
+mod no_unreachable;
+mod no_unused_vars;
+mod semi;
+
+use super::Rule;
+
+/// 全ての規則を名前の順に返す。
+pub(super) fn all() -> Vec<Box<dyn Rule>> {
+    vec![
+        Box::new(eqeqeq::Eqeqeq),
+        Box::new(no_dupe_keys::NoDupeKeys),
+        Box::new(no_fallthrough::NoFallthrough),
+        Box::new(no_self_assign::NoSelfAssign),
+        Box::new(no_undef::NoUndef),
+        Box::new(no_unreachable::NoUnreachable),
+        Box::new(no_unused_vars::NoUnusedVars),
+        Box::new(semi::Semi),
+    ]
+}
+
+#[cfg(test)]
+pub(super) mod test {
+    use crate::{
+        ast::{line_index::LineIndex, structs::SourceType},
+        lint::{parse, Applicability, Linter, Severity},
+    };
+
+    /// 1つの規則だけを有効にした [`Linter`]
+    fn linter(rule: &str) -> Linter {
+        let mut linter = Linter::new();
+        let ids: Vec<&str> = linter.rules().map(|(id, _)| id).collect();
+        for id in ids {
+            let severity = match id == rule {
+                true => Severity::Error,
+                false => Severity::Off,
+            };
+            linter.set_severity(id, severity).unwrap();
+        }
+        linter
+    }
+
+    /// 1つの規則だけを実行し、指摘を `行:列 メッセージ` の形で返す。
+    pub(in crate::lint) fn check(rule: &str, source: &str, source_type: SourceType) -> Vec<String> {
+        let (ast, tokens) =
+            parse(source, source_type).unwrap_or_else(|error| panic!("{source}: {error}"));
+        let index = LineIndex::new(source);
+        linter(rule)
+            .lint(&ast, source, &tokens)
+            .into_iter()
+            .map(|diagnostic| {
+                let position = index.line_column(diagnostic.span.start);
+                format!(
+                    "{}:{} {}",
+                    position.line,
+                    position.column + 1,
+                    diagnostic.message
+                )
+            })
+            .collect()
+    }
+
+    pub(in crate::lint) fn check_script(rule: &str, source: &str) -> Vec<String> {
+        check(rule, source, SourceType::Script)
+    }
+
+    /// 1つの規則だけを実行し、スクリプトに `applicability` 以下の修正を適用した結果を返す。
+    pub(in crate::lint) fn fix(rule: &str, source: &str, applicability: Applicability) -> String {
+        fix_as(rule, source, SourceType::Script, applicability)
+    }
+
+    pub(in crate::lint) fn fix_as(
+        rule: &str,
+        source: &str,
+        source_type: SourceType,
+        applicability: Applicability,
+    ) -> String {
+        let (ast, tokens) =
+            parse(source, source_type).unwrap_or_else(|error| panic!("{source}: {error}"));
+        linter(rule)
+            .fix(&ast, source, &tokens, applicability)
+            .source
+    }
+}
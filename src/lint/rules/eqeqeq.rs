@@ -0,0 +1,165 @@
+//! `eqeqeq`: `==` と `!=` の代わりに `===` と `!==` を使う。
+//!
+//! 片方が `typeof` の場合と、両方が同じ型のリテラルの場合は、置き換えても結果が変わらないため
+//! 安全な修正にする。それ以外は型の変換がなくなるため提案にとどめる。
+
+use crate::{
+    ast::{
+        structs::{
+            expression::{BinaryExpression, BinaryOperator, Expression, UnaryOperator},
+            literal::LiteralValue,
+            span::{Span, Spanned},
+        },
+        visit::{Visit, VisitWith},
+    },
+    lint::{Context, Fix, Rule, Severity, TextEdit},
+    token::structs::{
+        punctuator::Punctuator,
+        token::{Token, TokenType},
+    },
+};
+
+pub(super) struct Eqeqeq;
+
+impl Rule for Eqeqeq {
+    fn id(&self) -> &'static str {
+        "eqeqeq"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, context: &mut Context<'_>) {
+        let mut finder = Finder {
+            tokens: context.tokens,
+            found: Vec::new(),
+        };
+        context.ast.visit_with(&mut finder);
+        for (span, expected, actual, safe) in finder.found {
+            let edits = vec![TextEdit::replace(span, expected)];
+            context.report_with_fix(
+                span,
+                format!("expected '{expected}' and instead saw '{actual}'"),
+                match safe {
+                    true => Fix::safe(edits),
+                    false => Fix::suggested(edits),
+                },
+            );
+        }
+    }
+}
+
+struct Finder<'a> {
+    tokens: &'a [Token],
+
+    /// 演算子の範囲、使うべき演算子、使われた演算子、置き換えが安全であるか
+    found: Vec<(Span, &'static str, &'static str, bool)>,
+}
+
+impl<'ast> Visit<'ast> for Finder<'_> {
+    fn visit_binary_expression(&mut self, node: &'ast BinaryExpression) {
+        let operators = match node.operator {
+            BinaryOperator::Equal => Some(("===", "==", Punctuator::Equal)),
+            BinaryOperator::NotEqual => Some(("!==", "!=", Punctuator::NotEqual)),
+            _ => None,
+        };
+        if let Some((expected, actual, punctuator)) = operators {
+            let span = operator_span(self.tokens, node, punctuator);
+            let safe = is_typeof(&node.left)
+                || is_typeof(&node.right)
+                || same_literal_type(&node.left, &node.right);
+            self.found.push((span, expected, actual, safe));
+        }
+        node.visit_children_with(self);
+    }
+}
+
+/// 左辺と右辺の間のトークンから演算子の範囲を探す (見つからなければ式全体)。
+///
+/// 間にはコメントや左辺を閉じる `)` があるため、トークンで探す。
+fn operator_span(tokens: &[Token], node: &BinaryExpression, operator: Punctuator) -> Span {
+    let (left_end, right_start) = (node.left.span().end, node.right.span().start);
+    let first = tokens.partition_point(|token| token.start_index() < left_end);
+    tokens[first..]
+        .iter()
+        .take_while(|token| token.end_index() <= right_start)
+        .find(|token| token.token_type() == TokenType::Punctuator(operator))
+        .map_or(node.span, |token| {
+            Span::new(token.start_index(), token.end_index())
+        })
+}
+
+fn is_typeof(expression: &Expression) -> bool {
+    matches!(expression, Expression::Unary(unary) if unary.operator == UnaryOperator::Typeof)
+}
+
+fn same_literal_type(left: &Expression, right: &Expression) -> bool {
+    let (Expression::Literal(left), Expression::Literal(right)) = (left, right) else {
+        return false;
+    };
+    matches!(
+        (&left.value, &right.value),
+        (LiteralValue::Null, LiteralValue::Null)
+            | (LiteralValue::Boolean(_), LiteralValue::Boolean(_))
+            | (LiteralValue::Number(_), LiteralValue::Number(_))
+            | (LiteralValue::String(_), LiteralValue::String(_))
+            | (LiteralValue::BigInt(_), LiteralValue::BigInt(_))
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use crate::lint::{
+        rules::test::{check_script, fix},
+        Applicability,
+    };
+
+    #[test]
+    fn loose_equality() {
+        assert_eq!(
+            check_script("eqeqeq", "if (a == b) {}\nc != (d == e);"),
+            [
+                "1:7 expected '===' and instead saw '=='",
+                "2:3 expected '!==' and instead saw '!='",
+                "2:9 expected '===' and instead saw '=='",
+            ]
+        );
+        assert_eq!(
+            check_script("eqeqeq", "a === b; a !== b; a <= b;"),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn safe_and_suggested_fixes() {
+        let source = "typeof a == 'string'; 1 != 2; a == null;";
+        assert_eq!(
+            fix("eqeqeq", source, Applicability::Safe),
+            "typeof a === 'string'; 1 !== 2; a == null;"
+        );
+        assert_eq!(
+            fix("eqeqeq", source, Applicability::Suggested),
+            "typeof a === 'string'; 1 !== 2; a === null;"
+        );
+    }
+
+    #[test]
+    fn operator_after_comment() {
+        assert_eq!(
+            check_script(
+                "eqeqeq",
+                "a /* == */ == b;
+(c) /* != */ != d;"
+            ),
+            [
+                "1:12 expected '===' and instead saw '=='",
+                "2:14 expected '!==' and instead saw '!='",
+            ]
+        );
+        assert_eq!(
+            fix("eqeqeq", "'a' /* == */ == 'b';", Applicability::Safe),
+            "'a' /* == */ === 'b';"
+        );
+    }
+}
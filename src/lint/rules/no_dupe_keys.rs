@@ -0,0 +1,111 @@
+//! `no-dupe-keys`: オブジェクトリテラルで同じキーを2回書かない。
+//!
+//! ゲッターとセッターの組は重複としない。
+
+use crate::{
+    ast::{
+        structs::{
+            expression::{ObjectExpression, ObjectProperty, PropertyKey, PropertyKind},
+            literal::LiteralValue,
+            span::{Span, Spanned},
+        },
+        visit::{Visit, VisitWith},
+    },
+    lint::{Context, Rule, Severity},
+};
+
+pub(super) struct NoDupeKeys;
+
+impl Rule for NoDupeKeys {
+    fn id(&self) -> &'static str {
+        "no-dupe-keys"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, context: &mut Context<'_>) {
+        let mut finder = Finder { found: Vec::new() };
+        context.ast.visit_with(&mut finder);
+        for (span, name) in finder.found {
+            context.report(span, format!("duplicate key '{name}'"));
+        }
+    }
+}
+
+struct Finder {
+    found: Vec<(Span, String)>,
+}
+
+impl<'ast> Visit<'ast> for Finder {
+    fn visit_object_expression(&mut self, node: &'ast ObjectExpression) {
+        // キーの名前と、値 (Init)、ゲッター、セッターのどれがあるか
+        let mut seen: Vec<(String, [bool; 3])> = Vec::new();
+        for property in &node.properties {
+            let ObjectProperty::Property(property) = property else {
+                continue;
+            };
+            let Some(name) = key_name(&property.key) else {
+                continue;
+            };
+            let slot = match property.kind {
+                PropertyKind::Init => 0,
+                PropertyKind::Get => 1,
+                PropertyKind::Set => 2,
+            };
+            let index = match seen.iter().position(|(seen, _)| *seen == name) {
+                Some(index) => index,
+                None => {
+                    seen.push((name.clone(), [false; 3]));
+                    seen.len() - 1
+                }
+            };
+            let kinds = &mut seen[index].1;
+            let duplicate = match slot {
+                0 => kinds.iter().any(|&kind| kind),
+                slot => kinds[0] || kinds[slot],
+            };
+            if duplicate {
+                self.found.push((property.key.span(), name));
+            }
+            kinds[slot] = true;
+        }
+        node.visit_children_with(self);
+    }
+}
+
+/// 計算されないキーの名前 (数値は `1` と `1.0` を同じ名前にする)
+fn key_name(key: &PropertyKey) -> Option<String> {
+    match key {
+        PropertyKey::Literal(literal) => match literal.value {
+            LiteralValue::Number(value) => Some(value.to_string()),
+            _ => key.static_name().map(str::to_string),
+        },
+        key => key.static_name().map(str::to_string),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::lint::rules::test::check_script;
+
+    #[test]
+    fn duplicate_keys() {
+        assert_eq!(
+            check_script(
+                "no-dupe-keys",
+                "({ a: 1, b: 2, 'a': 3 });\n({ 1: x, 1.0: y, [a]: z, [a]: w });"
+            ),
+            ["1:16 duplicate key 'a'", "2:10 duplicate key '1'"]
+        );
+        assert_eq!(
+            check_script("no-dupe-keys", "({ get a() {}, set a(v) {}, get a() {} });"),
+            ["1:33 duplicate key 'a'"]
+        );
+        assert_eq!(
+            check_script("no-dupe-keys", "({ a, ...rest, b: { a } });"),
+            Vec::<String>::new()
+        );
+    }
+}
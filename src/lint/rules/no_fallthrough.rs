@@ -0,0 +1,173 @@
+//! `no-fallthrough`: switch 文のケースの終わりから次のケースへ進まない。
+//!
+//! 制御フローグラフで、到達できるブロックから次のケースへのフォールスルーの辺があるものを
+//! 指摘する。次のケースの直前のコメントが `falls through` (または `fallthrough`) で始まれば
+//! 意図したものとする。
+
+use std::collections::HashSet;
+
+use crate::{
+    ast::{
+        cfg::{ControlFlowGraph, EdgeKind, Node},
+        structs::{
+            span::{Span, Spanned},
+            statement::SwitchStatement,
+        },
+        visit::{Visit, VisitWith},
+    },
+    lint::{Context, Rule, Severity},
+    token::structs::comment::Comment,
+};
+
+pub(super) struct NoFallthrough;
+
+impl Rule for NoFallthrough {
+    fn id(&self) -> &'static str {
+        "no-fallthrough"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, context: &mut Context<'_>) {
+        let mut finder = Finder {
+            source: context.source,
+            comments: context.comments,
+            fallen_into: fallen_into(context.graphs),
+            found: Vec::new(),
+        };
+        context.ast.visit_with(&mut finder);
+        for (span, label) in finder.found {
+            context.report(
+                span,
+                format!("expected a 'break' statement before '{label}'"),
+            );
+        }
+    }
+}
+
+struct Finder<'a> {
+    source: &'a str,
+    comments: &'a [(Comment, Span)],
+
+    /// フォールスルーで進めるケースの範囲
+    fallen_into: HashSet<Span>,
+    found: Vec<(Span, &'static str)>,
+}
+
+impl<'ast> Visit<'ast> for Finder<'_> {
+    fn visit_switch_statement(&mut self, node: &'ast SwitchStatement) {
+        for pair in node.cases.windows(2) {
+            let [previous, next] = pair else {
+                unreachable!()
+            };
+            let Some(last) = previous.consequent.last() else {
+                continue;
+            };
+            if !self.fallen_into.contains(&next.span) {
+                continue;
+            }
+            // 前のケースの最後の文と次のケースの間にある、最後のコメント
+            let before = self
+                .comments
+                .partition_point(|(_, span)| span.end <= next.span.start);
+            let comment = before
+                .checked_sub(1)
+                .map(|index| self.comments[index])
+                .filter(|(_, span)| span.start >= last.span().end);
+            if let Some((kind, span)) = comment {
+                if is_fallthrough_comment(kind, span.source_text(self.source)) {
+                    continue;
+                }
+            }
+            let label = if next.test.is_some() {
+                "case"
+            } else {
+                "default"
+            };
+            self.found.push((next.span, label));
+        }
+        node.visit_children_with(self);
+    }
+}
+
+/// 到達できるブロックからのフォールスルーの辺で進めるケースの範囲を集める。
+fn fallen_into(graphs: &[ControlFlowGraph]) -> HashSet<Span> {
+    let mut cases = HashSet::new();
+    for graph in graphs {
+        let reachable = graph.reachable();
+        for edge in &graph.edges {
+            if edge.kind != EdgeKind::Fallthrough || !reachable[edge.from.0] {
+                continue;
+            }
+            if let Some(Node::SwitchCase(case)) = graph.block(edge.to).nodes.first() {
+                cases.insert(case.span);
+            }
+        }
+    }
+    cases
+}
+
+/// コメントの中身が `falls through` `fall through` `fallthrough` などで始まるかを返す。
+fn is_fallthrough_comment(kind: Comment, text: &str) -> bool {
+    let text = match kind {
+        Comment::SingleLineComment => text.strip_prefix("//"),
+        Comment::MultiLineComment => text
+            .strip_prefix("/*")
+            .and_then(|text| text.strip_suffix("*/")),
+        Comment::HashBangComment => None,
+    };
+    let Some(text) = text else {
+        return false;
+    };
+    let text = text.trim_start().to_lowercase();
+    let text = text.strip_prefix("fall").unwrap_or_default();
+    let text = text.strip_prefix('s').unwrap_or(text);
+    let text = text.strip_prefix(' ').unwrap_or(text);
+    text.starts_with("through")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::lint::rules::test::check_script;
+
+    #[test]
+    fn fallthrough() {
+        assert_eq!(
+            check_script(
+                "no-fallthrough",
+                "switch (a) {\n  case 1:\n    f();\n  case 2:\n    g();\n  default:\n    h();\n}"
+            ),
+            [
+                "4:3 expected a 'break' statement before 'case'",
+                "6:3 expected a 'break' statement before 'default'",
+            ]
+        );
+    }
+
+    #[test]
+    fn terminated_cases() {
+        assert_eq!(
+            check_script(
+                "no-fallthrough",
+                "function f(a) {\n  switch (a) {\n    case 0:\n    case 1:\n      return 1;\n    case 2:\n      if (b) { break; } else { throw e; }\n    case 3:\n      g();\n      // falls through\n    default:\n      h();\n  }\n}"
+            ),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn fallthrough_comments() {
+        assert_eq!(
+            check_script(
+                "no-fallthrough",
+                "switch (a) {\n  case 1:\n    f(); // Fall through\n  case 2:\n    f(); /* fallthrough to 3 */\n  case 3:\n    f(); // falls through\n    // done\n  case 4:\n    f(); // not falls through\n  case 5:\n    f(); // fallsthrough\n  default:\n}"
+            ),
+            [
+                "9:3 expected a 'break' statement before 'case'",
+                "11:3 expected a 'break' statement before 'case'",
+            ]
+        );
+    }
+}
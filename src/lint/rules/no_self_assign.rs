@@ -0,0 +1,204 @@
+//! `no-self-assign`: 変数やプロパティを自身に代入しない。
+//!
+//! `a = a`、`a.b = a.b`、`[a, b] = [a, b]` の各要素を調べる。
+//! `a["b"] = a.b` のように、リテラルで書いたプロパティは値で比べる。
+
+use std::borrow::Cow;
+
+use crate::{
+    ast::{
+        structs::{
+            expression::{
+                AssignmentExpression, AssignmentOperator, Expression, ExpressionOrSpread,
+                MemberExpression, MemberProperty,
+            },
+            literal::{Identifier, LiteralValue},
+            pattern::Pattern,
+            span::{Span, Spanned},
+        },
+        visit::{Visit, VisitWith},
+    },
+    lint::{Context, Rule, Severity},
+};
+
+pub(super) struct NoSelfAssign;
+
+impl Rule for NoSelfAssign {
+    fn id(&self) -> &'static str {
+        "no-self-assign"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, context: &mut Context<'_>) {
+        let mut finder = Finder {
+            source: context.source,
+            found: Vec::new(),
+        };
+        context.ast.visit_with(&mut finder);
+        for (span, text) in finder.found {
+            context.report(span, format!("'{text}' is assigned to itself"));
+        }
+    }
+}
+
+struct Finder<'a> {
+    source: &'a str,
+    found: Vec<(Span, String)>,
+}
+
+impl Finder<'_> {
+    fn compare(&mut self, left: &Pattern, right: &Expression) {
+        match (left, right) {
+            (Pattern::Identifier(left), Expression::Identifier(right))
+                if left.name == right.name =>
+            {
+                self.found.push((right.span, right.name.clone()));
+            }
+            (Pattern::Expression(left), Expression::Member(right)) => {
+                if let Expression::Member(left) = &**left {
+                    if same_member(left, right) {
+                        let text = right.span.source_text(self.source).to_string();
+                        self.found.push((right.span, text));
+                    }
+                }
+            }
+            (Pattern::Array(left), Expression::Array(right)) => {
+                for (left, right) in left.elements.iter().zip(&right.elements) {
+                    match (left, right) {
+                        (Some(left), Some(ExpressionOrSpread::Expression(right))) => {
+                            self.compare(left, right)
+                        }
+                        // 残りの要素の後ろは比べない
+                        _ => {
+                            if matches!(left, Some(Pattern::Rest(_)))
+                                || matches!(right, Some(ExpressionOrSpread::Spread(_)))
+                            {
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for Finder<'_> {
+    fn visit_assignment_expression(&mut self, node: &'ast AssignmentExpression) {
+        if matches!(
+            node.operator,
+            AssignmentOperator::Assign
+                | AssignmentOperator::LogicalAndAssign
+                | AssignmentOperator::LogicalOrAssign
+                | AssignmentOperator::NullishCoalesceAssign
+        ) {
+            self.compare(&node.left, &node.right);
+        }
+        node.visit_children_with(self);
+    }
+}
+
+/// 識別子と `this` と静的なプロパティからなる、同じメンバー参照であるかを返す。
+fn same_member(left: &MemberExpression, right: &MemberExpression) -> bool {
+    let same_property = match (property_key(&left.property), property_key(&right.property)) {
+        (Some(left), Some(right)) => left == right,
+        _ => false,
+    };
+    same_property && same_object(&left.object, &right.object)
+}
+
+/// 参照するプロパティが静的に決まる場合の、プロパティの名前
+#[derive(PartialEq)]
+enum PropertyKey<'a> {
+    /// `a.b` と `a["b"]` と `a[1]`
+    Name(Cow<'a, str>),
+
+    /// `a.#b`
+    Private(&'a str),
+}
+
+fn property_key(property: &MemberProperty) -> Option<PropertyKey<'_>> {
+    match property {
+        MemberProperty::Identifier(identifier) => {
+            Some(PropertyKey::Name(Cow::Borrowed(&identifier.name)))
+        }
+        MemberProperty::PrivateIdentifier(identifier) => {
+            Some(PropertyKey::Private(&identifier.name))
+        }
+        MemberProperty::Computed(Expression::Literal(literal)) => match &literal.value {
+            LiteralValue::String(value) => Some(PropertyKey::Name(Cow::Borrowed(value))),
+            // 整数でない数値は文字列にした表記を求めないため、比べない
+            LiteralValue::Number(value) if value.fract() == 0.0 && value.abs() < 1e21 => {
+                Some(PropertyKey::Name(Cow::Owned(value.to_string())))
+            }
+            _ => None,
+        },
+        MemberProperty::Computed(_) => None,
+    }
+}
+
+fn same_object(left: &Expression, right: &Expression) -> bool {
+    match (left, right) {
+        (Expression::Identifier(Identifier { name: left, .. }), Expression::Identifier(right)) => {
+            *left == right.name
+        }
+        (Expression::This(_), Expression::This(_)) => true,
+        (Expression::Member(left), Expression::Member(right)) => same_member(left, right),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::lint::rules::test::check_script;
+
+    #[test]
+    fn self_assignments() {
+        assert_eq!(
+            check_script(
+                "no-self-assign",
+                "a = a;\nthis.x.y = this.x.y;\n[b, c] = [b, d];\nx ||= x;"
+            ),
+            [
+                "1:5 'a' is assigned to itself",
+                "2:12 'this.x.y' is assigned to itself",
+                "3:11 'b' is assigned to itself",
+                "4:7 'x' is assigned to itself",
+            ]
+        );
+        assert_eq!(
+            check_script(
+                "no-self-assign",
+                "a = b; a += a; a[i] = a[i]; f().x = f().x; a.b = a.c; [...a] = [a];"
+            ),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn literal_properties() {
+        assert_eq!(
+            check_script(
+                "no-self-assign",
+                "a[\"x\"] = a[\"x\"];\na.x = a['x'];\na[0] = a['0'];\na[10] = a[0xa];"
+            ),
+            [
+                "1:10 'a[\"x\"]' is assigned to itself",
+                "2:7 'a['x']' is assigned to itself",
+                "3:8 'a['0']' is assigned to itself",
+                "4:9 'a[0xa]' is assigned to itself",
+            ]
+        );
+        assert_eq!(
+            check_script(
+                "no-self-assign",
+                "a['x'] = a['y']; a[1] = a['1.0']; a[0.5] = a[0.5]; a[x] = a['x'];"
+            ),
+            Vec::<String>::new()
+        );
+    }
+}
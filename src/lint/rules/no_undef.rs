@@ -0,0 +1,160 @@
+//! `no-undef`: 宣言されていない変数を使わない。
+//!
+//! 組み込みのグローバル変数と、`typeof` の引数は指摘しない。
+//! `with` の中の参照はオブジェクトのプロパティでありうるため指摘しない。
+
+use crate::{
+    ast::{
+        structs::{
+            expression::{Expression, UnaryExpression, UnaryOperator},
+            span::Span,
+        },
+        visit::{Visit, VisitWith},
+    },
+    lint::{Context, Rule, Severity},
+};
+
+/// ECMAScript の組み込みと、ブラウザと Node.js に共通するグローバル変数
+const GLOBALS: &[&str] = &[
+    "AggregateError",
+    "Array",
+    "ArrayBuffer",
+    "Atomics",
+    "BigInt",
+    "BigInt64Array",
+    "BigUint64Array",
+    "Boolean",
+    "DataView",
+    "Date",
+    "Error",
+    "EvalError",
+    "FinalizationRegistry",
+    "Float32Array",
+    "Float64Array",
+    "Function",
+    "Infinity",
+    "Int16Array",
+    "Int32Array",
+    "Int8Array",
+    "Intl",
+    "JSON",
+    "Map",
+    "Math",
+    "NaN",
+    "Number",
+    "Object",
+    "Promise",
+    "Proxy",
+    "RangeError",
+    "ReferenceError",
+    "Reflect",
+    "RegExp",
+    "Set",
+    "SharedArrayBuffer",
+    "String",
+    "Symbol",
+    "SyntaxError",
+    "TypeError",
+    "URIError",
+    "Uint16Array",
+    "Uint32Array",
+    "Uint8Array",
+    "Uint8ClampedArray",
+    "WeakMap",
+    "WeakRef",
+    "WeakSet",
+    "clearInterval",
+    "clearTimeout",
+    "console",
+    "decodeURI",
+    "decodeURIComponent",
+    "encodeURI",
+    "encodeURIComponent",
+    "escape",
+    "eval",
+    "globalThis",
+    "isFinite",
+    "isNaN",
+    "parseFloat",
+    "parseInt",
+    "queueMicrotask",
+    "setInterval",
+    "setTimeout",
+    "structuredClone",
+    "undefined",
+    "unescape",
+];
+
+pub(super) struct NoUndef;
+
+impl Rule for NoUndef {
+    fn id(&self) -> &'static str {
+        "no-undef"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, context: &mut Context<'_>) {
+        let mut typeof_arguments = TypeofArguments { spans: Vec::new() };
+        context.ast.visit_with(&mut typeof_arguments);
+
+        let undefined: Vec<(Span, String)> = context
+            .scopes
+            .unresolved()
+            .filter(|reference| !reference.dynamic)
+            .filter(|reference| GLOBALS.binary_search(&reference.name.as_str()).is_err())
+            .filter(|reference| !typeof_arguments.spans.contains(&reference.span))
+            .map(|reference| (reference.span, reference.name.clone()))
+            .collect();
+        for (span, name) in undefined {
+            context.report(span, format!("'{name}' is not defined"));
+        }
+    }
+}
+
+/// `typeof a` の `a` の範囲を集める。
+struct TypeofArguments {
+    spans: Vec<Span>,
+}
+
+impl<'ast> Visit<'ast> for TypeofArguments {
+    fn visit_unary_expression(&mut self, node: &'ast UnaryExpression) {
+        if let (UnaryOperator::Typeof, Expression::Identifier(identifier)) =
+            (node.operator, &node.argument)
+        {
+            self.spans.push(identifier.span);
+        }
+        node.visit_children_with(self);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::GLOBALS;
+    use crate::lint::rules::test::check_script;
+
+    #[test]
+    fn globals_are_sorted() {
+        assert!(GLOBALS.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn undefined_variables() {
+        assert_eq!(
+            check_script(
+                "no-undef",
+                "let a = b;\nfunction f(c) { return c + d + a; }\nconsole.log(Math.max(1, 2));"
+            ),
+            ["1:9 'b' is not defined", "2:28 'd' is not defined"]
+        );
+        assert_eq!(
+            check_script(
+                "no-undef",
+                "if (typeof window !== 'undefined') {}\nwith (o) { p; }\nundeclared = 1;"
+            ),
+            ["2:7 'o' is not defined", "3:1 'undeclared' is not defined"]
+        );
+    }
+}
@@ -0,0 +1,124 @@
+//! `no-unreachable`: `return`、`throw`、`break`、`continue` の後に実行されない文を書かない。
+//!
+//! 制御フローグラフで入口から辿れないブロックにしかない文を指摘する。
+//! 巻き上げられる関数宣言と初期化のない `var` は実行されなくても意味があるため除く。
+
+use std::collections::HashMap;
+
+use crate::{
+    ast::{
+        cfg::Node,
+        structs::{
+            span::{Span, Spanned},
+            statement::{Statement, VariableKind},
+        },
+    },
+    lint::{Context, Rule, Severity},
+};
+
+pub(super) struct NoUnreachable;
+
+impl Rule for NoUnreachable {
+    fn id(&self) -> &'static str {
+        "no-unreachable"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, context: &mut Context<'_>) {
+        let mut unreachable = Vec::new();
+        for graph in context.graphs {
+            let reachable = graph.reachable();
+
+            // finally の文は複数のブロックにあるため、どれかに到達できれば到達できる
+            let mut statements: HashMap<Span, (&Statement, bool)> = HashMap::new();
+            for (index, block) in graph.blocks.iter().enumerate() {
+                for node in &block.nodes {
+                    if let Node::Statement(statement) = node {
+                        statements
+                            .entry(statement.span())
+                            .or_insert((statement, false))
+                            .1 |= reachable[index];
+                    }
+                }
+            }
+            unreachable.extend(
+                statements
+                    .into_values()
+                    .filter(|&(statement, reachable)| !reachable && is_reportable(statement))
+                    .map(|(statement, _)| statement.span()),
+            );
+        }
+        unreachable.sort();
+
+        // 他の到達できない文の中にある文は除き、空白だけを挟んで続く文はまとめる
+        let mut merged: Vec<Span> = Vec::new();
+        for span in unreachable {
+            match merged.last_mut() {
+                Some(last) if span.end <= last.end => {}
+                Some(last)
+                    if Span::new(last.end, span.start)
+                        .source_text(context.source)
+                        .trim()
+                        .is_empty() =>
+                {
+                    last.end = span.end;
+                }
+                _ => merged.push(span),
+            }
+        }
+        for span in merged {
+            context.report(span, "unreachable code");
+        }
+    }
+}
+
+fn is_reportable(statement: &Statement) -> bool {
+    match statement {
+        Statement::FunctionDeclaration(_) | Statement::Empty(_) => false,
+        Statement::VariableDeclaration(declaration) => {
+            declaration.kind != VariableKind::Var
+                || declaration
+                    .declarations
+                    .iter()
+                    .any(|declarator| declarator.init.is_some())
+        }
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::lint::rules::test::check_script;
+
+    #[test]
+    fn unreachable_statements() {
+        assert_eq!(
+            check_script(
+                "no-unreachable",
+                "function f() {\n  return 1;\n  g();\n  if (a) { h(); }\n  var x;\n  function g() {}\n  var y = 2;\n}"
+            ),
+            ["3:3 unreachable code", "7:3 unreachable code"]
+        );
+        assert_eq!(
+            check_script(
+                "no-unreachable",
+                "while (true) {\n  if (a) { break; continue; }\n  throw e;\n}\nf();"
+            ),
+            ["2:19 unreachable code"]
+        );
+    }
+
+    #[test]
+    fn reachable_statements() {
+        assert_eq!(
+            check_script(
+                "no-unreachable",
+                "function f() {\n  try { return g(); } finally { h(); }\n}\nswitch (a) { case 1: break; default: i(); }\nfor (;;) { if (b) break; }\nj();"
+            ),
+            Vec::<String>::new()
+        );
+    }
+}
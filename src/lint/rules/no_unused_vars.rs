@@ -0,0 +1,341 @@
+//! `no-unused-vars`: 宣言した変数を読まずに残さない。
+//!
+//! 書き込みしかない変数も指摘する。仮引数は最後に使われた仮引数より後ろのものだけを指摘する。
+//! export される宣言と、直接の `eval` から見える変数は除く。
+//!
+//! 使われない import は取り除く修正を付ける。全ての名前が使われない import 文を消すと
+//! モジュールの副作用もなくなるため、その場合は提案にとどめる。
+
+use std::collections::HashSet;
+
+use crate::{
+    ast::{
+        scope::{Binding, BindingKind, ScopeId, SymbolTable},
+        structs::{
+            module::{
+                Declaration, ExportDefaultKind, ExportNamedDeclaration, ImportDeclaration,
+                ImportDeclarationSpecifier,
+            },
+            span::{Span, Spanned},
+        },
+        visit::{Visit, VisitWith},
+    },
+    lint::{Context, Fix, Rule, Severity, TextEdit},
+};
+
+pub(super) struct NoUnusedVars;
+
+impl Rule for NoUnusedVars {
+    fn id(&self) -> &'static str {
+        "no-unused-vars"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, context: &mut Context<'_>) {
+        let mut exports = Exports { spans: Vec::new() };
+        context.ast.visit_with(&mut exports);
+        let mut imports = Imports {
+            declarations: Vec::new(),
+        };
+        context.ast.visit_with(&mut imports);
+        let scopes = context.scopes;
+
+        // 直接の eval があるスコープと、その外側のスコープ
+        let mut eval_visible = vec![false; scopes.scopes.len()];
+        for (index, scope) in scopes.scopes.iter().enumerate() {
+            if scope.direct_eval {
+                for ancestor in scopes.ancestors(ScopeId(index)) {
+                    eval_visible[ancestor.0] = true;
+                }
+            }
+        }
+
+        // Annex B のブロックの関数は、ブロックと関数スコープに同じ宣言の束縛を持つ。
+        // どちらかが読まれていれば、両方とも使われているとみなす。
+        let used_functions: HashSet<Span> = scopes
+            .bindings
+            .iter()
+            .filter(|binding| binding.kind == BindingKind::Function && is_used(scopes, binding))
+            .map(|binding| binding.declarations[0])
+            .collect();
+
+        let mut unused = Vec::new();
+        for binding in &scopes.bindings {
+            let checked = matches!(
+                binding.kind,
+                BindingKind::Var
+                    | BindingKind::Let
+                    | BindingKind::Const
+                    | BindingKind::Class
+                    | BindingKind::Function
+                    | BindingKind::Parameter
+                    | BindingKind::CatchParameter
+                    | BindingKind::Import
+            );
+            if !checked || eval_visible[binding.scope.0] || is_used(scopes, binding) {
+                continue;
+            }
+            if binding.kind == BindingKind::Function
+                && used_functions.contains(&binding.declarations[0])
+            {
+                continue;
+            }
+            if unused
+                .iter()
+                .any(|(span, _)| *span == binding.declarations[0])
+            {
+                continue;
+            }
+            if binding
+                .declarations
+                .iter()
+                .any(|span| exports.spans.contains(span))
+            {
+                continue;
+            }
+            if binding.kind == BindingKind::Parameter && has_used_parameter_after(scopes, binding) {
+                continue;
+            }
+            unused.push((binding.declarations[0], binding.name.clone()));
+        }
+
+        let unused_spans: Vec<Span> = unused.iter().map(|(span, _)| *span).collect();
+        for (span, name) in unused {
+            let message = format!("'{name}' is defined but never used");
+            let fix = imports.declarations.iter().find_map(|declaration| {
+                remove_import(context.source, declaration, span, &unused_spans)
+            });
+            match fix {
+                Some(fix) => context.report_with_fix(span, message, fix),
+                None => context.report(span, message),
+            }
+        }
+    }
+}
+
+fn is_used(scopes: &SymbolTable, binding: &Binding) -> bool {
+    binding
+        .references
+        .iter()
+        .any(|&id| scopes.reference(id).access.is_read())
+}
+
+/// 同じ関数で、後ろに使われる仮引数があるかを返す。
+fn has_used_parameter_after(scopes: &SymbolTable, parameter: &Binding) -> bool {
+    scopes
+        .scope(parameter.scope)
+        .bindings
+        .iter()
+        .map(|&id| scopes.binding(id))
+        .filter(|binding| binding.kind == BindingKind::Parameter)
+        .any(|binding| {
+            binding.declarations[0].start > parameter.declarations[0].start
+                && is_used(scopes, binding)
+        })
+}
+
+/// import 文から名前 `local` を取り除く修正を返す (`local` がこの文の名前でなければ `None`)。
+fn remove_import(
+    source: &str,
+    declaration: &ImportDeclaration,
+    local: Span,
+    unused: &[Span],
+) -> Option<Fix> {
+    let specifiers = &declaration.specifiers;
+    let index = specifiers
+        .iter()
+        .position(|specifier| local_name(specifier) == local)?;
+
+    if specifiers
+        .iter()
+        .all(|specifier| unused.contains(&local_name(specifier)))
+    {
+        let mut span = declaration.span;
+        if source[span.end..].starts_with('\n') {
+            span.end += 1;
+        }
+        return Some(Fix::suggested(vec![TextEdit::delete(span)]));
+    }
+
+    let specifier = &specifiers[index];
+    let span = match specifier {
+        // `a, { b }` の `a, ` と `a, * as b` の `a, `
+        ImportDeclarationSpecifier::Default(_) => {
+            let rest = &source[specifier.span().end..declaration.span.end];
+            let comma = rest.find(',')? + 1;
+            let space = rest[comma..].len() - rest[comma..].trim_start().len();
+            Span::new(specifier.span().start, specifier.span().end + comma + space)
+        }
+        // `a, * as b` の `, * as b`
+        ImportDeclarationSpecifier::Namespace(_) => {
+            Span::new(specifiers[index - 1].span().end, specifier.span().end)
+        }
+        ImportDeclarationSpecifier::Named(_) => {
+            let named: Vec<Span> = specifiers
+                .iter()
+                .filter(|specifier| matches!(specifier, ImportDeclarationSpecifier::Named(_)))
+                .map(Spanned::span)
+                .collect();
+            let position = named.iter().position(|&span| span == specifier.span())?;
+            match (position.checked_sub(1), named.get(position + 1)) {
+                // `{ a, b }` の `a, `
+                (_, Some(next)) => Span::new(named[position].start, next.start),
+                // `{ a, b }` の `, b`
+                (Some(previous), None) => Span::new(named[previous].end, named[position].end),
+                // `a, { b }` の `, { b }`
+                (None, None) => {
+                    let close = source[named[position].end..declaration.span.end].find('}')?;
+                    Span::new(
+                        specifiers[index - 1].span().end,
+                        named[position].end + close + 1,
+                    )
+                }
+            }
+        }
+    };
+    Some(Fix::safe(vec![TextEdit::delete(span)]))
+}
+
+fn local_name(specifier: &ImportDeclarationSpecifier) -> Span {
+    match specifier {
+        ImportDeclarationSpecifier::Named(specifier) => specifier.local.span,
+        ImportDeclarationSpecifier::Default(specifier) => specifier.local.span,
+        ImportDeclarationSpecifier::Namespace(specifier) => specifier.local.span,
+    }
+}
+
+/// import 文を集める。
+struct Imports<'ast> {
+    declarations: Vec<&'ast ImportDeclaration>,
+}
+
+impl<'ast> Visit<'ast> for Imports<'ast> {
+    fn visit_import_declaration(&mut self, node: &'ast ImportDeclaration) {
+        self.declarations.push(node);
+    }
+}
+
+/// export される宣言の名前の範囲を集める。
+struct Exports {
+    spans: Vec<Span>,
+}
+
+impl Exports {
+    fn declaration(&mut self, declaration: &Declaration) {
+        match declaration {
+            Declaration::Function(function) => {
+                self.spans.extend(function.id.as_ref().map(|id| id.span))
+            }
+            Declaration::Class(class) => self.spans.extend(class.id.as_ref().map(|id| id.span)),
+            Declaration::Variable(declaration) => {
+                for declarator in &declaration.declarations {
+                    self.spans
+                        .extend(declarator.id.bound_names().iter().map(|id| id.span));
+                }
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for Exports {
+    fn visit_export_named_declaration(&mut self, node: &'ast ExportNamedDeclaration) {
+        if let Some(declaration) = &node.declaration {
+            self.declaration(declaration);
+        }
+    }
+
+    fn visit_export_default_kind(&mut self, node: &'ast ExportDefaultKind) {
+        match node {
+            ExportDefaultKind::Function(function) => {
+                self.spans.extend(function.id.as_ref().map(|id| id.span))
+            }
+            ExportDefaultKind::Class(class) => {
+                self.spans.extend(class.id.as_ref().map(|id| id.span))
+            }
+            ExportDefaultKind::Expression(_) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        ast::structs::SourceType,
+        lint::{
+            rules::test::{check, check_script, fix_as},
+            Applicability,
+        },
+    };
+
+    #[test]
+    fn unused_variables() {
+        assert_eq!(
+            check_script(
+                "no-unused-vars",
+                "var a = 1, b = 2;\nlet c;\nc = b;\nfunction f(x, y, z) { return y; }\ntry {} catch (e) {}\nclass C {}\nf(new C());"
+            ),
+            [
+                "1:5 'a' is defined but never used",
+                "2:5 'c' is defined but never used",
+                "4:18 'z' is defined but never used",
+                "5:15 'e' is defined but never used",
+            ]
+        );
+        assert_eq!(
+            check_script(
+                "no-unused-vars",
+                "let n = 0;\nn++;\nfunction g(s) { eval(s); var hidden; }\n(function h() {})();"
+            ),
+            Vec::<String>::new()
+        );
+        // Annex B のブロックの関数は、ブロックの内でも外でも使える
+        assert_eq!(
+            check_script("no-unused-vars", "{ function g() {} }\ng();"),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            check_script("no-unused-vars", "{ function g() {} g(); }"),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            check_script("no-unused-vars", "{ function g() {} }"),
+            ["1:12 'g' is defined but never used"]
+        );
+    }
+
+    #[test]
+    fn exports() {
+        assert_eq!(
+            check(
+                "no-unused-vars",
+                "import a, { b } from 'm';\nexport const c = b;\nexport default function d() {}\nlet e;\nexport { e };",
+                SourceType::Module
+            ),
+            ["1:8 'a' is defined but never used"]
+        );
+    }
+
+    #[test]
+    fn remove_unused_imports() {
+        let fix = |source, applicability| {
+            fix_as("no-unused-vars", source, SourceType::Module, applicability)
+        };
+        assert_eq!(
+            fix(
+                "import a, { b, c as d, e } from 'm';\nimport * as f from 'n';\nimport g, * as h from 'o';\nimport i, { j } from 'p';\nexport default [c, e, g, i];\n",
+                Applicability::Safe
+            ),
+            "import { e } from 'm';\nimport * as f from 'n';\nimport g from 'o';\nimport i from 'p';\nexport default [c, e, g, i];\n"
+        );
+        assert_eq!(
+            fix(
+                "import a, { b } from 'm';\nimport * as f from 'n';\nexport {};\n",
+                Applicability::Suggested
+            ),
+            "export {};\n"
+        );
+    }
+}
@@ -0,0 +1,144 @@
+//! `semi`: 文の終わりの `;` を省略しない。
+//!
+//! 自動セミコロン挿入に頼った文の終わりに `;` を補う。書き方の規則のため既定では実行しない。
+
+use crate::{
+    ast::{
+        structs::{
+            class::PropertyDefinition,
+            module::{Declaration, ExportDefaultKind, ModuleDeclaration},
+            span::{Span, Spanned},
+            statement::Statement,
+        },
+        visit::{Visit, VisitWith},
+    },
+    lint::{Context, Fix, Rule, Severity, TextEdit},
+};
+
+pub(super) struct Semi;
+
+impl Rule for Semi {
+    fn id(&self) -> &'static str {
+        "semi"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Off
+    }
+
+    fn check(&self, context: &mut Context<'_>) {
+        let mut finder = Finder {
+            source: context.source,
+            found: Vec::new(),
+        };
+        context.ast.visit_with(&mut finder);
+        for end in finder.found {
+            context.report_with_fix(
+                Span::new(end, end),
+                "missing semicolon",
+                Fix::safe(vec![TextEdit::insert(end, ";")]),
+            );
+        }
+    }
+}
+
+struct Finder<'a> {
+    source: &'a str,
+
+    /// `;` がない文の終わりの位置
+    found: Vec<usize>,
+}
+
+impl Finder<'_> {
+    fn check(&mut self, span: Span) {
+        if !span.source_text(self.source).ends_with(';') {
+            self.found.push(span.end);
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for Finder<'_> {
+    fn visit_statement(&mut self, node: &'ast Statement) {
+        match node {
+            Statement::Expression(_)
+            | Statement::VariableDeclaration(_)
+            | Statement::Return(_)
+            | Statement::Break(_)
+            | Statement::Continue(_)
+            | Statement::Throw(_)
+            | Statement::Debugger(_)
+            | Statement::DoWhile(_) => self.check(node.span()),
+            _ => {}
+        }
+        node.visit_children_with(self);
+    }
+
+    fn visit_module_declaration(&mut self, node: &'ast ModuleDeclaration) {
+        let needs_semicolon = match node {
+            ModuleDeclaration::Import(_) | ModuleDeclaration::ExportAll(_) => true,
+            ModuleDeclaration::ExportNamed(export) => {
+                matches!(export.declaration, None | Some(Declaration::Variable(_)))
+            }
+            ModuleDeclaration::ExportDefault(export) => {
+                matches!(export.declaration, ExportDefaultKind::Expression(_))
+            }
+        };
+        if needs_semicolon {
+            self.check(node.span());
+        }
+        node.visit_children_with(self);
+    }
+
+    fn visit_property_definition(&mut self, node: &'ast PropertyDefinition) {
+        self.check(node.span);
+        node.visit_children_with(self);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        ast::structs::SourceType,
+        lint::{
+            rules::test::{check, check_script, fix},
+            Applicability,
+        },
+    };
+
+    #[test]
+    fn missing_semicolons() {
+        assert_eq!(
+            check_script(
+                "semi",
+                "let a = 1\nfor (let i = 0; i < 1; i++) f()\ndo {} while (a)\nclass C { x = 1 }\nfunction g() { return }"
+            ),
+            [
+                "1:10 missing semicolon",
+                "2:32 missing semicolon",
+                "3:16 missing semicolon",
+                "4:16 missing semicolon",
+                "5:22 missing semicolon",
+            ]
+        );
+        assert_eq!(
+            check(
+                "semi",
+                "import a from 'a'\nexport { a }\nexport default a\nexport function f() {}\nexport * from 'b';",
+                SourceType::Module
+            ),
+            [
+                "1:18 missing semicolon",
+                "2:13 missing semicolon",
+                "3:17 missing semicolon",
+            ]
+        );
+    }
+
+    #[test]
+    fn insert_semicolons() {
+        assert_eq!(
+            fix("semi", "a = 1\nif (a) b()\n{ c() }", Applicability::Safe),
+            "a = 1;\nif (a) b();\n{ c(); }"
+        );
+    }
+}
@@ -0,0 +1,249 @@
+//! コメントの指示で指摘を抑える。
+//!
+//! - `// dwji-ignore-next-line a, b` は次の行の指摘を抑える。
+//! - `/* dwji-disable a */` は `/* dwji-enable a */` (なければファイルの終わり) までの指摘を抑える。
+//! - `/* dwji-disable-file a */` はファイル全体の指摘を抑える。
+//!
+//! 規則を書かなければ全ての規則を抑える。`--` の後ろは説明として読み飛ばす。
+//! 何も抑えなかった指示と、知らない規則の名前を書いた指示は [`UNUSED_SUPPRESSION`] として指摘する。
+
+use super::{Diagnostic, Severity};
+use crate::{ast::structs::span::Span, token::structs::comment::Comment};
+
+/// 使われない抑制の指摘の名前
+pub const UNUSED_SUPPRESSION: &str = "unused-suppression";
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum DirectiveKind {
+    IgnoreNextLine,
+    Disable,
+    Enable,
+    DisableFile,
+}
+
+impl DirectiveKind {
+    fn parse(text: &str) -> Option<DirectiveKind> {
+        match text {
+            "dwji-ignore-next-line" => Some(DirectiveKind::IgnoreNextLine),
+            "dwji-disable" => Some(DirectiveKind::Disable),
+            "dwji-enable" => Some(DirectiveKind::Enable),
+            "dwji-disable-file" => Some(DirectiveKind::DisableFile),
+            _ => None,
+        }
+    }
+}
+
+/// 1つの規則 (または全ての規則) の指摘を抑える範囲
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Suppression {
+    /// 指示のコメントの範囲
+    comment: Span,
+
+    /// `None` は全ての規則
+    rule: Option<String>,
+
+    /// 抑える指摘の始まりの位置の範囲
+    range: Span,
+    used: bool,
+}
+
+/// 指示のコメントから抑える範囲を作る。
+fn suppressions(source: &str, comments: &[(Comment, Span)]) -> Vec<Suppression> {
+    let mut suppressions: Vec<Suppression> = Vec::new();
+
+    // dwji-disable で始まり、まだ閉じていない範囲の添字
+    let mut open: Vec<usize> = Vec::new();
+
+    for &(comment, span) in comments {
+        let Some((kind, rules)) = directive(comment, span.source_text(source)) else {
+            continue;
+        };
+        let rules: Vec<Option<String>> = match rules.is_empty() {
+            true => vec![None],
+            false => rules.into_iter().map(Some).collect(),
+        };
+        match kind {
+            DirectiveKind::Enable => open.retain(|&index| {
+                let suppression = &mut suppressions[index];
+                let close = rules.contains(&None) || rules.contains(&suppression.rule);
+                if close {
+                    suppression.range.end = span.start;
+                }
+                !close
+            }),
+            kind => {
+                let range = match kind {
+                    DirectiveKind::IgnoreNextLine => next_line(source, span.end),
+                    DirectiveKind::Disable => Span::new(span.end, source.len()),
+                    _ => Span::new(0, source.len()),
+                };
+                for rule in rules {
+                    if kind == DirectiveKind::Disable {
+                        open.push(suppressions.len());
+                    }
+                    suppressions.push(Suppression {
+                        comment: span,
+                        rule,
+                        range,
+                        used: false,
+                    });
+                }
+            }
+        }
+    }
+    suppressions
+}
+
+/// コメントが指示であれば、種類と規則の名前を返す。
+fn directive(comment: Comment, text: &str) -> Option<(DirectiveKind, Vec<String>)> {
+    let body = match comment {
+        Comment::SingleLineComment => text.strip_prefix("//")?,
+        Comment::MultiLineComment => text.strip_prefix("/*")?.strip_suffix("*/")?,
+        Comment::HashBangComment => return None,
+    };
+    let body = body.split(" --").next().unwrap_or_default().trim();
+    let (name, rules) = body.split_once(char::is_whitespace).unwrap_or((body, ""));
+    let kind = DirectiveKind::parse(name)?;
+    let rules = rules
+        .split(|char: char| char == ',' || char.is_whitespace())
+        .filter(|rule| !rule.is_empty())
+        .map(str::to_string)
+        .collect();
+    Some((kind, rules))
+}
+
+/// `position` の次の行の範囲 (改行を含まない)
+fn next_line(source: &str, position: usize) -> Span {
+    let Some((newline, length)) = line_terminator(&source[position..]) else {
+        return Span::new(source.len(), source.len());
+    };
+    let start = position + newline + length;
+    let end = line_terminator(&source[start..]).map_or(source.len(), |(offset, _)| start + offset);
+    Span::new(start, end)
+}
+
+/// 最初の改行 (`\r\n` `\n` `\r` U+2028 U+2029) の位置とバイト数
+fn line_terminator(text: &str) -> Option<(usize, usize)> {
+    let (index, char) = text
+        .char_indices()
+        .find(|(_, char)| matches!(char, '\n' | '\r' | '\u{2028}' | '\u{2029}'))?;
+    let length = match text[index..].starts_with("\r\n") {
+        true => 2,
+        false => char.len_utf8(),
+    };
+    Some((index, length))
+}
+
+/// 指示で抑えられた指摘を取り除き、使われない指示の指摘を `severity` で加える。
+///
+/// `known` は登録された規則の名前、`enabled` は実行した規則の名前で、
+/// 実行しなかった規則への指示は使われないものとしない。
+pub(super) fn apply(
+    source: &str,
+    comments: &[(Comment, Span)],
+    diagnostics: Vec<Diagnostic>,
+    known: &[&str],
+    enabled: &[&str],
+    severity: Severity,
+) -> Vec<Diagnostic> {
+    let mut suppressions = suppressions(source, comments);
+    if suppressions.is_empty() {
+        return diagnostics;
+    }
+
+    let mut result: Vec<Diagnostic> = diagnostics
+        .into_iter()
+        .filter(|diagnostic| {
+            let mut suppressed = false;
+            for suppression in &mut suppressions {
+                let matches = suppression
+                    .rule
+                    .as_ref()
+                    .is_none_or(|rule| rule == diagnostic.rule);
+                // 行の終わりの指摘も抑えるため、範囲の終わりを含める
+                let position = diagnostic.span.start;
+                if matches
+                    && suppression.range.start <= position
+                    && position <= suppression.range.end
+                {
+                    suppression.used = true;
+                    suppressed = true;
+                }
+            }
+            !suppressed
+        })
+        .collect();
+
+    if severity != Severity::Off {
+        for suppression in suppressions {
+            let message = match &suppression.rule {
+                Some(rule) if rule != UNUSED_SUPPRESSION && !known.contains(&rule.as_str()) => {
+                    format!("unknown rule '{rule}' in suppression")
+                }
+                _ if suppression.used => continue,
+                Some(rule) if !enabled.contains(&rule.as_str()) => continue,
+                Some(rule) => format!("unused suppression for '{rule}'"),
+                None => "unused suppression".to_string(),
+            };
+            result.push(Diagnostic {
+                rule: UNUSED_SUPPRESSION,
+                severity,
+                span: suppression.comment,
+                message,
+                fix: None,
+            });
+        }
+        result.sort_by(|a, b| a.span.cmp(&b.span).then(a.rule.cmp(b.rule)));
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::{directive, next_line, DirectiveKind};
+    use crate::{ast::structs::span::Span, token::structs::comment::Comment};
+
+    #[test]
+    fn directives() {
+        assert_eq!(
+            directive(
+                Comment::SingleLineComment,
+                "// dwji-ignore-next-line eqeqeq, no-undef -- legacy code"
+            ),
+            Some((
+                DirectiveKind::IgnoreNextLine,
+                vec!["eqeqeq".to_string(), "no-undef".to_string()]
+            ))
+        );
+        assert_eq!(
+            directive(Comment::MultiLineComment, "/*dwji-disable*/"),
+            Some((DirectiveKind::Disable, Vec::new()))
+        );
+        assert_eq!(
+            directive(Comment::MultiLineComment, "/* dwji-disable-file semi */"),
+            Some((DirectiveKind::DisableFile, vec!["semi".to_string()]))
+        );
+        assert_eq!(
+            directive(Comment::SingleLineComment, "// dwji-disabled"),
+            None
+        );
+        assert_eq!(
+            directive(Comment::SingleLineComment, "// see dwji-disable"),
+            None
+        );
+    }
+
+    #[test]
+    fn next_lines() {
+        let source = "a // x\nbb\nc";
+        assert_eq!(next_line(source, 6), Span::new(7, 9));
+        assert_eq!(next_line(source, 9), Span::new(10, 11));
+        assert_eq!(next_line(source, 11), Span::new(11, 11));
+
+        let source = "a\r\nbb\rc\u{2028}d\u{2029}e";
+        assert_eq!(next_line(source, 0), Span::new(3, 5));
+        assert_eq!(next_line(source, 3), Span::new(6, 7));
+        assert_eq!(next_line(source, 6), Span::new(10, 11));
+        assert_eq!(next_line(source, 10), Span::new(14, 15));
+    }
+}
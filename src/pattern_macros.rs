@@ -44,6 +44,7 @@ macro_rules! character_patterns {
             | '^'
             | '~'
             | '?'
+            | ':'
     };
 
     (Punctuator div) => {
@@ -1,3 +1,5 @@
+// 仕様の名前に合わせる
+#[allow(clippy::enum_variant_names)]
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub enum Comment {
     SingleLineComment,
@@ -1,8 +1,12 @@
+/// `/` を割り算と正規表現のどちらとして読むか
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub enum DivContext {
     RegExp,
     Divide,
 }
 
+/// `}` をテンプレートの続きとブロックの終わりのどちらとして読むか
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub enum BraceContext {
     Template,
     Block,
@@ -43,6 +43,9 @@ pub enum Literal {
 
     /// "abc"
     DoubleString,
+
+    /// /abc/g
+    RegularExpression,
 }
 
 impl ToSourceString for Literal {
@@ -62,6 +65,8 @@ impl ToSourceString for Literal {
             Literal::LegacyOctalInteger => todo!(),
             Literal::SingleString => todo!(),
             Literal::DoubleString => todo!(),
+            // 本体とフラグはソースコードにだけある ([`SourceToken`](super::token::SourceToken))
+            Literal::RegularExpression => String::new(),
         }
     }
 }
@@ -3,6 +3,7 @@
 use super::comment::Comment;
 use super::literal::Literal;
 use super::punctuator::Punctuator;
+use super::template::Template;
 use crate::to_source_string::ToSourceString;
 
 #[derive(PartialEq, Eq, Debug, Clone)]
@@ -42,11 +43,57 @@ impl Token {
 
     pub fn new(token_type: TokenType, start_position: usize, end_position: usize) -> Token {
         Token {
-            token_type: token_type,
+            token_type,
             token_start_index: start_position,
             token_end_index: end_position,
         }
     }
+
+    pub fn token_type(&self) -> TokenType {
+        self.token_type
+    }
+
+    pub fn start_index(&self) -> usize {
+        self.token_start_index
+    }
+
+    pub fn end_index(&self) -> usize {
+        self.token_end_index
+    }
+
+    /// ソースコード上の表記を切り出す。
+    pub fn source_text<'a>(&self, source: &'a str) -> &'a str {
+        &source[self.token_start_index..self.token_end_index]
+    }
+
+    /// 位置をずらしたトークンを返す。
+    pub fn offset(self, offset: usize) -> Token {
+        Token {
+            token_start_index: self.token_start_index + offset,
+            token_end_index: self.token_end_index + offset,
+            ..self
+        }
+    }
+}
+
+/// ソースコードと組にしたトークン
+///
+/// 表記をソースコードにだけ持つトークン (`#abc` やテンプレート、正規表現) も文字列にできる。
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct SourceToken<'a> {
+    pub token: &'a Token,
+    pub source: &'a str,
+}
+
+impl ToSourceString for SourceToken<'_> {
+    fn to_source_string(&self) -> String {
+        match self.token.token_type {
+            TokenType::WhiteSpace | TokenType::LineTerminator | TokenType::EndOfFile => {
+                self.token.token_type.to_source_string()
+            }
+            _ => self.token.source_text(self.source).to_string(),
+        }
+    }
 }
 
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
@@ -56,9 +103,15 @@ pub enum TokenType {
     LineTerminator,
     Comment(Comment),
     Identifier,
+
+    /// #abc
+    PrivateIdentifier,
     Punctuator(Punctuator),
     Literal(Literal),
-    Template,
+    Template(Template),
+
+    /// ソースコードの終わり
+    EndOfFile,
 }
 
 impl TokenType {
@@ -67,6 +120,7 @@ impl TokenType {
     }
 }
 
+/// 表記をソースコードにだけ持つトークンは空文字列になる。表記は [`SourceToken`] で得る。
 impl ToSourceString for TokenType {
     fn to_source_string(&self) -> String {
         match self {
@@ -74,9 +128,11 @@ impl ToSourceString for TokenType {
             TokenType::LineTerminator => "\n".to_string(),
             TokenType::Comment(c) => todo!(),
             TokenType::Identifier => todo!(),
+            TokenType::PrivateIdentifier => String::new(),
             TokenType::Punctuator(_) => todo!(),
             TokenType::Literal(_) => todo!(),
-            TokenType::Template => todo!(),
+            TokenType::Template(_) => String::new(),
+            TokenType::EndOfFile => String::new(),
         }
     }
 }
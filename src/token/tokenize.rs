@@ -9,15 +9,28 @@ use crate::token::structs::{
 };
 
 use self::{
-    line_terminator::tokenize_line_terminator, literal::tokenize_numeric_literal,
-    punctuator::tokenize_punctuator, whitespace::tokenize_whitespace,
+    comment::{tokenize_hashbang_comment, tokenize_multiline_comment, tokenize_singleline_comment},
+    identifier::{tokenize_identifier, tokenize_private_identifier},
+    line_terminator::tokenize_line_terminator,
+    literal::tokenize_numeric_literal,
+    punctuator::tokenize_punctuator,
+    regexp::tokenize_regexp_literal,
+    string::tokenize_string_literal,
+    template::{tokenize_template_continuation, tokenize_template_start},
+    whitespace::tokenize_whitespace,
 };
 
+pub(crate) use self::identifier::{is_identifier_part, is_identifier_start};
+
 mod comment;
 pub mod error;
+mod identifier;
 mod line_terminator;
 mod literal;
 mod punctuator;
+mod regexp;
+mod string;
+mod template;
 mod whitespace;
 
 pub fn next_token(
@@ -25,33 +38,258 @@ pub fn next_token(
     div: DivContext,
     brace: BraceContext,
 ) -> Result<Token, TokenizeError> {
-    match chars.peek() {
+    let second = peek_second(chars);
+
+    match chars.peek().copied() {
         Some((_, character_patterns!(Whitespace))) => tokenize_whitespace(chars),
         Some((_, character_patterns!(LineTerminator))) => tokenize_line_terminator(chars),
+        Some((_, character_patterns!(Punctuator div))) => match second {
+            Some('/') => tokenize_singleline_comment(chars),
+            Some('*') => tokenize_multiline_comment(chars),
+            _ => match div {
+                DivContext::RegExp => tokenize_regexp_literal(chars),
+                DivContext::Divide => tokenize_punctuator(chars),
+            },
+        },
+        Some((_, character_patterns!(Punctuator right brace))) => match brace {
+            BraceContext::Template => tokenize_template_continuation(chars),
+            BraceContext::Block => tokenize_punctuator(chars),
+        },
+        Some((_, '.')) if matches!(second, Some(character_patterns!(Numeric))) => {
+            tokenize_numeric_literal(chars)
+        }
         Some((_, character_patterns!(Punctuator))) => tokenize_punctuator(chars),
         Some((_, character_patterns!(Numeric))) => tokenize_numeric_literal(chars),
+        Some((_, '"' | '\'')) => tokenize_string_literal(chars),
+        Some((_, '`')) => tokenize_template_start(chars),
+        Some((0, '#')) if second == Some('!') => tokenize_hashbang_comment(chars),
+        Some((_, '#')) => tokenize_private_identifier(chars),
+        Some((_, char)) if is_identifier_start(char) => tokenize_identifier(chars),
 
-        Some((index, char)) => Err("".to_string()),
+        Some((index, char)) => Err(format!("unexpected character '{char}' at {index}")),
         None => Err("reach to EOF".to_string()),
     }
 }
 
-pub(self) fn next_peeked_character(
+/// 次の次の文字を返す。
+fn peek_second(chars: &Peekable<CharIndices>) -> Option<char> {
+    let mut ahead = chars.clone();
+    ahead.next();
+    ahead.peek().map(|(_, char)| *char)
+}
+
+fn next_peeked_character(
     chars: &mut Peekable<CharIndices>,
 ) -> Result<(usize, char), TokenizeError> {
-    chars.next().ok_or(format!(
-        "error occurred. peek is has next but next is not have next."
-    ))
+    chars
+        .next()
+        .ok_or_else(|| "error occurred. peek is has next but next is not have next.".to_string())
 }
 
-pub(self) fn char_utf8_length(char: char) -> usize {
-    if char <= '\u{007F}' {
-        1
-    } else if char <= '\u{07FF}' {
-        2
-    } else if char <= '\u{FFFF}' {
+fn char_utf8_length(char: char) -> usize {
+    char.len_utf8()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::token::structs::{
+        comment::Comment,
+        context::{BraceContext, DivContext},
+        literal::Literal,
+        punctuator::Punctuator,
+        template::Template,
+        token::{Token, TokenType},
+    };
+
+    use super::next_token;
+
+    macro_rules! test_case {
+        ($test_name:ident, $source:expr, $div:expr, $brace:expr, $token_type:expr, $len:expr) => {
+            #[test]
+            fn $test_name() {
+                let param = $source;
+                let expected = Token::new($token_type, 0, $len);
+
+                let mut chars = param.char_indices().peekable();
+
+                let result = next_token(&mut chars, $div, $brace);
+
+                assert_eq!(result, Ok(expected));
+            }
+        };
+    }
+
+    use BraceContext::{Block, Template as InTemplate};
+    use DivContext::{Divide, RegExp};
+
+    test_case!(
+        identifier,
+        "abc def",
+        Divide,
+        Block,
+        TokenType::Identifier,
         3
-    } else {
+    );
+    test_case!(
+        identifier_unicode,
+        "日本語 = 1",
+        Divide,
+        Block,
+        TokenType::Identifier,
+        9
+    );
+    test_case!(
+        identifier_escape,
+        "\\u0061bc;",
+        Divide,
+        Block,
+        TokenType::Identifier,
+        8
+    );
+    test_case!(
+        private_identifier,
+        "#abc in",
+        Divide,
+        Block,
+        TokenType::PrivateIdentifier,
+        4
+    );
+    test_case!(
+        divide,
+        "/ 2",
+        Divide,
+        Block,
+        TokenType::Punctuator(Punctuator::Divide),
+        1
+    );
+    test_case!(
+        regexp,
+        "/a[/]\\//gi.test",
+        RegExp,
+        Block,
+        TokenType::Literal(Literal::RegularExpression),
+        10
+    );
+    test_case!(
+        single_line_comment,
+        "// abc\nd",
+        RegExp,
+        Block,
+        TokenType::Comment(Comment::SingleLineComment),
+        6
+    );
+    test_case!(
+        multi_line_comment,
+        "/* a\n*/b",
+        Divide,
+        Block,
+        TokenType::Comment(Comment::MultiLineComment),
+        7
+    );
+    test_case!(
+        hashbang_comment,
+        "#!/usr/bin/env node\n",
+        Divide,
+        Block,
+        TokenType::Comment(Comment::HashBangComment),
+        19
+    );
+    test_case!(
+        double_string,
+        "\"a\\\"b\" + c",
+        Divide,
+        Block,
+        TokenType::Literal(Literal::DoubleString),
+        6
+    );
+    test_case!(
+        single_string,
+        "'a\\\nb'",
+        Divide,
+        Block,
+        TokenType::Literal(Literal::SingleString),
+        6
+    );
+    test_case!(
+        template_no_substitution,
+        "`a\\`b` + c",
+        Divide,
+        Block,
+        TokenType::Template(Template::NoSubstitution),
+        6
+    );
+    test_case!(
+        template_head,
+        "`a${b}`",
+        Divide,
+        Block,
+        TokenType::Template(Template::Head),
         4
+    );
+    test_case!(
+        template_middle,
+        "}a${b}`",
+        Divide,
+        InTemplate,
+        TokenType::Template(Template::Middle),
+        4
+    );
+    test_case!(
+        template_tail,
+        "}a`;",
+        Divide,
+        InTemplate,
+        TokenType::Template(Template::Tail),
+        3
+    );
+    test_case!(
+        right_brace,
+        "}a`;",
+        Divide,
+        Block,
+        TokenType::Punctuator(Punctuator::RightBrace),
+        1
+    );
+    test_case!(
+        leading_dot_number,
+        ".5 + 1",
+        Divide,
+        Block,
+        TokenType::Literal(Literal::DecimalPointNumber),
+        2
+    );
+    test_case!(
+        carriage_return,
+        "\ra",
+        Divide,
+        Block,
+        TokenType::LineTerminator,
+        1
+    );
+
+    #[test]
+    fn unterminated_string() {
+        let mut chars = "'abc\n'".char_indices().peekable();
+        assert!(next_token(&mut chars, Divide, Block).is_err());
+    }
+
+    #[test]
+    fn source_token_text() {
+        use crate::{to_source_string::ToSourceString, token::structs::token::SourceToken};
+
+        for (source, div, brace, expected) in [
+            ("#abc in", Divide, Block, "#abc"),
+            ("`a${b}`", Divide, Block, "`a${"),
+            ("}b`;", Divide, InTemplate, "}b`"),
+            ("/a[/]b/gi.test", RegExp, Block, "/a[/]b/gi"),
+        ] {
+            let mut chars = source.char_indices().peekable();
+            let token = next_token(&mut chars, div, brace).unwrap();
+            let source_token = SourceToken {
+                token: &token,
+                source,
+            };
+            assert_eq!(source_token.to_source_string(), expected);
+        }
     }
 }
@@ -15,25 +15,39 @@ use super::char_utf8_length;
 /// 一行コメント
 pub(super) fn tokenize_singleline_comment(
     chars: &mut Peekable<CharIndices>,
+) -> Result<Token, TokenizeError> {
+    tokenize_until_line_terminator(chars, Comment::SingleLineComment)
+}
+
+/// ハッシュバンコメント `#!`
+pub(super) fn tokenize_hashbang_comment(
+    chars: &mut Peekable<CharIndices>,
+) -> Result<Token, TokenizeError> {
+    tokenize_until_line_terminator(chars, Comment::HashBangComment)
+}
+
+/// 改行文字の前までを一つのコメントにする
+fn tokenize_until_line_terminator(
+    chars: &mut Peekable<CharIndices>,
+    comment: Comment,
 ) -> Result<Token, TokenizeError> {
     let mut start_index = None;
     let mut end_index = None;
 
     while let Some((index, char)) = chars.peek() {
         match char {
-            char @ character_patterns!(LineTerminator) => {
+            character_patterns!(LineTerminator) => {
                 // 改行文字の場合
-                end_index = Some(*index);
                 break;
             }
 
             _ => {
-                if start_index == None {
-                    // 最初の空白文字でインデックスを設定する
-                    start_index = Some(index - char_utf8_length('/'));
+                if start_index.is_none() {
+                    // 最初の文字でインデックスを設定する
+                    start_index = Some(*index);
                 }
                 // 次の文字がない場合のインデックスを指定しておく
-                end_index = Some(index - char_utf8_length(*char));
+                end_index = Some(index + char_utf8_length(*char));
 
                 // 次の文字に進む
                 next_peeked_character(chars)?;
@@ -41,33 +55,35 @@ pub(super) fn tokenize_singleline_comment(
         }
     }
 
-    let token_type = Some(TokenType::WhiteSpace);
-    Token::try_new(token_type, start_index, end_index).ok_or(format!("err"))
+    let token_type = Some(TokenType::Comment(comment));
+    Token::try_new(token_type, start_index, end_index).ok_or_else(|| "err".to_string())
 }
 
 /// 複数行コメント
 pub(super) fn tokenize_multiline_comment(
     chars: &mut Peekable<CharIndices>,
 ) -> Result<Token, TokenizeError> {
-    let mut start_index = None;
     let mut end_index = None;
 
     let mut is_prev_asterisk = false;
 
-    // 最初の*を飛ばす (/*/で終了するのを防ぐ)
+    // 最初の/*を飛ばす (/*/で終了するのを防ぐ)
+    let start_index = match chars.next() {
+        Some((index, '/')) => Some(index),
+        _ => return Err("err".to_string()),
+    };
     match chars.next() {
         Some((_, '*')) => (),
-        _ => return Err(format!("")),
+        _ => return Err("err".to_string()),
     }
 
     while let Some((index, char)) = chars.peek() {
         match char {
-            '/' => {
+            '/' if is_prev_asterisk => {
                 // 前が*ならコメントを終了する
-                if is_prev_asterisk {
-                    end_index = Some(index + char_utf8_length(*char));
-                    break;
-                }
+                end_index = Some(index + char_utf8_length(*char));
+                next_peeked_character(chars)?;
+                break;
             }
 
             '*' => {
@@ -87,6 +103,7 @@ pub(super) fn tokenize_multiline_comment(
         }
     }
 
-    let token_type = Some(TokenType::Comment(Comment::SingleLineComment));
-    Token::try_new(token_type, start_index, end_index).ok_or(format!("err"))
+    let token_type = Some(TokenType::Comment(Comment::MultiLineComment));
+    Token::try_new(token_type, start_index, end_index)
+        .ok_or_else(|| "error multi line comment is not closed".to_string())
 }
@@ -0,0 +1,152 @@
+use std::{iter::Peekable, str::CharIndices};
+
+use crate::token::structs::{
+    error::TokenizeError,
+    token::{Token, TokenType},
+};
+
+use crate::token::tokenize::next_peeked_character;
+
+use super::char_utf8_length;
+
+/// 識別子の開始文字であるかを返す。
+///
+/// ASCII 以外は Unicode の ID_Start の代わりに Alphabetic で判定する。
+pub(crate) fn is_identifier_start(char: char) -> bool {
+    match char {
+        'a'..='z' | 'A'..='Z' | '$' | '_' | '\\' => true,
+        char if char.is_ascii() => false,
+        char => char.is_alphabetic(),
+    }
+}
+
+/// 識別子の2文字目以降の文字であるかを返す。
+pub(crate) fn is_identifier_part(char: char) -> bool {
+    match char {
+        'a'..='z' | 'A'..='Z' | '0'..='9' | '$' | '_' | '\\' => true,
+        char if char.is_ascii() => false,
+
+        // <ZWNJ> <ZWJ>
+        '\u{200C}' | '\u{200D}' => true,
+
+        // 結合文字と連結句読点
+        '\u{0300}'..='\u{036F}'
+        | '\u{1AB0}'..='\u{1AFF}'
+        | '\u{1DC0}'..='\u{1DFF}'
+        | '\u{20D0}'..='\u{20FF}'
+        | '\u{FE20}'..='\u{FE2F}'
+        | '\u{203F}'
+        | '\u{2040}'
+        | '\u{2054}'
+        | '\u{FE33}'
+        | '\u{FE34}'
+        | '\u{FE4D}'..='\u{FE4F}'
+        | '\u{FF3F}' => true,
+        char => char.is_alphanumeric(),
+    }
+}
+
+/// 識別子と予約語
+pub(super) fn tokenize_identifier(
+    chars: &mut Peekable<CharIndices>,
+) -> Result<Token, TokenizeError> {
+    let (start_index, end_index) = read_identifier_name(chars)?;
+
+    Ok(Token::new(TokenType::Identifier, start_index, end_index))
+}
+
+/// プライベート名 `#abc`
+pub(super) fn tokenize_private_identifier(
+    chars: &mut Peekable<CharIndices>,
+) -> Result<Token, TokenizeError> {
+    let start_index = match chars.next() {
+        Some((index, '#')) => index,
+        _ => return Err("err".to_string()),
+    };
+
+    match chars.peek() {
+        Some((_, char)) if is_identifier_start(*char) => {
+            let (_, end_index) = read_identifier_name(chars)?;
+            Ok(Token::new(
+                TokenType::PrivateIdentifier,
+                start_index,
+                end_index,
+            ))
+        }
+        _ => Err("error '#' must be followed by identifier".to_string()),
+    }
+}
+
+/// 識別子名を読み進めて、開始位置と終了位置を返す。
+fn read_identifier_name(
+    chars: &mut Peekable<CharIndices>,
+) -> Result<(usize, usize), TokenizeError> {
+    let mut start_index = None;
+    let mut end_index = None;
+
+    while let Some((index, char)) = chars.peek() {
+        let index = *index;
+        let char = *char;
+
+        match char {
+            '\\' => {
+                if start_index.is_none() {
+                    start_index = Some(index);
+                }
+                end_index = Some(read_unicode_escape(chars)?);
+            }
+
+            char if (start_index.is_none() && is_identifier_start(char))
+                || (start_index.is_some() && is_identifier_part(char)) =>
+            {
+                if start_index.is_none() {
+                    start_index = Some(index);
+                }
+                end_index = Some(index + char_utf8_length(char));
+                next_peeked_character(chars)?;
+            }
+
+            _ => break,
+        }
+    }
+
+    start_index
+        .zip(end_index)
+        .ok_or_else(|| "error identifier is empty".to_string())
+}
+
+/// `a` と `\u{61}` を読み進めて、終了位置を返す。
+fn read_unicode_escape(chars: &mut Peekable<CharIndices>) -> Result<usize, TokenizeError> {
+    next_peeked_character(chars)?;
+
+    match chars.next() {
+        Some((_, 'u')) => {}
+        _ => return Err("error identifier escape must be unicode escape".to_string()),
+    }
+
+    match chars.peek() {
+        Some((_, '{')) => {
+            next_peeked_character(chars)?;
+            let mut digits = 0;
+
+            loop {
+                match chars.next() {
+                    Some((_, char)) if char.is_ascii_hexdigit() => digits += 1,
+                    Some((index, '}')) if digits > 0 => return Ok(index + 1),
+                    _ => return Err("error invalid unicode escape".to_string()),
+                }
+            }
+        }
+
+        _ => {
+            let mut end_index = 0;
+            for _ in 0..4 {
+                match chars.next() {
+                    Some((index, char)) if char.is_ascii_hexdigit() => end_index = index + 1,
+                    _ => return Err("error invalid unicode escape".to_string()),
+                }
+            }
+            Ok(end_index)
+        }
+    }
+}
@@ -21,10 +21,12 @@ pub fn tokenize_line_terminator(chars: &mut Peekable<CharIndices>) -> Result<Tok
             // <CR><LF>のパターンは１つの改行とみなす
             '\u{000D}' => {
                 start_index = Some(*index);
+                end_index = Some(index + char_utf8_length(*char));
                 next_peeked_character(chars)?;
 
                 if let Some((index, char @ '\u{000A}')) = chars.peek() {
                     end_index = Some(index + char_utf8_length(*char));
+                    next_peeked_character(chars)?;
                 }
             }
             char @ character_patterns!(LineTerminator without CR) => {
@@ -41,5 +43,5 @@ pub fn tokenize_line_terminator(chars: &mut Peekable<CharIndices>) -> Result<Tok
     }
 
     let token_type = Some(TokenType::LineTerminator);
-    Token::try_new(token_type, start_index, end_index).ok_or(format!("err"))
+    Token::try_new(token_type, start_index, end_index).ok_or_else(|| "err".to_string())
 }
@@ -1,10 +1,6 @@
-use std::fmt::format;
 use std::{iter::Peekable, str::CharIndices};
 
-use crate::character_patterns;
-
 use crate::token::structs::literal::Literal;
-use crate::token::structs::punctuator::Punctuator;
 use crate::token::structs::{
     error::TokenizeError,
     token::{Token, TokenType},
@@ -13,315 +9,248 @@ use crate::token::structs::{
 use crate::token::tokenize::next_peeked_character;
 
 use super::char_utf8_length;
-use super::comment::{tokenize_multiline_comment, tokenize_singleline_comment};
+use super::identifier::is_identifier_start;
+use super::peek_second;
 
 pub fn tokenize_numeric_literal(chars: &mut Peekable<CharIndices>) -> Result<Token, TokenizeError> {
-    match chars.peek() {
-        Some((index, char @ '0')) => {
-            let index = *index;
-            let char = *char;
-            next_peeked_character(chars)?;
-
-            match chars.peek() {
-                Some((_, 'b' | 'B')) => tokenize_binary_number_literal(chars),
-                Some((_, 'o' | 'O')) => tokenize_octal_number_literal(chars),
-                Some((_, 'x' | 'X')) => tokenize_hex_number_literal(chars),
-                Some((_, '1'..='9')) => tokenize_legacy_octal_number_literal(chars),
-                Some((_, _)) => tokenize_decimal_number_literal(chars),
-
-                _ => Err(format!("")),
-            }
+    let second = peek_second(chars);
+    let token = match (chars.peek().copied(), second) {
+        (Some((_, '0')), Some('b' | 'B')) => tokenize_radix_number_literal(
+            chars,
+            2,
+            Literal::BinaryInteger,
+            Literal::BinaryBigInteger,
+        ),
+        (Some((_, '0')), Some('o' | 'O')) => {
+            tokenize_radix_number_literal(chars, 8, Literal::OctalInteger, Literal::OctalBigInteger)
+        }
+        (Some((_, '0')), Some('x' | 'X')) => {
+            tokenize_radix_number_literal(chars, 16, Literal::HexInteger, Literal::HexBigInteger)
         }
+        (Some((_, '0')), Some('0'..='9' | '_')) => tokenize_legacy_octal_number_literal(chars),
+        (Some((_, '0'..='9' | '.')), _) => tokenize_decimal_number_literal(chars),
 
-        Some((index, '1'..='9' | '.')) => tokenize_decimal_number_literal(chars),
+        _ => Err("err".to_string()),
+    }?;
 
-        _ => Err(format!("")),
+    // 数値の直後に識別子や数字を続けることはできない (3in)
+    match chars.peek() {
+        Some((_, char)) if is_identifier_start(*char) || char.is_ascii_digit() => {
+            Err("error identifier starts immediately after numeric literal".to_string())
+        }
+        _ => Ok(token),
     }
 }
 
-fn tokenize_decimal_number_literal(
+/// 区切り文字を含む数字の並びを読み進める。
+///
+/// 読んだ数字の数を返し、`end_position` を最後の数字の後ろに更新する。
+fn read_digits(
     chars: &mut Peekable<CharIndices>,
-) -> Result<Token, TokenizeError> {
-    let mut token_type = None;
-    let mut start_position = None;
-    let mut end_position = None;
-
+    radix: u32,
+    end_position: &mut usize,
+) -> Result<usize, TokenizeError> {
+    let mut digits = 0;
     let mut is_prev_separator = false;
 
     while let Some((index, char)) = chars.peek() {
         match char {
-            '0'..='9' => {
-                if start_position == None {
-                    start_position = Some(*index);
-                }
-
-                end_position = Some(index + char_utf8_length(*char));
+            char if char.is_digit(radix) => {
+                *end_position = index + char_utf8_length(*char);
+                digits += 1;
                 is_prev_separator = false;
                 next_peeked_character(chars)?;
             }
+
             '_' => {
                 if is_prev_separator {
-                    return Err(format!(
-                        "error number cannot contain multiple adjacent underscores"
-                    ));
+                    return Err(
+                        "error number cannot contain multiple adjacent underscores".to_string()
+                    );
+                } else if digits == 0 {
+                    return Err("error underscore can appear only between digits".to_string());
                 } else {
                     is_prev_separator = true;
                     next_peeked_character(chars)?;
                 }
             }
+
             _ => break,
         }
     }
 
     if is_prev_separator {
-        return Err(format!("error underscore can appear only between digits"));
+        return Err("error underscore can appear only between digits".to_string());
     }
 
+    Ok(digits)
+}
+
+fn tokenize_decimal_number_literal(
+    chars: &mut Peekable<CharIndices>,
+) -> Result<Token, TokenizeError> {
+    let start_position = match chars.peek() {
+        Some((index, _)) => *index,
+        None => return Err("err".to_string()),
+    };
+    let mut end_position = start_position;
+
+    let integer_digits = read_digits(chars, 10, &mut end_position)?;
+    let mut has_point = false;
+    let mut has_exponent = false;
+
     if let Some((index, char @ '.')) = chars.peek() {
+        end_position = index + char_utf8_length(*char);
+        has_point = true;
         next_peeked_character(chars)?;
-        is_prev_separator = false;
 
-        while let Some((index, char)) = chars.peek() {
-            match char {
-                '0'..='9' => {
-                    if start_position == None {
-                        start_position = Some(*index);
-                    }
-
-                    end_position = Some(index + char_utf8_length(*char));
-                    is_prev_separator = false;
-                    next_peeked_character(chars)?;
-                }
-                '_' => {
-                    if is_prev_separator {
-                        return Err(format!(
-                            "error number cannot contain multiple adjacent underscores"
-                        ));
-                    } else {
-                        is_prev_separator = true;
-                        next_peeked_character(chars)?;
-                    }
-                }
-                _ => break,
-            }
+        let fraction_digits = read_digits(chars, 10, &mut end_position)?;
+        if integer_digits == 0 && fraction_digits == 0 {
+            return Err("error number must have digits".to_string());
         }
     }
 
-    Token::try_new(token_type, start_position, end_position).ok_or(format!("err"))
-}
-
-fn tokenize_binary_number_literal(
-    chars: &mut Peekable<CharIndices>,
-) -> Result<Token, TokenizeError> {
-    let mut token_type = Literal::BinaryInteger;
-    let mut start_position = None;
-    let mut end_position = None;
-
-    let mut is_prev_separator = false;
+    if let Some((_, 'e' | 'E')) = chars.peek() {
+        has_exponent = true;
+        next_peeked_character(chars)?;
 
-    match chars.peek() {
-        Some((index, 'b' | 'B')) => {
-            start_position = Some(index - char_utf8_length('0'));
-            next_peeked_character(chars);
+        if let Some((_, '+' | '-')) = chars.peek() {
+            next_peeked_character(chars)?;
         }
-        _ => {
-            return Err(format!("err"));
+
+        if read_digits(chars, 10, &mut end_position)? == 0 {
+            return Err("error exponent must have digits".to_string());
         }
     }
 
-    while let Some((index, char)) = chars.peek() {
-        match char {
-            '0' | '1' => {
-                end_position = Some(index + char_utf8_length(*char));
-                is_prev_separator = false;
+    let token_type = match (has_point, has_exponent) {
+        (false, false) => match chars.peek() {
+            Some((index, char @ 'n')) => {
+                end_position = index + char_utf8_length(*char);
                 next_peeked_character(chars)?;
+                Literal::DecimalBigInteger
             }
+            _ => Literal::DecimalInteger,
+        },
+        (true, false) => Literal::DecimalPointNumber,
+        (false, true) => Literal::DecimalExponentNumber,
+        (true, true) => Literal::DecimalPointExponentNumber,
+    };
+
+    Ok(Token::new(
+        TokenType::Literal(token_type),
+        start_position,
+        end_position,
+    ))
+}
 
-            '_' => {
-                if is_prev_separator {
-                    return Err(format!(
-                        "error number cannot contain multiple adjacent underscores"
-                    ));
-                } else {
-                    end_position = Some(index + char_utf8_length(*char));
-                    is_prev_separator = true;
-                    next_peeked_character(chars)?;
-                }
-            }
+/// 0b011 0o067 0x0ef とその BigInt
+fn tokenize_radix_number_literal(
+    chars: &mut Peekable<CharIndices>,
+    radix: u32,
+    integer: Literal,
+    big_integer: Literal,
+) -> Result<Token, TokenizeError> {
+    let (start_position, _) = next_peeked_character(chars)?;
+    let (_, prefix) = next_peeked_character(chars)?;
+    let mut end_position = start_position;
 
-            'n' => {
-                if is_prev_separator {
-                    return Err(format!("error"));
-                } else {
-                    end_position = Some(index + char_utf8_length(*char));
-                    token_type = Literal::BinaryBigInteger;
-                    is_prev_separator = true;
-                    next_peeked_character(chars)?;
-                }
-            }
+    if read_digits(chars, radix, &mut end_position)? == 0 {
+        return Err(format!("error number must have digits after 0{prefix}"));
+    }
 
-            _ => {
-                if is_prev_separator {
-                    return Err(format!("error"));
-                } else {
-                    break;
-                }
-            }
+    let token_type = match chars.peek() {
+        Some((index, char @ 'n')) => {
+            end_position = index + char_utf8_length(*char);
+            next_peeked_character(chars)?;
+            big_integer
         }
-    }
+        _ => integer,
+    };
 
-    Token::try_new(
-        Some(TokenType::Literal(token_type)),
+    Ok(Token::new(
+        TokenType::Literal(token_type),
         start_position,
         end_position,
-    )
-    .ok_or(format!("err"))
+    ))
 }
 
-fn tokenize_octal_number_literal(
+/// 067 と、8 か 9 を含む 089 (10進数として扱う)
+fn tokenize_legacy_octal_number_literal(
     chars: &mut Peekable<CharIndices>,
 ) -> Result<Token, TokenizeError> {
-    let mut token_type = Literal::OctalInteger;
     let mut start_position = None;
-    let mut end_position = None;
-
-    let mut is_prev_separator = false;
-
-    match chars.peek() {
-        Some((index, 'o' | 'O')) => {
-            start_position = Some(index - char_utf8_length('0'));
-            next_peeked_character(chars);
-        }
-        _ => {
-            return Err(format!("err"));
-        }
-    }
+    let mut end_position = 0;
+    let mut is_octal = true;
 
     while let Some((index, char)) = chars.peek() {
         match char {
-            '0'..='7' => {
-                end_position = Some(index + char_utf8_length(*char));
-                is_prev_separator = false;
+            '0'..='9' => {
+                if start_position.is_none() {
+                    start_position = Some(*index);
+                }
+                is_octal &= *char <= '7';
+                end_position = index + char_utf8_length(*char);
                 next_peeked_character(chars)?;
             }
-
             '_' => {
-                if is_prev_separator {
-                    return Err(format!(
-                        "error number cannot contain multiple adjacent underscores"
-                    ));
-                } else {
-                    end_position = Some(index + char_utf8_length(*char));
-                    is_prev_separator = true;
-                    next_peeked_character(chars)?;
-                }
-            }
-
-            'n' => {
-                if is_prev_separator {
-                    return Err(format!("error"));
-                } else {
-                    end_position = Some(index + char_utf8_length(*char));
-                    token_type = Literal::OctalBigInteger;
-                    is_prev_separator = true;
-                    next_peeked_character(chars)?;
-                }
-            }
-
-            _ => {
-                if is_prev_separator {
-                    return Err(format!("error"));
-                } else {
-                    break;
-                }
+                return Err("error underscore cannot be used in legacy octal literal".to_string())
             }
+            _ => break,
         }
     }
 
-    Token::try_new(
-        Some(TokenType::Literal(token_type)),
-        start_position,
-        end_position,
-    )
-    .ok_or(format!("err"))
-}
+    let start_position = start_position.ok_or_else(|| "err".to_string())?;
 
-fn tokenize_hex_number_literal(chars: &mut Peekable<CharIndices>) -> Result<Token, TokenizeError> {
-    let mut token_type = Literal::HexInteger;
-    let mut start_position = None;
-    let mut end_position = None;
+    if is_octal {
+        return Ok(Token::new(
+            TokenType::Literal(Literal::LegacyOctalInteger),
+            start_position,
+            end_position,
+        ));
+    }
 
-    let mut is_prev_separator = false;
+    // 089.5 のように小数部と指数部を続けることができる
+    let mut has_point = false;
+    let mut has_exponent = false;
 
-    match chars.peek() {
-        Some((index, 'x' | 'X')) => {
-            start_position = Some(index - char_utf8_length('0'));
-            next_peeked_character(chars);
-        }
-        _ => {
-            return Err(format!("err"));
-        }
+    if let Some((index, char @ '.')) = chars.peek() {
+        end_position = index + char_utf8_length(*char);
+        has_point = true;
+        next_peeked_character(chars)?;
+        read_digits(chars, 10, &mut end_position)?;
     }
 
-    while let Some((index, char)) = chars.peek() {
-        match char {
-            '0'..='9' | 'a'..='f' | 'A'..='F' => {
-                end_position = Some(index + char_utf8_length(*char));
-                is_prev_separator = false;
-                next_peeked_character(chars)?;
-            }
-
-            '_' => {
-                if is_prev_separator {
-                    return Err(format!(
-                        "error number cannot contain multiple adjacent underscores"
-                    ));
-                } else {
-                    end_position = Some(index + char_utf8_length(*char));
-                    is_prev_separator = true;
-                    next_peeked_character(chars)?;
-                }
-            }
+    if let Some((_, 'e' | 'E')) = chars.peek() {
+        has_exponent = true;
+        next_peeked_character(chars)?;
 
-            'n' => {
-                if is_prev_separator {
-                    return Err(format!("error"));
-                } else {
-                    end_position = Some(index + char_utf8_length(*char));
-                    token_type = Literal::HexBigInteger;
-                    is_prev_separator = true;
-                    next_peeked_character(chars)?;
-                }
-            }
+        if let Some((_, '+' | '-')) = chars.peek() {
+            next_peeked_character(chars)?;
+        }
 
-            _ => {
-                if is_prev_separator {
-                    return Err(format!("error"));
-                } else {
-                    break;
-                }
-            }
+        if read_digits(chars, 10, &mut end_position)? == 0 {
+            return Err("error exponent must have digits".to_string());
         }
     }
 
-    Token::try_new(
-        Some(TokenType::Literal(token_type)),
+    let token_type = match (has_point, has_exponent) {
+        (false, false) => Literal::DecimalInteger,
+        (true, false) => Literal::DecimalPointNumber,
+        (false, true) => Literal::DecimalExponentNumber,
+        (true, true) => Literal::DecimalPointExponentNumber,
+    };
+
+    Ok(Token::new(
+        TokenType::Literal(token_type),
         start_position,
         end_position,
-    )
-    .ok_or(format!("err"))
-}
-
-fn tokenize_legacy_octal_number_literal(
-    chars: &mut Peekable<CharIndices>,
-) -> Result<Token, TokenizeError> {
-    Err(format!(""))
+    ))
 }
 
 #[cfg(test)]
 mod test {
     use crate::token::structs::literal::Literal;
-    use crate::token::structs::punctuator::Punctuator;
     use crate::token::structs::token::{Token, TokenType};
 
     use super::tokenize_numeric_literal;
@@ -400,9 +329,28 @@ mod test {
     test_case!(hex_int_sep, "0x12_ef", Literal::HexInteger, 7);
     test_case!(hex_int_zero, "0x0", Literal::HexInteger, 3);
     test_case!(hex_bigint_lower, "0x12efn", Literal::HexBigInteger, 7);
-    test_case!(hex_bigint_upper, "0S12efn", Literal::HexBigInteger, 7);
+    test_case!(hex_bigint_upper, "0X12efn", Literal::HexBigInteger, 7);
     test_case!(hex_bigint_sep, "0x12_efn", Literal::HexBigInteger, 8);
     test_case!(hex_bigint_zero, "0x0n", Literal::HexBigInteger, 4);
     test_case!(legacy_octal_int_1, "01267", Literal::LegacyOctalInteger, 5);
     test_case!(legacy_octal_int_2, "01268", Literal::DecimalInteger, 5);
+    test_case!(dec_point_leading, ".5", Literal::DecimalPointNumber, 2);
+    test_case!(dec_point_trailing, "5.", Literal::DecimalPointNumber, 2);
+    test_case!(dec_exp_sign, "1e-7", Literal::DecimalExponentNumber, 4);
+
+    test_case_fail!(
+        dec_sep_trailing,
+        "12_",
+        "error underscore can appear only between digits"
+    );
+    test_case_fail!(
+        dec_identifier_after,
+        "3in",
+        "error identifier starts immediately after numeric literal"
+    );
+    test_case_fail!(
+        hex_no_digits,
+        "0x",
+        "error number must have digits after 0x"
+    );
 }
@@ -1,8 +1,5 @@
-use std::fmt::format;
 use std::{iter::Peekable, str::CharIndices};
 
-use crate::character_patterns;
-
 use crate::token::structs::punctuator::Punctuator;
 use crate::token::structs::{
     error::TokenizeError,
@@ -12,7 +9,13 @@ use crate::token::structs::{
 use crate::token::tokenize::next_peeked_character;
 
 use super::char_utf8_length;
-use super::comment::{tokenize_multiline_comment, tokenize_singleline_comment};
+
+/// 次の次の文字が数字であるかを返す。
+fn is_next_digit(chars: &Peekable<CharIndices>) -> bool {
+    let mut ahead = chars.clone();
+    ahead.next();
+    matches!(ahead.peek(), Some((_, '0'..='9')))
+}
 
 pub fn tokenize_punctuator(chars: &mut Peekable<CharIndices>) -> Result<Token, TokenizeError> {
     let mut token_type = None;
@@ -58,12 +61,12 @@ pub fn tokenize_punctuator(chars: &mut Peekable<CharIndices>) -> Result<Token, T
                         end_index = Some(index + char_utf8_length(*char));
                         token_type = Some(TokenType::Punctuator(Punctuator::DotThree));
                     } else {
-                        return Err(format!("Syntax error '..' is not punctuator"));
+                        return Err("Syntax error '..' is not punctuator".to_string());
                     }
                 } else {
                     token_type = Some(TokenType::Punctuator(Punctuator::Dot));
                     return Token::try_new(token_type, start_index, end_index)
-                        .ok_or(format!("err"));
+                        .ok_or("err".to_string());
                 }
             }
 
@@ -98,7 +101,7 @@ pub fn tokenize_punctuator(chars: &mut Peekable<CharIndices>) -> Result<Token, T
                             _ => {
                                 token_type = Some(TokenType::Punctuator(Punctuator::LeftShift));
                                 return Token::try_new(token_type, start_index, end_index)
-                                    .ok_or(format!("err"));
+                                    .ok_or("err".to_string());
                             }
                         }
                     }
@@ -106,7 +109,7 @@ pub fn tokenize_punctuator(chars: &mut Peekable<CharIndices>) -> Result<Token, T
                     _ => {
                         token_type = Some(TokenType::Punctuator(Punctuator::LessThan));
                         return Token::try_new(token_type, start_index, end_index)
-                            .ok_or(format!("err"));
+                            .ok_or("err".to_string());
                     }
                 }
             }
@@ -148,7 +151,7 @@ pub fn tokenize_punctuator(chars: &mut Peekable<CharIndices>) -> Result<Token, T
                                             Punctuator::UnsignedRightShift,
                                         ));
                                         return Token::try_new(token_type, start_index, end_index)
-                                            .ok_or(format!("err"));
+                                            .ok_or("err".to_string());
                                     }
                                 }
                             }
@@ -156,7 +159,7 @@ pub fn tokenize_punctuator(chars: &mut Peekable<CharIndices>) -> Result<Token, T
                             _ => {
                                 token_type = Some(TokenType::Punctuator(Punctuator::RightShift));
                                 return Token::try_new(token_type, start_index, end_index)
-                                    .ok_or(format!("err"));
+                                    .ok_or("err".to_string());
                             }
                         }
                     }
@@ -164,7 +167,7 @@ pub fn tokenize_punctuator(chars: &mut Peekable<CharIndices>) -> Result<Token, T
                     _ => {
                         token_type = Some(TokenType::Punctuator(Punctuator::GreaterThan));
                         return Token::try_new(token_type, start_index, end_index)
-                            .ok_or(format!("err"));
+                            .ok_or("err".to_string());
                     }
                 }
             }
@@ -186,7 +189,7 @@ pub fn tokenize_punctuator(chars: &mut Peekable<CharIndices>) -> Result<Token, T
                             _ => {
                                 token_type = Some(TokenType::Punctuator(Punctuator::Equal));
                                 return Token::try_new(token_type, start_index, end_index)
-                                    .ok_or(format!("err"));
+                                    .ok_or("err".to_string());
                             }
                         }
                     }
@@ -199,7 +202,7 @@ pub fn tokenize_punctuator(chars: &mut Peekable<CharIndices>) -> Result<Token, T
                     _ => {
                         token_type = Some(TokenType::Punctuator(Punctuator::Assign));
                         return Token::try_new(token_type, start_index, end_index)
-                            .ok_or(format!("err"));
+                            .ok_or("err".to_string());
                     }
                 }
             }
@@ -222,7 +225,7 @@ pub fn tokenize_punctuator(chars: &mut Peekable<CharIndices>) -> Result<Token, T
                             _ => {
                                 token_type = Some(TokenType::Punctuator(Punctuator::NotEqual));
                                 return Token::try_new(token_type, start_index, end_index)
-                                    .ok_or(format!("err"));
+                                    .ok_or("err".to_string());
                             }
                         }
                     }
@@ -230,7 +233,7 @@ pub fn tokenize_punctuator(chars: &mut Peekable<CharIndices>) -> Result<Token, T
                     _ => {
                         token_type = Some(TokenType::Punctuator(Punctuator::LogicalNot));
                         return Token::try_new(token_type, start_index, end_index)
-                            .ok_or(format!("err"));
+                            .ok_or("err".to_string());
                     }
                 }
             }
@@ -252,7 +255,7 @@ pub fn tokenize_punctuator(chars: &mut Peekable<CharIndices>) -> Result<Token, T
                     _ => {
                         token_type = Some(TokenType::Punctuator(Punctuator::Plus));
                         return Token::try_new(token_type, start_index, end_index)
-                            .ok_or(format!("err"));
+                            .ok_or("err".to_string());
                     }
                 }
             }
@@ -274,7 +277,7 @@ pub fn tokenize_punctuator(chars: &mut Peekable<CharIndices>) -> Result<Token, T
                     _ => {
                         token_type = Some(TokenType::Punctuator(Punctuator::Minus));
                         return Token::try_new(token_type, start_index, end_index)
-                            .ok_or(format!("err"));
+                            .ok_or("err".to_string());
                     }
                 }
             }
@@ -303,7 +306,7 @@ pub fn tokenize_punctuator(chars: &mut Peekable<CharIndices>) -> Result<Token, T
                                 token_type =
                                     Some(TokenType::Punctuator(Punctuator::Exponentiation));
                                 return Token::try_new(token_type, start_index, end_index)
-                                    .ok_or(format!("err"));
+                                    .ok_or("err".to_string());
                             }
                         }
                     }
@@ -311,7 +314,7 @@ pub fn tokenize_punctuator(chars: &mut Peekable<CharIndices>) -> Result<Token, T
                     _ => {
                         token_type = Some(TokenType::Punctuator(Punctuator::Multiply));
                         return Token::try_new(token_type, start_index, end_index)
-                            .ok_or(format!("err"));
+                            .ok_or("err".to_string());
                     }
                 }
             }
@@ -320,14 +323,6 @@ pub fn tokenize_punctuator(chars: &mut Peekable<CharIndices>) -> Result<Token, T
                 next_peeked_character(chars)?;
 
                 match chars.peek() {
-                    Some((index, char @ '/')) => {
-                        return tokenize_singleline_comment(chars);
-                    }
-
-                    Some((index, char @ '*')) => {
-                        return tokenize_multiline_comment(chars);
-                    }
-
                     Some((index, char @ '=')) => {
                         end_index = Some(index + char_utf8_length(*char));
                         token_type = Some(TokenType::Punctuator(Punctuator::DivideAssign));
@@ -336,7 +331,7 @@ pub fn tokenize_punctuator(chars: &mut Peekable<CharIndices>) -> Result<Token, T
                     _ => {
                         token_type = Some(TokenType::Punctuator(Punctuator::Divide));
                         return Token::try_new(token_type, start_index, end_index)
-                            .ok_or(format!("err"));
+                            .ok_or("err".to_string());
                     }
                 }
             }
@@ -353,7 +348,7 @@ pub fn tokenize_punctuator(chars: &mut Peekable<CharIndices>) -> Result<Token, T
                     _ => {
                         token_type = Some(TokenType::Punctuator(Punctuator::Remainder));
                         return Token::try_new(token_type, start_index, end_index)
-                            .ok_or(format!("err"));
+                            .ok_or("err".to_string());
                     }
                 }
             }
@@ -381,7 +376,7 @@ pub fn tokenize_punctuator(chars: &mut Peekable<CharIndices>) -> Result<Token, T
                             _ => {
                                 token_type = Some(TokenType::Punctuator(Punctuator::LogicalAnd));
                                 return Token::try_new(token_type, start_index, end_index)
-                                    .ok_or(format!("err"));
+                                    .ok_or("err".to_string());
                             }
                         }
                     }
@@ -389,7 +384,7 @@ pub fn tokenize_punctuator(chars: &mut Peekable<CharIndices>) -> Result<Token, T
                     _ => {
                         token_type = Some(TokenType::Punctuator(Punctuator::BitAnd));
                         return Token::try_new(token_type, start_index, end_index)
-                            .ok_or(format!("err"));
+                            .ok_or("err".to_string());
                     }
                 }
             }
@@ -417,7 +412,7 @@ pub fn tokenize_punctuator(chars: &mut Peekable<CharIndices>) -> Result<Token, T
                             _ => {
                                 token_type = Some(TokenType::Punctuator(Punctuator::LogicalOr));
                                 return Token::try_new(token_type, start_index, end_index)
-                                    .ok_or(format!("err"));
+                                    .ok_or("err".to_string());
                             }
                         }
                     }
@@ -425,7 +420,7 @@ pub fn tokenize_punctuator(chars: &mut Peekable<CharIndices>) -> Result<Token, T
                     _ => {
                         token_type = Some(TokenType::Punctuator(Punctuator::BitOr));
                         return Token::try_new(token_type, start_index, end_index)
-                            .ok_or(format!("err"));
+                            .ok_or("err".to_string());
                     }
                 }
             }
@@ -442,7 +437,7 @@ pub fn tokenize_punctuator(chars: &mut Peekable<CharIndices>) -> Result<Token, T
                     _ => {
                         token_type = Some(TokenType::Punctuator(Punctuator::BitXor));
                         return Token::try_new(token_type, start_index, end_index)
-                            .ok_or(format!("err"));
+                            .ok_or("err".to_string());
                     }
                 }
             }
@@ -453,6 +448,7 @@ pub fn tokenize_punctuator(chars: &mut Peekable<CharIndices>) -> Result<Token, T
 
             '?' => {
                 next_peeked_character(chars)?;
+                let is_digit_after_dot = is_next_digit(chars);
 
                 match chars.peek() {
                     Some((index, char @ '?')) => {
@@ -470,12 +466,13 @@ pub fn tokenize_punctuator(chars: &mut Peekable<CharIndices>) -> Result<Token, T
                                 token_type =
                                     Some(TokenType::Punctuator(Punctuator::NullishCoalesce));
                                 return Token::try_new(token_type, start_index, end_index)
-                                    .ok_or(format!("err"));
+                                    .ok_or("err".to_string());
                             }
                         }
                     }
 
-                    Some((index, char @ '.')) => {
+                    // `a?.5:b` は条件演算子とみなす
+                    Some((index, char @ '.')) if !is_digit_after_dot => {
                         end_index = Some(index + char_utf8_length(*char));
                         token_type = Some(TokenType::Punctuator(Punctuator::OptionalChain));
                     }
@@ -483,7 +480,7 @@ pub fn tokenize_punctuator(chars: &mut Peekable<CharIndices>) -> Result<Token, T
                     _ => {
                         token_type = Some(TokenType::Punctuator(Punctuator::TernaryQuestion));
                         return Token::try_new(token_type, start_index, end_index)
-                            .ok_or(format!("err"));
+                            .ok_or("err".to_string());
                     }
                 }
             }
@@ -493,17 +490,15 @@ pub fn tokenize_punctuator(chars: &mut Peekable<CharIndices>) -> Result<Token, T
             }
 
             c => {
-                // 改行文字ではない場合
-
-                println!("{c}");
-                panic!();
+                // 演算子ではない場合
+                return Err(format!("'{c}' is not punctuator"));
             }
         }
 
         next_peeked_character(chars)?;
     }
 
-    Token::try_new(token_type, start_index, end_index).ok_or(format!("err"))
+    Token::try_new(token_type, start_index, end_index).ok_or("err".to_string())
 }
 
 #[cfg(test)]
@@ -593,4 +588,10 @@ mod test {
     test_case!(nullish_coalesce, "??abc", NullishCoalesce, 2);
     test_case!(nullish_coalesce_assign, "??=abc", NullishCoalesceAssign, 3);
     test_case!(optional_chain, "?.abc", OptionalChain, 2);
+    test_case!(
+        ternary_question_before_number,
+        "?.5:abc",
+        TernaryQuestion,
+        1
+    );
 }
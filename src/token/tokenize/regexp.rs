@@ -0,0 +1,69 @@
+use std::{iter::Peekable, str::CharIndices};
+
+use crate::character_patterns;
+
+use crate::token::structs::literal::Literal;
+use crate::token::structs::{
+    error::TokenizeError,
+    token::{Token, TokenType},
+};
+
+use crate::token::tokenize::next_peeked_character;
+
+use super::char_utf8_length;
+use super::identifier::is_identifier_part;
+
+/// 正規表現リテラル `/abc/g`
+///
+/// パターンの中身は検査しない。
+pub(super) fn tokenize_regexp_literal(
+    chars: &mut Peekable<CharIndices>,
+) -> Result<Token, TokenizeError> {
+    let start_index = match chars.next() {
+        Some((index, '/')) => index,
+        _ => return Err("err".to_string()),
+    };
+
+    let mut is_in_class = false;
+    let mut end_index = None;
+
+    while let Some((index, char)) = chars.next() {
+        match char {
+            '/' if !is_in_class => {
+                end_index = Some(index + char_utf8_length(char));
+                break;
+            }
+
+            '[' => is_in_class = true,
+            ']' => is_in_class = false,
+
+            '\\' => match chars.next() {
+                Some((_, character_patterns!(LineTerminator))) | None => break,
+                Some(_) => {}
+            },
+
+            character_patterns!(LineTerminator) => break,
+
+            _ => {}
+        }
+    }
+
+    let mut end_index =
+        end_index.ok_or_else(|| "error regular expression literal is not closed".to_string())?;
+
+    // フラグ
+    while let Some((index, char)) = chars.peek() {
+        if *char != '\\' && is_identifier_part(*char) {
+            end_index = index + char_utf8_length(*char);
+            next_peeked_character(chars)?;
+        } else {
+            break;
+        }
+    }
+
+    Ok(Token::new(
+        TokenType::Literal(Literal::RegularExpression),
+        start_index,
+        end_index,
+    ))
+}
@@ -0,0 +1,57 @@
+use std::{iter::Peekable, str::CharIndices};
+
+use crate::token::structs::literal::Literal;
+use crate::token::structs::{
+    error::TokenizeError,
+    token::{Token, TokenType},
+};
+
+use crate::token::tokenize::next_peeked_character;
+
+use super::char_utf8_length;
+
+/// 文字列リテラル `"abc"` `'abc'`
+///
+/// エスケープは読み飛ばすだけで、値は構文解析で解釈する。
+pub(super) fn tokenize_string_literal(
+    chars: &mut Peekable<CharIndices>,
+) -> Result<Token, TokenizeError> {
+    let (start_index, quote) = next_peeked_character(chars)?;
+    let token_type = match quote {
+        '"' => Literal::DoubleString,
+        '\'' => Literal::SingleString,
+        _ => return Err("err".to_string()),
+    };
+
+    while let Some((index, char)) = chars.next() {
+        match char {
+            char if char == quote => {
+                return Ok(Token::new(
+                    TokenType::Literal(token_type),
+                    start_index,
+                    index + char_utf8_length(char),
+                ));
+            }
+
+            '\\' => match chars.next() {
+                // <CR><LF>の行継続は１つの改行とみなす
+                Some((_, '\u{000D}')) => {
+                    if let Some((_, '\u{000A}')) = chars.peek() {
+                        next_peeked_character(chars)?;
+                    }
+                }
+                Some(_) => {}
+                None => break,
+            },
+
+            // <LS> <PS> は文字列に含めることができる
+            '\u{000A}' | '\u{000D}' => {
+                return Err("error string literal cannot contain line terminator".to_string());
+            }
+
+            _ => {}
+        }
+    }
+
+    Err("error string literal is not closed".to_string())
+}
@@ -0,0 +1,74 @@
+use std::{iter::Peekable, str::CharIndices};
+
+use crate::token::structs::template::Template;
+use crate::token::structs::{
+    error::TokenizeError,
+    token::{Token, TokenType},
+};
+
+use crate::token::tokenize::next_peeked_character;
+
+use super::char_utf8_length;
+
+/// テンプレートの開始 `` `abc` `` `` `abc${ ``
+pub(super) fn tokenize_template_start(
+    chars: &mut Peekable<CharIndices>,
+) -> Result<Token, TokenizeError> {
+    match chars.peek() {
+        Some((_, '`')) => tokenize_template_part(chars, Template::NoSubstitution, Template::Head),
+        _ => Err("err".to_string()),
+    }
+}
+
+/// 置換の後のテンプレートの続き `` }abc` `` `` }abc${ ``
+pub(super) fn tokenize_template_continuation(
+    chars: &mut Peekable<CharIndices>,
+) -> Result<Token, TokenizeError> {
+    match chars.peek() {
+        Some((_, '}')) => tokenize_template_part(chars, Template::Tail, Template::Middle),
+        _ => Err("err".to_string()),
+    }
+}
+
+/// `` ` `` で終わる場合は `closed`、`${` で終わる場合は `substitution` になる。
+fn tokenize_template_part(
+    chars: &mut Peekable<CharIndices>,
+    closed: Template,
+    substitution: Template,
+) -> Result<Token, TokenizeError> {
+    let (start_index, _) = next_peeked_character(chars)?;
+
+    while let Some((index, char)) = chars.next() {
+        match char {
+            '`' => {
+                return Ok(Token::new(
+                    TokenType::Template(closed),
+                    start_index,
+                    index + char_utf8_length(char),
+                ));
+            }
+
+            '$' => {
+                if let Some((index, char @ '{')) = chars.peek() {
+                    let end_index = index + char_utf8_length(*char);
+                    next_peeked_character(chars)?;
+
+                    return Ok(Token::new(
+                        TokenType::Template(substitution),
+                        start_index,
+                        end_index,
+                    ));
+                }
+            }
+
+            // エスケープされた文字を飛ばす
+            '\\' => {
+                chars.next();
+            }
+
+            _ => {}
+        }
+    }
+
+    Err("error template literal is not closed".to_string())
+}
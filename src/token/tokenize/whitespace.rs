@@ -25,7 +25,7 @@ pub(super) fn tokenize_whitespace(
         match char {
             char @ character_patterns!(Whitespace) => {
                 // 空白文字の場合
-                if start_index == None {
+                if start_index.is_none() {
                     // 最初の空白文字でインデックスを設定する
                     start_index = Some(*index);
                 }
@@ -46,5 +46,5 @@ pub(super) fn tokenize_whitespace(
     }
 
     let token_type = Some(TokenType::WhiteSpace);
-    Token::try_new(token_type, start_index, end_index).ok_or(format!("err"))
+    Token::try_new(token_type, start_index, end_index).ok_or_else(|| "err".to_string())
 }